@@ -1,4 +1,5 @@
 use lemao_core::lemao_common_platform::input::InputEvent;
+use lemao_core::lemao_common_platform::input::Key;
 use lemao_core::lemao_common_platform::window::WindowStyle;
 use lemao_core::lemao_math::color::SolidColor;
 use lemao_core::lemao_math::vec2::Vec2;
@@ -32,10 +33,11 @@ pub fn main() -> Result<(), String> {
     let window_position = Default::default();
     let mut window_size = Vec2::new(1366.0, 768.0);
 
-    let mut window = WindowContext::new("Benchmark", WindowStyle::Window { position: window_position, size: window_size })?;
+    let mut window = WindowContext::new("Benchmark", WindowStyle::Window { position: window_position, size: window_size }, Default::default())?;
     let mut renderer = window.create_renderer()?;
     let mut ui = UiContext::new(&mut renderer)?;
     renderer.set_swap_interval(0);
+    renderer.enable_gpu_timer()?;
 
     let cell_texture_id = renderer.textures.store(Texture::new(&renderer, &bmp::load("./assets/cell.bmp")?)?);
     let font_id = renderer.fonts.store(Font::new(&renderer, &bff::load("./assets/inconsolata.bff")?)?);
@@ -68,6 +70,7 @@ pub fn main() -> Result<(), String> {
     let mut now = Instant::now();
     let mut frames = 0;
     let mut is_running = true;
+    let mut instanced = true;
 
     while is_running {
         while let Some(event) = window.poll_event() {
@@ -76,6 +79,9 @@ pub fn main() -> Result<(), String> {
                     window_size = size;
                     renderer.set_viewport_size(size)?;
                 }
+                InputEvent::KeyPressed(Key::Space) => {
+                    instanced = !instanced;
+                }
                 InputEvent::WindowClosed => {
                     is_running = false;
                 }
@@ -85,6 +91,7 @@ pub fn main() -> Result<(), String> {
             ui.process_window_event(&mut renderer, &event)?;
         }
 
+        renderer.begin_frame();
         renderer.clear(SolidColor::new(0.5, 0.5, 0.5, 1.0));
 
         for cell in &mut cells {
@@ -104,12 +111,23 @@ pub fn main() -> Result<(), String> {
             cell.sprite.position += cell.velocity;
             cell.sprite.update();
 
-            renderer.batcher_add_drawable(&cell.sprite)?;
+            if instanced {
+                renderer.instancer_add_drawable(&cell.sprite)?;
+            } else {
+                renderer.batcher_add_drawable(&cell.sprite)?;
+            }
         }
 
         if now.elapsed().as_millis() >= 1000 {
             let fps_text = ui.components.get_and_cast_mut::<Label>(fps_text_id)?;
-            fps_text.label_text = format!("FPS:{frames}");
+            let statistics = renderer.statistics;
+            let gpu_time = statistics.gpu_time.map(|p| format!("{:.2}ms", p)).unwrap_or_else(|| "-".to_string());
+            let mode = if instanced { "instanced" } else { "batched" };
+
+            fps_text.label_text = format!(
+                "FPS:{} Mode:{} DrawCalls:{} Batches:{} Vertices:{} Uploads:{} GPU:{}",
+                frames, mode, statistics.draw_calls, statistics.batches, statistics.vertices, statistics.buffer_uploads, gpu_time
+            );
             fps_text.dirty = true;
 
             now = Instant::now();
@@ -119,8 +137,14 @@ pub fn main() -> Result<(), String> {
         ui.update(&mut renderer)?;
 
         frames += 1;
-        renderer.batcher_draw()?;
+        if instanced {
+            renderer.instancer_draw()?;
+        } else {
+            renderer.batcher_draw()?;
+        }
+
         ui.draw(&mut renderer, fps_text_id)?;
+        renderer.end_frame();
         window.swap_buffers();
     }
 
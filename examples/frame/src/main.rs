@@ -26,7 +26,7 @@ pub fn main() -> Result<(), String> {
     let window_position = Default::default();
     let window_size = Vec2::new(1366.0, 768.0);
 
-    let mut window = WindowContext::new("Frame", WindowStyle::Window { position: window_position, size: window_size })?;
+    let mut window = WindowContext::new("Frame", WindowStyle::Window { position: window_position, size: window_size }, Default::default())?;
     let mut renderer = window.create_renderer()?;
     let mut ui = UiContext::new(&mut renderer)?;
     renderer.set_swap_interval(1);
@@ -1,14 +1,15 @@
 use lemao_core::lemao_common_platform::window::WindowStyle;
 use lemao_core::lemao_math::vec2::Vec2;
 use lemao_framework::app::Application;
+use lemao_framework::error::FrameworkError;
 use scenes::first::FirstScene;
 use scenes::second::SecondScene;
 
 pub mod global;
 pub mod scenes;
 
-pub fn main() -> Result<(), String> {
-    Application::new("Framework", WindowStyle::Window { position: Default::default(), size: Vec2::new(1366.0, 768.0) })?
+pub fn main() -> Result<(), FrameworkError> {
+    Application::new("Framework", WindowStyle::Window { position: Default::default(), size: Vec2::new(1366.0, 768.0) }, Default::default())?
         .register_scene("Scene 1", |app| Box::new(FirstScene::new(app)), true)?
         .register_scene("Scene 2", |app| Box::new(SecondScene::new(app)), false)?
         .run()
@@ -11,6 +11,7 @@ use lemao_ui::components::label::Label;
 use lemao_ui::components::ComponentPosition;
 use lemao_ui::context::UiContext;
 use std::any::Any;
+use std::error::Error;
 
 #[rustfmt::skip]
 const DESCRIPTION: &str = 
@@ -29,7 +30,7 @@ impl SecondScene {
 }
 
 impl Scene<GlobalAppData> for SecondScene {
-    fn on_init(&mut self, app: &mut Application<GlobalAppData>) -> Result<(), String> {
+    fn on_init(&mut self, app: &mut Application<GlobalAppData>) -> Result<(), Box<dyn Error>> {
         let font_id = app.renderer.fonts.store(Font::new(&app.renderer, &bff::load("./assets/inconsolata.bff")?)?);
 
         self.description_text_id = self.ui.components.store(Label::new(&mut app.renderer, font_id)?);
@@ -43,15 +44,15 @@ impl Scene<GlobalAppData> for SecondScene {
         Ok(())
     }
 
-    fn on_activation(&mut self, _app: &mut Application<GlobalAppData>) -> Result<(), String> {
+    fn on_activation(&mut self, _app: &mut Application<GlobalAppData>) -> Result<(), Box<dyn Error>> {
         Ok(())
     }
 
-    fn on_deactivation(&mut self, _app: &mut Application<GlobalAppData>) -> Result<(), String> {
+    fn on_deactivation(&mut self, _app: &mut Application<GlobalAppData>) -> Result<(), Box<dyn Error>> {
         Ok(())
     }
 
-    fn on_tick(&mut self, app: &mut Application<GlobalAppData>) -> Result<(), String> {
+    fn on_tick(&mut self, app: &mut Application<GlobalAppData>) -> Result<(), Box<dyn Error>> {
         while let Some(event) = app.window.poll_event() {
             match event {
                 InputEvent::KeyPressed(Key::Enter) => {
@@ -24,9 +24,9 @@ pub fn main() -> Result<(), String> {
     let window_position = Default::default();
     let window_size = Vec2::new(1366.0, 768.0);
 
-    let mut window = WindowContext::new("Physics", WindowStyle::Window { position: window_position, size: window_size })?;
+    let mut window = WindowContext::new("Physics", WindowStyle::Window { position: window_position, size: window_size }, Default::default())?;
     let mut renderer = window.create_renderer()?;
-    let mut physics = PhysicsContext::new(&mut renderer)?;
+    let mut physics = PhysicsContext::new()?;
     let mut ui = UiContext::new(&mut renderer)?;
     renderer.set_swap_interval(1);
 
@@ -80,6 +80,7 @@ pub fn main() -> Result<(), String> {
         renderer.clear(SolidColor::new(0.5, 0.5, 0.5, 1.0));
         physics.draw(&mut renderer)?;
         ui.draw(&mut renderer, description_text_id)?;
+        renderer.debug_draw()?;
         window.swap_buffers();
     }
 
@@ -10,6 +10,7 @@ use lemao_core::lemao_math::vec2::Vec2;
 use lemao_core::renderer::drawable::Color;
 use lemao_core::renderer::fonts::bff;
 use lemao_core::renderer::fonts::Font;
+use lemao_core::renderer::richtext::TextMarkup;
 use lemao_core::renderer::textures::bmp;
 use lemao_core::renderer::textures::Texture;
 use lemao_core::window::context::WindowContext;
@@ -38,7 +39,7 @@ pub fn main() -> Result<(), String> {
     let window_position = Default::default();
     let window_size = Vec2::new(1366.0, 768.0);
 
-    let mut window = match WindowContext::new("UI", WindowStyle::Window { position: window_position, size: window_size }) {
+    let mut window = match WindowContext::new("UI", WindowStyle::Window { position: window_position, size: window_size }, Default::default()) {
         Ok(window) => window,
         Err(message) => panic!("{}", message),
     };
@@ -57,14 +58,14 @@ pub fn main() -> Result<(), String> {
     let mut regular_font = bff::load("./assets/regular.bff")?;
     let mut bold_font = bff::load("./assets/bold.bff")?;
 
-    regular_font.set_character(200, Vec2::new(0.0, 4.0), &coin_icon);
-    regular_font.set_character(201, Vec2::new(0.0, 3.0), &hammer_icon);
-    regular_font.set_character(202, Vec2::new(0.0, 3.0), &happiness_icon);
-    regular_font.set_character(203, Vec2::new(0.0, 3.0), &blank_icon);
+    regular_font.set_character(200, Vec2::new(0.0, 4.0), &coin_icon)?;
+    regular_font.set_character(201, Vec2::new(0.0, 3.0), &hammer_icon)?;
+    regular_font.set_character(202, Vec2::new(0.0, 3.0), &happiness_icon)?;
+    regular_font.set_character(203, Vec2::new(0.0, 3.0), &blank_icon)?;
 
-    bold_font.set_character(200, Vec2::new(0.0, 4.0), &coin_icon);
-    bold_font.set_character(201, Vec2::new(0.0, 3.0), &hammer_icon);
-    bold_font.set_character(202, Vec2::new(0.0, 3.0), &happiness_icon);
+    bold_font.set_character(200, Vec2::new(0.0, 4.0), &coin_icon)?;
+    bold_font.set_character(201, Vec2::new(0.0, 3.0), &hammer_icon)?;
+    bold_font.set_character(202, Vec2::new(0.0, 3.0), &happiness_icon)?;
 
     let regular_font_id = renderer.fonts.store(Font::new(&renderer, &regular_font)?);
     let bold_font_id = renderer.fonts.store(Font::new(&renderer, &bold_font)?);
@@ -75,6 +76,7 @@ pub fn main() -> Result<(), String> {
     let box_unchecked_id = renderer.textures.store(Texture::new(&renderer, &bmp::load("./assets/box_unchecked.bmp")?)?);
 
     let mut ui = UiContext::new(&mut renderer)?;
+    ui.enable_logical_pixels(&mut renderer, window.get_scale_factor())?;
     // ui.set_debug_flag(true);
 
     /* #region Progress bar */
@@ -203,9 +205,10 @@ pub fn main() -> Result<(), String> {
     quote.position = ComponentPosition::RelativeToParent(Vec2::new(0.0, 1.0));
     quote.anchor = Vec2::new(0.0, 1.0);
     quote.margin = ComponentMargin::new(10.0, 10.0, 10.0, 10.0);
+    quote.label_markup = TextMarkup::Rich;
     quote.set_multiline_text(
-        "\"To forget how to dig the earth and to tend the soil is to forget ourselves.\" - °200,255,200,255°Mahatma Gandhi°255,255,255,255°\n\n".to_string() + 
-        "\"Agriculture is our wisest pursuit, because it will in the end contribute most to real wealth, good morals, and happiness.\" - °200,255,200,255°Thomas Jefferson°255,255,255,255°",
+        "\"To forget how to dig the earth and to tend the soil is to forget ourselves.\" - [color=200,255,200,255]Mahatma Gandhi[/color]\n\n".to_string() + 
+        "\"Agriculture is our wisest pursuit, because it will in the end contribute most to real wealth, good morals, and happiness.\" - [color=200,255,200,255]Thomas Jefferson[/color]",
         360.0,
     );
     quote.shadow_enabled = true;
@@ -653,9 +656,10 @@ pub fn main() -> Result<(), String> {
     pie_chart_legend.position = ComponentPosition::RelativeToParent(Vec2::new(0.5, 1.0));
     pie_chart_legend.anchor = Vec2::new(0.5, 1.0);
     pie_chart_legend.offset = Vec2::new(0.0, -320.0);
-    pie_chart_legend.label_text = "°254,135,177,255°\u{CB}°255,255,255,255° - import ".to_string()
-        + "°175,69,166,255°\u{CB}°255,255,255,255° - export "
-        + "°147,82,186,255°\u{CB}°255,255,255,255° - domestic";
+    pie_chart_legend.label_markup = TextMarkup::Rich;
+    pie_chart_legend.label_text = "[color=254,135,177,255]\u{CB}[/color] - import ".to_string()
+        + "[color=175,69,166,255]\u{CB}[/color] - export "
+        + "[color=147,82,186,255]\u{CB}[/color] - domestic";
     pie_chart_legend.shadow_enabled = true;
     pie_chart_legend.shadow_offset = Vec2::new(1.0, -1.0);
     pie_chart_legend.shadow_color = Color::SolidColor(SolidColor::new(0.0, 0.0, 0.0, 0.5));
@@ -759,9 +763,9 @@ pub fn main() -> Result<(), String> {
         ui.draw(&mut renderer, description_panel_id)?;
 
         ui.draw(&mut renderer, description_scrollbox_id)?;
-        ui.begin_scrollbox(description_scrollbox_id, &renderer)?;
+        ui.begin_scrollbox(description_scrollbox_id, &mut renderer)?;
         ui.draw(&mut renderer, description_id)?;
-        ui.end_scrollbox(&renderer);
+        ui.end_scrollbox(&mut renderer)?;
 
         ui.draw(&mut renderer, effect_panel_1_id)?;
         ui.draw(&mut renderer, effect_panel_2_id)?;
@@ -22,7 +22,7 @@ pub fn main() -> Result<(), String> {
     let window_position = Default::default();
     let window_size = Vec2::new(1366.0, 768.0);
 
-    let mut window = match WindowContext::new("Window", WindowStyle::Window { position: window_position, size: window_size }) {
+    let mut window = match WindowContext::new("Window", WindowStyle::Window { position: window_position, size: window_size }, Default::default()) {
         Ok(window) => window,
         Err(message) => panic!("{}", message),
     };
@@ -1,6 +1,7 @@
 use lemao_core::lemao_common_platform::window::WindowStyle;
 use lemao_core::lemao_math::vec2::Vec2;
 use lemao_framework::app::Application;
+use lemao_framework::error::FrameworkError;
 use scenes::game::scene::GameScene;
 use scenes::menu::scene::MenuScene;
 use state::global::GlobalAppData;
@@ -20,8 +21,8 @@ pub static NvOptimusEnablement: i32 = 1;
 #[cfg(not(debug_assertions))]
 pub static AmdPowerXpressRequestHighPerformance: i32 = 1;
 
-pub fn main() -> Result<(), String> {
-    Application::new("Theo The Snake", WindowStyle::Window { position: Default::default(), size: Vec2::new(1366.0, 768.0) })?
+pub fn main() -> Result<(), FrameworkError> {
+    Application::new("Theo The Snake", WindowStyle::Window { position: Default::default(), size: Vec2::new(1366.0, 768.0) }, Default::default())?
         .register_scene("Menu", |app| Box::new(MenuScene::new(app)), true)?
         .register_scene("Game", |app| Box::new(GameScene::new(app)), false)?
         .run()
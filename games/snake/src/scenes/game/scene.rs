@@ -28,6 +28,7 @@ use lemao_framework::ecs::components::list::ComponentList;
 use lemao_framework::ecs::world::World;
 use lemao_ui::context::UiContext;
 use std::any::Any;
+use std::error::Error;
 use std::sync::Arc;
 use std::sync::RwLock;
 
@@ -46,11 +47,11 @@ impl GameScene {
 }
 
 impl Scene<GlobalAppData> for GameScene {
-    fn on_init(&mut self, _app: &mut GameApp) -> Result<(), String> {
+    fn on_init(&mut self, _app: &mut GameApp) -> Result<(), Box<dyn Error>> {
         Ok(())
     }
 
-    fn on_activation(&mut self, _app: &mut GameApp) -> Result<(), String> {
+    fn on_activation(&mut self, _app: &mut GameApp) -> Result<(), Box<dyn Error>> {
         self.world = Arc::new(RwLock::new(World::default()));
         self.state = Default::default();
 
@@ -100,7 +101,7 @@ impl Scene<GlobalAppData> for GameScene {
         Ok(())
     }
 
-    fn on_deactivation(&mut self, app: &mut GameApp) -> Result<(), String> {
+    fn on_deactivation(&mut self, app: &mut GameApp) -> Result<(), Box<dyn Error>> {
         for sound in app.audio.sounds.iter_mut() {
             sound.stop()?;
         }
@@ -108,7 +109,7 @@ impl Scene<GlobalAppData> for GameScene {
         Ok(())
     }
 
-    fn on_tick(&mut self, app: &mut GameApp) -> Result<(), String> {
+    fn on_tick(&mut self, app: &mut GameApp) -> Result<(), Box<dyn Error>> {
         let world = self.world.clone();
         world.write().unwrap().update(app, self)?;
 
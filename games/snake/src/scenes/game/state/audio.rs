@@ -1,6 +1,9 @@
+use lemao_core::audio::sounds::Sound;
+use lemao_core::utils::storage::Handle;
+
 #[derive(Debug, Default)]
 pub struct AudioState {
-    pub music_id: usize,
-    pub click_id: usize,
-    pub hit_id: usize,
+    pub music_id: Handle<Sound>,
+    pub click_id: Handle<Sound>,
+    pub hit_id: Handle<Sound>,
 }
@@ -7,6 +7,7 @@ use lemao_core::audio::sounds::Sound;
 use lemao_framework::ecs::systems::System;
 use lemao_framework::ecs::systems::SystemStage;
 use std::any::TypeId;
+use std::error::Error;
 
 #[derive(Default)]
 pub struct AudioPlayerSystem {}
@@ -20,7 +21,7 @@ impl System<GlobalAppData, GameScene, Message> for AudioPlayerSystem {
         TypeId::of::<Self>()
     }
 
-    fn update(&mut self, app: &mut GameApp, scene: &mut GameScene, world: &mut GameWorld) -> Result<(), String> {
+    fn update(&mut self, app: &mut GameApp, scene: &mut GameScene, world: &mut GameWorld) -> Result<(), Box<dyn Error>> {
         while let Some(message) = world.messages.poll_message::<Self>() {
             match message {
                 Message::Init => {
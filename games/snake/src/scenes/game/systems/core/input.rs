@@ -6,6 +6,7 @@ use crate::GameApp;
 use lemao_framework::ecs::systems::System;
 use lemao_framework::ecs::systems::SystemStage;
 use std::any::TypeId;
+use std::error::Error;
 
 #[derive(Default)]
 pub struct InputSystem {}
@@ -19,7 +20,7 @@ impl System<GlobalAppData, GameScene, Message> for InputSystem {
         TypeId::of::<Self>()
     }
 
-    fn update(&mut self, app: &mut GameApp, _scene: &mut GameScene, world: &mut GameWorld) -> Result<(), String> {
+    fn update(&mut self, app: &mut GameApp, _scene: &mut GameScene, world: &mut GameWorld) -> Result<(), Box<dyn Error>> {
         while let Some(event) = app.window.poll_event() {
             world.messages.broadcast(Message::InputEvent(event))?;
         }
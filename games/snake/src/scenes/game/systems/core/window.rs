@@ -8,6 +8,7 @@ use lemao_core::lemao_math::vec2::Vec2;
 use lemao_framework::ecs::systems::System;
 use lemao_framework::ecs::systems::SystemStage;
 use std::any::TypeId;
+use std::error::Error;
 
 #[derive(Default)]
 pub struct WindowSystem {}
@@ -21,7 +22,7 @@ impl System<GlobalAppData, GameScene, Message> for WindowSystem {
         TypeId::of::<Self>()
     }
 
-    fn update(&mut self, app: &mut GameApp, _scene: &mut GameScene, world: &mut GameWorld) -> Result<(), String> {
+    fn update(&mut self, app: &mut GameApp, _scene: &mut GameScene, world: &mut GameWorld) -> Result<(), Box<dyn Error>> {
         while let Some(message) = world.messages.poll_message::<Self>() {
             if let Message::InputEvent(event) = message {
                 match event {
@@ -14,6 +14,7 @@ use lemao_framework::ecs::commands::spawn::SpawnCommand;
 use lemao_framework::ecs::systems::System;
 use lemao_framework::ecs::systems::SystemStage;
 use std::any::TypeId;
+use std::error::Error;
 
 #[derive(Default)]
 pub struct BoardSystem {}
@@ -27,7 +28,7 @@ impl System<GlobalAppData, GameScene, Message> for BoardSystem {
         TypeId::of::<Self>()
     }
 
-    fn update(&mut self, app: &mut GameApp, _scene: &mut GameScene, world: &mut GameWorld) -> Result<(), String> {
+    fn update(&mut self, app: &mut GameApp, _scene: &mut GameScene, world: &mut GameWorld) -> Result<(), Box<dyn Error>> {
         while let Some(message) = world.messages.poll_message::<Self>() {
             if let Message::Init = message {
                 for row in 0..app.global_data.board_height {
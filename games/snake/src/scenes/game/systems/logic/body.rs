@@ -12,6 +12,7 @@ use lemao_framework::ecs::systems::System;
 use lemao_framework::ecs::systems::SystemStage;
 use std::any::TypeId;
 use std::cmp;
+use std::error::Error;
 
 #[derive(Default)]
 pub struct BodySystem {}
@@ -25,7 +26,7 @@ impl System<GlobalAppData, GameScene, Message> for BodySystem {
         TypeId::of::<Self>()
     }
 
-    fn update(&mut self, _app: &mut GameApp, scene: &mut GameScene, world: &mut GameWorld) -> Result<(), String> {
+    fn update(&mut self, _app: &mut GameApp, scene: &mut GameScene, world: &mut GameWorld) -> Result<(), Box<dyn Error>> {
         let mut tick = false;
 
         while let Some(message) = world.messages.poll_message::<Self>() {
@@ -17,6 +17,7 @@ use lemao_framework::ecs::commands::spawn::SpawnCommand;
 use lemao_framework::ecs::systems::System;
 use lemao_framework::ecs::systems::SystemStage;
 use std::any::TypeId;
+use std::error::Error;
 use std::time::SystemTime;
 
 #[derive(Default)]
@@ -31,7 +32,7 @@ impl System<GlobalAppData, GameScene, Message> for FoodSystem {
         TypeId::of::<Self>()
     }
 
-    fn update(&mut self, app: &mut GameApp, scene: &mut GameScene, world: &mut GameWorld) -> Result<(), String> {
+    fn update(&mut self, app: &mut GameApp, scene: &mut GameScene, world: &mut GameWorld) -> Result<(), Box<dyn Error>> {
         while let Some(message) = world.messages.poll_message::<Self>() {
             if let Message::GameTick = message {
                 let foods = world.components.get_and_cast_mut::<FoodComponent>()?;
@@ -25,6 +25,7 @@ use lemao_framework::ecs::systems::SystemStage;
 use std::any::TypeId;
 use std::cmp;
 use std::collections::HashMap;
+use std::error::Error;
 use std::time::SystemTime;
 
 pub struct HeadSystem {
@@ -40,7 +41,7 @@ impl System<GlobalAppData, GameScene, Message> for HeadSystem {
         TypeId::of::<Self>()
     }
 
-    fn update(&mut self, app: &mut GameApp, scene: &mut GameScene, world: &mut GameWorld) -> Result<(), String> {
+    fn update(&mut self, app: &mut GameApp, scene: &mut GameScene, world: &mut GameWorld) -> Result<(), Box<dyn Error>> {
         while let Some(message) = world.messages.poll_message::<Self>() {
             match message {
                 Message::Init => {
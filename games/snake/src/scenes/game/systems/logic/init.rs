@@ -6,6 +6,7 @@ use crate::GameApp;
 use lemao_framework::ecs::systems::System;
 use lemao_framework::ecs::systems::SystemStage;
 use std::any::TypeId;
+use std::error::Error;
 
 #[derive(Default)]
 pub struct InitSystem {}
@@ -19,7 +20,7 @@ impl System<GlobalAppData, GameScene, Message> for InitSystem {
         TypeId::of::<Self>()
     }
 
-    fn update(&mut self, _app: &mut GameApp, _scene: &mut GameScene, world: &mut GameWorld) -> Result<(), String> {
-        world.messages.broadcast(Message::Init)
+    fn update(&mut self, _app: &mut GameApp, _scene: &mut GameScene, world: &mut GameWorld) -> Result<(), Box<dyn Error>> {
+        Ok(world.messages.broadcast(Message::Init)?)
     }
 }
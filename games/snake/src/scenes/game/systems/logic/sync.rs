@@ -10,6 +10,7 @@ use lemao_core::lemao_common_platform::input::Key;
 use lemao_framework::ecs::systems::System;
 use lemao_framework::ecs::systems::SystemStage;
 use std::any::TypeId;
+use std::error::Error;
 use std::time::SystemTime;
 
 #[derive(Default)]
@@ -24,7 +25,7 @@ impl System<GlobalAppData, GameScene, Message> for SyncSystem {
         TypeId::of::<Self>()
     }
 
-    fn update(&mut self, app: &mut GameApp, scene: &mut GameScene, world: &mut GameWorld) -> Result<(), String> {
+    fn update(&mut self, app: &mut GameApp, scene: &mut GameScene, world: &mut GameWorld) -> Result<(), Box<dyn Error>> {
         let mut tick = scene.state.game.tick_length;
         if app.window.is_key_pressed(Key::Space) {
             tick /= app.global_data.space_acceleration;
@@ -7,6 +7,7 @@ use lemao_core::lemao_math::color::SolidColor;
 use lemao_framework::ecs::systems::System;
 use lemao_framework::ecs::systems::SystemStage;
 use std::any::TypeId;
+use std::error::Error;
 
 #[derive(Default)]
 pub struct FrameBeginSystem {}
@@ -20,7 +21,7 @@ impl System<GlobalAppData, GameScene, Message> for FrameBeginSystem {
         TypeId::of::<Self>()
     }
 
-    fn update(&mut self, app: &mut GameApp, _scene: &mut GameScene, _world: &mut GameWorld) -> Result<(), String> {
+    fn update(&mut self, app: &mut GameApp, _scene: &mut GameScene, _world: &mut GameWorld) -> Result<(), Box<dyn Error>> {
         app.renderer.clear(SolidColor::new_rgb(210, 150, 100, 255));
 
         Ok(())
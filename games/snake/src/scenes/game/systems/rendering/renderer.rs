@@ -10,6 +10,7 @@ use lemao_core::lemao_math::vec2::Vec2;
 use lemao_framework::ecs::systems::System;
 use lemao_framework::ecs::systems::SystemStage;
 use std::any::TypeId;
+use std::error::Error;
 use std::time::SystemTime;
 
 #[derive(Default)]
@@ -24,7 +25,7 @@ impl System<GlobalAppData, GameScene, Message> for RendererSystem {
         TypeId::of::<Self>()
     }
 
-    fn update(&mut self, app: &mut GameApp, _scene: &mut GameScene, world: &mut GameWorld) -> Result<(), String> {
+    fn update(&mut self, app: &mut GameApp, _scene: &mut GameScene, world: &mut GameWorld) -> Result<(), Box<dyn Error>> {
         let (sprites, positions) = world.components.get_and_cast_mut_2::<SpriteComponent, PositionComponent>()?;
         let mut layers = Vec::new();
 
@@ -13,6 +13,7 @@ use lemao_framework::ecs::systems::SystemStage;
 use lemao_ui::components::label::Label;
 use lemao_ui::components::ComponentPosition;
 use std::any::TypeId;
+use std::error::Error;
 
 #[derive(Default)]
 pub struct UiLogicSystem {}
@@ -26,7 +27,7 @@ impl System<GlobalAppData, GameScene, Message> for UiLogicSystem {
         TypeId::of::<Self>()
     }
 
-    fn update(&mut self, app: &mut GameApp, scene: &mut GameScene, world: &mut GameWorld) -> Result<(), String> {
+    fn update(&mut self, app: &mut GameApp, scene: &mut GameScene, world: &mut GameWorld) -> Result<(), Box<dyn Error>> {
         let mut update_score = false;
 
         while let Some(message) = world.messages.poll_message::<Self>() {
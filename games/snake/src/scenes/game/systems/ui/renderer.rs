@@ -6,6 +6,7 @@ use crate::GameApp;
 use lemao_framework::ecs::systems::System;
 use lemao_framework::ecs::systems::SystemStage;
 use std::any::TypeId;
+use std::error::Error;
 
 #[derive(Default)]
 pub struct UiRenderingSystem {}
@@ -19,7 +20,7 @@ impl System<GlobalAppData, GameScene, Message> for UiRenderingSystem {
         TypeId::of::<Self>()
     }
 
-    fn update(&mut self, app: &mut GameApp, scene: &mut GameScene, _world: &mut GameWorld) -> Result<(), String> {
+    fn update(&mut self, app: &mut GameApp, scene: &mut GameScene, _world: &mut GameWorld) -> Result<(), Box<dyn Error>> {
         scene.ui.update(&mut app.renderer)?;
         scene.ui.draw(&mut app.renderer, scene.state.ui.score_label_id)?;
         scene.ui.draw(&mut app.renderer, scene.state.ui.best_score_label_id)?;
@@ -17,6 +17,7 @@ use lemao_ui::components::ComponentShape;
 use lemao_ui::context::UiContext;
 use lemao_ui::events::UiEvent;
 use std::any::Any;
+use std::error::Error;
 
 pub struct MenuScene {
     pub ui: UiContext,
@@ -30,7 +31,7 @@ impl MenuScene {
 }
 
 impl Scene<GlobalAppData> for MenuScene {
-    fn on_init(&mut self, app: &mut GameApp) -> Result<(), String> {
+    fn on_init(&mut self, app: &mut GameApp) -> Result<(), Box<dyn Error>> {
         let mut assets_loader = AssetsLoader::default();
         assets_loader.set_queue("./assets/")?;
         assets_loader.start_loading();
@@ -92,17 +93,17 @@ impl Scene<GlobalAppData> for MenuScene {
         Ok(())
     }
 
-    fn on_activation(&mut self, app: &mut GameApp) -> Result<(), String> {
+    fn on_activation(&mut self, app: &mut GameApp) -> Result<(), Box<dyn Error>> {
         app.window.set_cursor_visibility(true);
         Ok(())
     }
 
-    fn on_deactivation(&mut self, app: &mut GameApp) -> Result<(), String> {
+    fn on_deactivation(&mut self, app: &mut GameApp) -> Result<(), Box<dyn Error>> {
         app.window.set_cursor_visibility(false);
         Ok(())
     }
 
-    fn on_tick(&mut self, app: &mut GameApp) -> Result<(), String> {
+    fn on_tick(&mut self, app: &mut GameApp) -> Result<(), Box<dyn Error>> {
         while let Some(event) = app.window.poll_event() {
             match event {
                 InputEvent::WindowSizeChanged(size) => {
@@ -1,5 +1,6 @@
 use super::samples::Sample;
 use super::sounds::Sound;
+use crate::error::CoreError;
 use crate::utils::storage::Storage;
 use lemao_openal::bindings::openal;
 use std::ptr;
@@ -13,27 +14,27 @@ pub struct AudioContext {
 }
 
 impl AudioContext {
-    pub fn new() -> Result<Self, String> {
+    pub fn new() -> Result<Self, CoreError> {
         unsafe {
             let device_id = openal::alcOpenDevice(ptr::null());
             let error = openal::alcGetError(device_id);
 
             if device_id.is_null() || error != openal::AL_NO_ERROR as i32 {
-                return Err(format!("Error while creating a new device: {}", error));
+                return Err(CoreError::OpenAL { message: "device creation failed".to_string(), code: error });
             }
 
             let context_id = openal::alcCreateContext(device_id, ptr::null_mut());
             let error = openal::alcGetError(device_id);
 
             if context_id.is_null() || error != openal::AL_NO_ERROR as i32 {
-                return Err(format!("Error while creating a new context: {}", error));
+                return Err(CoreError::OpenAL { message: "context creation failed".to_string(), code: error });
             }
 
             let success = openal::alcMakeContextCurrent(context_id);
             let error = openal::alcGetError(device_id);
 
             if success == 0 || error != openal::AL_NO_ERROR as i32 {
-                return Err(format!("Error while making context as current: {}", error));
+                return Err(CoreError::OpenAL { message: "making context as current failed".to_string(), code: error });
             }
 
             Ok(Self { device: device_id, context: context_id, samples: Default::default(), sounds: Default::default() })
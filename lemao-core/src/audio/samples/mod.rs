@@ -1,4 +1,6 @@
 use super::context::AudioContext;
+use crate::error::CoreError;
+use crate::utils::storage::Handle;
 use crate::utils::storage::StorageItem;
 use lemao_openal::bindings::openal;
 use std::ffi::c_void;
@@ -13,7 +15,7 @@ pub struct RawSample {
 }
 
 pub struct Sample {
-    pub(crate) id: usize,
+    pub(crate) id: Handle<Sample>,
     pub(crate) name: Option<String>,
     pub(crate) buffer_id: u32,
 }
@@ -25,20 +27,20 @@ impl RawSample {
 }
 
 impl Sample {
-    pub fn new(_audio: &AudioContext, raw: &RawSample) -> Result<Self, String> {
+    pub fn new(_audio: &AudioContext, raw: &RawSample) -> Result<Self, CoreError> {
         unsafe {
             let format = match raw.channels_count {
                 1 => match raw.bits_per_sample {
                     8 => openal::AL_FORMAT_MONO8,
                     16 => openal::AL_FORMAT_MONO16,
-                    _ => return Err(format!("{} bits per sample not supported", raw.bits_per_sample)),
+                    _ => return Err(CoreError::UnsupportedFormat(format!("{} bits per sample", raw.bits_per_sample))),
                 },
                 2 => match raw.bits_per_sample {
                     8 => openal::AL_FORMAT_STEREO8,
                     16 => openal::AL_FORMAT_STEREO16,
-                    _ => return Err(format!("{} bits per sample not supported", raw.bits_per_sample)),
+                    _ => return Err(CoreError::UnsupportedFormat(format!("{} bits per sample", raw.bits_per_sample))),
                 },
-                _ => return Err(format!("{} channels not supported", raw.channels_count)),
+                _ => return Err(CoreError::UnsupportedFormat(format!("{} channels", raw.channels_count))),
             };
 
             let mut buffer_id = 0;
@@ -48,20 +50,20 @@ impl Sample {
 
             let error = openal::alGetError();
             if error != openal::AL_NO_ERROR as i32 {
-                return Err(format!("OpenAL error, code {}", error));
+                return Err(CoreError::OpenAL { message: "buffer creation failed".to_string(), code: error });
             }
 
-            Ok(Self { id: 0, name: None, buffer_id })
+            Ok(Self { id: Default::default(), name: None, buffer_id })
         }
     }
 }
 
 impl StorageItem for Sample {
-    fn get_id(&self) -> usize {
+    fn get_id(&self) -> Handle<Self> {
         self.id
     }
 
-    fn set_id(&mut self, id: usize) {
+    fn set_id(&mut self, id: Handle<Self>) {
         self.id = id;
     }
 
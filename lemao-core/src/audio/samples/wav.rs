@@ -1,34 +1,35 @@
 use super::*;
+use crate::error::CoreError;
 use crate::utils::binary;
 use std::fs::File;
 use std::io::Read;
 
-pub fn load(path: &str) -> Result<RawSample, String> {
+pub fn load(path: &str) -> Result<RawSample, CoreError> {
     ////////////////////////////////////////////////////////////////////////////////////////////////////
     // WAV specification: https://sites.google.com/site/musicgapi/technical-documents/wav-file-format //
     ////////////////////////////////////////////////////////////////////////////////////////////////////
 
     let mut file = match File::open(path) {
         Ok(file) => file,
-        Err(_) => return Err(format!("File {} not found", path)),
+        Err(error) => return Err(CoreError::Io { path: path.to_string(), message: error.to_string() }),
     };
 
     let mut wav = Vec::new();
-    if let Err(message) = file.read_to_end(&mut wav) {
-        return Err(format!("Error while reading file: {}", message));
+    if let Err(error) = file.read_to_end(&mut wav) {
+        return Err(CoreError::Io { path: path.to_string(), message: error.to_string() });
     }
 
     if binary::read_le_u32(&wav, 0) != 0x46464952 {
-        return Err("Invalid signature, not recognized as WAV file".to_string());
+        return Err(CoreError::parse("Invalid signature, not recognized as WAV file", Some(0)));
     }
 
     let file_size = binary::read_le_u32(&wav, 4) as usize;
     if file_size + 8 != wav.len() {
-        return Err("Invalid file, expected size doesn't match the real one".to_string());
+        return Err(CoreError::parse("Invalid file, expected size doesn't match the real one", Some(4)));
     }
 
     if binary::read_le_u32(&wav, 8) != 0x45564157 {
-        return Err("Invalid signature, not recognized as WAV file".to_string());
+        return Err(CoreError::parse("Invalid signature, not recognized as WAV file", Some(8)));
     }
 
     let mut channels_count = 0;
@@ -45,19 +46,19 @@ pub fn load(path: &str) -> Result<RawSample, String> {
 
                 let compression_code = binary::read_le_u16(&wav, index + 8);
                 if compression_code != 1 {
-                    return Err("Only PCM/uncompressed WAV is supported".to_string());
+                    return Err(CoreError::UnsupportedFormat("only PCM/uncompressed WAV is supported".to_string()));
                 }
 
                 channels_count = binary::read_le_u16(&wav, index + 10) as u32;
                 if channels_count != 1 && channels_count != 2 {
-                    return Err("Only mono and stereo WAV is supported".to_string());
+                    return Err(CoreError::UnsupportedFormat("only mono and stereo WAV is supported".to_string()));
                 }
 
                 frequency = binary::read_le_u32(&wav, index + 12);
 
                 bits_per_sample = binary::read_le_u16(&wav, index + 22) as u32;
                 if bits_per_sample != 8 && bits_per_sample != 16 {
-                    return Err("Only 8 and 16 bits per sample WAVs are supported".to_string());
+                    return Err(CoreError::UnsupportedFormat("only 8 and 16 bits per sample WAVs are supported".to_string()));
                 }
 
                 index += chunk_size as usize;
@@ -1,23 +1,25 @@
 use super::samples::Sample;
+use crate::error::CoreError;
+use crate::utils::storage::Handle;
 use crate::utils::storage::StorageItem;
 use lemao_openal::bindings::openal;
 
 pub struct Sound {
-    pub id: usize,
+    pub id: Handle<Sound>,
     pub name: Option<String>,
-    pub(crate) sample_id: usize,
+    pub(crate) sample_id: Handle<Sample>,
     pub(crate) source_id: u32,
 }
 
 impl Sound {
-    pub fn new(sample: &Sample) -> Result<Self, String> {
-        let mut sound = Sound { id: 0, name: None, sample_id: 0, source_id: 0 };
+    pub fn new(sample: &Sample) -> Result<Self, CoreError> {
+        let mut sound = Sound { id: Default::default(), name: None, sample_id: Default::default(), source_id: 0 };
         sound.set_sample(sample)?;
 
         Ok(sound)
     }
 
-    pub fn set_sample(&mut self, sample: &Sample) -> Result<(), String> {
+    pub fn set_sample(&mut self, sample: &Sample) -> Result<(), CoreError> {
         unsafe {
             if self.source_id == 0 {
                 openal::alGenSources(1, &mut self.source_id);
@@ -32,7 +34,7 @@ impl Sound {
         }
     }
 
-    pub fn get_volume(&self) -> Result<f32, String> {
+    pub fn get_volume(&self) -> Result<f32, CoreError> {
         unsafe {
             let mut volume = 0.0;
 
@@ -43,10 +45,10 @@ impl Sound {
         }
     }
 
-    pub fn set_volume(&mut self, volume: f32) -> Result<(), String> {
+    pub fn set_volume(&mut self, volume: f32) -> Result<(), CoreError> {
         unsafe {
             if !(0.0..=1.0).contains(&volume) {
-                return Err("Value of the volume expected to be between 0.0 and 1.0".to_string());
+                return Err(CoreError::InvalidArgument("value of the volume expected to be between 0.0 and 1.0".to_string()));
             }
 
             openal::alSourcef(self.source_id, openal::AL_GAIN as i32, volume);
@@ -54,7 +56,7 @@ impl Sound {
         }
     }
 
-    pub fn is_playing(&self) -> Result<bool, String> {
+    pub fn is_playing(&self) -> Result<bool, CoreError> {
         unsafe {
             let mut state = 0;
 
@@ -65,39 +67,39 @@ impl Sound {
         }
     }
 
-    pub fn play(&mut self) -> Result<(), String> {
+    pub fn play(&mut self) -> Result<(), CoreError> {
         unsafe {
             openal::alSourcePlay(self.source_id);
             self.check_al_error()
         }
     }
 
-    pub fn pause(&mut self) -> Result<(), String> {
+    pub fn pause(&mut self) -> Result<(), CoreError> {
         unsafe {
             openal::alSourcePause(self.source_id);
             self.check_al_error()
         }
     }
 
-    pub fn stop(&mut self) -> Result<(), String> {
+    pub fn stop(&mut self) -> Result<(), CoreError> {
         unsafe {
             openal::alSourceStop(self.source_id);
             self.check_al_error()
         }
     }
 
-    pub fn rewind(&mut self) -> Result<(), String> {
+    pub fn rewind(&mut self) -> Result<(), CoreError> {
         unsafe {
             openal::alSourceRewind(self.source_id);
             self.check_al_error()
         }
     }
 
-    fn check_al_error(&self) -> Result<(), String> {
+    fn check_al_error(&self) -> Result<(), CoreError> {
         unsafe {
             let error = openal::alGetError();
             if error != openal::AL_NO_ERROR as i32 {
-                return Err(format!("OpenAL error, code {}", error));
+                return Err(CoreError::OpenAL { message: "source operation failed".to_string(), code: error });
             }
 
             Ok(())
@@ -106,11 +108,11 @@ impl Sound {
 }
 
 impl StorageItem for Sound {
-    fn get_id(&self) -> usize {
+    fn get_id(&self) -> Handle<Self> {
         self.id
     }
 
-    fn set_id(&mut self, id: usize) {
+    fn set_id(&mut self, id: Handle<Self>) {
         self.id = id;
     }
 
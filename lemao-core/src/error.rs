@@ -0,0 +1,64 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CoreError {
+    Io { path: String, message: String },
+    AssetParse { message: String, offset: Option<usize> },
+    UnsupportedFormat(String),
+    OpenGL(String),
+    OpenAL { message: String, code: i32 },
+    NotFound { item: &'static str, id: Option<usize>, name: Option<String> },
+    Removed { item: &'static str, id: usize, generation: u32 },
+    AlreadyExists { item: &'static str, name: String },
+    CapacityExceeded { resource: &'static str, capacity: usize },
+    InvalidArgument(String),
+    InvalidOperation(String),
+    Platform(String),
+}
+
+impl CoreError {
+    pub fn parse(message: &str, offset: Option<usize>) -> Self {
+        Self::AssetParse { message: message.to_string(), offset }
+    }
+
+    pub fn not_found_by_id(item: &'static str, id: usize) -> Self {
+        Self::NotFound { item, id: Some(id), name: None }
+    }
+
+    pub fn not_found_by_name(item: &'static str, name: &str) -> Self {
+        Self::NotFound { item, id: None, name: Some(name.to_string()) }
+    }
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, message } => write!(f, "Error while reading file {}: {}", path, message),
+            Self::AssetParse { message, offset: Some(offset) } => write!(f, "{} at offset {}", message, offset),
+            Self::AssetParse { message, offset: None } => write!(f, "{}", message),
+            Self::UnsupportedFormat(message) => write!(f, "Unsupported format: {}", message),
+            Self::OpenGL(message) => write!(f, "OpenGL error: {}", message),
+            Self::OpenAL { message, code } => write!(f, "OpenAL error: {}, code {}", message, code),
+            Self::NotFound { item, id: Some(id), name: Some(name) } => write!(f, "{} {} ({}) not found", item, name, id),
+            Self::NotFound { item, id: Some(id), name: None } => write!(f, "{} {} not found", item, id),
+            Self::NotFound { item, id: None, name: Some(name) } => write!(f, "{} {} not found", item, name),
+            Self::NotFound { item, id: None, name: None } => write!(f, "{} not found", item),
+            Self::Removed { item, id, generation } => write!(f, "{} {}:{} has been removed", item, id, generation),
+            Self::AlreadyExists { item, name } => write!(f, "{} {} already exists", item, name),
+            Self::CapacityExceeded { resource, capacity } => write!(f, "Too many {}, capacity is {}", resource, capacity),
+            Self::InvalidArgument(message) => write!(f, "Invalid argument: {}", message),
+            Self::InvalidOperation(message) => write!(f, "Invalid operation: {}", message),
+            Self::Platform(message) => write!(f, "Platform error: {}", message),
+        }
+    }
+}
+
+impl Error for CoreError {}
+
+// Keeps applications returning plain strings working with the ? operator
+impl From<CoreError> for String {
+    fn from(error: CoreError) -> Self {
+        error.to_string()
+    }
+}
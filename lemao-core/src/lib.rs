@@ -8,6 +8,7 @@
 )]
 
 pub mod audio;
+pub mod error;
 pub mod renderer;
 pub mod utils;
 pub mod window;
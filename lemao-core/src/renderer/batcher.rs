@@ -1,11 +1,17 @@
 use super::context::RendererContext;
 use super::drawable::Color;
 use super::shaders::Shader;
+use super::shapes::Shape;
+use super::state::StateCache;
+use super::statistics::RendererStatistics;
+use crate::error::CoreError;
+use crate::utils::storage::Handle;
 use lemao_math::color::SolidColor;
 use lemao_math::mat4x4::Mat4x4;
 use lemao_math::vec4::Vec4;
 use lemao_opengl::bindings::opengl;
 use lemao_opengl::pointers::OpenGLPointers;
+use std::cell::RefCell;
 use std::ffi::c_void;
 use std::mem;
 use std::ptr;
@@ -16,6 +22,8 @@ pub struct BatchRenderer {
     pub(crate) vbo_gl_id: u32,
     pub(crate) ebo_gl_id: u32,
     gl: Rc<OpenGLPointers>,
+    state: Rc<StateCache>,
+    statistics: Rc<RefCell<RendererStatistics>>,
 
     max_vertices_count: usize,
     max_indices_count: usize,
@@ -28,7 +36,7 @@ pub struct BatchRenderer {
 }
 
 pub struct Batch<'a> {
-    pub(crate) shape_id: Option<usize>,
+    pub(crate) shape_id: Option<Handle<Shape>>,
     pub(crate) vertices: Option<&'a Vec<f32>>,
     pub(crate) indices: Option<&'a Vec<u32>>,
     pub(crate) texture_gl_id: Option<u32>,
@@ -39,23 +47,24 @@ impl BatchRenderer {
     pub fn new(renderer: &RendererContext, max_vertices_count: usize, max_indices_count: usize) -> Self {
         unsafe {
             let gl = renderer.gl.clone();
+            let state = renderer.state.clone();
 
             let mut vao_gl_id = 0;
             (gl.glGenVertexArrays)(1, &mut vao_gl_id);
-            (gl.glBindVertexArray)(vao_gl_id);
+            state.bind_vertex_array(vao_gl_id);
 
             let data_size = (mem::size_of::<f32>() * max_vertices_count) as i64;
 
             let mut vbo_gl_id = 0;
             (gl.glGenBuffers)(1, &mut vbo_gl_id);
-            (gl.glBindBuffer)(opengl::GL_ARRAY_BUFFER, vbo_gl_id);
+            state.bind_buffer(opengl::GL_ARRAY_BUFFER, vbo_gl_id);
             (gl.glBufferData)(opengl::GL_ARRAY_BUFFER, data_size, ptr::null(), opengl::GL_STATIC_DRAW);
 
             let indices_size = (mem::size_of::<u32>() * max_indices_count) as i64;
 
             let mut ebo_gl_id = 0;
             (gl.glGenBuffers)(1, &mut ebo_gl_id);
-            (gl.glBindBuffer)(opengl::GL_ELEMENT_ARRAY_BUFFER, ebo_gl_id);
+            state.bind_buffer(opengl::GL_ELEMENT_ARRAY_BUFFER, ebo_gl_id);
             (gl.glBufferData)(opengl::GL_ELEMENT_ARRAY_BUFFER, indices_size, ptr::null(), opengl::GL_STATIC_DRAW);
 
             let attrib_size = (9 * mem::size_of::<f32>()) as i32;
@@ -72,6 +81,8 @@ impl BatchRenderer {
                 vbo_gl_id,
                 ebo_gl_id,
                 gl,
+                state,
+                statistics: renderer.frame_statistics.clone(),
                 max_vertices_count,
                 max_indices_count,
                 first_batch_added: false,
@@ -84,14 +95,14 @@ impl BatchRenderer {
         }
     }
 
-    pub fn add(&mut self, transformation_matrix: Mat4x4, batch: &Batch) -> Result<(), String> {
+    pub fn add(&mut self, transformation_matrix: Mat4x4, batch: &Batch, uv_rect: Vec4) -> Result<(), CoreError> {
         if self.first_batch_added {
             if self.texture_gl_id != batch.texture_gl_id.unwrap() {
-                return Err("Invalid texture".to_string());
+                return Err(CoreError::InvalidOperation("batches must share the same texture".to_string()));
             }
 
             if self.color != *batch.color.unwrap() {
-                return Err("Invalid color".to_string());
+                return Err(CoreError::InvalidOperation("batches must share the same color".to_string()));
             }
         } else {
             self.texture_gl_id = batch.texture_gl_id.unwrap();
@@ -100,12 +111,12 @@ impl BatchRenderer {
 
         let vertices = batch.vertices.unwrap();
         if self.vertices.len() + vertices.len() > self.max_vertices_count {
-            return Err("Too many vertices".to_string());
+            return Err(CoreError::CapacityExceeded { resource: "vertices", capacity: self.max_vertices_count });
         }
 
         let indices = batch.indices.unwrap();
         if self.indices.len() + indices.len() > self.max_indices_count {
-            return Err("Too many indices".to_string());
+            return Err(CoreError::CapacityExceeded { resource: "indices", capacity: self.max_indices_count });
         }
 
         for index in 0..(vertices.len() / 9) {
@@ -120,8 +131,8 @@ impl BatchRenderer {
             self.vertices.push(vertices[index * 9 + 4]);
             self.vertices.push(vertices[index * 9 + 5]);
             self.vertices.push(vertices[index * 9 + 6]);
-            self.vertices.push(vertices[index * 9 + 7]);
-            self.vertices.push(vertices[index * 9 + 8]);
+            self.vertices.push(uv_rect.x + vertices[index * 9 + 7] * uv_rect.z);
+            self.vertices.push(uv_rect.y + vertices[index * 9 + 8] * uv_rect.w);
         }
 
         let base_indice = self.max_indice;
@@ -139,26 +150,30 @@ impl BatchRenderer {
         &self.color
     }
 
-    pub fn draw(&mut self, shader: &Shader) -> Result<(), String> {
+    pub fn draw(&mut self, shader: &Shader) -> Result<(), CoreError> {
         unsafe {
             let data_size = (mem::size_of::<f32>() * self.vertices.len()) as i64;
             let data_ptr = self.vertices.as_ptr() as *const c_void;
 
-            (self.gl.glBindVertexArray)(self.vao_gl_id);
-            (self.gl.glBindBuffer)(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
+            self.state.bind_vertex_array(self.vao_gl_id);
+            self.state.bind_buffer(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
             (self.gl.glBufferData)(opengl::GL_ARRAY_BUFFER, data_size, data_ptr, opengl::GL_STATIC_DRAW);
+            self.statistics.borrow_mut().add_buffer_upload(data_size);
 
             let indices_size = (mem::size_of::<u32>() * self.indices.len()) as i64;
             let indices_ptr = self.indices.as_ptr() as *const c_void;
 
-            (self.gl.glBindBuffer)(opengl::GL_ELEMENT_ARRAY_BUFFER, self.ebo_gl_id);
+            self.state.bind_buffer(opengl::GL_ELEMENT_ARRAY_BUFFER, self.ebo_gl_id);
             (self.gl.glBufferData)(opengl::GL_ELEMENT_ARRAY_BUFFER, indices_size, indices_ptr, opengl::GL_STATIC_DRAW);
+            self.statistics.borrow_mut().add_buffer_upload(indices_size);
 
             shader.set_parameter("model", Mat4x4::identity().as_ptr())?;
+            shader.set_uv_rect(Vec4::new(0.0, 0.0, 1.0, 1.0))?;
             shader.set_color(&self.color)?;
 
-            (self.gl.glBindTexture)(opengl::GL_TEXTURE_2D, self.texture_gl_id);
+            self.state.bind_texture(0, self.texture_gl_id);
             (self.gl.glDrawElements)(opengl::GL_TRIANGLES, self.indices.len() as i32, opengl::GL_UNSIGNED_INT, ptr::null());
+            self.statistics.borrow_mut().add_draw_call(self.vertices.len() / 9, self.indices.len());
 
             self.clear();
 
@@ -177,7 +192,7 @@ impl BatchRenderer {
 
 impl<'a> Batch<'a> {
     pub fn new(
-        shape_id: Option<usize>,
+        shape_id: Option<Handle<Shape>>,
         vertices: Option<&'a Vec<f32>>,
         indices: Option<&'a Vec<u32>>,
         texture_gl_id: Option<u32>,
@@ -1,24 +1,32 @@
+use crate::utils::storage::Handle;
 use crate::utils::storage::StorageItem;
 use lemao_math::mat4x4::Mat4x4;
 use lemao_math::vec2::Vec2;
 use lemao_math::vec3::Vec3;
 
 pub struct Camera {
-    pub id: usize,
+    pub id: Handle<Camera>,
     pub name: Option<String>,
 
     pub position: Vec2,
     pub size: Vec2,
+    pub scale_factor: f32,
     pub dirty: bool,
 }
 
 impl Camera {
     pub fn new(position: Vec2, size: Vec2) -> Self {
-        Self { id: 0, name: None, position, size, dirty: false }
+        Self { id: Default::default(), name: None, position, size, scale_factor: 1.0, dirty: false }
+    }
+
+    // Size in logical pixels, which are used by everything drawn with this camera when the scale factor is different than 1
+    pub fn get_logical_size(&self) -> Vec2 {
+        self.size / self.scale_factor
     }
 
     pub(crate) fn get_projection_matrix(&self) -> Mat4x4 {
-        Mat4x4::ortho(self.size.x, self.size.y, 0.1, 100.0)
+        let logical_size = self.get_logical_size();
+        Mat4x4::ortho(logical_size.x, logical_size.y, 0.1, 100.0)
     }
 
     pub(crate) fn get_view_matrix(&self) -> Mat4x4 {
@@ -27,11 +35,11 @@ impl Camera {
 }
 
 impl StorageItem for Camera {
-    fn get_id(&self) -> usize {
+    fn get_id(&self) -> Handle<Self> {
         self.id
     }
 
-    fn set_id(&mut self, id: usize) {
+    fn set_id(&mut self, id: Handle<Self>) {
         self.id = id;
     }
 
@@ -1,22 +1,38 @@
 use super::batcher::BatchRenderer;
 use super::cameras::Camera;
+use super::debug::DebugRenderer;
+use super::debug::DebugShape;
 use super::drawable::circle::Circle;
 use super::drawable::disc::Disc;
 use super::drawable::frame::Frame;
 use super::drawable::line::Line;
+use super::drawable::mesh::Mesh;
+use super::drawable::mesh::MeshPrimitive;
 use super::drawable::rectangle::Rectangle;
 use super::drawable::text::Text;
 use super::drawable::tilemap::Tilemap;
 use super::drawable::Color;
 use super::drawable::Drawable;
 use super::fonts::Font;
+use super::fonts::FontType;
+use super::instancer::InstanceRenderer;
+use super::lighting::LightingSystem;
+use super::postprocessing::PostProcessor;
 use super::shaders::Shader;
 use super::shaders::DEFAULT_VERTEX_SHADER;
 use super::shaders::GRADIENT_FRAGMENT_SHADER;
+use super::shaders::INSTANCED_VERTEX_SHADER;
+use super::shaders::SDF_FRAGMENT_SHADER;
 use super::shaders::SOLID_FRAGMENT_SHADER;
 use super::shapes::Shape;
+use super::state::StateCache;
+use super::statistics::GpuTimer;
+use super::statistics::RendererStatistics;
 use super::textures::RawTexture;
 use super::textures::Texture;
+use super::vectors::RawVectorImage;
+use crate::error::CoreError;
+use crate::utils::storage::Handle;
 use crate::utils::storage::Storage;
 use lemao_common_platform::renderer::RendererPlatformSpecific;
 use lemao_math::color::SolidColor;
@@ -24,24 +40,33 @@ use lemao_math::vec2::Vec2;
 use lemao_math::vec3::Vec3;
 use lemao_opengl::bindings::opengl;
 use lemao_opengl::pointers::OpenGLPointers;
+use std::cell::RefCell;
 use std::ffi::c_void;
 use std::rc::Rc;
 
 pub struct RendererContext {
     pub(crate) gl: Rc<OpenGLPointers>,
+    pub(crate) state: Rc<StateCache>,
 
     pub viewport_size: Vec2,
-    pub default_camera_id: usize,
-    pub active_camera_id: usize,
-    pub default_solid_shader_id: usize,
-    pub default_gradient_shader_id: usize,
-    pub active_shader_id: usize,
-    pub default_line_shape_id: usize,
-    pub default_rectangle_shape_id: usize,
-    pub default_sprite_shape_id: usize,
-    pub default_texture_id: usize,
+    pub default_camera_id: Handle<Camera>,
+    pub active_camera_id: Handle<Camera>,
+    pub default_solid_shader_id: Handle<Shader>,
+    pub default_gradient_shader_id: Handle<Shader>,
+    pub default_sdf_shader_id: Handle<Shader>,
+    pub default_instanced_shader_id: Handle<Shader>,
+    pub active_shader_id: Handle<Shader>,
+    pub default_line_shape_id: Handle<Shape>,
+    pub default_rectangle_shape_id: Handle<Shape>,
+    pub default_sprite_shape_id: Handle<Shape>,
+    pub default_texture_id: Handle<Texture>,
     pub swap_interval: u32,
+    pub statistics: RendererStatistics,
 
+    pub(crate) frame_statistics: Rc<RefCell<RendererStatistics>>,
+    gpu_timer: Option<GpuTimer>,
+    clip_rectangles: Vec<(Vec2, Vec2)>,
+    clip_masks_count: u32,
     renderer_platform_specific: Box<dyn RendererPlatformSpecific>,
 
     pub textures: Storage<Texture>,
@@ -51,25 +76,40 @@ pub struct RendererContext {
     pub shapes: Storage<Shape>,
 
     pub batch_renderer: Option<BatchRenderer>,
+    pub instance_renderer: Option<InstanceRenderer>,
+    pub post_processor: Option<PostProcessor>,
+    pub lighting: Option<LightingSystem>,
+    pub debug: Option<DebugRenderer>,
 }
 
 impl RendererContext {
-    pub fn new(renderer_platform_specific: Box<dyn RendererPlatformSpecific>, viewport_size: Vec2) -> Result<Self, String> {
+    pub fn new(renderer_platform_specific: Box<dyn RendererPlatformSpecific>, viewport_size: Vec2) -> Result<Self, CoreError> {
+        let gl = Rc::new(OpenGLPointers::new(renderer_platform_specific.get_profile()));
+        let frame_statistics: Rc<RefCell<RendererStatistics>> = Default::default();
+
         Ok(RendererContext {
-            gl: Default::default(),
+            gl: gl.clone(),
+            state: Rc::new(StateCache::new(gl, frame_statistics.clone())),
 
             viewport_size,
-            default_camera_id: 0,
-            active_camera_id: 0,
-            default_solid_shader_id: 0,
-            default_gradient_shader_id: 0,
-            active_shader_id: 0,
-            default_line_shape_id: 0,
-            default_rectangle_shape_id: 0,
-            default_sprite_shape_id: 0,
-            default_texture_id: 0,
+            default_camera_id: Default::default(),
+            active_camera_id: Default::default(),
+            default_solid_shader_id: Default::default(),
+            default_gradient_shader_id: Default::default(),
+            default_sdf_shader_id: Default::default(),
+            default_instanced_shader_id: Default::default(),
+            active_shader_id: Default::default(),
+            default_line_shape_id: Default::default(),
+            default_rectangle_shape_id: Default::default(),
+            default_sprite_shape_id: Default::default(),
+            default_texture_id: Default::default(),
             swap_interval: 0,
+            statistics: Default::default(),
 
+            frame_statistics,
+            gpu_timer: None,
+            clip_rectangles: Vec::new(),
+            clip_masks_count: 0,
             renderer_platform_specific,
 
             textures: Default::default(),
@@ -79,17 +119,23 @@ impl RendererContext {
             shapes: Default::default(),
 
             batch_renderer: None,
+            instance_renderer: None,
+            post_processor: None,
+            lighting: None,
+            debug: None,
         })
     }
 
-    pub fn init(&mut self) -> Result<(), String> {
+    pub fn init(&mut self) -> Result<(), CoreError> {
         #[cfg(debug_assertions)]
-        unsafe {
-            (self.gl.glEnable)(opengl::GL_DEBUG_OUTPUT);
-            (self.gl.glDebugMessageCallback)(gl_error, std::ptr::null_mut());
-
-            // Test error handler
-            // (self.gl.glEnable)(99999);
+        if let Some(debug_message_callback) = self.gl.glDebugMessageCallback {
+            unsafe {
+                (self.gl.glEnable)(opengl::GL_DEBUG_OUTPUT);
+                debug_message_callback(gl_error, std::ptr::null_mut());
+
+                // Test error handler
+                // (self.gl.glEnable)(99999);
+            }
         }
 
         self.init_default_camera()?;
@@ -98,11 +144,15 @@ impl RendererContext {
         self.init_default_shapes()?;
         self.init_default_texture()?;
         self.init_batch_renderer();
+        self.init_instance_renderer();
+        self.init_post_processor()?;
+        self.init_lighting()?;
+        self.init_debug();
 
         Ok(())
     }
 
-    pub fn init_default_camera(&mut self) -> Result<(), String> {
+    pub fn init_default_camera(&mut self) -> Result<(), CoreError> {
         let camera = Camera::new(Default::default(), Default::default());
         self.default_camera_id = self.cameras.store(camera);
         self.set_camera_as_active(self.default_camera_id)?;
@@ -110,17 +160,23 @@ impl RendererContext {
         Ok(())
     }
 
-    pub fn init_default_shaders(&mut self) -> Result<(), String> {
+    pub fn init_default_shaders(&mut self) -> Result<(), CoreError> {
         let solid_shader = Shader::new(self, DEFAULT_VERTEX_SHADER, SOLID_FRAGMENT_SHADER)?;
         self.default_solid_shader_id = self.shaders.store(solid_shader);
 
         let gradient_shader = Shader::new(self, DEFAULT_VERTEX_SHADER, GRADIENT_FRAGMENT_SHADER)?;
         self.default_gradient_shader_id = self.shaders.store(gradient_shader);
 
+        let sdf_shader = Shader::new(self, DEFAULT_VERTEX_SHADER, SDF_FRAGMENT_SHADER)?;
+        self.default_sdf_shader_id = self.shaders.store(sdf_shader);
+
+        let instanced_shader = Shader::new(self, INSTANCED_VERTEX_SHADER, SOLID_FRAGMENT_SHADER)?;
+        self.default_instanced_shader_id = self.shaders.store(instanced_shader);
+
         Ok(())
     }
 
-    pub fn init_default_shapes(&mut self) -> Result<(), String> {
+    pub fn init_default_shapes(&mut self) -> Result<(), CoreError> {
         let sprite_shape = Shape::new(
             self,
             vec![Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 0.0), Vec3::new(0.0, 1.0, 0.0)],
@@ -151,7 +207,7 @@ impl RendererContext {
         Ok(())
     }
 
-    pub fn init_default_texture(&mut self) -> Result<(), String> {
+    pub fn init_default_texture(&mut self) -> Result<(), CoreError> {
         let texture = Texture::new(self, &RawTexture::new(Vec2::new(1.0, 1.0), vec![255, 255, 255, 255]))?;
         self.default_texture_id = self.textures.store(texture);
 
@@ -162,7 +218,25 @@ impl RendererContext {
         self.batch_renderer = Some(BatchRenderer::new(self, 1024 * 1024, 1024 * 1024));
     }
 
-    pub fn set_viewport_size(&mut self, size: Vec2) -> Result<(), String> {
+    pub fn init_instance_renderer(&mut self) {
+        self.instance_renderer = Some(InstanceRenderer::new(self, 64 * 1024));
+    }
+
+    pub fn init_post_processor(&mut self) -> Result<(), CoreError> {
+        self.post_processor = Some(PostProcessor::new(self)?);
+        Ok(())
+    }
+
+    pub fn init_lighting(&mut self) -> Result<(), CoreError> {
+        self.lighting = Some(LightingSystem::new(self)?);
+        Ok(())
+    }
+
+    pub fn init_debug(&mut self) {
+        self.debug = Some(DebugRenderer::new(self));
+    }
+
+    pub fn set_viewport_size(&mut self, size: Vec2) -> Result<(), CoreError> {
         unsafe {
             (self.gl.glViewport)(0, 0, size.x as i32, size.y as i32);
             self.cameras.get_mut(self.active_camera_id)?.size = size;
@@ -172,7 +246,7 @@ impl RendererContext {
         }
     }
 
-    pub fn set_shader_as_active(&mut self, shader_id: usize) -> Result<(), String> {
+    pub fn set_shader_as_active(&mut self, shader_id: Handle<Shader>) -> Result<(), CoreError> {
         let shader = self.shaders.get_mut(shader_id)?;
 
         self.active_shader_id = shader_id;
@@ -180,7 +254,7 @@ impl RendererContext {
         Ok(())
     }
 
-    pub fn set_camera_as_active(&mut self, camera_id: usize) -> Result<(), String> {
+    pub fn set_camera_as_active(&mut self, camera_id: Handle<Camera>) -> Result<(), CoreError> {
         let camera = self.cameras.get_mut(camera_id)?;
 
         self.active_camera_id = camera_id;
@@ -189,28 +263,28 @@ impl RendererContext {
         Ok(())
     }
 
-    pub fn create_circle(&mut self) -> Result<Circle, String> {
+    pub fn create_circle(&mut self) -> Result<Circle, CoreError> {
         let texture = self.textures.get(self.default_texture_id)?;
         let circle = Circle::new(self, texture);
 
         Ok(circle)
     }
 
-    pub fn create_disc(&mut self) -> Result<Disc, String> {
+    pub fn create_disc(&mut self) -> Result<Disc, CoreError> {
         let texture = self.textures.get(self.default_texture_id)?;
         let disc = Disc::new(self, texture);
 
         Ok(disc)
     }
 
-    pub fn create_frame(&mut self) -> Result<Frame, String> {
+    pub fn create_frame(&mut self) -> Result<Frame, CoreError> {
         let texture = self.textures.get(self.default_texture_id)?;
         let frame = Frame::new(self, texture);
 
         Ok(frame)
     }
 
-    pub fn create_line(&mut self) -> Result<Line, String> {
+    pub fn create_line(&mut self) -> Result<Line, CoreError> {
         let shape = self.shapes.get(self.default_line_shape_id)?;
         let texture = self.textures.get(self.default_texture_id)?;
         let line = Line::new(self, shape, texture);
@@ -218,7 +292,21 @@ impl RendererContext {
         Ok(line)
     }
 
-    pub fn create_rectangle(&mut self) -> Result<Rectangle, String> {
+    pub fn create_mesh(&mut self, primitive: MeshPrimitive) -> Result<Mesh, CoreError> {
+        let texture = self.textures.get(self.default_texture_id)?;
+        let mesh = Mesh::new(self, texture, primitive);
+
+        Ok(mesh)
+    }
+
+    pub fn create_mesh_from_vector(&mut self, raw: &RawVectorImage) -> Result<Mesh, CoreError> {
+        let mut mesh = self.create_mesh(MeshPrimitive::Triangles)?;
+        mesh.set_geometry(&raw.vertices, &raw.indices)?;
+
+        Ok(mesh)
+    }
+
+    pub fn create_rectangle(&mut self) -> Result<Rectangle, CoreError> {
         let shape = self.shapes.get(self.default_rectangle_shape_id)?;
         let texture = self.textures.get(self.default_texture_id)?;
         let rectangle = Rectangle::new(self, shape, texture);
@@ -226,14 +314,14 @@ impl RendererContext {
         Ok(rectangle)
     }
 
-    pub fn create_text(&mut self, font_id: usize) -> Result<Text, String> {
+    pub fn create_text(&mut self, font_id: Handle<Font>) -> Result<Text, CoreError> {
         let font = self.fonts.get(font_id)?;
         let text = Text::new(self, font);
 
         Ok(text)
     }
 
-    pub fn create_tilemap(&mut self, texture_id: usize) -> Result<Tilemap, String> {
+    pub fn create_tilemap(&mut self, texture_id: Handle<Texture>) -> Result<Tilemap, CoreError> {
         let texture = self.textures.get(texture_id)?;
         let tilemap = Tilemap::new(self, texture);
 
@@ -241,19 +329,75 @@ impl RendererContext {
     }
 
     pub fn enable_scissor(&self, position: Vec2, size: Vec2) {
-        unsafe {
-            (self.gl.glEnable)(opengl::GL_SCISSOR_TEST);
-            (self.gl.glScissor)(position.x as i32, position.y as i32, size.x as i32, size.y as i32);
-        }
+        self.state.set_scissor(true);
+        self.state.set_scissor_box(position.x as i32, position.y as i32, size.x as i32, size.y as i32);
     }
 
     pub fn disable_scissor(&self) {
-        unsafe {
-            (self.gl.glDisable)(opengl::GL_SCISSOR_TEST);
+        self.state.set_scissor(false);
+    }
+
+    pub fn push_clip_rectangle(&mut self, position: Vec2, size: Vec2) {
+        // Nested rectangle can't exceed its parent, so only the common part is used
+        let (position, size) = match self.clip_rectangles.last() {
+            Some((parent_position, parent_size)) => {
+                let min = Vec2::new(position.x.max(parent_position.x), position.y.max(parent_position.y));
+                let max = Vec2::new((position.x + size.x).min(parent_position.x + parent_size.x), (position.y + size.y).min(parent_position.y + parent_size.y));
+
+                (min, Vec2::new((max.x - min.x).max(0.0), (max.y - min.y).max(0.0)))
+            }
+            None => (position, size),
+        };
+
+        self.clip_rectangles.push((position, size));
+        self.enable_clip_scissor(position, size);
+    }
+
+    pub fn pop_clip_rectangle(&mut self) -> Result<(), CoreError> {
+        if self.clip_rectangles.pop().is_none() {
+            return Err(CoreError::InvalidOperation("clip rectangle stack is empty".to_string()));
+        }
+
+        match self.clip_rectangles.last() {
+            Some((position, size)) => self.enable_clip_scissor(*position, *size),
+            None => self.disable_scissor(),
+        }
+
+        Ok(())
+    }
+
+    pub fn push_clip_mask<T: Drawable>(&mut self, drawable: &mut T) -> Result<(), CoreError> {
+        if self.clip_masks_count == 255 {
+            return Err(CoreError::CapacityExceeded { resource: "clip masks", capacity: 255 });
         }
+
+        // Mask is drawn only where all previous masks passed, so the stencil value equals the nesting level
+        self.draw_clip_mask(drawable, opengl::GL_INCR)?;
+        self.clip_masks_count += 1;
+        self.update_stencil_test();
+
+        Ok(())
+    }
+
+    pub fn pop_clip_mask<T: Drawable>(&mut self, drawable: &mut T) -> Result<(), CoreError> {
+        if self.clip_masks_count == 0 {
+            return Err(CoreError::InvalidOperation("clip mask stack is empty".to_string()));
+        }
+
+        self.draw_clip_mask(drawable, opengl::GL_DECR)?;
+        self.clip_masks_count -= 1;
+        self.update_stencil_test();
+
+        Ok(())
     }
 
-    pub fn batcher_add_drawable<T: Drawable>(&mut self, drawable: &T) -> Result<(), String> {
+    pub fn batcher_add_drawable<T: Drawable>(&mut self, drawable: &T) -> Result<(), CoreError> {
+        if let Some(FontType::Sdf | FontType::Msdf) = drawable.get_font_type() {
+            return Err(CoreError::InvalidOperation("distance field fonts can't be batched".to_string()));
+        }
+
+        self.check_drawable_resources(drawable)?;
+
         let transformation_matrix = drawable.get_transformation_matrix();
         let mut batch = drawable.get_batch();
 
@@ -263,11 +407,15 @@ impl RendererContext {
             batch.indices = Some(&shape.indices);
         }
 
-        self.batch_renderer.as_mut().unwrap().add(transformation_matrix, &batch)?;
+        if batch.indices.is_none() {
+            return Err(CoreError::InvalidOperation("only triangle based drawables can be batched".to_string()));
+        }
+
+        self.batch_renderer.as_mut().unwrap().add(transformation_matrix, &batch, drawable.get_uv_rect())?;
         Ok(())
     }
 
-    pub fn batcher_draw(&mut self) -> Result<(), String> {
+    pub fn batcher_draw(&mut self) -> Result<(), CoreError> {
         let shader_id = match self.batch_renderer.as_ref().unwrap().get_color() {
             Color::SolidColor(_) => self.default_solid_shader_id,
             Color::Gradient(_) => self.default_gradient_shader_id,
@@ -283,15 +431,70 @@ impl RendererContext {
             camera.dirty = false;
         }
 
+        self.frame_statistics.borrow_mut().batches += 1;
+
         self.batch_renderer.as_mut().unwrap().draw(self.shaders.get(shader_id)?)
     }
 
-    pub fn draw<T: Drawable>(&mut self, drawable: &mut T) -> Result<(), String> {
+    pub fn instancer_add_drawable<T: Drawable>(&mut self, drawable: &T) -> Result<(), CoreError> {
+        if drawable.get_font_type().is_some() {
+            return Err(CoreError::InvalidOperation("texts can't be instanced".to_string()));
+        }
+
+        self.check_drawable_resources(drawable)?;
+
+        let color = match drawable.get_color() {
+            Color::SolidColor(color) => *color,
+            Color::Gradient(_) => return Err(CoreError::InvalidOperation("gradient colors can't be instanced".to_string())),
+        };
+
+        let batch = drawable.get_batch();
+        let shape_id = match batch.shape_id {
+            Some(shape_id) => shape_id,
+            None => return Err(CoreError::InvalidOperation("only drawables using a shared shape can be instanced".to_string())),
+        };
+
+        let texture_gl_id = match batch.texture_gl_id {
+            Some(texture_gl_id) => texture_gl_id,
+            None => return Err(CoreError::InvalidOperation("only textured drawables can be instanced".to_string())),
+        };
+
+        let transformation_matrix = drawable.get_transformation_matrix();
+        let uv_rect = drawable.get_uv_rect();
+
+        self.instance_renderer.as_mut().unwrap().add(shape_id, texture_gl_id, transformation_matrix, color, uv_rect)
+    }
+
+    pub fn instancer_draw(&mut self) -> Result<(), CoreError> {
+        let batches_count = self.instance_renderer.as_ref().unwrap().get_batches_count();
+        if batches_count == 0 {
+            return Ok(());
+        }
+
+        let shader_id = self.default_instanced_shader_id;
+
+        if shader_id != self.active_shader_id || self.cameras.get(self.active_camera_id)?.dirty {
+            self.set_shader_as_active(shader_id)?;
+
+            let camera = self.cameras.get_mut(self.active_camera_id)?;
+            let shader = self.shaders.get(shader_id)?;
+            shader.set_parameter("proj", camera.get_projection_matrix().as_ptr())?;
+            shader.set_parameter("view", camera.get_view_matrix().as_ptr())?;
+            camera.dirty = false;
+        }
+
+        self.frame_statistics.borrow_mut().batches += batches_count as u32;
+        self.instance_renderer.as_mut().unwrap().draw(&self.shapes, self.shaders.get(shader_id)?)
+    }
+
+    pub fn draw<T: Drawable>(&mut self, drawable: &mut T) -> Result<(), CoreError> {
+        self.check_drawable_resources(drawable)?;
         let color = drawable.get_color();
 
-        let shader_id = match color {
-            Color::SolidColor(_) => self.default_solid_shader_id,
-            Color::Gradient(_) => self.default_gradient_shader_id,
+        let shader_id = match (drawable.get_font_type(), color) {
+            (Some(FontType::Sdf | FontType::Msdf), _) => self.default_sdf_shader_id,
+            (_, Color::SolidColor(_)) => self.default_solid_shader_id,
+            (_, Color::Gradient(_)) => self.default_gradient_shader_id,
         };
 
         if shader_id != self.active_shader_id || self.cameras.get(self.active_camera_id)?.dirty {
@@ -304,17 +507,180 @@ impl RendererContext {
             camera.dirty = false;
         }
 
+        let batch = drawable.get_batch();
+        let (vertices_count, indices_count) = match batch.shape_id {
+            Some(shape_id) => {
+                let shape = self.shapes.get(shape_id)?;
+                (shape.vertices.len() / 9, shape.indices.len())
+            }
+            None => (batch.vertices.map(|p| p.len() / 9).unwrap_or(0), batch.indices.map(|p| p.len()).unwrap_or(0)),
+        };
+        self.frame_statistics.borrow_mut().add_draw_call(vertices_count, indices_count);
+
         let shader = self.shaders.get(shader_id)?;
+        shader.set_uv_rect(drawable.get_uv_rect())?;
         drawable.draw(shader)?;
 
         Ok(())
     }
 
+    pub fn begin_frame(&mut self) {
+        *self.frame_statistics.borrow_mut() = Default::default();
+
+        if let Some(gpu_timer) = &mut self.gpu_timer {
+            gpu_timer.begin();
+        }
+    }
+
+    pub fn end_frame(&mut self) {
+        self.statistics = *self.frame_statistics.borrow();
+
+        // Debug shapes not flushed during this frame would pile up otherwise
+        if let Some(debug) = &mut self.debug {
+            debug.remove_expired();
+        }
+
+        if let Some(gpu_timer) = &mut self.gpu_timer {
+            gpu_timer.end();
+            self.statistics.gpu_time = gpu_timer.get_time();
+        }
+    }
+
+    pub fn enable_gpu_timer(&mut self) -> Result<(), CoreError> {
+        if self.gpu_timer.is_none() {
+            self.gpu_timer = Some(GpuTimer::new(self.gl.clone())?);
+        }
+
+        Ok(())
+    }
+
+    pub fn disable_gpu_timer(&mut self) {
+        self.gpu_timer = None;
+    }
+
+    pub fn invalidate_state_cache(&self) {
+        self.state.invalidate();
+    }
+
+    pub fn begin_post_processing(&mut self) -> Result<(), CoreError> {
+        let mut post_processor = self.post_processor.take().ok_or_else(|| CoreError::InvalidOperation("post-processor hasn't been initialized".to_string()))?;
+        let result = post_processor.begin(self);
+        self.post_processor = Some(post_processor);
+
+        result
+    }
+
+    pub fn end_post_processing(&mut self) -> Result<(), CoreError> {
+        let mut post_processor = self.post_processor.take().ok_or_else(|| CoreError::InvalidOperation("post-processor hasn't been initialized".to_string()))?;
+        let result = post_processor.end(self);
+        self.post_processor = Some(post_processor);
+
+        result
+    }
+
+    pub fn apply_lighting(&mut self) -> Result<(), CoreError> {
+        let mut lighting = self.lighting.take().unwrap();
+        let result = lighting.apply(self);
+        self.lighting = Some(lighting);
+
+        result
+    }
+
+    pub fn debug_add_line(&mut self, from: Vec2, to: Vec2, color: SolidColor, duration: Option<f32>) {
+        self.debug.as_mut().unwrap().add(DebugShape::Line { from, to }, color, self.active_camera_id, duration);
+    }
+
+    pub fn debug_add_rectangle(&mut self, position: Vec2, size: Vec2, color: SolidColor, duration: Option<f32>) {
+        let points = vec![position, position + Vec2::new(size.x, 0.0), position + size, position + Vec2::new(0.0, size.y)];
+        self.debug.as_mut().unwrap().add(DebugShape::Polygon { points }, color, self.active_camera_id, duration);
+    }
+
+    pub fn debug_add_polygon(&mut self, points: Vec<Vec2>, color: SolidColor, duration: Option<f32>) {
+        self.debug.as_mut().unwrap().add(DebugShape::Polygon { points }, color, self.active_camera_id, duration);
+    }
+
+    pub fn debug_add_circle(&mut self, center: Vec2, radius: f32, color: SolidColor, duration: Option<f32>) {
+        self.debug.as_mut().unwrap().add(DebugShape::Circle { center, radius }, color, self.active_camera_id, duration);
+    }
+
+    pub fn debug_add_arrow(&mut self, from: Vec2, to: Vec2, color: SolidColor, duration: Option<f32>) {
+        self.debug.as_mut().unwrap().add(DebugShape::Arrow { from, to }, color, self.active_camera_id, duration);
+    }
+
+    pub fn debug_add_text(&mut self, position: Vec2, text: &str, color: SolidColor, duration: Option<f32>) {
+        self.debug.as_mut().unwrap().add(DebugShape::Text { position, text: text.to_string() }, color, self.active_camera_id, duration);
+    }
+
+    pub fn debug_draw(&mut self) -> Result<(), CoreError> {
+        let mut debug = self.debug.take().unwrap();
+        let result = debug.draw(self);
+        self.debug = Some(debug);
+
+        result
+    }
+
     pub fn clear(&self, color: SolidColor) {
+        // Clear color is encoded by sRGB framebuffer the same way as the shader output
+        let color = if self.is_srgb() { color.to_linear() } else { color };
+
         unsafe {
             (self.gl.glClearColor)(color.r, color.g, color.b, color.a);
-            (self.gl.glClear)(opengl::GL_COLOR_BUFFER_BIT);
+            (self.gl.glClear)(opengl::GL_COLOR_BUFFER_BIT | opengl::GL_STENCIL_BUFFER_BIT);
+        }
+    }
+
+    // Drawables keep GL names of their resources, so using them after the resource was removed would touch a deleted or recycled object
+    fn check_drawable_resources<T: Drawable>(&self, drawable: &T) -> Result<(), CoreError> {
+        if let Some(texture_id) = drawable.get_texture_id() {
+            self.textures.get(texture_id)?;
+        }
+
+        if let Some(font_id) = drawable.get_font_id() {
+            self.fonts.get(font_id)?;
+        }
+
+        if let Some(shape_id) = drawable.get_batch().shape_id {
+            self.shapes.get(shape_id)?;
         }
+
+        Ok(())
+    }
+
+    fn draw_clip_mask<T: Drawable>(&mut self, drawable: &mut T, operation: u32) -> Result<(), CoreError> {
+        self.state.set_stencil(true);
+
+        unsafe {
+            (self.gl.glColorMask)(opengl::GL_FALSE as u8, opengl::GL_FALSE as u8, opengl::GL_FALSE as u8, opengl::GL_FALSE as u8);
+            (self.gl.glStencilFunc)(opengl::GL_EQUAL, self.clip_masks_count as i32, 0xff);
+            (self.gl.glStencilOp)(opengl::GL_KEEP, opengl::GL_KEEP, operation);
+        }
+
+        let result = self.draw(drawable);
+
+        unsafe {
+            (self.gl.glColorMask)(opengl::GL_TRUE as u8, opengl::GL_TRUE as u8, opengl::GL_TRUE as u8, opengl::GL_TRUE as u8);
+            (self.gl.glStencilOp)(opengl::GL_KEEP, opengl::GL_KEEP, opengl::GL_KEEP);
+        }
+
+        result
+    }
+
+    fn update_stencil_test(&self) {
+        if self.clip_masks_count == 0 {
+            self.state.set_stencil(false);
+        } else {
+            unsafe { (self.gl.glStencilFunc)(opengl::GL_EQUAL, self.clip_masks_count as i32, 0xff) };
+        }
+    }
+
+    fn enable_clip_scissor(&self, position: Vec2, size: Vec2) {
+        // Clip rectangles are in logical pixels of the active camera, but the scissor box always works on the framebuffer ones
+        let scale_factor = self.cameras.get(self.active_camera_id).map(|p| p.scale_factor).unwrap_or(1.0);
+        self.enable_scissor(position * scale_factor, size * scale_factor);
+    }
+
+    pub fn is_srgb(&self) -> bool {
+        self.renderer_platform_specific.is_srgb()
     }
 
     pub fn set_swap_interval(&mut self, interval: u32) {
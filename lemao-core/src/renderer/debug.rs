@@ -0,0 +1,271 @@
+use super::cameras::Camera;
+use super::context::RendererContext;
+use super::drawable::text::Text;
+use super::drawable::Color;
+use super::fonts::Font;
+use super::state::StateCache;
+use super::statistics::RendererStatistics;
+use crate::error::CoreError;
+use crate::utils::storage::Handle;
+use lemao_math::color::SolidColor;
+use lemao_math::mat4x4::Mat4x4;
+use lemao_math::vec2::Vec2;
+use lemao_math::vec4::Vec4;
+use lemao_opengl::bindings::opengl;
+use lemao_opengl::pointers::OpenGLPointers;
+use std::cell::RefCell;
+use std::f32::consts;
+use std::ffi::c_void;
+use std::mem;
+use std::ptr;
+use std::rc::Rc;
+use std::time::Duration;
+use std::time::Instant;
+
+const CIRCLE_SEGMENTS: usize = 32;
+const ARROW_HEAD_ANGLE: f32 = 0.5;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DebugShape {
+    Line { from: Vec2, to: Vec2 },
+    Polygon { points: Vec<Vec2> },
+    Circle { center: Vec2, radius: f32 },
+    Arrow { from: Vec2, to: Vec2 },
+    Text { position: Vec2, text: String },
+}
+
+#[derive(Clone, Debug)]
+struct DebugEntry {
+    shape: DebugShape,
+    color: SolidColor,
+    camera_id: Handle<Camera>,
+    expiration: Option<Instant>,
+}
+
+pub struct DebugRenderer {
+    pub line_thickness: f32,
+    pub arrow_head_size: f32,
+    pub font_id: Option<Handle<Font>>,
+
+    entries: Vec<DebugEntry>,
+    texts: Vec<Text>,
+    vertices: Vec<f32>,
+    vao_gl_id: u32,
+    vbo_gl_id: u32,
+    gl: Rc<OpenGLPointers>,
+    state: Rc<StateCache>,
+    statistics: Rc<RefCell<RendererStatistics>>,
+}
+
+impl DebugRenderer {
+    pub fn new(renderer: &RendererContext) -> Self {
+        unsafe {
+            let gl = renderer.gl.clone();
+            let state = renderer.state.clone();
+
+            let mut vao_gl_id = 0;
+            (gl.glGenVertexArrays)(1, &mut vao_gl_id);
+            state.bind_vertex_array(vao_gl_id);
+
+            let mut vbo_gl_id = 0;
+            (gl.glGenBuffers)(1, &mut vbo_gl_id);
+            state.bind_buffer(opengl::GL_ARRAY_BUFFER, vbo_gl_id);
+
+            let attrib_size = (9 * mem::size_of::<f32>()) as i32;
+            (gl.glVertexAttribPointer)(0, 3, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, ptr::null_mut());
+            (gl.glVertexAttribPointer)(1, 4, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, (3 * mem::size_of::<f32>()) as *const c_void);
+            (gl.glVertexAttribPointer)(2, 2, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, (7 * mem::size_of::<f32>()) as *const c_void);
+
+            (gl.glEnableVertexAttribArray)(0);
+            (gl.glEnableVertexAttribArray)(1);
+            (gl.glEnableVertexAttribArray)(2);
+
+            Self {
+                line_thickness: 1.0,
+                arrow_head_size: 10.0,
+                font_id: None,
+
+                entries: Vec::new(),
+                texts: Vec::new(),
+                vertices: Vec::new(),
+                vao_gl_id,
+                vbo_gl_id,
+                gl,
+                state,
+                statistics: renderer.frame_statistics.clone(),
+            }
+        }
+    }
+
+    // Shapes without duration are drawn only once, during the nearest flush
+    pub fn add(&mut self, shape: DebugShape, color: SolidColor, camera_id: Handle<Camera>, duration: Option<f32>) {
+        let expiration = duration.map(|p| Instant::now() + Duration::from_secs_f32(p.max(0.0)));
+        self.entries.push(DebugEntry { shape, color, camera_id, expiration });
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), CoreError> {
+        let active_camera_id = renderer.active_camera_id;
+        let mut camera_ids = Vec::new();
+
+        // Entries of removed cameras would make every next flush fail, so they are dropped here
+        self.entries.retain(|p| renderer.cameras.contains(p.camera_id));
+
+        for entry in &self.entries {
+            if !camera_ids.contains(&entry.camera_id) {
+                camera_ids.push(entry.camera_id);
+            }
+        }
+
+        let result = self.draw_cameras(renderer, camera_ids);
+        self.remove_expired();
+
+        if renderer.cameras.contains(active_camera_id) {
+            renderer.set_camera_as_active(active_camera_id)?;
+        }
+
+        result
+    }
+
+    pub fn remove_expired(&mut self) {
+        let now = Instant::now();
+        self.entries.retain(|p| p.expiration.map(|p| p > now).unwrap_or(false));
+    }
+
+    fn draw_cameras(&mut self, renderer: &mut RendererContext, camera_ids: Vec<Handle<Camera>>) -> Result<(), CoreError> {
+        // Every camera gets a single draw call with all its shapes, texts are drawn separately on top of them
+        for camera_id in camera_ids {
+            renderer.set_camera_as_active(camera_id)?;
+            self.vertices.clear();
+
+            for entry in self.entries.iter().filter(|p| p.camera_id == camera_id) {
+                match &entry.shape {
+                    DebugShape::Line { from, to } => {
+                        add_line_vertices(&mut self.vertices, *from, *to, self.line_thickness, entry.color);
+                    }
+                    DebugShape::Polygon { points } => {
+                        for index in 0..points.len() {
+                            add_line_vertices(&mut self.vertices, points[index], points[(index + 1) % points.len()], self.line_thickness, entry.color);
+                        }
+                    }
+                    DebugShape::Circle { center, radius } => {
+                        for index in 0..CIRCLE_SEGMENTS {
+                            let from = *center + Vec2::new_from_angle(index as f32 / CIRCLE_SEGMENTS as f32 * consts::TAU) * *radius;
+                            let to = *center + Vec2::new_from_angle((index + 1) as f32 / CIRCLE_SEGMENTS as f32 * consts::TAU) * *radius;
+                            add_line_vertices(&mut self.vertices, from, to, self.line_thickness, entry.color);
+                        }
+                    }
+                    DebugShape::Arrow { from, to } => {
+                        let direction = *to - *from;
+                        let angle = direction.y.atan2(direction.x);
+                        let left = *to + Vec2::new_from_angle(angle + consts::PI - ARROW_HEAD_ANGLE) * self.arrow_head_size;
+                        let right = *to + Vec2::new_from_angle(angle - consts::PI + ARROW_HEAD_ANGLE) * self.arrow_head_size;
+
+                        add_line_vertices(&mut self.vertices, *from, *to, self.line_thickness, entry.color);
+                        add_line_vertices(&mut self.vertices, *to, left, self.line_thickness, entry.color);
+                        add_line_vertices(&mut self.vertices, *to, right, self.line_thickness, entry.color);
+                    }
+                    DebugShape::Text { .. } => {}
+                }
+            }
+
+            if !self.vertices.is_empty() {
+                self.draw_shapes(renderer)?;
+            }
+
+            self.draw_texts(renderer, camera_id)?;
+        }
+
+        Ok(())
+    }
+
+    fn draw_shapes(&self, renderer: &mut RendererContext) -> Result<(), CoreError> {
+        let shader_id = renderer.default_solid_shader_id;
+        renderer.set_shader_as_active(shader_id)?;
+
+        let camera = renderer.cameras.get(renderer.active_camera_id)?;
+        let shader = renderer.shaders.get(shader_id)?;
+        shader.set_parameter("proj", camera.get_projection_matrix().as_ptr())?;
+        shader.set_parameter("view", camera.get_view_matrix().as_ptr())?;
+        shader.set_parameter("model", Mat4x4::identity().as_ptr())?;
+        shader.set_uv_rect(Vec4::new(0.0, 0.0, 1.0, 1.0))?;
+        shader.set_color(&Color::SolidColor(SolidColor::new(1.0, 1.0, 1.0, 1.0)))?;
+
+        let texture = renderer.textures.get(renderer.default_texture_id)?;
+        self.state.bind_texture(0, texture.texture_gl_id);
+
+        unsafe {
+            let data_size = mem::size_of_val(self.vertices.as_slice()) as i64;
+            let data_ptr = self.vertices.as_ptr() as *const c_void;
+
+            self.state.bind_vertex_array(self.vao_gl_id);
+            self.state.bind_buffer(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
+            (self.gl.glBufferData)(opengl::GL_ARRAY_BUFFER, data_size, data_ptr, opengl::GL_DYNAMIC_DRAW);
+            self.statistics.borrow_mut().add_buffer_upload(data_size);
+
+            (self.gl.glDrawArrays)(opengl::GL_TRIANGLES, 0, (self.vertices.len() / 9) as i32);
+            self.statistics.borrow_mut().add_draw_call(self.vertices.len() / 9, 0);
+        }
+
+        Ok(())
+    }
+
+    fn draw_texts(&mut self, renderer: &mut RendererContext, camera_id: Handle<Camera>) -> Result<(), CoreError> {
+        let mut texts_count = 0;
+
+        for entry in self.entries.iter().filter(|p| p.camera_id == camera_id) {
+            if let DebugShape::Text { position, text } = &entry.shape {
+                let font_id = match self.font_id {
+                    Some(font_id) => font_id,
+                    None => return Err(CoreError::InvalidOperation("debug font is not set".to_string())),
+                };
+                let font = renderer.fonts.get(font_id)?;
+
+                // Text drawables are kept between frames, so their buffers don't have to be recreated every time
+                if texts_count == self.texts.len() {
+                    self.texts.push(Text::new(renderer, font));
+                }
+
+                let debug_text = &mut self.texts[texts_count];
+                if debug_text.font_id != font_id {
+                    debug_text.set_font(font);
+                }
+
+                debug_text.position = *position;
+                debug_text.color = Color::SolidColor(entry.color);
+                debug_text.text = text.clone();
+                debug_text.update()?;
+
+                renderer.draw(debug_text)?;
+                texts_count += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for DebugRenderer {
+    fn drop(&mut self) {
+        if self.vbo_gl_id != 0 {
+            self.state.delete_buffer(self.vbo_gl_id);
+        }
+
+        if self.vao_gl_id != 0 {
+            self.state.delete_vertex_array(self.vao_gl_id);
+        }
+    }
+}
+
+fn add_line_vertices(vertices: &mut Vec<f32>, from: Vec2, to: Vec2, thickness: f32, color: SolidColor) {
+    let direction = (to - from).normalized();
+    let normal = Vec2::new(-direction.y, direction.x) * (thickness / 2.0);
+    let corners = [from - normal, from + normal, to + normal, from - normal, to + normal, to - normal];
+
+    for corner in corners {
+        vertices.extend_from_slice(&[corner.x, corner.y, 0.0, color.r, color.g, color.b, color.a, 0.0, 0.0]);
+    }
+}
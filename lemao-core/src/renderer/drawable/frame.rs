@@ -1,11 +1,16 @@
 use super::*;
+use crate::error::CoreError;
 use crate::renderer::context::RendererContext;
+use crate::renderer::state::StateCache;
+use crate::renderer::statistics::RendererStatistics;
 use crate::renderer::textures::Texture;
+use crate::utils::storage::Handle;
 use lemao_math::mat4x4::Mat4x4;
+use lemao_math::transform2d::Transform2D;
 use lemao_math::vec2::Vec2;
-use lemao_math::vec3::Vec3;
 use lemao_opengl::bindings::opengl;
 use lemao_opengl::pointers::OpenGLPointers;
+use std::cell::RefCell;
 use std::ffi::c_void;
 use std::mem;
 use std::ops::Add;
@@ -18,9 +23,11 @@ pub struct Frame {
     pub(crate) vao_gl_id: u32,
     pub(crate) vbo_gl_id: u32,
     pub(crate) ebo_gl_id: u32,
-    pub(crate) texture_id: usize,
+    pub(crate) texture_id: Handle<Texture>,
     pub(crate) texture_gl_id: u32,
     gl: Rc<OpenGLPointers>,
+    state: Rc<StateCache>,
+    statistics: Rc<RefCell<RendererStatistics>>,
 
     pub position: Vec2,
     pub scale: Vec2,
@@ -52,6 +59,8 @@ impl Frame {
             texture_id: texture.id,
             texture_gl_id: texture.texture_gl_id,
             gl: renderer.gl.clone(),
+            state: renderer.state.clone(),
+            statistics: renderer.frame_statistics.clone(),
 
             position: Default::default(),
             scale: Vec2::new(1.0, 1.0),
@@ -68,13 +77,13 @@ impl Frame {
 
         unsafe {
             (frame.gl.glGenVertexArrays)(1, &mut frame.vao_gl_id);
-            (frame.gl.glBindVertexArray)(frame.vao_gl_id);
+            frame.state.bind_vertex_array(frame.vao_gl_id);
 
             (frame.gl.glGenBuffers)(1, &mut frame.vbo_gl_id);
-            (frame.gl.glBindBuffer)(opengl::GL_ARRAY_BUFFER, frame.vbo_gl_id);
+            frame.state.bind_buffer(opengl::GL_ARRAY_BUFFER, frame.vbo_gl_id);
 
             (frame.gl.glGenBuffers)(1, &mut frame.ebo_gl_id);
-            (frame.gl.glBindBuffer)(opengl::GL_ELEMENT_ARRAY_BUFFER, frame.ebo_gl_id);
+            frame.state.bind_buffer(opengl::GL_ELEMENT_ARRAY_BUFFER, frame.ebo_gl_id);
 
             let attrib_size = (9 * mem::size_of::<f32>()) as i32;
             (frame.gl.glVertexAttribPointer)(0, 3, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, ptr::null_mut());
@@ -242,15 +251,17 @@ impl Frame {
             let vertices_size = (mem::size_of::<f32>() * self.vertices.len()) as i64;
             let vertices_ptr = self.vertices.as_ptr() as *const c_void;
 
-            (self.gl.glBindVertexArray)(self.vao_gl_id);
-            (self.gl.glBindBuffer)(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
+            self.state.bind_vertex_array(self.vao_gl_id);
+            self.state.bind_buffer(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
             (self.gl.glBufferData)(opengl::GL_ARRAY_BUFFER, vertices_size, vertices_ptr, opengl::GL_STATIC_DRAW);
+            self.statistics.borrow_mut().add_buffer_upload(vertices_size);
 
             let indices_size = (mem::size_of::<u32>() * self.indices.len()) as i64;
             let indices_ptr = self.indices.as_ptr() as *const c_void;
 
-            (self.gl.glBindBuffer)(opengl::GL_ELEMENT_ARRAY_BUFFER, self.ebo_gl_id);
+            self.state.bind_buffer(opengl::GL_ELEMENT_ARRAY_BUFFER, self.ebo_gl_id);
             (self.gl.glBufferData)(opengl::GL_ELEMENT_ARRAY_BUFFER, indices_size, indices_ptr, opengl::GL_STATIC_DRAW);
+            self.statistics.borrow_mut().add_buffer_upload(indices_size);
         }
     }
 
@@ -308,11 +319,8 @@ impl Frame {
 
 impl Drawable for Frame {
     fn get_transformation_matrix(&self) -> Mat4x4 {
-        let translation = Mat4x4::translate(Vec3::from(self.position));
-        let anchor_offset = Mat4x4::translate(-Vec3::from(self.anchor * self.size).floor());
-        let scale = Mat4x4::scale(Vec3::from(self.scale));
-        let rotation = Mat4x4::rotate(self.rotation);
-        translation * rotation * scale * anchor_offset
+        let pivot = (self.anchor * self.size).floor();
+        Transform2D { pivot, ..Transform2D::new(self.position, self.rotation, self.scale) }.get_mat4x4()
     }
 
     fn get_batch(&self) -> Batch {
@@ -323,15 +331,19 @@ impl Drawable for Frame {
         &self.color
     }
 
-    fn draw(&mut self, shader: &Shader) -> Result<(), String> {
+    fn get_texture_id(&self) -> Option<Handle<Texture>> {
+        Some(self.texture_id)
+    }
+
+    fn draw(&mut self, shader: &Shader) -> Result<(), CoreError> {
         unsafe {
             let model = self.get_transformation_matrix();
 
             shader.set_parameter("model", model.as_ptr())?;
             shader.set_color(&self.color)?;
 
-            (self.gl.glBindVertexArray)(self.vao_gl_id);
-            (self.gl.glBindTexture)(opengl::GL_TEXTURE_2D, self.texture_gl_id);
+            self.state.bind_vertex_array(self.vao_gl_id);
+            self.state.bind_texture(0, self.texture_gl_id);
             (self.gl.glDrawElements)(opengl::GL_TRIANGLES, self.elements_count as i32, opengl::GL_UNSIGNED_INT, ptr::null());
 
             Ok(())
@@ -341,18 +353,16 @@ impl Drawable for Frame {
 
 impl Drop for Frame {
     fn drop(&mut self) {
-        unsafe {
-            if self.vbo_gl_id != 0 {
-                (self.gl.glDeleteBuffers)(1, &mut self.vbo_gl_id);
-            }
+        if self.vbo_gl_id != 0 {
+            self.state.delete_buffer(self.vbo_gl_id);
+        }
 
-            if self.ebo_gl_id != 0 {
-                (self.gl.glDeleteBuffers)(1, &mut self.ebo_gl_id);
-            }
+        if self.ebo_gl_id != 0 {
+            self.state.delete_buffer(self.ebo_gl_id);
+        }
 
-            if self.vao_gl_id != 0 {
-                (self.gl.glDeleteVertexArrays)(1, &mut self.vao_gl_id);
-            }
+        if self.vao_gl_id != 0 {
+            self.state.delete_vertex_array(self.vao_gl_id);
         }
     }
 }
@@ -1,7 +1,10 @@
 use super::*;
+use crate::error::CoreError;
 use crate::renderer::context::RendererContext;
 use crate::renderer::shapes::Shape;
+use crate::renderer::state::StateCache;
 use crate::renderer::textures::Texture;
+use crate::utils::storage::Handle;
 use lemao_math::mat4x4::Mat4x4;
 use lemao_math::vec2::Vec2;
 use lemao_math::vec3::Vec3;
@@ -12,10 +15,12 @@ use std::rc::Rc;
 
 #[derive(Debug)]
 pub struct Line {
-    pub(crate) shape_id: usize,
+    pub(crate) shape_id: Handle<Shape>,
     pub(crate) shape_vao_gl_id: u32,
+    pub(crate) texture_id: Handle<Texture>,
     pub(crate) texture_gl_id: u32,
     gl: Rc<OpenGLPointers>,
+    state: Rc<StateCache>,
 
     pub position: Vec2,
     pub scale: Vec2,
@@ -32,8 +37,10 @@ impl Line {
         Line {
             shape_id: shape.id,
             shape_vao_gl_id: shape.vao_gl_id,
+            texture_id: texture.id,
             texture_gl_id: texture.texture_gl_id,
             gl: renderer.gl.clone(),
+            state: renderer.state.clone(),
 
             position: Default::default(),
             scale: Vec2::new(1.0, 1.0),
@@ -70,15 +77,19 @@ impl Drawable for Line {
         &self.color
     }
 
-    fn draw(&mut self, shader: &Shader) -> Result<(), String> {
+    fn get_texture_id(&self) -> Option<Handle<Texture>> {
+        Some(self.texture_id)
+    }
+
+    fn draw(&mut self, shader: &Shader) -> Result<(), CoreError> {
         unsafe {
             let model = self.get_transformation_matrix();
 
             shader.set_parameter("model", model.as_ptr())?;
             shader.set_color(&self.color)?;
 
-            (self.gl.glBindVertexArray)(self.shape_vao_gl_id);
-            (self.gl.glBindTexture)(opengl::GL_TEXTURE_2D, self.texture_gl_id);
+            self.state.bind_vertex_array(self.shape_vao_gl_id);
+            self.state.bind_texture(0, self.texture_gl_id);
             (self.gl.glDrawElements)(opengl::GL_TRIANGLES, 6, opengl::GL_UNSIGNED_INT, ptr::null());
 
             Ok(())
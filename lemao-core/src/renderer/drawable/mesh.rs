@@ -0,0 +1,334 @@
+use super::*;
+use crate::error::CoreError;
+use crate::renderer::context::RendererContext;
+use crate::renderer::state::StateCache;
+use crate::renderer::statistics::RendererStatistics;
+use crate::renderer::textures::Texture;
+use crate::utils::storage::Handle;
+use lemao_math::mat4x4::Mat4x4;
+use lemao_math::transform2d::Transform2D;
+use lemao_math::vec2::Vec2;
+use lemao_math::vec3::Vec3;
+use lemao_opengl::bindings::opengl;
+use lemao_opengl::pointers::OpenGLPointers;
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::mem;
+use std::ptr;
+use std::rc::Rc;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MeshPrimitive {
+    Triangles,
+    TriangleStrip,
+    TriangleFan,
+    Lines,
+    LineStrip,
+    Points,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct MeshVertex {
+    pub position: Vec3,
+    pub color: SolidColor,
+    pub uv: Vec2,
+}
+
+#[derive(Debug)]
+pub struct Mesh {
+    pub(crate) vao_gl_id: u32,
+    pub(crate) vbo_gl_id: u32,
+    pub(crate) ebo_gl_id: u32,
+    pub(crate) texture_id: Handle<Texture>,
+    pub(crate) texture_gl_id: u32,
+    gl: Rc<OpenGLPointers>,
+    state: Rc<StateCache>,
+    statistics: Rc<RefCell<RendererStatistics>>,
+
+    pub position: Vec2,
+    pub scale: Vec2,
+    pub rotation: f32,
+    pub color: Color,
+    primitive: MeshPrimitive,
+    vertices: Vec<f32>,
+    indices: Vec<u32>,
+    batch_indices: Vec<u32>,
+    vertices_capacity: usize,
+    indices_capacity: usize,
+}
+
+impl MeshVertex {
+    pub fn new(position: Vec3, color: SolidColor, uv: Vec2) -> Self {
+        Self { position, color, uv }
+    }
+}
+
+impl Mesh {
+    pub fn new(renderer: &RendererContext, texture: &Texture, primitive: MeshPrimitive) -> Self {
+        let mut mesh = Mesh {
+            vao_gl_id: 0,
+            vbo_gl_id: 0,
+            ebo_gl_id: 0,
+            texture_id: texture.id,
+            texture_gl_id: texture.texture_gl_id,
+            gl: renderer.gl.clone(),
+            state: renderer.state.clone(),
+            statistics: renderer.frame_statistics.clone(),
+
+            position: Default::default(),
+            scale: Vec2::new(1.0, 1.0),
+            rotation: 0.0,
+            color: Color::SolidColor(SolidColor::new(1.0, 1.0, 1.0, 1.0)),
+            primitive,
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            batch_indices: Vec::new(),
+            vertices_capacity: 0,
+            indices_capacity: 0,
+        };
+
+        unsafe {
+            (mesh.gl.glGenVertexArrays)(1, &mut mesh.vao_gl_id);
+            mesh.state.bind_vertex_array(mesh.vao_gl_id);
+
+            (mesh.gl.glGenBuffers)(1, &mut mesh.vbo_gl_id);
+            mesh.state.bind_buffer(opengl::GL_ARRAY_BUFFER, mesh.vbo_gl_id);
+
+            (mesh.gl.glGenBuffers)(1, &mut mesh.ebo_gl_id);
+            mesh.state.bind_buffer(opengl::GL_ELEMENT_ARRAY_BUFFER, mesh.ebo_gl_id);
+
+            let attrib_size = (9 * mem::size_of::<f32>()) as i32;
+            (mesh.gl.glVertexAttribPointer)(0, 3, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, ptr::null_mut());
+            (mesh.gl.glVertexAttribPointer)(1, 4, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, (3 * mem::size_of::<f32>()) as *const c_void);
+            (mesh.gl.glVertexAttribPointer)(2, 2, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, (7 * mem::size_of::<f32>()) as *const c_void);
+
+            (mesh.gl.glEnableVertexAttribArray)(0);
+            (mesh.gl.glEnableVertexAttribArray)(1);
+            (mesh.gl.glEnableVertexAttribArray)(2);
+        }
+
+        mesh
+    }
+
+    pub fn set_texture(&mut self, texture: &Texture) {
+        self.texture_id = texture.id;
+        self.texture_gl_id = texture.texture_gl_id;
+    }
+
+    pub fn get_primitive(&self) -> MeshPrimitive {
+        self.primitive
+    }
+
+    pub fn set_primitive(&mut self, primitive: MeshPrimitive) {
+        self.primitive = primitive;
+        self.update_batch_indices();
+    }
+
+    pub fn get_vertices_count(&self) -> usize {
+        self.vertices.len() / 9
+    }
+
+    pub fn get_indices_count(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn set_geometry(&mut self, vertices: &[MeshVertex], indices: &[u32]) -> Result<(), CoreError> {
+        if indices.iter().any(|p| *p as usize >= vertices.len()) {
+            return Err(CoreError::InvalidArgument("mesh index out of vertices range".to_string()));
+        }
+
+        self.vertices.clear();
+        for vertex in vertices {
+            self.vertices.extend_from_slice(&get_vertex_data(vertex));
+        }
+
+        self.indices.clear();
+        self.indices.extend_from_slice(indices);
+        self.update_batch_indices();
+
+        unsafe {
+            let vertices_size = mem::size_of_val(self.vertices.as_slice()) as i64;
+            let vertices_ptr = self.vertices.as_ptr() as *const c_void;
+
+            self.state.bind_vertex_array(self.vao_gl_id);
+            self.state.bind_buffer(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
+
+            // Buffers are reallocated only when they grow, otherwise the existing storage is reused
+            if self.vertices.len() > self.vertices_capacity {
+                (self.gl.glBufferData)(opengl::GL_ARRAY_BUFFER, vertices_size, vertices_ptr, opengl::GL_DYNAMIC_DRAW);
+                self.vertices_capacity = self.vertices.len();
+            } else {
+                (self.gl.glBufferSubData)(opengl::GL_ARRAY_BUFFER, 0, vertices_size, vertices_ptr);
+            }
+            self.statistics.borrow_mut().add_buffer_upload(vertices_size);
+
+            let indices_size = mem::size_of_val(self.indices.as_slice()) as i64;
+            let indices_ptr = self.indices.as_ptr() as *const c_void;
+
+            self.state.bind_buffer(opengl::GL_ELEMENT_ARRAY_BUFFER, self.ebo_gl_id);
+
+            if self.indices.len() > self.indices_capacity {
+                (self.gl.glBufferData)(opengl::GL_ELEMENT_ARRAY_BUFFER, indices_size, indices_ptr, opengl::GL_DYNAMIC_DRAW);
+                self.indices_capacity = self.indices.len();
+            } else {
+                (self.gl.glBufferSubData)(opengl::GL_ELEMENT_ARRAY_BUFFER, 0, indices_size, indices_ptr);
+            }
+            self.statistics.borrow_mut().add_buffer_upload(indices_size);
+        }
+
+        Ok(())
+    }
+
+    pub fn update_vertices(&mut self, offset: usize, vertices: &[MeshVertex]) -> Result<(), CoreError> {
+        if offset + vertices.len() > self.get_vertices_count() {
+            return Err(CoreError::InvalidArgument("mesh vertices update out of range".to_string()));
+        }
+
+        for (index, vertex) in vertices.iter().enumerate() {
+            let data_offset = (offset + index) * 9;
+            self.vertices[data_offset..data_offset + 9].copy_from_slice(&get_vertex_data(vertex));
+        }
+
+        unsafe {
+            let data_offset = (offset * 9 * mem::size_of::<f32>()) as i64;
+            let data_size = (vertices.len() * 9 * mem::size_of::<f32>()) as i64;
+            let data_ptr = self.vertices[offset * 9..].as_ptr() as *const c_void;
+
+            self.state.bind_vertex_array(self.vao_gl_id);
+            self.state.bind_buffer(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
+            (self.gl.glBufferSubData)(opengl::GL_ARRAY_BUFFER, data_offset, data_size, data_ptr);
+            self.statistics.borrow_mut().add_buffer_upload(data_size);
+        }
+
+        Ok(())
+    }
+
+    pub fn update_indices(&mut self, offset: usize, indices: &[u32]) -> Result<(), CoreError> {
+        if offset + indices.len() > self.indices.len() {
+            return Err(CoreError::InvalidArgument("mesh indices update out of range".to_string()));
+        }
+
+        if indices.iter().any(|p| *p as usize >= self.get_vertices_count()) {
+            return Err(CoreError::InvalidArgument("mesh index out of vertices range".to_string()));
+        }
+
+        self.indices[offset..offset + indices.len()].copy_from_slice(indices);
+        self.update_batch_indices();
+
+        unsafe {
+            let data_offset = (offset * mem::size_of::<u32>()) as i64;
+            let data_size = mem::size_of_val(indices) as i64;
+            let data_ptr = indices.as_ptr() as *const c_void;
+
+            self.state.bind_vertex_array(self.vao_gl_id);
+            self.state.bind_buffer(opengl::GL_ELEMENT_ARRAY_BUFFER, self.ebo_gl_id);
+            (self.gl.glBufferSubData)(opengl::GL_ELEMENT_ARRAY_BUFFER, data_offset, data_size, data_ptr);
+            self.statistics.borrow_mut().add_buffer_upload(data_size);
+        }
+
+        Ok(())
+    }
+
+    // Batcher works only with triangle lists, so strips and fans are unrolled here
+    fn update_batch_indices(&mut self) {
+        self.batch_indices.clear();
+
+        match self.primitive {
+            MeshPrimitive::Triangles => {
+                self.batch_indices.extend_from_slice(&self.indices[..self.indices.len() / 3 * 3]);
+            }
+            MeshPrimitive::TriangleStrip => {
+                for index in 2..self.indices.len() {
+                    let (a, b, c) = (self.indices[index - 2], self.indices[index - 1], self.indices[index]);
+                    self.batch_indices.extend_from_slice(&if index % 2 == 0 { [a, b, c] } else { [b, a, c] });
+                }
+            }
+            MeshPrimitive::TriangleFan => {
+                for index in 2..self.indices.len() {
+                    self.batch_indices.extend_from_slice(&[self.indices[0], self.indices[index - 1], self.indices[index]]);
+                }
+            }
+            MeshPrimitive::Lines | MeshPrimitive::LineStrip | MeshPrimitive::Points => {}
+        }
+    }
+}
+
+impl Drawable for Mesh {
+    fn get_transformation_matrix(&self) -> Mat4x4 {
+        Transform2D::new(self.position, self.rotation, self.scale).get_mat4x4()
+    }
+
+    fn get_batch(&self) -> Batch<'_> {
+        match self.primitive {
+            MeshPrimitive::Triangles | MeshPrimitive::TriangleStrip | MeshPrimitive::TriangleFan => {
+                Batch::new(None, Some(&self.vertices), Some(&self.batch_indices), Some(self.texture_gl_id), Some(&self.color))
+            }
+            MeshPrimitive::Lines | MeshPrimitive::LineStrip | MeshPrimitive::Points => {
+                Batch::new(None, Some(&self.vertices), None, Some(self.texture_gl_id), Some(&self.color))
+            }
+        }
+    }
+
+    fn get_color(&self) -> &Color {
+        &self.color
+    }
+
+    fn get_texture_id(&self) -> Option<Handle<Texture>> {
+        Some(self.texture_id)
+    }
+
+    fn draw(&mut self, shader: &Shader) -> Result<(), CoreError> {
+        let mode = match self.primitive {
+            MeshPrimitive::Triangles => opengl::GL_TRIANGLES,
+            MeshPrimitive::TriangleStrip => opengl::GL_TRIANGLE_STRIP,
+            MeshPrimitive::TriangleFan => opengl::GL_TRIANGLE_FAN,
+            MeshPrimitive::Lines => opengl::GL_LINES,
+            MeshPrimitive::LineStrip => opengl::GL_LINE_STRIP,
+            MeshPrimitive::Points => opengl::GL_POINTS,
+        };
+
+        unsafe {
+            let model = self.get_transformation_matrix();
+
+            shader.set_parameter("model", model.as_ptr())?;
+            shader.set_color(&self.color)?;
+
+            self.state.bind_vertex_array(self.vao_gl_id);
+            self.state.bind_texture(0, self.texture_gl_id);
+            (self.gl.glDrawElements)(mode, self.indices.len() as i32, opengl::GL_UNSIGNED_INT, ptr::null());
+
+            Ok(())
+        }
+    }
+}
+
+impl Drop for Mesh {
+    fn drop(&mut self) {
+        if self.vbo_gl_id != 0 {
+            self.state.delete_buffer(self.vbo_gl_id);
+        }
+
+        if self.ebo_gl_id != 0 {
+            self.state.delete_buffer(self.ebo_gl_id);
+        }
+
+        if self.vao_gl_id != 0 {
+            self.state.delete_vertex_array(self.vao_gl_id);
+        }
+    }
+}
+
+#[rustfmt::skip]
+fn get_vertex_data(vertex: &MeshVertex) -> [f32; 9] {
+    [
+        /* v.x */ vertex.position.x,
+        /* v.y */ vertex.position.y,
+        /* v.z */ vertex.position.z,
+        /* c.r */ vertex.color.r,
+        /* c.g */ vertex.color.g,
+        /* c.b */ vertex.color.b,
+        /* c.a */ vertex.color.a,
+        /* t.u */ vertex.uv.x,
+        /* t.v */ vertex.uv.y,
+    ]
+}
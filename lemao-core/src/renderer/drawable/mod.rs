@@ -1,13 +1,20 @@
 use super::batcher::Batch;
+use super::fonts::Font;
+use super::fonts::FontType;
 use super::shaders::Shader;
+use super::textures::Texture;
+use crate::error::CoreError;
+use crate::utils::storage::Handle;
 use lemao_math::color::SolidColor;
 use lemao_math::gradient::Gradient;
 use lemao_math::mat4x4::Mat4x4;
+use lemao_math::vec4::Vec4;
 
 pub mod circle;
 pub mod disc;
 pub mod frame;
 pub mod line;
+pub mod mesh;
 pub mod rectangle;
 pub mod text;
 pub mod tilemap;
@@ -32,6 +39,7 @@ pub enum DrawableEnum {
     Disc,
     Frame,
     Line,
+    Mesh,
     Rectangle,
     Text,
     Tilemap,
@@ -41,14 +49,31 @@ pub trait Drawable {
     fn get_transformation_matrix(&self) -> Mat4x4;
     fn get_batch(&self) -> Batch;
     fn get_color(&self) -> &Color;
-    fn draw(&mut self, shader: &Shader) -> Result<(), String>;
+    fn draw(&mut self, shader: &Shader) -> Result<(), CoreError>;
+
+    fn get_font_type(&self) -> Option<FontType> {
+        None
+    }
+
+    fn get_texture_id(&self) -> Option<Handle<Texture>> {
+        None
+    }
+
+    fn get_font_id(&self) -> Option<Handle<Font>> {
+        None
+    }
+
+    // Position (xy) and size (zw) of the used texture area, negative size flips the texture
+    fn get_uv_rect(&self) -> Vec4 {
+        Vec4::new(0.0, 0.0, 1.0, 1.0)
+    }
 }
 
 impl Color {
     pub fn get_alpha(&self) -> f32 {
         match self {
             Self::SolidColor(solid) => solid.a,
-            Self::Gradient(gradient) => gradient.steps[0].color.a,
+            Self::Gradient(gradient) => gradient.steps.iter().map(|p| p.color.a).fold(0.0, f32::max),
         }
     }
 
@@ -69,3 +94,32 @@ impl CornerRounding {
         Self { left_bottom, right_bottom, right_top, left_top }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lemao_math::gradient::GradientStep;
+    use lemao_math::gradient::GradientType;
+    use lemao_math::vec2::Vec2;
+
+    #[test]
+    fn set_alpha_fade() {
+        let mut gradient = Gradient::new(GradientType::Horizontal, Vec2::new(0.0, 0.0));
+        gradient.steps.push(GradientStep::new(SolidColor::new(1.0, 0.0, 0.0, 0.5), 0.0));
+        gradient.steps.push(GradientStep::new(SolidColor::new(0.0, 0.0, 1.0, 1.0), 1.0));
+
+        let mut color = Color::Gradient(gradient);
+        assert_eq!(color.get_alpha(), 1.0);
+
+        color.set_alpha(0.0);
+        assert_eq!(color.get_alpha(), 0.0);
+
+        color.set_alpha(1.0);
+        assert_eq!(color.get_alpha(), 1.0);
+
+        match color {
+            Color::Gradient(gradient) => assert!(gradient.steps.iter().all(|p| p.color.a == 1.0)),
+            Color::SolidColor(_) => unreachable!(),
+        }
+    }
+}
@@ -1,12 +1,18 @@
 use super::*;
+use crate::error::CoreError;
 use crate::renderer::context::RendererContext;
 use crate::renderer::shapes::Shape;
+use crate::renderer::state::StateCache;
+use crate::renderer::statistics::RendererStatistics;
 use crate::renderer::textures::Texture;
+use crate::utils::storage::Handle;
 use lemao_math::mat4x4::Mat4x4;
+use lemao_math::transform2d::Transform2D;
 use lemao_math::vec2::Vec2;
-use lemao_math::vec3::Vec3;
+use lemao_math::vec4::Vec4;
 use lemao_opengl::bindings::opengl;
 use lemao_opengl::pointers::OpenGLPointers;
+use std::cell::RefCell;
 use std::ffi::c_void;
 use std::mem;
 use std::ptr;
@@ -14,22 +20,29 @@ use std::rc::Rc;
 
 #[derive(Debug)]
 pub struct Rectangle {
-    pub(crate) shape_id: usize,
+    pub(crate) shape_id: Handle<Shape>,
     pub(crate) shape_vao_gl_id: u32,
     pub(crate) vao_gl_id: u32,
     pub(crate) vbo_gl_id: u32,
     pub(crate) ebo_gl_id: u32,
-    pub(crate) texture_id: usize,
+    pub(crate) texture_id: Handle<Texture>,
     pub(crate) texture_gl_id: u32,
+    texture_size: Vec2,
     gl: Rc<OpenGLPointers>,
+    state: Rc<StateCache>,
+    statistics: Rc<RefCell<RendererStatistics>>,
 
     pub position: Vec2,
     pub scale: Vec2,
     pub rotation: f32,
     pub size: Vec2,
     pub anchor: Vec2,
+    pub pivot: Option<Vec2>,
     pub color: Color,
     pub corner_rounding: CornerRounding,
+    pub source: Option<SourceRectangle>,
+    pub flip_x: bool,
+    pub flip_y: bool,
     pub custom_shape: bool,
     custom_shape_initialized: bool,
     elements_count: u32,
@@ -37,6 +50,12 @@ pub struct Rectangle {
     indices: Vec<u32>,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SourceRectangle {
+    Pixels { position: Vec2, size: Vec2 },
+    Uv { position: Vec2, size: Vec2 },
+}
+
 impl Rectangle {
     pub fn new(renderer: &RendererContext, shape: &Shape, texture: &Texture) -> Self {
         Rectangle {
@@ -47,15 +66,22 @@ impl Rectangle {
             ebo_gl_id: 0,
             texture_id: texture.id,
             texture_gl_id: texture.texture_gl_id,
+            texture_size: texture.size,
             gl: renderer.gl.clone(),
+            state: renderer.state.clone(),
+            statistics: renderer.frame_statistics.clone(),
 
             position: Default::default(),
             scale: Vec2::new(1.0, 1.0),
             rotation: 0.0,
             size: Default::default(),
             anchor: Default::default(),
+            pivot: None,
             color: Color::SolidColor(SolidColor::new(1.0, 1.0, 1.0, 1.0)),
             corner_rounding: Default::default(),
+            source: None,
+            flip_x: false,
+            flip_y: false,
             custom_shape: false,
             custom_shape_initialized: false,
             elements_count: 0,
@@ -67,21 +93,23 @@ impl Rectangle {
     pub fn set_texture(&mut self, texture: &Texture) {
         self.texture_id = texture.id;
         self.texture_gl_id = texture.texture_gl_id;
+        self.texture_size = texture.size;
     }
 
     pub fn update(&mut self) {
         unsafe {
+            // Source rectangle and flipping are expressed by the UV rectangle, so only rounded corners require own vertices
             self.custom_shape = self.corner_rounding != Default::default();
 
             if self.custom_shape && !self.custom_shape_initialized {
                 (self.gl.glGenVertexArrays)(1, &mut self.vao_gl_id);
-                (self.gl.glBindVertexArray)(self.vao_gl_id);
+                self.state.bind_vertex_array(self.vao_gl_id);
 
                 (self.gl.glGenBuffers)(1, &mut self.vbo_gl_id);
-                (self.gl.glBindBuffer)(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
+                self.state.bind_buffer(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
 
                 (self.gl.glGenBuffers)(1, &mut self.ebo_gl_id);
-                (self.gl.glBindBuffer)(opengl::GL_ELEMENT_ARRAY_BUFFER, self.ebo_gl_id);
+                self.state.bind_buffer(opengl::GL_ELEMENT_ARRAY_BUFFER, self.ebo_gl_id);
 
                 let attrib_size = (9 * mem::size_of::<f32>()) as i32;
                 (self.gl.glVertexAttribPointer)(0, 3, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, ptr::null_mut());
@@ -201,21 +229,25 @@ impl Rectangle {
                 let vertices_size = (mem::size_of::<f32>() * self.vertices.len()) as i64;
                 let vertices_ptr = self.vertices.as_ptr() as *const c_void;
 
-                (self.gl.glBindVertexArray)(self.vao_gl_id);
-                (self.gl.glBindBuffer)(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
+                self.state.bind_vertex_array(self.vao_gl_id);
+                self.state.bind_buffer(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
                 (self.gl.glBufferData)(opengl::GL_ARRAY_BUFFER, vertices_size, vertices_ptr, opengl::GL_STATIC_DRAW);
+                self.statistics.borrow_mut().add_buffer_upload(vertices_size);
 
                 let indices_size = (mem::size_of::<u32>() * self.indices.len()) as i64;
                 let indices_ptr = self.indices.as_ptr() as *const c_void;
 
-                (self.gl.glBindBuffer)(opengl::GL_ELEMENT_ARRAY_BUFFER, self.ebo_gl_id);
+                self.state.bind_buffer(opengl::GL_ELEMENT_ARRAY_BUFFER, self.ebo_gl_id);
                 (self.gl.glBufferData)(opengl::GL_ELEMENT_ARRAY_BUFFER, indices_size, indices_ptr, opengl::GL_STATIC_DRAW);
+                self.statistics.borrow_mut().add_buffer_upload(indices_size);
             }
         }
     }
 
     #[rustfmt::skip]
     fn get_vertices(&self, position: Vec2, uv: Vec2, color: SolidColor) -> [f32; 9] {
+        let uv = self.get_uv(uv);
+
         [
             /* v.x */ position.x,
             /* v.y */ position.y,
@@ -229,6 +261,22 @@ impl Rectangle {
         ]
     }
 
+    fn get_uv(&self, uv: Vec2) -> Vec2 {
+        let uv = Vec2::new(if self.flip_x { 1.0 - uv.x } else { uv.x }, if self.flip_y { 1.0 - uv.y } else { uv.y });
+
+        // Pixel coordinates start at the top-left corner of the texture, like in the tilemap
+        let (source_position, source_size) = match self.source {
+            Some(SourceRectangle::Pixels { position, size }) => (
+                Vec2::new(position.x / self.texture_size.x, 1.0 - (position.y + size.y) / self.texture_size.y),
+                Vec2::new(size.x / self.texture_size.x, size.y / self.texture_size.y),
+            ),
+            Some(SourceRectangle::Uv { position, size }) => (position, size),
+            None => (Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0)),
+        };
+
+        source_position + uv * source_size
+    }
+
     fn get_corner(&mut self, center: Vec2, corner_rounding: f32, from_angle: f32, to_angle: f32) {
         let mut angle = from_angle;
         let step = (to_angle - from_angle) / corner_rounding;
@@ -258,18 +306,18 @@ impl Rectangle {
 
 impl Drawable for Rectangle {
     fn get_transformation_matrix(&self) -> Mat4x4 {
-        let translation = Mat4x4::translate(Vec3::from(self.position));
-        let rotation = Mat4x4::rotate(self.rotation);
+        // Rotation is applied around the pivot, which is then moved to the place it would have without rotation
+        let pivot = self.pivot.unwrap_or(self.anchor);
+        let pivot_position = self.position + (pivot - self.anchor) * self.size * self.scale;
 
-        if self.custom_shape {
-            let anchor_offset = Mat4x4::translate(-Vec3::from(self.anchor * self.size));
-            let scale = Mat4x4::scale(Vec3::from(self.scale).floor());
-            translation * rotation * scale * anchor_offset
+        // Custom shapes have vertices in pixels, the default one is a unit square scaled to the size
+        let transform = if self.custom_shape {
+            Transform2D { pivot: pivot * self.size, ..Transform2D::new(pivot_position, self.rotation, self.scale.floor()) }
         } else {
-            let anchor_offset = Mat4x4::translate(-Vec3::from(self.anchor));
-            let scale = Mat4x4::scale(Vec3::from(self.scale * self.size).floor());
-            translation * rotation * scale * anchor_offset
-        }
+            Transform2D { pivot, ..Transform2D::new(pivot_position, self.rotation, (self.scale * self.size).floor()) }
+        };
+
+        transform.get_mat4x4()
     }
 
     fn get_batch(&self) -> Batch {
@@ -284,7 +332,23 @@ impl Drawable for Rectangle {
         &self.color
     }
 
-    fn draw(&mut self, shader: &Shader) -> Result<(), String> {
+    fn get_texture_id(&self) -> Option<Handle<Texture>> {
+        Some(self.texture_id)
+    }
+
+    fn get_uv_rect(&self) -> Vec4 {
+        // Custom shapes have the source rectangle and flipping already applied to their vertices
+        if self.custom_shape {
+            return Vec4::new(0.0, 0.0, 1.0, 1.0);
+        }
+
+        let from = self.get_uv(Vec2::new(0.0, 0.0));
+        let to = self.get_uv(Vec2::new(1.0, 1.0));
+
+        Vec4::new(from.x, from.y, to.x - from.x, to.y - from.y)
+    }
+
+    fn draw(&mut self, shader: &Shader) -> Result<(), CoreError> {
         unsafe {
             let model = self.get_transformation_matrix();
 
@@ -292,12 +356,12 @@ impl Drawable for Rectangle {
             shader.set_color(&self.color)?;
 
             if self.custom_shape {
-                (self.gl.glBindVertexArray)(self.vao_gl_id);
-                (self.gl.glBindTexture)(opengl::GL_TEXTURE_2D, self.texture_gl_id);
+                self.state.bind_vertex_array(self.vao_gl_id);
+                self.state.bind_texture(0, self.texture_gl_id);
                 (self.gl.glDrawElements)(opengl::GL_TRIANGLES, self.elements_count as i32, opengl::GL_UNSIGNED_INT, ptr::null());
             } else {
-                (self.gl.glBindVertexArray)(self.shape_vao_gl_id);
-                (self.gl.glBindTexture)(opengl::GL_TEXTURE_2D, self.texture_gl_id);
+                self.state.bind_vertex_array(self.shape_vao_gl_id);
+                self.state.bind_texture(0, self.texture_gl_id);
                 (self.gl.glDrawElements)(opengl::GL_TRIANGLES, 6, opengl::GL_UNSIGNED_INT, ptr::null());
             }
 
@@ -308,18 +372,16 @@ impl Drawable for Rectangle {
 
 impl Drop for Rectangle {
     fn drop(&mut self) {
-        unsafe {
-            if self.vbo_gl_id != 0 {
-                (self.gl.glDeleteBuffers)(1, &mut self.vbo_gl_id);
-            }
+        if self.vbo_gl_id != 0 {
+            self.state.delete_buffer(self.vbo_gl_id);
+        }
 
-            if self.ebo_gl_id != 0 {
-                (self.gl.glDeleteBuffers)(1, &mut self.ebo_gl_id);
-            }
+        if self.ebo_gl_id != 0 {
+            self.state.delete_buffer(self.ebo_gl_id);
+        }
 
-            if self.vao_gl_id != 0 {
-                (self.gl.glDeleteVertexArrays)(1, &mut self.vao_gl_id);
-            }
+        if self.vao_gl_id != 0 {
+            self.state.delete_vertex_array(self.vao_gl_id);
         }
     }
 }
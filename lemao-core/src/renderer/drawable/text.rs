@@ -1,24 +1,41 @@
 use super::*;
+use crate::error::CoreError;
 use crate::renderer::context::RendererContext;
 use crate::renderer::fonts::Font;
+use crate::renderer::fonts::FontAtlas;
+use crate::renderer::fonts::FontGlyph;
+use crate::renderer::fonts::FontType;
+use crate::renderer::richtext::markup;
+use crate::renderer::richtext::RichText;
+use crate::renderer::richtext::TextMarkup;
+use crate::renderer::richtext::TextStyle;
+use crate::renderer::state::StateCache;
+use crate::renderer::statistics::RendererStatistics;
+use crate::utils::storage::Handle;
 use lemao_math::mat4x4::Mat4x4;
+use lemao_math::transform2d::Transform2D;
 use lemao_math::vec2::Vec2;
-use lemao_math::vec3::Vec3;
 use lemao_opengl::bindings::opengl;
 use lemao_opengl::pointers::OpenGLPointers;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::ffi::c_void;
 use std::mem;
 use std::ptr;
 use std::rc::Rc;
 
+const OUTLINE_DIRECTIONS: [(f32, f32); 8] = [(-1.0, -1.0), (0.0, -1.0), (1.0, -1.0), (-1.0, 0.0), (1.0, 0.0), (-1.0, 1.0), (0.0, 1.0), (1.0, 1.0)];
+
 #[derive(Debug)]
 pub struct Text {
     pub(crate) vao_gl_id: u32,
     pub(crate) vbo_gl_id: u32,
     pub(crate) ebo_gl_id: u32,
-    pub(crate) font_id: usize,
+    pub(crate) font_id: Handle<Font>,
     pub(crate) texture_gl_id: u32,
     gl: Rc<OpenGLPointers>,
+    state: Rc<StateCache>,
+    statistics: Rc<RefCell<RendererStatistics>>,
 
     pub position: Vec2,
     pub scale: Vec2,
@@ -27,15 +44,31 @@ pub struct Text {
     pub anchor: Vec2,
     pub color: Color,
     pub text: String,
+    pub markup: TextMarkup,
     pub line_height: u32,
+    pub smoothing: f32,
+    pub outline_thickness: f32,
+    pub outline_color: SolidColor,
+    pub glow_thickness: f32,
+    pub glow_color: SolidColor,
     elements_count: u32,
     vertices: Vec<f32>,
     indices: Vec<u32>,
 
     font_size: Vec2,
-    font_cell_size: Vec2,
-    font_base_character_offset: u8,
-    font_character_widths: Vec<u8>,
+    font_glyphs: HashMap<char, FontGlyph>,
+    font_kerning: HashMap<(char, char), f32>,
+    font_type: FontType,
+    font_distance_range: f32,
+    font_atlas: Rc<RefCell<FontAtlas>>,
+    font_atlas_version: u32,
+}
+
+#[derive(Debug, Default)]
+struct TextLine {
+    characters: Vec<(char, f32, TextStyle)>,
+    width: f32,
+    height: f32,
 }
 
 impl Text {
@@ -47,6 +80,8 @@ impl Text {
             font_id: font.id,
             texture_gl_id: font.texture_gl_id,
             gl: renderer.gl.clone(),
+            state: renderer.state.clone(),
+            statistics: renderer.frame_statistics.clone(),
 
             position: Default::default(),
             scale: Vec2::new(1.0, 1.0),
@@ -55,26 +90,35 @@ impl Text {
             anchor: Default::default(),
             color: Color::SolidColor(SolidColor::new(1.0, 1.0, 1.0, 1.0)),
             text: Default::default(),
-            line_height: font.cell_size.y as u32,
+            markup: TextMarkup::None,
+            line_height: font.line_height as u32,
+            smoothing: 1.0,
+            outline_thickness: 0.0,
+            outline_color: SolidColor::new(0.0, 0.0, 0.0, 1.0),
+            glow_thickness: 0.0,
+            glow_color: SolidColor::new(0.0, 0.0, 0.0, 1.0),
             elements_count: 0,
             vertices: Vec::new(),
             indices: Vec::new(),
 
             font_size: font.size,
-            font_cell_size: font.cell_size,
-            font_base_character_offset: font.base_character_offset,
-            font_character_widths: font.character_widths.clone(),
+            font_glyphs: font.glyphs.clone(),
+            font_kerning: font.kerning.clone(),
+            font_type: font.r#type,
+            font_distance_range: font.distance_range,
+            font_atlas: font.atlas.clone(),
+            font_atlas_version: font.atlas.borrow().version,
         };
 
         unsafe {
             (text.gl.glGenVertexArrays)(1, &mut text.vao_gl_id);
-            (text.gl.glBindVertexArray)(text.vao_gl_id);
+            text.state.bind_vertex_array(text.vao_gl_id);
 
             (text.gl.glGenBuffers)(1, &mut text.vbo_gl_id);
-            (text.gl.glBindBuffer)(opengl::GL_ARRAY_BUFFER, text.vbo_gl_id);
+            text.state.bind_buffer(opengl::GL_ARRAY_BUFFER, text.vbo_gl_id);
 
             (text.gl.glGenBuffers)(1, &mut text.ebo_gl_id);
-            (text.gl.glBindBuffer)(opengl::GL_ELEMENT_ARRAY_BUFFER, text.ebo_gl_id);
+            text.state.bind_buffer(opengl::GL_ELEMENT_ARRAY_BUFFER, text.ebo_gl_id);
 
             let attrib_size = (9 * mem::size_of::<f32>()) as i32;
             (text.gl.glVertexAttribPointer)(0, 3, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, ptr::null_mut());
@@ -92,89 +136,103 @@ impl Text {
     pub fn set_font(&mut self, font: &Font) {
         self.font_id = font.id;
         self.texture_gl_id = font.texture_gl_id;
-        self.line_height = font.cell_size.y as u32;
+        self.line_height = font.line_height as u32;
+        self.font_size = font.size;
+        self.font_glyphs = font.glyphs.clone();
+        self.font_kerning = font.kerning.clone();
+        self.font_type = font.r#type;
+        self.font_distance_range = font.distance_range;
+        self.font_atlas = font.atlas.clone();
+        self.font_atlas_version = font.atlas.borrow().version;
     }
 
-    pub fn calculate_text_size(&self, text: String) -> Vec2 {
-        let mut text_size = Vec2::new(0.0, self.line_height as f32);
-        let mut line_width = 0.0;
-        let mut color_section = false;
-
-        for char in text.chars() {
-            if char == '\n' {
-                line_width = 0.0;
-                text_size.y += self.line_height as f32;
-                continue;
-            } else if char == '°' {
-                color_section = !color_section;
-                continue;
-            }
-
-            if color_section {
-                continue;
-            }
+    pub fn set_rich_text(&mut self, rich_text: &RichText) {
+        self.text = rich_text.to_string();
+        self.markup = TextMarkup::Rich;
+    }
 
-            line_width += self.font_character_widths[char as usize] as f32;
-            text_size.x = f32::max(text_size.x, line_width);
+    pub fn parse(&self, text: &str) -> Result<RichText, CoreError> {
+        match self.markup {
+            TextMarkup::None => Ok(RichText::new_plain(text)),
+            TextMarkup::Rich => markup::parse(text),
+            TextMarkup::Legacy => markup::parse_legacy(text),
         }
+    }
 
-        text_size
+    pub fn calculate_text_size(&self, text: String) -> Result<Vec2, CoreError> {
+        Ok(self.calculate_rich_text_size(&self.parse(&text)?))
     }
 
-    pub fn update(&mut self) {
+    pub fn calculate_rich_text_size(&self, rich_text: &RichText) -> Vec2 {
+        let lines = self.get_lines(rich_text);
+        let width = lines.iter().fold(0.0, |width, line| f32::max(width, line.width));
+        let height = lines.iter().map(|p| p.height).sum();
+
+        Vec2::new(width, height)
+    }
+
+    pub fn wrap(&self, rich_text: &RichText, max_width: f32) -> RichText {
+        wrap_rich_text(rich_text, max_width, |previous_char, char| self.font_glyphs.contains_key(&char).then(|| self.get_character_advance(previous_char, char)))
+    }
+
+    pub fn update(&mut self) -> Result<(), CoreError> {
+        self.update_atlas();
+
         unsafe {
-            let mut offset: Vec2 = Default::default();
-            let mut size: Vec2 = Vec2::new(0.0, self.line_height as f32);
-
-            let characters_per_row = (self.font_size.x / self.font_cell_size.x) as u8;
-            let uv_width = self.font_cell_size.x / self.font_size.x;
-            let uv_height = self.font_cell_size.y / self.font_size.y;
-            let uv_size = Vec2::new(uv_width, uv_height);
-            let mut index = 0;
-            let mut color = SolidColor::new(1.0, 1.0, 1.0, 1.0);
-            let mut color_section = false;
-            let mut color_definition = String::new();
+            let rich_text = self.parse(&self.text)?;
+            let lines = self.get_lines(&rich_text);
+            let size = self.calculate_rich_text_size(&rich_text);
 
-            self.vertices.clear();
-            self.indices.clear();
+            let mut shadows_vertices = Vec::new();
+            let mut outlines_vertices = Vec::new();
+            let mut characters_vertices = Vec::new();
+            let mut underlines_vertices = Vec::new();
+            let mut line_top = size.y;
 
-            for char in self.text.chars() {
-                if char == '\n' {
-                    offset.x = 0.0;
-                    offset.y -= self.line_height as f32;
-                    size.y += self.line_height as f32;
-                    continue;
-                } else if char == '°' {
-                    if color_section {
-                        let tokens = color_definition.split(',').collect::<Vec<&str>>();
-                        let r = tokens[0].parse::<u8>().unwrap() as f32;
-                        let g = tokens[1].parse::<u8>().unwrap() as f32;
-                        let b = tokens[2].parse::<u8>().unwrap() as f32;
-                        let a = tokens[3].parse::<u8>().unwrap() as f32;
-
-                        color = SolidColor::new(r / 255.0, g / 255.0, b / 255.0, a / 255.0);
-                        color_section = false;
-                    } else {
-                        color_section = true;
-                        color_definition.clear();
+            for line in lines {
+                let line_bottom = line_top - line.height;
+
+                for (char, x, style) in line.characters {
+                    let glyph = &self.font_glyphs[&char];
+                    let (position, size, uv, uv_size) = self.get_glyph_quad(glyph, x, line_bottom, style.scale);
+
+                    if let Some(shadow) = style.shadow {
+                        shadows_vertices.extend_from_slice(&self.get_vertices(size, position + shadow.offset, uv, uv_size, shadow.color));
                     }
 
-                    continue;
-                }
+                    if let Some(outline) = style.outline {
+                        for direction in OUTLINE_DIRECTIONS {
+                            let outline_position = position + Vec2::new(direction.0, direction.1) * outline.thickness;
+                            outlines_vertices.extend_from_slice(&self.get_vertices(size, outline_position, uv, uv_size, outline.color));
+                        }
+                    }
 
-                if color_section {
-                    color_definition += &char.to_string();
-                    continue;
-                }
+                    characters_vertices.extend_from_slice(&self.get_vertices(size, position, uv, uv_size, style.color));
 
-                let row = (char as u8 - self.font_base_character_offset) % characters_per_row;
-                let col = (char as u8 - self.font_base_character_offset) / characters_per_row;
+                    // Underline is made from the middle column of the underscore glyph, stretched to the advance of the character
+                    if let (true, Some(underscore_glyph)) = (style.underline, self.font_glyphs.get(&'_')) {
+                        let (underline_position, underline_size, underline_uv, underline_uv_size) = self.get_glyph_quad(underscore_glyph, x, line_bottom, style.scale);
+                        let underline_position = Vec2::new(x, underline_position.y);
+                        let underline_size = Vec2::new(glyph.advance * style.scale, underline_size.y);
+                        let underline_uv =
+                            Vec2::new(underline_uv.x + f32::min(underscore_glyph.advance, underscore_glyph.size.x) / 2.0 / self.font_size.x, underline_uv.y);
+                        let underline_uv_size = Vec2::new(0.0, underline_uv_size.y);
 
-                let character_width = self.font_character_widths[char as usize];
-                let uv = Vec2::new(row as f32 * uv_width, 1.0 - col as f32 * uv_height - uv_height);
+                        underlines_vertices.extend_from_slice(&self.get_vertices(underline_size, underline_position, underline_uv, underline_uv_size, style.color));
+                    }
+                }
 
-                self.vertices.extend_from_slice(&self.get_vertices(self.font_cell_size.x as u32, self.font_cell_size.y as u32, offset, uv, uv_size, color));
+                line_top = line_bottom;
+            }
+
+            self.vertices.clear();
+            self.vertices.extend_from_slice(&shadows_vertices);
+            self.vertices.extend_from_slice(&outlines_vertices);
+            self.vertices.extend_from_slice(&characters_vertices);
+            self.vertices.extend_from_slice(&underlines_vertices);
 
+            self.indices.clear();
+            for index in 0..(self.vertices.len() / 36) {
                 let indices_offset = (index * 4) as u32;
                 self.indices.extend_from_slice(&[
                     0 + indices_offset,
@@ -184,36 +242,122 @@ impl Text {
                     2 + indices_offset,
                     3 + indices_offset,
                 ]);
-
-                offset += Vec2::new(character_width as f32, 0.0);
-                size.x = size.x.max(offset.x);
-                index += 1;
-            }
-
-            // Adjust vertices, so the default anchor is in the left-bottom corner
-            for index in 0..(self.vertices.len() / 9) {
-                self.vertices[index * 9 + 1] += size.y - self.font_cell_size.y;
             }
 
             let vertices_size = (mem::size_of::<f32>() * self.vertices.len()) as i64;
             let vertices_ptr = self.vertices.as_ptr() as *const c_void;
 
-            (self.gl.glBindVertexArray)(self.vao_gl_id);
-            (self.gl.glBindBuffer)(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
+            self.state.bind_vertex_array(self.vao_gl_id);
+            self.state.bind_buffer(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
             (self.gl.glBufferData)(opengl::GL_ARRAY_BUFFER, vertices_size, vertices_ptr, opengl::GL_STATIC_DRAW);
+            self.statistics.borrow_mut().add_buffer_upload(vertices_size);
 
             let indices_size = (mem::size_of::<u32>() * self.indices.len()) as i64;
             let indices_ptr = self.indices.as_ptr() as *const c_void;
 
-            (self.gl.glBindBuffer)(opengl::GL_ELEMENT_ARRAY_BUFFER, self.ebo_gl_id);
+            self.state.bind_buffer(opengl::GL_ELEMENT_ARRAY_BUFFER, self.ebo_gl_id);
             (self.gl.glBufferData)(opengl::GL_ELEMENT_ARRAY_BUFFER, indices_size, indices_ptr, opengl::GL_STATIC_DRAW);
+            self.statistics.borrow_mut().add_buffer_upload(indices_size);
 
             self.size = size;
             self.elements_count = self.indices.len() as u32;
+
+            Ok(())
+        }
+    }
+
+    // Atlas only grows and glyphs never move inside it, so outdated texture coordinates just have to be rescaled to the new size
+    fn update_atlas(&mut self) {
+        let font_atlas = self.font_atlas.clone();
+        let atlas = font_atlas.borrow();
+        if atlas.version == self.font_atlas_version {
+            return;
+        }
+
+        let scale = self.font_size / atlas.size;
+        for vertex in self.vertices.chunks_exact_mut(9) {
+            vertex[7] *= scale.x;
+            vertex[8] *= scale.y;
+        }
+
+        // Glyphs rasterized after the font has been set are available since the next update
+        self.font_size = atlas.size;
+        self.font_glyphs = atlas.glyphs.clone();
+        self.font_kerning = atlas.kerning.clone();
+        self.font_atlas_version = atlas.version;
+
+        unsafe {
+            let vertices_size = (mem::size_of::<f32>() * self.vertices.len()) as i64;
+            let vertices_ptr = self.vertices.as_ptr() as *const c_void;
+
+            self.state.bind_vertex_array(self.vao_gl_id);
+            self.state.bind_buffer(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
+            (self.gl.glBufferData)(opengl::GL_ARRAY_BUFFER, vertices_size, vertices_ptr, opengl::GL_STATIC_DRAW);
+            self.statistics.borrow_mut().add_buffer_upload(vertices_size);
+        }
+    }
+
+    fn get_lines(&self, rich_text: &RichText) -> Vec<TextLine> {
+        let mut lines = vec![TextLine::default()];
+        let mut previous_char = None;
+
+        for span in &rich_text.spans {
+            for char in span.text.chars() {
+                if char == '\n' {
+                    lines.push(TextLine::default());
+                    previous_char = None;
+                    continue;
+                }
+
+                // Characters not present in the font are skipped instead of indexing outside of it
+                if !self.font_glyphs.contains_key(&char) {
+                    continue;
+                }
+
+                let line = lines.last_mut().unwrap();
+                let x = line.width + self.get_kerning(previous_char, char) * span.style.scale;
+
+                line.characters.push((char, x, span.style));
+                line.width = x + self.font_glyphs[&char].advance * span.style.scale;
+                previous_char = Some(char);
+            }
+        }
+
+        for line in &mut lines {
+            let scale = line.characters.iter().fold(0.0, |scale, (_, _, style)| f32::max(scale, style.scale));
+            line.height = self.line_height as f32 * if line.characters.is_empty() { 1.0 } else { scale };
         }
+
+        lines
+    }
+
+    fn get_character_advance(&self, previous_char: Option<char>, char: char) -> f32 {
+        let advance = match self.font_glyphs.get(&char) {
+            Some(glyph) => glyph.advance,
+            None => 0.0,
+        };
+
+        advance + self.get_kerning(previous_char, char)
+    }
+
+    fn get_kerning(&self, previous_char: Option<char>, char: char) -> f32 {
+        match previous_char {
+            Some(previous_char) => self.font_kerning.get(&(previous_char, char)).copied().unwrap_or(0.0),
+            None => 0.0,
+        }
+    }
+
+    fn get_glyph_quad(&self, glyph: &FontGlyph, x: f32, line_bottom: f32, scale: f32) -> (Vec2, Vec2, Vec2, Vec2) {
+        // Glyph offsets are relative to the top-left corner of the line, while the quads are positioned from the bottom
+        let position = Vec2::new(x + glyph.offset.x * scale, line_bottom + (self.line_height as f32 - glyph.offset.y - glyph.size.y) * scale);
+        let size = glyph.size * scale;
+        let uv = glyph.position / self.font_size;
+        let uv_size = glyph.size / self.font_size;
+
+        (position, size, uv, uv_size)
     }
 
-    fn get_vertices(&self, width: u32, height: u32, offset: Vec2, uv: Vec2, uv_size: Vec2, color: SolidColor) -> [f32; 36] {
+    fn get_vertices(&self, size: Vec2, offset: Vec2, uv: Vec2, uv_size: Vec2, color: SolidColor) -> [f32; 36] {
         [
             // Left-bottom
             /* v.x */ 0.0 + offset.x,
@@ -226,7 +370,7 @@ impl Text {
             /* t.u */ uv.x,
             /* t.v */ uv.y,
             // Right-bottom
-            /* v.x */ (width as f32) + offset.x,
+            /* v.x */ size.x + offset.x,
             /* v.y */ 0.0 + offset.y,
             /* v.z */ 0.0,
             /* c.r */ color.r,
@@ -236,8 +380,8 @@ impl Text {
             /* t.u */ uv.x + uv_size.x,
             /* t.v */ uv.y,
             // Right-top
-            /* v.x */ (width as f32) + offset.x,
-            /* v.y */ (height as f32) + offset.y,
+            /* v.x */ size.x + offset.x,
+            /* v.y */ size.y + offset.y,
             /* v.z */ 0.0,
             /* c.r */ color.r,
             /* c.g */ color.g,
@@ -247,7 +391,7 @@ impl Text {
             /* t.v */ uv.y + uv_size.y,
             // Left-top
             /* v.x */ 0.0 + offset.x,
-            /* v.y */ (height as f32) + offset.y,
+            /* v.y */ size.y + offset.y,
             /* v.z */ 0.0,
             /* c.r */ color.r,
             /* c.g */ color.g,
@@ -261,11 +405,8 @@ impl Text {
 
 impl Drawable for Text {
     fn get_transformation_matrix(&self) -> Mat4x4 {
-        let translation = Mat4x4::translate(Vec3::from(self.position));
-        let anchor_offset = Mat4x4::translate(-Vec3::from(self.anchor * self.size).floor());
-        let scale = Mat4x4::scale(Vec3::from(self.scale));
-        let rotation = Mat4x4::rotate(self.rotation);
-        translation * rotation * scale * anchor_offset
+        let pivot = (self.anchor * self.size).floor();
+        Transform2D { pivot, ..Transform2D::new(self.position, self.rotation, self.scale) }.get_mat4x4()
     }
 
     fn get_batch(&self) -> Batch {
@@ -276,15 +417,40 @@ impl Drawable for Text {
         &self.color
     }
 
-    fn draw(&mut self, shader: &Shader) -> Result<(), String> {
+    fn get_font_type(&self) -> Option<FontType> {
+        Some(self.font_type)
+    }
+
+    fn get_font_id(&self) -> Option<Handle<Font>> {
+        Some(self.font_id)
+    }
+
+    fn draw(&mut self, shader: &Shader) -> Result<(), CoreError> {
+        self.update_atlas();
+
         unsafe {
             let model = self.get_transformation_matrix();
 
             shader.set_parameter("model", model.as_ptr())?;
+
+            if self.font_type != FontType::Bitmap {
+                if let Color::Gradient(_) = self.color {
+                    return Err(CoreError::InvalidOperation("gradient color is not supported by distance field fonts".to_string()));
+                }
+
+                shader.set_parameter("msdf", &(if self.font_type == FontType::Msdf { 1.0 } else { 0.0 }))?;
+                shader.set_parameter("distanceRange", &self.font_distance_range)?;
+                shader.set_parameter("smoothing", &self.smoothing)?;
+                shader.set_parameter("outlineThickness", &self.outline_thickness)?;
+                shader.set_solid_color("outlineColor", &self.outline_color)?;
+                shader.set_parameter("glowThickness", &self.glow_thickness)?;
+                shader.set_solid_color("glowColor", &self.glow_color)?;
+            }
+
             shader.set_color(&self.color)?;
 
-            (self.gl.glBindVertexArray)(self.vao_gl_id);
-            (self.gl.glBindTexture)(opengl::GL_TEXTURE_2D, self.texture_gl_id);
+            self.state.bind_vertex_array(self.vao_gl_id);
+            self.state.bind_texture(0, self.texture_gl_id);
             (self.gl.glDrawElements)(opengl::GL_TRIANGLES, self.elements_count as i32, opengl::GL_UNSIGNED_INT, ptr::null());
 
             Ok(())
@@ -294,18 +460,110 @@ impl Drawable for Text {
 
 impl Drop for Text {
     fn drop(&mut self) {
-        unsafe {
-            if self.vbo_gl_id != 0 {
-                (self.gl.glDeleteBuffers)(1, &mut self.vbo_gl_id);
-            }
+        if self.vbo_gl_id != 0 {
+            self.state.delete_buffer(self.vbo_gl_id);
+        }
 
-            if self.ebo_gl_id != 0 {
-                (self.gl.glDeleteBuffers)(1, &mut self.ebo_gl_id);
-            }
+        if self.ebo_gl_id != 0 {
+            self.state.delete_buffer(self.ebo_gl_id);
+        }
+
+        if self.vao_gl_id != 0 {
+            self.state.delete_vertex_array(self.vao_gl_id);
+        }
+    }
+}
+
+// Characters without a glyph are skipped, so they don't affect the line width
+fn wrap_rich_text(rich_text: &RichText, max_width: f32, get_advance: impl Fn(Option<char>, char) -> Option<f32>) -> RichText {
+    let mut characters = Vec::new();
+    for span in &rich_text.spans {
+        characters.extend(span.text.chars().map(|p| (p, span.style)));
+    }
 
-            if self.vao_gl_id != 0 {
-                (self.gl.glDeleteVertexArrays)(1, &mut self.vao_gl_id);
+    let mut line_width = 0.0;
+    let mut last_space = None;
+    let mut previous_char = None;
+
+    for index in 0..characters.len() {
+        let (char, style) = characters[index];
+        if char == '\n' {
+            line_width = 0.0;
+            last_space = None;
+            previous_char = None;
+            continue;
+        }
+
+        let advance = match get_advance(previous_char, char) {
+            Some(advance) => advance,
+            None => continue,
+        };
+
+        line_width += advance * style.scale;
+        previous_char = Some(char);
+
+        if char == ' ' {
+            last_space = Some((index, line_width));
+        } else if line_width > max_width {
+            if let Some((space_index, width_to_space)) = last_space {
+                characters[space_index].0 = '\n';
+                line_width -= width_to_space;
+                last_space = None;
+                previous_char = None;
             }
         }
     }
+
+    let mut wrapped_rich_text = RichText::new();
+    for (char, style) in characters {
+        wrapped_rich_text.add_span(&char.to_string(), style);
+    }
+
+    wrapped_rich_text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::richtext::TextSpan;
+
+    fn get_advance(_: Option<char>, char: char) -> Option<f32> {
+        if char == '#' {
+            None
+        } else {
+            Some(10.0)
+        }
+    }
+
+    #[test]
+    fn wrap_at_spaces() {
+        let wrapped = wrap_rich_text(&RichText::new_plain("aaa bbb ccc"), 75.0, get_advance);
+        assert_eq!(wrapped.get_plain_text(), "aaa bbb\nccc");
+
+        let wrapped = wrap_rich_text(&RichText::new_plain("aaa bbb ccc"), 35.0, get_advance);
+        assert_eq!(wrapped.get_plain_text(), "aaa\nbbb\nccc");
+    }
+
+    #[test]
+    fn wrap_long_word() {
+        let wrapped = wrap_rich_text(&RichText::new_plain("aaaaaaaa bb"), 50.0, get_advance);
+        assert_eq!(wrapped.get_plain_text(), "aaaaaaaa\nbb");
+    }
+
+    #[test]
+    fn wrap_keeps_styles() {
+        let large = TextStyle { scale: 2.0, ..Default::default() };
+        let mut rich_text = RichText::new();
+        rich_text.add_span("aa ", TextStyle::default());
+        rich_text.add_span("bb", large);
+
+        let wrapped = wrap_rich_text(&rich_text, 50.0, get_advance);
+        assert_eq!(wrapped.spans, vec![TextSpan::new("aa\n", TextStyle::default()), TextSpan::new("bb", large)]);
+    }
+
+    #[test]
+    fn wrap_skips_missing_glyphs() {
+        let wrapped = wrap_rich_text(&RichText::new_plain("aa ####### bb"), 60.0, get_advance);
+        assert_eq!(wrapped.get_plain_text(), "aa ####### bb");
+    }
 }
@@ -1,25 +1,26 @@
 use super::*;
+use crate::error::CoreError;
 use crate::utils::binary;
 use std::fs::File;
 use std::io::Read;
 
-pub fn load(path: &str) -> Result<RawFont, String> {
+pub fn load(path: &str) -> Result<RawFont, CoreError> {
     /////////////////////////////////////////////////////////////////////////////////////////////////
     // BFF specification: https://documentation.help/Codehead-Bitmap-Font-Generator/bffformat.html //
     /////////////////////////////////////////////////////////////////////////////////////////////////
 
     let mut file = match File::open(path) {
         Ok(file) => file,
-        Err(_) => return Err(format!("File {} not found", path)),
+        Err(error) => return Err(CoreError::Io { path: path.to_string(), message: error.to_string() }),
     };
 
     let mut bff = Vec::new();
-    if let Err(message) = file.read_to_end(&mut bff) {
-        return Err(format!("Error while reading file: {}", message));
+    if let Err(error) = file.read_to_end(&mut bff) {
+        return Err(CoreError::Io { path: path.to_string(), message: error.to_string() });
     }
 
     if binary::read_le_u16(&bff, 0) != 0xf2bf {
-        return Err("Invalid signature, not recognized as BFF file".to_string());
+        return Err(CoreError::parse("Invalid signature, not recognized as BFF file", Some(0)));
     }
 
     let width = binary::read_le_u32(&bff, 2);
@@ -29,9 +30,28 @@ pub fn load(path: &str) -> Result<RawFont, String> {
     let bits_per_pixel = binary::read_u8(&bff, 18);
     let base_character_offset = binary::read_u8(&bff, 19);
 
-    let mut character_widths = Vec::new();
-    for index in 0..256 {
-        character_widths.push(binary::read_u8(&bff, 20 + index));
+    if cell_width == 0 || cell_height == 0 || cell_width > width || cell_height > height {
+        return Err(CoreError::parse("Invalid cell size", Some(10)));
+    }
+
+    // Cells are laid out from the top-left corner, while the texture data is stored from the bottom
+    let mut glyphs = HashMap::new();
+    let characters_per_row = width / cell_width;
+    let characters_per_col = height / cell_height;
+
+    for char in base_character_offset as u32..256 {
+        let row = (char - base_character_offset as u32) % characters_per_row;
+        let col = (char - base_character_offset as u32) / characters_per_row;
+
+        if col >= characters_per_col {
+            break;
+        }
+
+        let position = Vec2::new((row * cell_width) as f32, (height - col * cell_height - cell_height) as f32);
+        let size = Vec2::new(cell_width as f32, cell_height as f32);
+        let advance = binary::read_u8(&bff, 20 + char as usize) as f32;
+
+        glyphs.insert(char::from(char as u8), FontGlyph::new(position, size, Default::default(), advance));
     }
 
     let mut data = Vec::new();
@@ -52,7 +72,7 @@ pub fn load(path: &str) -> Result<RawFont, String> {
                     binary::read_u8(&bff, (276 + index * 4 + 2) as usize),
                     binary::read_u8(&bff, (276 + index * 4 + 3) as usize),
                 ),
-                _ => return Err("Unsupported format, only 8, 24 and 32 bits per pixel are allowed".to_string()),
+                _ => return Err(CoreError::UnsupportedFormat("only 8, 24 and 32 bits per pixel are allowed".to_string())),
             };
 
             data.push(r);
@@ -62,5 +82,5 @@ pub fn load(path: &str) -> Result<RawFont, String> {
         }
     }
 
-    Ok(RawFont::new(Vec2::new(width as f32, height as f32), Vec2::new(cell_width as f32, cell_height as f32), base_character_offset, character_widths, data))
+    Ok(RawFont::new(Vec2::new(width as f32, height as f32), cell_height as f32, glyphs, HashMap::new(), data))
 }
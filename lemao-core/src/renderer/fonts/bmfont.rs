@@ -0,0 +1,348 @@
+use super::*;
+use crate::error::CoreError;
+use crate::renderer::textures::bmp;
+use crate::renderer::textures::tga;
+use crate::utils::binary;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+#[derive(Default)]
+struct FontDescription {
+    line_height: u32,
+    page_width: u32,
+    page_height: u32,
+    packed: bool,
+    pages: Vec<String>,
+    chars: Vec<CharDescription>,
+    kerning: Vec<(u32, u32, i32)>,
+}
+
+struct CharDescription {
+    id: u32,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    x_offset: i32,
+    y_offset: i32,
+    x_advance: i32,
+    page: u32,
+    offset: usize,
+}
+
+pub fn load(path: &str) -> Result<RawFont, CoreError> {
+    //////////////////////////////////////////////////////////////////////////////////////////
+    // BMFont specification: https://www.angelcode.com/products/bmfont/doc/file_format.html //
+    //////////////////////////////////////////////////////////////////////////////////////////
+
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) => return Err(CoreError::Io { path: path.to_string(), message: error.to_string() }),
+    };
+
+    let mut fnt = Vec::new();
+    if let Err(error) = file.read_to_end(&mut fnt) {
+        return Err(CoreError::Io { path: path.to_string(), message: error.to_string() });
+    }
+
+    let description = if fnt.starts_with(b"BMF") { parse_binary(&fnt)? } else { parse_text(&fnt)? };
+
+    if description.packed {
+        return Err(CoreError::UnsupportedFormat("glyphs packed into separate channels are not allowed".to_string()));
+    }
+
+    if description.pages.is_empty() || description.page_width == 0 || description.page_height == 0 {
+        return Err(CoreError::parse("Invalid file, no pages defined", None));
+    }
+
+    let (width, height, data_length) = get_atlas_size(&description)?;
+    let mut data = vec![0; data_length];
+
+    // Pages are stacked vertically into a single atlas, the first one at the top
+    for (page_index, page_file) in description.pages.iter().enumerate() {
+        let page_path = match Path::new(path).parent() {
+            Some(directory) => directory.join(page_file),
+            None => Path::new(page_file).to_path_buf(),
+        };
+        let page_path = page_path.to_string_lossy().to_string();
+
+        let page = match page_path.rsplit('.').next().map(|p| p.to_lowercase()).as_deref() {
+            Some("bmp") => bmp::load(&page_path)?,
+            Some("tga") => tga::load(&page_path)?,
+            _ => return Err(CoreError::UnsupportedFormat(format!("page texture {} is not BMP nor TGA file", page_file))),
+        };
+
+        if page.size.x as u32 != description.page_width || page.size.y as u32 != description.page_height {
+            return Err(CoreError::parse(&format!("Invalid page texture {}, expected size doesn't match the real one", page_file), None));
+        }
+
+        // Pages without alpha channel are treated as grayscale coverage maps
+        let opaque = page.data.chunks(4).all(|p| p[3] == 0xff);
+        let page_offset = (height as usize - (page_index + 1) * description.page_height as usize) * width as usize * 4;
+
+        for (index, pixel) in page.data.chunks(4).enumerate() {
+            let (r, g, b, a) = if opaque { (0xff, 0xff, 0xff, pixel[0]) } else { (pixel[0], pixel[1], pixel[2], pixel[3]) };
+            let data_index = page_offset + index * 4;
+
+            data[data_index + 0] = r;
+            data[data_index + 1] = g;
+            data[data_index + 2] = b;
+            data[data_index + 3] = a;
+        }
+    }
+
+    let mut glyphs = HashMap::new();
+    for char in &description.chars {
+        // Invalid characters are marked with -1 by some of the generators
+        let id = match char::from_u32(char.id) {
+            Some(id) => id,
+            None => continue,
+        };
+
+        if char.page as usize >= description.pages.len()
+            || char.x as u64 + char.width as u64 > width as u64
+            || char.y as u64 + char.height as u64 > description.page_height as u64
+        {
+            return Err(CoreError::parse(&format!("Invalid character {}, rectangle is outside of the page", char.id), Some(char.offset)));
+        }
+
+        let position = Vec2::new(char.x as f32, (height - char.page * description.page_height - char.y - char.height) as f32);
+        let size = Vec2::new(char.width as f32, char.height as f32);
+        let offset = Vec2::new(char.x_offset as f32, char.y_offset as f32);
+
+        glyphs.insert(id, FontGlyph::new(position, size, offset, char.x_advance as f32));
+    }
+
+    let mut kerning = HashMap::new();
+    for (first, second, amount) in description.kerning {
+        if let (Some(first), Some(second)) = (char::from_u32(first), char::from_u32(second)) {
+            kerning.insert((first, second), amount as f32);
+        }
+    }
+
+    Ok(RawFont::new(Vec2::new(width as f32, height as f32), description.line_height as f32, glyphs, kerning, data))
+}
+
+// Sizes are read directly from the file, so they are validated before the atlas buffer is allocated
+fn get_atlas_size(description: &FontDescription) -> Result<(u32, u32, usize), CoreError> {
+    let width = description.page_width;
+    let height = u32::try_from(description.pages.len()).ok().and_then(|p| description.page_height.checked_mul(p));
+    let data_length = height.and_then(|p| p.checked_mul(width)).and_then(|p| p.checked_mul(4));
+
+    match (height, data_length) {
+        (Some(height), Some(data_length)) => Ok((width, height, data_length as usize)),
+        _ => Err(CoreError::parse("Invalid file, atlas size is too large", None)),
+    }
+}
+
+fn parse_text(fnt: &[u8]) -> Result<FontDescription, CoreError> {
+    let text = String::from_utf8_lossy(fnt);
+    let mut description = FontDescription::default();
+
+    let mut line_offset = 0;
+
+    for (line_index, line) in text.split_inclusive('\n').enumerate() {
+        let offset = line_offset;
+        line_offset += line.len();
+
+        let (tag, attributes) = tokenize(line.trim_end_matches(['\r', '\n']));
+        let get = |name: &str| -> Result<i32, CoreError> {
+            match attributes.get(name) {
+                Some(value) => match value.parse::<i32>() {
+                    Ok(value) => Ok(value),
+                    Err(_) => Err(CoreError::parse(&format!("Invalid value of {} attribute in line {}", name, line_index + 1), Some(offset))),
+                },
+                None => Err(CoreError::parse(&format!("Missing {} attribute in line {}", name, line_index + 1), Some(offset))),
+            }
+        };
+
+        // Sizes and positions can't be negative, casting them would wrap around to huge values
+        let get_unsigned = |name: &str| -> Result<u32, CoreError> {
+            match get(name)? {
+                value if value >= 0 => Ok(value as u32),
+                value => Err(CoreError::parse(&format!("Invalid value of {} attribute in line {}, {} is negative", name, line_index + 1, value), Some(offset))),
+            }
+        };
+
+        match tag.as_str() {
+            "common" => {
+                description.line_height = get_unsigned("lineHeight")?;
+                description.page_width = get_unsigned("scaleW")?;
+                description.page_height = get_unsigned("scaleH")?;
+                description.packed = attributes.get("packed").map(|p| p == "1").unwrap_or(false);
+            }
+            "page" => {
+                let id = get_unsigned("id")? as usize;
+                let file = match attributes.get("file") {
+                    Some(file) => file.clone(),
+                    None => return Err(CoreError::parse(&format!("Missing file attribute in line {}", line_index + 1), Some(offset))),
+                };
+
+                if description.pages.len() <= id {
+                    description.pages.resize(id + 1, String::new());
+                }
+                description.pages[id] = file;
+            }
+            "char" => {
+                description.chars.push(CharDescription {
+                    id: get("id")? as u32,
+                    x: get_unsigned("x")?,
+                    y: get_unsigned("y")?,
+                    width: get_unsigned("width")?,
+                    height: get_unsigned("height")?,
+                    x_offset: get("xoffset")?,
+                    y_offset: get("yoffset")?,
+                    x_advance: get("xadvance")?,
+                    page: get_unsigned("page")?,
+                    offset,
+                });
+            }
+            "kerning" => {
+                description.kerning.push((get("first")? as u32, get("second")? as u32, get("amount")?));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(description)
+}
+
+fn parse_binary(fnt: &[u8]) -> Result<FontDescription, CoreError> {
+    if fnt.len() < 4 || binary::read_u8(fnt, 3) != 3 {
+        return Err(CoreError::UnsupportedFormat("only the third version of binary format is allowed".to_string()));
+    }
+
+    let mut description = FontDescription::default();
+    let mut index = 4;
+
+    while index + 5 <= fnt.len() {
+        let block_type = binary::read_u8(fnt, index);
+        let block_size = binary::read_le_u32(fnt, index + 1) as usize;
+        let block_start = index + 5;
+
+        if block_start + block_size > fnt.len() {
+            return Err(CoreError::parse("Invalid file, block exceeds the file size", Some(index)));
+        }
+
+        let block = &fnt[block_start..block_start + block_size];
+        match block_type {
+            // Common
+            2 => {
+                if block.len() < 15 {
+                    return Err(CoreError::parse("Invalid file, common block is too short", Some(index)));
+                }
+
+                description.line_height = binary::read_le_u16(block, 0) as u32;
+                description.page_width = binary::read_le_u16(block, 4) as u32;
+                description.page_height = binary::read_le_u16(block, 6) as u32;
+                description.packed = binary::read_u8(block, 10) & 0x80 != 0;
+            }
+            // Pages, each name is terminated with null character
+            3 => {
+                for name in block.split(|p| *p == 0).filter(|p| !p.is_empty()) {
+                    description.pages.push(String::from_utf8_lossy(name).to_string());
+                }
+            }
+            // Chars
+            4 => {
+                for (char_index, char) in block.chunks_exact(20).enumerate() {
+                    description.chars.push(CharDescription {
+                        id: binary::read_le_u32(char, 0),
+                        x: binary::read_le_u16(char, 4) as u32,
+                        y: binary::read_le_u16(char, 6) as u32,
+                        width: binary::read_le_u16(char, 8) as u32,
+                        height: binary::read_le_u16(char, 10) as u32,
+                        x_offset: binary::read_le_u16(char, 12) as i16 as i32,
+                        y_offset: binary::read_le_u16(char, 14) as i16 as i32,
+                        x_advance: binary::read_le_u16(char, 16) as i16 as i32,
+                        page: binary::read_u8(char, 18) as u32,
+                        offset: block_start + char_index * 20,
+                    });
+                }
+            }
+            // Kerning pairs
+            5 => {
+                for pair in block.chunks_exact(10) {
+                    description.kerning.push((binary::read_le_u32(pair, 0), binary::read_le_u32(pair, 4), binary::read_le_u16(pair, 8) as i16 as i32));
+                }
+            }
+            _ => {}
+        }
+
+        index = block_start + block_size;
+    }
+
+    Ok(description)
+}
+
+fn tokenize(line: &str) -> (String, HashMap<String, String>) {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut quoted = false;
+
+    for char in line.chars() {
+        match char {
+            '"' => quoted = !quoted,
+            ' ' | '\t' if !quoted => {
+                if !token.is_empty() {
+                    tokens.push(token.clone());
+                    token.clear();
+                }
+            }
+            _ => token.push(char),
+        }
+    }
+
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+
+    let tag = tokens.first().cloned().unwrap_or_default();
+    let attributes = tokens.iter().skip(1).filter_map(|p| p.split_once('=')).map(|(name, value)| (name.to_string(), value.to_string())).collect();
+
+    (tag, attributes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_text_char() {
+        let fnt = b"common lineHeight=16 scaleW=64 scaleH=64 packed=0\nchar id=-1 x=0 y=0 width=4 height=8 xoffset=-1 yoffset=2 xadvance=5 page=0\n";
+        let description = parse_text(fnt).unwrap();
+
+        assert_eq!(description.line_height, 16);
+        assert_eq!(description.chars[0].id, u32::MAX);
+        assert_eq!(description.chars[0].x_offset, -1);
+        assert_eq!(description.chars[0].offset, 50);
+    }
+
+    #[test]
+    fn parse_text_negative() {
+        let fnt = b"common lineHeight=16 scaleW=64 scaleH=64\r\nchar id=65 x=-2 y=0 width=4 height=8 xoffset=0 yoffset=0 xadvance=5 page=0\r\n";
+        assert!(matches!(parse_text(fnt), Err(CoreError::AssetParse { offset: Some(42), .. })));
+
+        let fnt = b"common lineHeight=16 scaleW=-64 scaleH=64\n";
+        assert!(parse_text(fnt).is_err());
+
+        let fnt = b"page id=-1 file=\"font.tga\"\n";
+        assert!(parse_text(fnt).is_err());
+    }
+
+    #[test]
+    fn get_atlas_size_overflow() {
+        let pages = vec![String::new(); 2];
+        let description = FontDescription { page_width: 64, page_height: 32, pages, ..Default::default() };
+        assert_eq!(get_atlas_size(&description).unwrap(), (64, 64, 64 * 64 * 4));
+
+        let pages = vec![String::new()];
+        let description = FontDescription { page_width: 65535, page_height: 65535, pages, ..Default::default() };
+        assert!(matches!(get_atlas_size(&description), Err(CoreError::AssetParse { .. })));
+
+        let pages = vec![String::new(); 70000];
+        let description = FontDescription { page_width: 1, page_height: 65535, pages, ..Default::default() };
+        assert!(matches!(get_atlas_size(&description), Err(CoreError::AssetParse { .. })));
+    }
+}
@@ -1,63 +1,130 @@
+use self::ttf::TrueTypeSource;
 use super::context::RendererContext;
+use super::state::StateCache;
 use super::textures::RawTexture;
+use crate::error::CoreError;
+use crate::utils::storage::Handle;
 use crate::utils::storage::StorageItem;
 use lemao_math::vec2::Vec2;
 use lemao_opengl::bindings::opengl;
 use lemao_opengl::pointers::OpenGLPointers;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::ffi::c_void;
 use std::rc::Rc;
 
 pub mod bff;
+pub mod bmfont;
+pub mod sdf;
+pub mod ttf;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum FontType {
+    #[default]
+    Bitmap,
+    Sdf,
+    Msdf,
+}
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct RawFont {
     pub size: Vec2,
-    pub cell_size: Vec2,
-    pub base_character_offset: u8,
-    pub character_widths: Vec<u8>,
+    pub line_height: f32,
+    pub glyphs: HashMap<char, FontGlyph>,
+    pub kerning: HashMap<(char, char), f32>,
+    pub r#type: FontType,
+    pub distance_range: f32,
+    pub source: Option<TrueTypeSource>,
     pub data: Vec<u8>,
 }
 
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct FontGlyph {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub offset: Vec2,
+    pub advance: f32,
+}
+
+// Version is increased every time the atlas is uploaded, so texts can detect that their texture coordinates and glyphs are outdated
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct FontAtlas {
+    pub version: u32,
+    pub size: Vec2,
+    pub glyphs: HashMap<char, FontGlyph>,
+    pub kerning: HashMap<(char, char), f32>,
+}
+
 pub struct Font {
-    pub id: usize,
+    pub id: Handle<Font>,
     pub name: Option<String>,
     pub(crate) texture_gl_id: u32,
     gl: Rc<OpenGLPointers>,
+    state: Rc<StateCache>,
 
     pub size: Vec2,
-    pub cell_size: Vec2,
-    pub base_character_offset: u8,
-    pub character_widths: Vec<u8>,
+    pub line_height: f32,
+    pub glyphs: HashMap<char, FontGlyph>,
+    pub kerning: HashMap<(char, char), f32>,
+    pub r#type: FontType,
+    pub distance_range: f32,
+
+    pub(crate) atlas: Rc<RefCell<FontAtlas>>,
+    raw: Option<RawFont>,
 }
 
 impl RawFont {
-    pub fn new(size: Vec2, cell_size: Vec2, base_character_offset: u8, character_widths: Vec<u8>, data: Vec<u8>) -> Self {
-        Self { size, cell_size, base_character_offset, character_widths, data }
+    pub fn new(size: Vec2, line_height: f32, glyphs: HashMap<char, FontGlyph>, kerning: HashMap<(char, char), f32>, data: Vec<u8>) -> Self {
+        Self { size, line_height, glyphs, kerning, r#type: FontType::Bitmap, distance_range: 0.0, source: None, data }
+    }
+
+    pub fn new_distance_field(
+        size: Vec2,
+        line_height: f32,
+        glyphs: HashMap<char, FontGlyph>,
+        kerning: HashMap<(char, char), f32>,
+        r#type: FontType,
+        distance_range: f32,
+        data: Vec<u8>,
+    ) -> Self {
+        Self { size, line_height, glyphs, kerning, r#type, distance_range, source: None, data }
     }
 
-    pub fn set_character(&mut self, char: u8, offset: Vec2, texture: &RawTexture) {
+    pub fn add_characters(&mut self, characters: &str) -> Result<bool, CoreError> {
+        let mut source = match self.source.take() {
+            Some(source) => source,
+            None => return Ok(false),
+        };
+
+        let result = ttf::add_characters(self, &mut source, characters);
+        self.source = Some(source);
+
+        result
+    }
+
+    pub fn set_character(&mut self, char: u8, offset: Vec2, texture: &RawTexture) -> Result<(), CoreError> {
         let texture_size = texture.size;
         let texture_data = &texture.data;
 
-        let characters_per_row = (self.size.x / self.cell_size.x) as u8;
-        let row = (char - self.base_character_offset) % characters_per_row;
-        let col = (char - self.base_character_offset) / characters_per_row;
-        let initial_x = (row as f32 * self.cell_size.x) as usize;
-        let initial_y = (self.size.y - (col as f32 * self.cell_size.y) - self.cell_size.y) as usize;
+        let glyph = match self.glyphs.get_mut(&char::from(char)) {
+            Some(glyph) => glyph,
+            None => return Err(CoreError::NotFound { item: "Character", id: Some(char as usize), name: Some(char::from(char).to_string()) }),
+        };
 
-        let mut texture_data_index = 0;
-        for y in initial_y..initial_y + self.cell_size.y as usize {
-            for x in initial_x..initial_x + self.cell_size.x as usize {
+        let initial_x = glyph.position.x as usize;
+        let initial_y = glyph.position.y as usize;
+
+        for y in initial_y..initial_y + glyph.size.y as usize {
+            for x in initial_x..initial_x + glyph.size.x as usize {
                 for p in 0..4 {
                     self.data[x * 4 + y * self.size.x as usize * 4 + p] = 0;
-                    texture_data_index += 1;
                 }
             }
         }
 
         let initial_x = initial_x + offset.x as usize;
         let initial_y = initial_y + offset.y as usize;
-        texture_data_index = 0;
+        let mut texture_data_index = 0;
 
         for y in initial_y..initial_y + texture_size.y as usize {
             for x in initial_x..initial_x + texture_size.x as usize {
@@ -68,49 +135,100 @@ impl RawFont {
             }
         }
 
-        self.character_widths[char as usize] = texture_size.x as u8;
+        glyph.advance = texture_size.x;
+        Ok(())
+    }
+}
+
+impl FontGlyph {
+    pub fn new(position: Vec2, size: Vec2, offset: Vec2, advance: f32) -> Self {
+        Self { position, size, offset, advance }
     }
 }
 
 impl Font {
-    pub fn new(renderer: &RendererContext, raw: &RawFont) -> Result<Self, String> {
+    pub fn new(renderer: &RendererContext, raw: &RawFont) -> Result<Self, CoreError> {
         unsafe {
             let gl = renderer.gl.clone();
+            let state = renderer.state.clone();
             let mut texture_gl_id = 0;
 
             (gl.glGenTextures)(1, &mut texture_gl_id);
-            (gl.glBindTexture)(opengl::GL_TEXTURE_2D, texture_gl_id);
+            state.bind_texture(0, texture_gl_id);
             (gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_WRAP_S, opengl::GL_MIRRORED_REPEAT as i32);
             (gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_WRAP_T, opengl::GL_MIRRORED_REPEAT as i32);
-            (gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_MIN_FILTER, opengl::GL_NEAREST_MIPMAP_LINEAR as i32);
-            (gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_MAG_FILTER, opengl::GL_NEAREST as i32);
 
-            let format = opengl::GL_RGBA;
-            let texture_ptr = raw.data.as_ptr() as *const c_void;
+            // Distance fields have to be interpolated linearly, otherwise edges will be as blocky as in bitmap fonts
+            let (min_filter, mag_filter) = match raw.r#type {
+                FontType::Bitmap => (opengl::GL_NEAREST_MIPMAP_LINEAR, opengl::GL_NEAREST),
+                FontType::Sdf | FontType::Msdf => (opengl::GL_LINEAR, opengl::GL_LINEAR),
+            };
 
-            (gl.glTexImage2D)(opengl::GL_TEXTURE_2D, 0, format as i32, raw.size.x as i32, raw.size.y as i32, 0, format, opengl::GL_UNSIGNED_BYTE, texture_ptr);
-            (gl.glGenerateMipmap)(opengl::GL_TEXTURE_2D);
+            (gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_MIN_FILTER, min_filter as i32);
+            (gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_MAG_FILTER, mag_filter as i32);
 
-            Ok(Self {
-                id: 0,
+            let mut font = Self {
+                id: Default::default(),
                 name: None,
                 texture_gl_id,
                 gl,
-                size: raw.size,
-                cell_size: raw.cell_size,
-                base_character_offset: raw.base_character_offset,
-                character_widths: raw.character_widths.clone(),
-            })
+                state,
+                size: Default::default(),
+                line_height: raw.line_height,
+                glyphs: Default::default(),
+                kerning: Default::default(),
+                r#type: raw.r#type,
+                distance_range: raw.distance_range,
+                atlas: Default::default(),
+                // Only fonts which are able to generate new glyphs need to keep the data on the CPU side
+                raw: if raw.source.is_some() { Some(raw.clone()) } else { None },
+            };
+            font.set_data(raw);
+
+            Ok(font)
         }
     }
+
+    pub fn set_data(&mut self, raw: &RawFont) {
+        unsafe {
+            self.state.bind_texture(0, self.texture_gl_id);
+
+            let format = opengl::GL_RGBA;
+            let texture_ptr = raw.data.as_ptr() as *const c_void;
+
+            (self.gl.glTexImage2D)(opengl::GL_TEXTURE_2D, 0, format as i32, raw.size.x as i32, raw.size.y as i32, 0, format, opengl::GL_UNSIGNED_BYTE, texture_ptr);
+            (self.gl.glGenerateMipmap)(opengl::GL_TEXTURE_2D);
+
+            self.size = raw.size;
+            self.glyphs = raw.glyphs.clone();
+            self.kerning = raw.kerning.clone();
+            let version = self.atlas.borrow().version + 1;
+            *self.atlas.borrow_mut() = FontAtlas { version, size: raw.size, glyphs: raw.glyphs.clone(), kerning: raw.kerning.clone() };
+        }
+    }
+
+    pub fn add_characters(&mut self, characters: &str) -> Result<(), CoreError> {
+        let mut raw = match self.raw.take() {
+            Some(raw) => raw,
+            None => return Ok(()),
+        };
+
+        let result = raw.add_characters(characters);
+        if let Ok(true) = result {
+            self.set_data(&raw);
+        }
+
+        self.raw = Some(raw);
+        result.map(|_| ())
+    }
 }
 
 impl StorageItem for Font {
-    fn get_id(&self) -> usize {
+    fn get_id(&self) -> Handle<Self> {
         self.id
     }
 
-    fn set_id(&mut self, id: usize) {
+    fn set_id(&mut self, id: Handle<Self>) {
         self.id = id;
     }
 
@@ -125,10 +243,8 @@ impl StorageItem for Font {
 
 impl Drop for Font {
     fn drop(&mut self) {
-        unsafe {
-            if self.texture_gl_id != 0 {
-                (self.gl.glDeleteTextures)(1, &self.texture_gl_id);
-            }
+        if self.texture_gl_id != 0 {
+            self.state.delete_texture(self.texture_gl_id);
         }
     }
 }
@@ -0,0 +1,55 @@
+use super::*;
+use crate::error::CoreError;
+
+pub fn generate(raw: &RawFont, spread: u32) -> Result<RawFont, CoreError> {
+    if raw.r#type != FontType::Bitmap {
+        return Err(CoreError::InvalidArgument("only bitmap fonts can be converted into distance field".to_string()));
+    }
+
+    if spread == 0 {
+        return Err(CoreError::InvalidArgument("spread has to be greater than zero".to_string()));
+    }
+
+    let width = raw.size.x as i32;
+    let spread = spread as i32;
+
+    let is_inside = |x: i32, y: i32| raw.data[(x * 4 + y * width * 4 + 3) as usize] >= 0x80;
+    let mut data = vec![0xff; raw.data.len()];
+
+    for index in 0..raw.data.len() / 4 {
+        data[index * 4 + 3] = 0;
+    }
+
+    // The distance is searched only within the rectangle of the current glyph, so neighbour glyphs don't leak into each other
+    for glyph in raw.glyphs.values() {
+        let left = glyph.position.x as i32;
+        let bottom = glyph.position.y as i32;
+        let right = left + glyph.size.x as i32;
+        let top = bottom + glyph.size.y as i32;
+
+        for y in bottom..top {
+            for x in left..right {
+                let inside = is_inside(x, y);
+                let mut nearest_distance_squared = (spread * spread) as f32;
+
+                for search_y in i32::max(y - spread, bottom)..i32::min(y + spread + 1, top) {
+                    for search_x in i32::max(x - spread, left)..i32::min(x + spread + 1, right) {
+                        if is_inside(search_x, search_y) != inside {
+                            let distance_squared = ((search_x - x) * (search_x - x) + (search_y - y) * (search_y - y)) as f32;
+                            nearest_distance_squared = f32::min(nearest_distance_squared, distance_squared);
+                        }
+                    }
+                }
+
+                // The edge lies between two pixels, so half of the pixel is subtracted to keep it at 0.5
+                let distance = f32::min(nearest_distance_squared.sqrt(), spread as f32) - 0.5;
+                let signed_distance = if inside { distance } else { -distance };
+                let value = (0.5 + signed_distance / (2 * spread) as f32).clamp(0.0, 1.0);
+
+                data[(x * 4 + y * width * 4 + 3) as usize] = (value * 255.0).round() as u8;
+            }
+        }
+    }
+
+    Ok(RawFont::new_distance_field(raw.size, raw.line_height, raw.glyphs.clone(), raw.kerning.clone(), FontType::Sdf, (2 * spread) as f32, data))
+}
@@ -0,0 +1,685 @@
+use super::*;
+use crate::error::CoreError;
+use crate::utils::binary;
+use std::fs::File;
+use std::io::Read;
+use std::sync::Arc;
+
+const MIN_ATLAS_WIDTH: u32 = 256;
+const MAX_ATLAS_WIDTH: u32 = 4096;
+const MAX_ATLAS_HEIGHT: u32 = 8192;
+const MAX_COMPOUND_DEPTH: u32 = 8;
+const GLYPH_PADDING: u32 = 1;
+
+#[derive(Debug, PartialEq)]
+pub struct TrueTypeFont {
+    data: Vec<u8>,
+    glyf_offset: usize,
+    glyf_length: usize,
+    loca_offset: usize,
+    hmtx_offset: usize,
+    cmap_offset: usize,
+    cmap_format: u16,
+    units_per_em: f32,
+    index_to_loc_format: i16,
+    glyphs_count: u16,
+    horizontal_metrics_count: u16,
+    ascender: f32,
+    descender: f32,
+    line_gap: f32,
+    kerning: HashMap<(u16, u16), i16>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrueTypeSource {
+    pub font: Arc<TrueTypeFont>,
+    pub pixel_size: f32,
+    cursor: Vec2,
+    shelf_height: f32,
+}
+
+struct GlyphBitmap {
+    width: u32,
+    height: u32,
+    left: i32,
+    bottom: i32,
+    data: Vec<u8>,
+}
+
+pub fn load(path: &str, pixel_size: f32) -> Result<RawFont, CoreError> {
+    ////////////////////////////////////////////////////////////////////////////////////////
+    // TrueType specification: https://learn.microsoft.com/en-us/typography/opentype/spec //
+    ////////////////////////////////////////////////////////////////////////////////////////
+
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) => return Err(CoreError::Io { path: path.to_string(), message: error.to_string() }),
+    };
+
+    let mut ttf = Vec::new();
+    if let Err(error) = file.read_to_end(&mut ttf) {
+        return Err(CoreError::Io { path: path.to_string(), message: error.to_string() });
+    }
+
+    generate(Arc::new(TrueTypeFont::new(ttf)?), pixel_size)
+}
+
+pub fn generate(font: Arc<TrueTypeFont>, pixel_size: f32) -> Result<RawFont, CoreError> {
+    if !pixel_size.is_finite() || pixel_size <= 0.0 {
+        return Err(CoreError::InvalidArgument("pixel size has to be greater than zero".to_string()));
+    }
+
+    let scale = pixel_size / font.units_per_em;
+    let line_height = ((font.ascender - font.descender + font.line_gap) * scale).ceil();
+    let width = ((pixel_size * 16.0).ceil() as u32).next_power_of_two().clamp(MIN_ATLAS_WIDTH, MAX_ATLAS_WIDTH);
+    let height = width / 4;
+
+    let mut raw = RawFont::new(Vec2::new(width as f32, height as f32), line_height, HashMap::new(), HashMap::new(), vec![0; (width * height * 4) as usize]);
+    raw.source = Some(TrueTypeSource { font, pixel_size, cursor: Default::default(), shelf_height: 0.0 });
+
+    // Printable ASCII characters are generated upfront, the rest is added on demand
+    raw.add_characters(&(0x20..0x7f).map(char::from).collect::<String>())?;
+
+    Ok(raw)
+}
+
+pub(super) fn add_characters(raw: &mut RawFont, source: &mut TrueTypeSource, characters: &str) -> Result<bool, CoreError> {
+    let font = source.font.clone();
+    let scale = source.pixel_size / font.units_per_em;
+    let ascender = (font.ascender * scale).round();
+    let mut added = false;
+
+    for char in characters.chars() {
+        if char.is_control() || raw.glyphs.contains_key(&char) {
+            continue;
+        }
+
+        // Characters missing in the font are rendered using the .notdef glyph, which has always index 0
+        let glyph_index = font.get_glyph_index(char);
+        let bitmap = font.rasterize(glyph_index, scale)?;
+        let position = pack(raw, source, bitmap.width, bitmap.height)?;
+        let atlas_width = raw.size.x as usize;
+
+        for y in 0..bitmap.height as usize {
+            for x in 0..bitmap.width as usize {
+                let index = ((position.y as usize + y) * atlas_width + position.x as usize + x) * 4;
+                let coverage = bitmap.data[y * bitmap.width as usize + x];
+
+                raw.data[index + 0] = 0xff;
+                raw.data[index + 1] = 0xff;
+                raw.data[index + 2] = 0xff;
+                raw.data[index + 3] = coverage;
+            }
+        }
+
+        let size = Vec2::new(bitmap.width as f32, bitmap.height as f32);
+        let offset = Vec2::new(bitmap.left as f32, ascender - (bitmap.bottom + bitmap.height as i32) as f32);
+        let advance = (font.get_advance(glyph_index)? * scale).round();
+
+        raw.glyphs.insert(char, FontGlyph::new(position, size, offset, advance));
+        added = true;
+    }
+
+    if added && !font.kerning.is_empty() {
+        let mut chars_by_glyph: HashMap<u16, Vec<char>> = HashMap::new();
+        for char in raw.glyphs.keys() {
+            chars_by_glyph.entry(font.get_glyph_index(*char)).or_default().push(*char);
+        }
+
+        for ((left, right), value) in &font.kerning {
+            if let (Some(left_chars), Some(right_chars)) = (chars_by_glyph.get(left), chars_by_glyph.get(right)) {
+                for left_char in left_chars {
+                    for right_char in right_chars {
+                        raw.kerning.insert((*left_char, *right_char), (*value as f32 * scale).round());
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(added)
+}
+
+fn pack(raw: &mut RawFont, source: &mut TrueTypeSource, width: u32, height: u32) -> Result<Vec2, CoreError> {
+    if width == 0 || height == 0 {
+        return Ok(Default::default());
+    }
+
+    let atlas_width = raw.size.x as u32;
+    if width + GLYPH_PADDING > atlas_width {
+        return Err(CoreError::CapacityExceeded { resource: "glyph pixels in the atlas row", capacity: atlas_width as usize });
+    }
+
+    // Glyphs are placed on shelves from the bottom, so growing the atlas doesn't move the ones already added
+    if source.cursor.x as u32 + width + GLYPH_PADDING > atlas_width {
+        source.cursor = Vec2::new(0.0, source.cursor.y + source.shelf_height);
+        source.shelf_height = 0.0;
+    }
+
+    while source.cursor.y as u32 + height + GLYPH_PADDING > raw.size.y as u32 {
+        let atlas_height = raw.size.y as u32 * 2;
+        if atlas_height > MAX_ATLAS_HEIGHT {
+            return Err(CoreError::CapacityExceeded { resource: "atlas rows", capacity: MAX_ATLAS_HEIGHT as usize });
+        }
+
+        raw.data.resize((atlas_width * atlas_height * 4) as usize, 0);
+        raw.size.y = atlas_height as f32;
+    }
+
+    let position = source.cursor;
+    source.cursor.x += (width + GLYPH_PADDING) as f32;
+    source.shelf_height = f32::max(source.shelf_height, (height + GLYPH_PADDING) as f32);
+
+    Ok(position)
+}
+
+impl TrueTypeFont {
+    pub fn new(data: Vec<u8>) -> Result<Self, CoreError> {
+        let version = read_u32(&data, 0)?;
+        if version == 0x4f54544f {
+            return Err(CoreError::UnsupportedFormat("only TrueType outlines are allowed".to_string()));
+        }
+
+        if version != 0x00010000 && version != 0x74727565 {
+            return Err(CoreError::parse("Invalid signature, not recognized as TrueType file", Some(0)));
+        }
+
+        let tables_count = read_u16(&data, 4)? as usize;
+        let mut tables = HashMap::new();
+
+        for index in 0..tables_count {
+            let record = 12 + index * 16;
+            let offset = read_u32(&data, record + 8)? as usize;
+            let length = read_u32(&data, record + 12)? as usize;
+            let tag = String::from_utf8_lossy(&data[record..record + 4]).to_string();
+
+            if offset + length > data.len() {
+                return Err(CoreError::parse(&format!("Invalid file, table {} exceeds the file size", tag), Some(record)));
+            }
+
+            tables.insert(tag, (offset, length));
+        }
+
+        // Missing tables are reported at the start of the table records, right after the 12 bytes long offset table
+        let table = |tag: &str| match tables.get(tag) {
+            Some(table) => Ok(*table),
+            None => Err(CoreError::parse(&format!("Invalid file, required table {} not found", tag), Some(12))),
+        };
+
+        let (head_offset, _) = table("head")?;
+        let (hhea_offset, _) = table("hhea")?;
+        let (maxp_offset, _) = table("maxp")?;
+        let (cmap_offset, _) = table("cmap")?;
+        let (hmtx_offset, _) = table("hmtx")?;
+        let (loca_offset, _) = table("loca")?;
+        let (glyf_offset, glyf_length) = table("glyf")?;
+
+        let units_per_em = read_u16(&data, head_offset + 18)? as f32;
+        let index_to_loc_format = read_u16(&data, head_offset + 50)? as i16;
+        let ascender = read_u16(&data, hhea_offset + 4)? as i16 as f32;
+        let descender = read_u16(&data, hhea_offset + 6)? as i16 as f32;
+        let line_gap = read_u16(&data, hhea_offset + 8)? as i16 as f32;
+        let horizontal_metrics_count = read_u16(&data, hhea_offset + 34)?;
+        let glyphs_count = read_u16(&data, maxp_offset + 4)?;
+
+        if units_per_em == 0.0 || horizontal_metrics_count == 0 {
+            return Err(CoreError::parse("Invalid file, font metrics are corrupted", Some(head_offset)));
+        }
+
+        // Full Unicode subtable (format 12) is preferred over the one limited to the basic plane (format 4)
+        let mut cmap_subtable = None;
+        for index in 0..read_u16(&data, cmap_offset + 2)? as usize {
+            let record = cmap_offset + 4 + index * 8;
+            let platform = read_u16(&data, record)?;
+            let subtable_offset = cmap_offset + read_u32(&data, record + 4)? as usize;
+            let format = read_u16(&data, subtable_offset)?;
+
+            if platform != 0 && platform != 3 {
+                continue;
+            }
+
+            match (format, cmap_subtable) {
+                (12, _) => cmap_subtable = Some((subtable_offset, format)),
+                (4, None) => cmap_subtable = Some((subtable_offset, format)),
+                _ => {}
+            }
+        }
+
+        let (cmap_offset, cmap_format) = match cmap_subtable {
+            Some(cmap_subtable) => cmap_subtable,
+            None => return Err(CoreError::UnsupportedFormat("no Unicode character map found".to_string())),
+        };
+
+        let mut kerning = HashMap::new();
+        if let Some((kern_offset, _)) = tables.get("kern") {
+            // Only the original version of the table with horizontal pairs (format 0) is supported
+            if read_u16(&data, *kern_offset)? == 0 {
+                let mut subtable_offset = kern_offset + 4;
+
+                for _ in 0..read_u16(&data, kern_offset + 2)? {
+                    let length = read_u16(&data, subtable_offset + 2)? as usize;
+                    let coverage = read_u16(&data, subtable_offset + 4)?;
+
+                    if coverage >> 8 == 0 && coverage & 0x01 != 0 {
+                        for pair in 0..read_u16(&data, subtable_offset + 6)? as usize {
+                            let pair_offset = subtable_offset + 14 + pair * 6;
+                            let left = read_u16(&data, pair_offset)?;
+                            let right = read_u16(&data, pair_offset + 2)?;
+                            let value = read_u16(&data, pair_offset + 4)? as i16;
+
+                            kerning.insert((left, right), value);
+                        }
+                    }
+
+                    if length == 0 {
+                        break;
+                    }
+
+                    subtable_offset += length;
+                }
+            }
+        }
+
+        Ok(Self {
+            data,
+            glyf_offset,
+            glyf_length,
+            loca_offset,
+            hmtx_offset,
+            cmap_offset,
+            cmap_format,
+            units_per_em,
+            index_to_loc_format,
+            glyphs_count,
+            horizontal_metrics_count,
+            ascender,
+            descender,
+            line_gap,
+            kerning,
+        })
+    }
+
+    pub fn get_glyph_index(&self, char: char) -> u16 {
+        let code = char as u32;
+        let index = match self.cmap_format {
+            4 => self.get_glyph_index_format4(code),
+            _ => self.get_glyph_index_format12(code),
+        };
+
+        match index {
+            Ok(index) if index < self.glyphs_count => index,
+            _ => 0,
+        }
+    }
+
+    fn get_glyph_index_format4(&self, code: u32) -> Result<u16, CoreError> {
+        if code > 0xffff {
+            return Ok(0);
+        }
+
+        let segments_count = read_u16(&self.data, self.cmap_offset + 6)? as usize / 2;
+        let end_codes_offset = self.cmap_offset + 14;
+        let start_codes_offset = end_codes_offset + segments_count * 2 + 2;
+        let id_deltas_offset = start_codes_offset + segments_count * 2;
+        let id_range_offsets_offset = id_deltas_offset + segments_count * 2;
+
+        for segment in 0..segments_count {
+            if code > read_u16(&self.data, end_codes_offset + segment * 2)? as u32 {
+                continue;
+            }
+
+            let start_code = read_u16(&self.data, start_codes_offset + segment * 2)? as u32;
+            if code < start_code {
+                return Ok(0);
+            }
+
+            let id_delta = read_u16(&self.data, id_deltas_offset + segment * 2)?;
+            let id_range_offset_address = id_range_offsets_offset + segment * 2;
+            let id_range_offset = read_u16(&self.data, id_range_offset_address)? as usize;
+
+            if id_range_offset == 0 {
+                return Ok((code as u16).wrapping_add(id_delta));
+            }
+
+            let glyph_index = read_u16(&self.data, id_range_offset_address + id_range_offset + (code - start_code) as usize * 2)?;
+            return Ok(if glyph_index == 0 { 0 } else { glyph_index.wrapping_add(id_delta) });
+        }
+
+        Ok(0)
+    }
+
+    fn get_glyph_index_format12(&self, code: u32) -> Result<u16, CoreError> {
+        let groups_count = read_u32(&self.data, self.cmap_offset + 12)? as usize;
+
+        for group in 0..groups_count {
+            let group_offset = self.cmap_offset + 16 + group * 12;
+            let start_code = read_u32(&self.data, group_offset)?;
+            let end_code = read_u32(&self.data, group_offset + 4)?;
+
+            if code >= start_code && code <= end_code {
+                return Ok((read_u32(&self.data, group_offset + 8)? + code - start_code) as u16);
+            }
+        }
+
+        Ok(0)
+    }
+
+    fn get_advance(&self, glyph_index: u16) -> Result<f32, CoreError> {
+        let metric_index = u16::min(glyph_index, self.horizontal_metrics_count - 1) as usize;
+        Ok(read_u16(&self.data, self.hmtx_offset + metric_index * 4)? as f32)
+    }
+
+    fn get_glyph_range(&self, glyph_index: u16) -> Result<(usize, usize), CoreError> {
+        let index = glyph_index as usize;
+        let (start, end) = match self.index_to_loc_format {
+            0 => (read_u16(&self.data, self.loca_offset + index * 2)? as usize * 2, read_u16(&self.data, self.loca_offset + index * 2 + 2)? as usize * 2),
+            _ => (read_u32(&self.data, self.loca_offset + index * 4)? as usize, read_u32(&self.data, self.loca_offset + index * 4 + 4)? as usize),
+        };
+
+        if start > end || end > self.glyf_length {
+            return Err(CoreError::parse(&format!("Invalid glyph {}, outline exceeds the glyf table", glyph_index), Some(self.loca_offset)));
+        }
+
+        Ok((self.glyf_offset + start, self.glyf_offset + end))
+    }
+
+    fn get_glyph_contours(&self, glyph_index: u16, depth: u32) -> Result<Vec<Vec<(Vec2, bool)>>, CoreError> {
+        let (start, end) = self.get_glyph_range(glyph_index)?;
+        if depth > MAX_COMPOUND_DEPTH {
+            return Err(CoreError::parse(&format!("Invalid glyph {}, too many nested components", glyph_index), Some(start)));
+        }
+
+        if start == end {
+            return Ok(Vec::new());
+        }
+
+        let glyph = &self.data[start..end];
+        let contours_count = read_u16(glyph, 0)? as i16;
+        let mut contours = Vec::new();
+
+        if contours_count >= 0 {
+            let mut end_points = Vec::new();
+            for contour in 0..contours_count as usize {
+                end_points.push(read_u16(glyph, 10 + contour * 2)? as usize);
+            }
+
+            let points_count = match end_points.last() {
+                Some(end_point) => end_point + 1,
+                None => return Ok(Vec::new()),
+            };
+
+            let instructions_length = read_u16(glyph, 10 + contours_count as usize * 2)? as usize;
+            let mut index = 12 + contours_count as usize * 2 + instructions_length;
+
+            let mut flags = Vec::new();
+            while flags.len() < points_count {
+                let flag = read_u8(glyph, index)?;
+                flags.push(flag);
+                index += 1;
+
+                if flag & 0x08 != 0 {
+                    for _ in 0..read_u8(glyph, index)? {
+                        flags.push(flag);
+                    }
+                    index += 1;
+                }
+            }
+            flags.truncate(points_count);
+
+            // Coordinates are stored as deltas, either as unsigned bytes with the sign in the flag or as signed words
+            let mut coordinates = vec![(0, 0); points_count];
+            for (short_flag, same_flag, is_y) in [(0x02, 0x10, false), (0x04, 0x20, true)] {
+                let mut value = 0i32;
+
+                for (point, flag) in flags.iter().enumerate() {
+                    if flag & short_flag != 0 {
+                        let delta = read_u8(glyph, index)? as i32;
+                        value += if flag & same_flag != 0 { delta } else { -delta };
+                        index += 1;
+                    } else if flag & same_flag == 0 {
+                        value += read_u16(glyph, index)? as i16 as i32;
+                        index += 2;
+                    }
+
+                    if is_y {
+                        coordinates[point].1 = value;
+                    } else {
+                        coordinates[point].0 = value;
+                    }
+                }
+            }
+
+            let mut contour_start = 0;
+            for end_point in end_points {
+                if end_point < contour_start || end_point >= points_count {
+                    return Err(CoreError::parse(&format!("Invalid glyph {}, contours are corrupted", glyph_index), Some(start)));
+                }
+
+                let contour = (contour_start..=end_point).map(|p| (Vec2::new(coordinates[p].0 as f32, coordinates[p].1 as f32), flags[p] & 0x01 != 0));
+                contours.push(contour.collect());
+                contour_start = end_point + 1;
+            }
+        } else {
+            let mut index = 10;
+            loop {
+                let flags = read_u16(glyph, index)?;
+                let component_index = read_u16(glyph, index + 2)?;
+                index += 4;
+
+                let (dx, dy) = if flags & 0x0001 != 0 {
+                    index += 4;
+                    (read_u16(glyph, index - 4)? as i16 as f32, read_u16(glyph, index - 2)? as i16 as f32)
+                } else {
+                    index += 2;
+                    (read_u8(glyph, index - 2)? as i8 as f32, read_u8(glyph, index - 1)? as i8 as f32)
+                };
+
+                // Point matching is not supported, components placed this way are left without offset
+                let (dx, dy) = if flags & 0x0002 != 0 { (dx, dy) } else { (0.0, 0.0) };
+                let f2dot14 = |offset: usize| -> Result<f32, CoreError> { Ok(read_u16(glyph, offset)? as i16 as f32 / 16384.0) };
+
+                let (a, b, c, d) = if flags & 0x0008 != 0 {
+                    index += 2;
+                    let scale = f2dot14(index - 2)?;
+                    (scale, 0.0, 0.0, scale)
+                } else if flags & 0x0040 != 0 {
+                    index += 4;
+                    (f2dot14(index - 4)?, 0.0, 0.0, f2dot14(index - 2)?)
+                } else if flags & 0x0080 != 0 {
+                    index += 8;
+                    (f2dot14(index - 8)?, f2dot14(index - 6)?, f2dot14(index - 4)?, f2dot14(index - 2)?)
+                } else {
+                    (1.0, 0.0, 0.0, 1.0)
+                };
+
+                for contour in self.get_glyph_contours(component_index, depth + 1)? {
+                    let transformed = contour.iter().map(|(p, on_curve)| (Vec2::new(a * p.x + c * p.y + dx, b * p.x + d * p.y + dy), *on_curve));
+                    contours.push(transformed.collect());
+                }
+
+                if flags & 0x0020 == 0 {
+                    break;
+                }
+            }
+        }
+
+        Ok(contours)
+    }
+
+    fn rasterize(&self, glyph_index: u16, scale: f32) -> Result<GlyphBitmap, CoreError> {
+        let contours = self.get_glyph_contours(glyph_index, 0)?;
+        let points = contours.iter().flatten().map(|(p, _)| *p * scale).collect::<Vec<Vec2>>();
+
+        if points.is_empty() {
+            return Ok(GlyphBitmap { width: 0, height: 0, left: 0, bottom: 0, data: Vec::new() });
+        }
+
+        // Control points always enclose the curve, so they are enough to get the bounding box
+        let left = points.iter().fold(f32::MAX, |min, p| f32::min(min, p.x)).floor() as i32 - 1;
+        let bottom = points.iter().fold(f32::MAX, |min, p| f32::min(min, p.y)).floor() as i32 - 1;
+        let right = points.iter().fold(f32::MIN, |max, p| f32::max(max, p.x)).ceil() as i32 + 1;
+        let top = points.iter().fold(f32::MIN, |max, p| f32::max(max, p.y)).ceil() as i32 + 1;
+
+        let width = (right - left) as u32;
+        let height = (top - bottom) as u32;
+        let origin = Vec2::new(left as f32, bottom as f32);
+        let mut accumulator = vec![0.0; (width * height + 1) as usize];
+
+        for contour in contours {
+            let contour = contour.iter().map(|(p, on_curve)| (*p * scale - origin, *on_curve)).collect::<Vec<(Vec2, bool)>>();
+            let polyline = flatten(&contour);
+
+            for index in 0..polyline.len() {
+                draw_line(&mut accumulator, width as usize, height as usize, polyline[index], polyline[(index + 1) % polyline.len()]);
+            }
+        }
+
+        // Accumulated signed area gives coverage for the non-zero winding rule
+        let mut sum = 0.0;
+        let mut data = Vec::with_capacity((width * height) as usize);
+
+        for value in accumulator.iter().take((width * height) as usize) {
+            sum += value;
+            data.push((f32::min(f32::abs(sum), 1.0) * 255.0).round() as u8);
+        }
+
+        Ok(GlyphBitmap { width, height, left, bottom, data })
+    }
+}
+
+fn flatten(contour: &[(Vec2, bool)]) -> Vec<Vec2> {
+    let mut polyline = Vec::new();
+    if contour.len() < 2 {
+        return polyline;
+    }
+
+    // Two consecutive off-curve points imply the on-curve point in the middle of them
+    let start_index = contour.iter().position(|(_, on_curve)| *on_curve);
+    let start = match start_index {
+        Some(index) => contour[index].0,
+        None => (contour[0].0 + contour[1].0) / 2.0,
+    };
+
+    let first_index = start_index.map(|p| p + 1).unwrap_or(1);
+    let mut control: Option<Vec2> = None;
+    let mut last = start;
+    polyline.push(start);
+
+    for index in first_index..first_index + contour.len() {
+        let (point, on_curve) = contour[index % contour.len()];
+
+        match (on_curve, control) {
+            (true, Some(control_point)) => {
+                add_curve(&mut polyline, last, control_point, point);
+                last = point;
+                control = None;
+            }
+            (true, None) => {
+                polyline.push(point);
+                last = point;
+            }
+            (false, Some(control_point)) => {
+                let middle = (control_point + point) / 2.0;
+                add_curve(&mut polyline, last, control_point, middle);
+                last = middle;
+                control = Some(point);
+            }
+            (false, None) => {
+                control = Some(point);
+            }
+        }
+    }
+
+    if let Some(control_point) = control {
+        add_curve(&mut polyline, last, control_point, start);
+    }
+
+    // Last point is the same as the first one, the contour is closed by the caller
+    polyline.pop();
+    polyline
+}
+
+fn add_curve(polyline: &mut Vec<Vec2>, from: Vec2, control: Vec2, to: Vec2) {
+    let deviation = from - control * 2.0 + to;
+    let segments = (1.0 + (deviation.x * deviation.x + deviation.y * deviation.y).sqrt().sqrt() * 2.0).min(16.0) as u32;
+
+    for segment in 1..=segments {
+        let t = segment as f32 / segments as f32;
+        polyline.push(from * ((1.0 - t) * (1.0 - t)) + control * (2.0 * (1.0 - t) * t) + to * (t * t));
+    }
+}
+
+fn draw_line(accumulator: &mut [f32], width: usize, height: usize, from: Vec2, to: Vec2) {
+    if from.y == to.y {
+        return;
+    }
+
+    let (direction, from, to) = if from.y < to.y { (1.0, from, to) } else { (-1.0, to, from) };
+    let dxdy = (to.x - from.x) / (to.y - from.y);
+    let mut x = from.x;
+
+    for y in from.y.max(0.0) as usize..usize::min(height, to.y.ceil() as usize) {
+        let line_start = y * width;
+        let dy = f32::min((y + 1) as f32, to.y) - f32::max(y as f32, from.y);
+        let x_next = x + dxdy * dy;
+        let d = dy * direction;
+        let (x0, x1) = if x < x_next { (x, x_next) } else { (x_next, x) };
+        let x0_floor = x0.floor();
+        let x0_index = x0_floor as usize;
+        let x1_ceil = x1.ceil();
+        let x1_index = x1_ceil as usize;
+
+        if x1_index <= x0_index + 1 {
+            let middle = 0.5 * (x + x_next) - x0_floor;
+            accumulator[line_start + x0_index] += d - d * middle;
+            accumulator[line_start + x0_index + 1] += d * middle;
+        } else {
+            let s = 1.0 / (x1 - x0);
+            let x0_fraction = x0 - x0_floor;
+            let a0 = 0.5 * s * (1.0 - x0_fraction) * (1.0 - x0_fraction);
+            let x1_fraction = x1 - x1_ceil + 1.0;
+            let am = 0.5 * s * x1_fraction * x1_fraction;
+
+            accumulator[line_start + x0_index] += d * a0;
+
+            if x1_index == x0_index + 2 {
+                accumulator[line_start + x0_index + 1] += d * (1.0 - a0 - am);
+            } else {
+                let a1 = s * (1.5 - x0_fraction);
+                accumulator[line_start + x0_index + 1] += d * (a1 - a0);
+
+                for x_index in x0_index + 2..x1_index - 1 {
+                    accumulator[line_start + x_index] += d * s;
+                }
+
+                let a2 = a1 + (x1_index - x0_index - 3) as f32 * s;
+                accumulator[line_start + x1_index - 1] += d * (1.0 - a2 - am);
+            }
+
+            accumulator[line_start + x1_index] += d * am;
+        }
+
+        x = x_next;
+    }
+}
+
+fn read_u8(data: &[u8], index: usize) -> Result<u8, CoreError> {
+    match index < data.len() {
+        true => Ok(binary::read_u8(data, index)),
+        false => Err(CoreError::parse("Invalid file, unexpected end of data", Some(index))),
+    }
+}
+
+fn read_u16(data: &[u8], index: usize) -> Result<u16, CoreError> {
+    match index + 2 <= data.len() {
+        true => Ok(binary::read_be_u16(data, index)),
+        false => Err(CoreError::parse("Invalid file, unexpected end of data", Some(index))),
+    }
+}
+
+fn read_u32(data: &[u8], index: usize) -> Result<u32, CoreError> {
+    match index + 4 <= data.len() {
+        true => Ok(binary::read_be_u32(data, index)),
+        false => Err(CoreError::parse("Invalid file, unexpected end of data", Some(index))),
+    }
+}
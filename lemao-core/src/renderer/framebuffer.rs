@@ -0,0 +1,78 @@
+use super::context::RendererContext;
+use super::state::StateCache;
+use crate::error::CoreError;
+use lemao_math::vec2::Vec2;
+use lemao_opengl::bindings::opengl;
+use lemao_opengl::pointers::OpenGLPointers;
+use std::ptr;
+use std::rc::Rc;
+
+pub struct Framebuffer {
+    pub(crate) framebuffer_gl_id: u32,
+    pub(crate) texture_gl_id: u32,
+    pub(crate) renderbuffer_gl_id: u32,
+    gl: Rc<OpenGLPointers>,
+    state: Rc<StateCache>,
+
+    pub size: Vec2,
+}
+
+impl Framebuffer {
+    pub fn new(renderer: &RendererContext, size: Vec2) -> Result<Self, CoreError> {
+        unsafe {
+            let gl = renderer.gl.clone();
+            let state = renderer.state.clone();
+
+            let mut texture_gl_id = 0;
+            (gl.glGenTextures)(1, &mut texture_gl_id);
+            state.bind_texture(0, texture_gl_id);
+
+            // sRGB storage keeps more precision in dark tones when the pipeline works in linear space
+            let format = opengl::GL_RGBA;
+            let internal_format = if renderer.is_srgb() { opengl::GL_SRGB8_ALPHA8 } else { opengl::GL_RGBA8 };
+            (gl.glTexImage2D)(opengl::GL_TEXTURE_2D, 0, internal_format as i32, size.x as i32, size.y as i32, 0, format, opengl::GL_UNSIGNED_BYTE, ptr::null());
+            (gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_WRAP_S, opengl::GL_CLAMP_TO_EDGE as i32);
+            (gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_WRAP_T, opengl::GL_CLAMP_TO_EDGE as i32);
+            (gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_MIN_FILTER, opengl::GL_LINEAR as i32);
+            (gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_MAG_FILTER, opengl::GL_LINEAR as i32);
+
+            let mut framebuffer_gl_id = 0;
+            (gl.glGenFramebuffers)(1, &mut framebuffer_gl_id);
+            state.bind_framebuffer(framebuffer_gl_id);
+            (gl.glFramebufferTexture2D)(opengl::GL_FRAMEBUFFER, opengl::GL_COLOR_ATTACHMENT0, opengl::GL_TEXTURE_2D, texture_gl_id, 0);
+
+            // Stencil buffer is required by clip masks, depth is attached too since it's the only widely supported packed format
+            let mut renderbuffer_gl_id = 0;
+            (gl.glGenRenderbuffers)(1, &mut renderbuffer_gl_id);
+            (gl.glBindRenderbuffer)(opengl::GL_RENDERBUFFER, renderbuffer_gl_id);
+            (gl.glRenderbufferStorage)(opengl::GL_RENDERBUFFER, opengl::GL_DEPTH24_STENCIL8, size.x as i32, size.y as i32);
+            (gl.glFramebufferRenderbuffer)(opengl::GL_FRAMEBUFFER, opengl::GL_DEPTH_STENCIL_ATTACHMENT, opengl::GL_RENDERBUFFER, renderbuffer_gl_id);
+
+            let status = (gl.glCheckFramebufferStatus)(opengl::GL_FRAMEBUFFER);
+            state.bind_framebuffer(0);
+
+            let framebuffer = Self { framebuffer_gl_id, texture_gl_id, renderbuffer_gl_id, gl, state, size };
+            if status != opengl::GL_FRAMEBUFFER_COMPLETE {
+                return Err(CoreError::OpenGL(format!("framebuffer is not complete, status {}", status)));
+            }
+
+            Ok(framebuffer)
+        }
+    }
+}
+
+impl Drop for Framebuffer {
+    fn drop(&mut self) {
+        if self.framebuffer_gl_id != 0 {
+            self.state.delete_framebuffer(self.framebuffer_gl_id);
+        }
+
+        if self.texture_gl_id != 0 {
+            self.state.delete_texture(self.texture_gl_id);
+        }
+
+        if self.renderbuffer_gl_id != 0 {
+            unsafe { (self.gl.glDeleteRenderbuffers)(1, &self.renderbuffer_gl_id) };
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use super::context::RendererContext;
+use super::drawable::Color;
+use super::shaders::Shader;
+use super::shapes::Shape;
+use super::state::StateCache;
+use super::statistics::RendererStatistics;
+use crate::error::CoreError;
+use crate::utils::storage::Handle;
+use crate::utils::storage::Storage;
+use lemao_math::color::SolidColor;
+use lemao_math::mat4x4::Mat4x4;
+use lemao_math::vec4::Vec4;
+use lemao_opengl::bindings::opengl;
+use lemao_opengl::pointers::OpenGLPointers;
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::mem;
+use std::ptr;
+use std::rc::Rc;
+
+// Transformation matrix (16), color (4) and UV rectangle (4)
+const INSTANCE_SIZE: usize = 24;
+
+pub struct InstanceRenderer {
+    pub(crate) vao_gl_id: u32,
+    pub(crate) instances_vbo_gl_id: u32,
+    gl: Rc<OpenGLPointers>,
+    state: Rc<StateCache>,
+    statistics: Rc<RefCell<RendererStatistics>>,
+
+    max_instances_count: usize,
+    instances: Vec<f32>,
+    batches: Vec<InstanceBatch>,
+}
+
+struct InstanceBatch {
+    shape_id: Handle<Shape>,
+    texture_gl_id: u32,
+    first_instance: usize,
+    instances_count: usize,
+}
+
+impl InstanceRenderer {
+    pub fn new(renderer: &RendererContext, max_instances_count: usize) -> Self {
+        unsafe {
+            let gl = renderer.gl.clone();
+            let state = renderer.state.clone();
+
+            let mut vao_gl_id = 0;
+            (gl.glGenVertexArrays)(1, &mut vao_gl_id);
+            state.bind_vertex_array(vao_gl_id);
+
+            let data_size = (mem::size_of::<f32>() * INSTANCE_SIZE * max_instances_count) as i64;
+
+            let mut instances_vbo_gl_id = 0;
+            (gl.glGenBuffers)(1, &mut instances_vbo_gl_id);
+            state.bind_buffer(opengl::GL_ARRAY_BUFFER, instances_vbo_gl_id);
+            (gl.glBufferData)(opengl::GL_ARRAY_BUFFER, data_size, ptr::null(), opengl::GL_DYNAMIC_DRAW);
+
+            // Matrix takes four attribute locations, one per column
+            let attrib_size = (INSTANCE_SIZE * mem::size_of::<f32>()) as i32;
+            for index in 0..6 {
+                let offset = (index * 4 * mem::size_of::<f32>()) as *const c_void;
+
+                (gl.glVertexAttribPointer)(3 + index as u32, 4, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, offset);
+                (gl.glVertexAttribDivisor)(3 + index as u32, 1);
+                (gl.glEnableVertexAttribArray)(3 + index as u32);
+            }
+
+            Self {
+                vao_gl_id,
+                instances_vbo_gl_id,
+                gl,
+                state,
+                statistics: renderer.frame_statistics.clone(),
+                max_instances_count,
+                instances: Vec::new(),
+                batches: Vec::new(),
+            }
+        }
+    }
+
+    pub fn add(&mut self, shape_id: Handle<Shape>, texture_gl_id: u32, transformation_matrix: Mat4x4, color: SolidColor, uv_rect: Vec4) -> Result<(), CoreError> {
+        let instances_count = self.get_instances_count();
+        if instances_count + 1 > self.max_instances_count {
+            return Err(CoreError::CapacityExceeded { resource: "instances", capacity: self.max_instances_count });
+        }
+
+        // Instances with a different shape or texture can't be drawn by the same call, so the current batch is closed and a new one started
+        match self.batches.last_mut() {
+            Some(batch) if batch.shape_id == shape_id && batch.texture_gl_id == texture_gl_id => batch.instances_count += 1,
+            _ => self.batches.push(InstanceBatch { shape_id, texture_gl_id, first_instance: instances_count, instances_count: 1 }),
+        }
+
+        for index in 0..16 {
+            self.instances.push(transformation_matrix[index]);
+        }
+
+        self.instances.extend_from_slice(&[color.r, color.g, color.b, color.a]);
+        self.instances.extend_from_slice(&[uv_rect.x, uv_rect.y, uv_rect.z, uv_rect.w]);
+
+        Ok(())
+    }
+
+    pub fn get_batches_count(&self) -> usize {
+        self.batches.len()
+    }
+
+    pub fn get_instances_count(&self) -> usize {
+        self.instances.len() / INSTANCE_SIZE
+    }
+
+    pub fn draw(&mut self, shapes: &Storage<Shape>, shader: &Shader) -> Result<(), CoreError> {
+        if self.instances.is_empty() {
+            return Ok(());
+        }
+
+        unsafe {
+            self.state.bind_vertex_array(self.vao_gl_id);
+
+            let data_size = (mem::size_of::<f32>() * self.instances.len()) as i64;
+            let data_ptr = self.instances.as_ptr() as *const c_void;
+
+            self.state.bind_buffer(opengl::GL_ARRAY_BUFFER, self.instances_vbo_gl_id);
+            (self.gl.glBufferSubData)(opengl::GL_ARRAY_BUFFER, 0, data_size, data_ptr);
+            self.statistics.borrow_mut().add_buffer_upload(data_size);
+
+            shader.set_color(&Color::SolidColor(SolidColor::new(1.0, 1.0, 1.0, 1.0)))?;
+
+            for batch in &self.batches {
+                let shape = shapes.get(batch.shape_id)?;
+
+                // Shape buffers are attached every time, so any shape can be used without keeping separate vertex arrays
+                let attrib_size = (9 * mem::size_of::<f32>()) as i32;
+                self.state.bind_buffer(opengl::GL_ARRAY_BUFFER, shape.vbo_gl_id);
+                (self.gl.glVertexAttribPointer)(0, 3, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, ptr::null_mut());
+                (self.gl.glVertexAttribPointer)(1, 4, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, (3 * mem::size_of::<f32>()) as *const c_void);
+                (self.gl.glVertexAttribPointer)(2, 2, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, (7 * mem::size_of::<f32>()) as *const c_void);
+
+                (self.gl.glEnableVertexAttribArray)(0);
+                (self.gl.glEnableVertexAttribArray)(1);
+                (self.gl.glEnableVertexAttribArray)(2);
+
+                self.state.bind_buffer(opengl::GL_ELEMENT_ARRAY_BUFFER, shape.ebo_gl_id);
+
+                // Instance attributes start at the first instance of the batch, base instance isn't available in OpenGL 3.3
+                let attrib_size = (INSTANCE_SIZE * mem::size_of::<f32>()) as i32;
+                self.state.bind_buffer(opengl::GL_ARRAY_BUFFER, self.instances_vbo_gl_id);
+                for index in 0..6 {
+                    let offset = ((batch.first_instance * INSTANCE_SIZE + index * 4) * mem::size_of::<f32>()) as *const c_void;
+                    (self.gl.glVertexAttribPointer)(3 + index as u32, 4, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, offset);
+                }
+
+                self.state.bind_texture(0, batch.texture_gl_id);
+                (self.gl.glDrawElementsInstanced)(opengl::GL_TRIANGLES, shape.indices.len() as i32, opengl::GL_UNSIGNED_INT, ptr::null(), batch.instances_count as i32);
+                self.statistics.borrow_mut().add_draw_call(shape.vertices.len() / 9 * batch.instances_count, shape.indices.len() * batch.instances_count);
+            }
+
+            self.clear();
+
+            Ok(())
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.instances.clear();
+        self.batches.clear();
+    }
+}
+
+impl Drop for InstanceRenderer {
+    fn drop(&mut self) {
+        if self.instances_vbo_gl_id != 0 {
+            self.state.delete_buffer(self.instances_vbo_gl_id);
+        }
+
+        if self.vao_gl_id != 0 {
+            self.state.delete_vertex_array(self.vao_gl_id);
+        }
+    }
+}
@@ -0,0 +1,329 @@
+use super::context::RendererContext;
+use super::framebuffer::Framebuffer;
+use super::shaders::Shader;
+use super::shaders::BLUR_FRAGMENT_SHADER;
+use super::shaders::COPY_FRAGMENT_SHADER;
+use super::shaders::DEFAULT_VERTEX_SHADER;
+use super::shaders::FULLSCREEN_VERTEX_SHADER;
+use super::shaders::LIGHT_FRAGMENT_SHADER;
+use super::shaders::SHADOW_FRAGMENT_SHADER;
+use super::state::StateCache;
+use super::statistics::RendererStatistics;
+use crate::error::CoreError;
+use crate::utils::storage::Handle;
+use lemao_math::color::SolidColor;
+use lemao_math::mat4x4::Mat4x4;
+use lemao_math::vec2::Vec2;
+use lemao_math::vec3::Vec3;
+use lemao_math::vec4::Vec4;
+use lemao_opengl::bindings::opengl;
+use lemao_opengl::pointers::OpenGLPointers;
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::mem;
+use std::ptr;
+use std::rc::Rc;
+
+// Shadow volumes are extruded far enough to leave the light circle even for edges seen at a wide angle
+const SHADOW_EXTRUSION_FACTOR: f32 = 100.0;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LightType {
+    Point,
+    Spot { direction: f32, angle: f32 },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ShadowType {
+    None,
+    Hard,
+    Soft { radius: f32 },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Light {
+    pub r#type: LightType,
+    pub position: Vec2,
+    pub color: SolidColor,
+    pub radius: f32,
+    pub falloff: f32,
+    pub intensity: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Occluder {
+    pub points: Vec<Vec2>,
+}
+
+pub struct LightingSystem {
+    pub ambient: SolidColor,
+    pub shadows: ShadowType,
+    pub lights: Vec<Light>,
+    pub occluders: Vec<Occluder>,
+
+    light_map: Option<Framebuffer>,
+    light_buffer: Option<Framebuffer>,
+    blur_buffer: Option<Framebuffer>,
+    vao_gl_id: u32,
+    vbo_gl_id: u32,
+    gl: Rc<OpenGLPointers>,
+    state: Rc<StateCache>,
+    statistics: Rc<RefCell<RendererStatistics>>,
+
+    light_shader_id: Handle<Shader>,
+    shadow_shader_id: Handle<Shader>,
+    blur_shader_id: Handle<Shader>,
+    composite_shader_id: Handle<Shader>,
+}
+
+impl Light {
+    pub fn new_point(position: Vec2, color: SolidColor, radius: f32) -> Self {
+        Self { r#type: LightType::Point, position, color, radius, falloff: 1.0, intensity: 1.0 }
+    }
+
+    pub fn new_spot(position: Vec2, color: SolidColor, radius: f32, direction: f32, angle: f32) -> Self {
+        Self { r#type: LightType::Spot { direction, angle }, position, color, radius, falloff: 1.0, intensity: 1.0 }
+    }
+}
+
+impl Occluder {
+    pub fn new_rectangle(position: Vec2, size: Vec2) -> Self {
+        Self { points: vec![position, position + Vec2::new(size.x, 0.0), position + size, position + Vec2::new(0.0, size.y)] }
+    }
+
+    pub fn new_polygon(points: Vec<Vec2>) -> Result<Self, CoreError> {
+        if points.len() < 3 {
+            return Err(CoreError::InvalidArgument("polygon must have at least 3 points".to_string()));
+        }
+
+        Ok(Self { points })
+    }
+}
+
+impl LightingSystem {
+    pub fn new(renderer: &mut RendererContext) -> Result<Self, CoreError> {
+        let mut create_shader = |vertex_shader: &str, fragment_shader: &str| -> Result<Handle<Shader>, CoreError> {
+            let shader = Shader::new(renderer, vertex_shader, fragment_shader)?;
+            Ok(renderer.shaders.store(shader))
+        };
+
+        let light_shader_id = create_shader(DEFAULT_VERTEX_SHADER, LIGHT_FRAGMENT_SHADER)?;
+        let shadow_shader_id = create_shader(DEFAULT_VERTEX_SHADER, SHADOW_FRAGMENT_SHADER)?;
+        let blur_shader_id = create_shader(FULLSCREEN_VERTEX_SHADER, BLUR_FRAGMENT_SHADER)?;
+        let composite_shader_id = create_shader(FULLSCREEN_VERTEX_SHADER, COPY_FRAGMENT_SHADER)?;
+
+        unsafe {
+            let gl = renderer.gl.clone();
+            let state = renderer.state.clone();
+
+            let mut vao_gl_id = 0;
+            (gl.glGenVertexArrays)(1, &mut vao_gl_id);
+            state.bind_vertex_array(vao_gl_id);
+
+            let mut vbo_gl_id = 0;
+            (gl.glGenBuffers)(1, &mut vbo_gl_id);
+            state.bind_buffer(opengl::GL_ARRAY_BUFFER, vbo_gl_id);
+
+            let attrib_size = (9 * mem::size_of::<f32>()) as i32;
+            (gl.glVertexAttribPointer)(0, 3, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, ptr::null_mut());
+            (gl.glVertexAttribPointer)(1, 4, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, (3 * mem::size_of::<f32>()) as *const c_void);
+            (gl.glVertexAttribPointer)(2, 2, opengl::GL_FLOAT, opengl::GL_FALSE as u8, attrib_size, (7 * mem::size_of::<f32>()) as *const c_void);
+
+            (gl.glEnableVertexAttribArray)(0);
+            (gl.glEnableVertexAttribArray)(1);
+            (gl.glEnableVertexAttribArray)(2);
+
+            Ok(Self {
+                ambient: SolidColor::new(0.0, 0.0, 0.0, 1.0),
+                shadows: ShadowType::Hard,
+                lights: Vec::new(),
+                occluders: Vec::new(),
+
+                light_map: None,
+                light_buffer: None,
+                blur_buffer: None,
+                vao_gl_id,
+                vbo_gl_id,
+                gl,
+                state,
+                statistics: renderer.frame_statistics.clone(),
+
+                light_shader_id,
+                shadow_shader_id,
+                blur_shader_id,
+                composite_shader_id,
+            })
+        }
+    }
+
+    pub fn apply(&mut self, renderer: &mut RendererContext) -> Result<(), CoreError> {
+        let target_framebuffer_gl_id = self.state.get_framebuffer();
+
+        if self.light_map.as_ref().map(|p| p.size != renderer.viewport_size).unwrap_or(true) {
+            self.light_map = Some(Framebuffer::new(renderer, renderer.viewport_size)?);
+            self.light_buffer = Some(Framebuffer::new(renderer, renderer.viewport_size)?);
+            self.blur_buffer = Some(Framebuffer::new(renderer, renderer.viewport_size)?);
+        }
+
+        let light_map = self.light_map.as_ref().unwrap();
+        let light_buffer = self.light_buffer.as_ref().unwrap();
+        let blur_buffer = self.blur_buffer.as_ref().unwrap();
+
+        self.state.set_scissor(false);
+        self.state.set_stencil(false);
+        self.state.bind_framebuffer(light_map.framebuffer_gl_id);
+        renderer.clear(self.ambient);
+
+        let camera = renderer.cameras.get(renderer.active_camera_id)?;
+        let projection_matrix = camera.get_projection_matrix();
+        let view_matrix = camera.get_view_matrix();
+
+        for light in &self.lights {
+            self.state.bind_framebuffer(light_buffer.framebuffer_gl_id);
+            renderer.clear(SolidColor::new(0.0, 0.0, 0.0, 1.0));
+
+            renderer.set_shader_as_active(self.light_shader_id)?;
+            self.state.set_blend(false);
+
+            let (spot_direction, spot_cutoff) = match light.r#type {
+                LightType::Point => (Vec2::new(1.0, 0.0), -1.0),
+                LightType::Spot { direction, angle } => (Vec2::new_from_angle(direction), (angle / 2.0).cos()),
+            };
+
+            let model_matrix = Mat4x4::translate(Vec3::from(light.position - Vec2::new(light.radius, light.radius)))
+                * Mat4x4::scale(Vec3::new(light.radius * 2.0, light.radius * 2.0, 1.0));
+
+            let shader = renderer.shaders.get(self.light_shader_id)?;
+            shader.set_parameter("proj", projection_matrix.as_ptr())?;
+            shader.set_parameter("view", view_matrix.as_ptr())?;
+            shader.set_parameter("model", model_matrix.as_ptr())?;
+            shader.set_uv_rect(Vec4::new(0.0, 0.0, 1.0, 1.0))?;
+            let light_color = if renderer.is_srgb() { light.color.to_linear() } else { light.color };
+            shader.set_parameter("color", light_color.as_ptr())?;
+            shader.set_parameter("intensity", &light.intensity)?;
+            shader.set_parameter("falloff", &light.falloff)?;
+            shader.set_parameter("spotDirection", spot_direction.as_ptr())?;
+            shader.set_parameter("spotCutoff", &spot_cutoff)?;
+
+            let shape = renderer.shapes.get(renderer.default_sprite_shape_id)?;
+            self.state.bind_vertex_array(shape.vao_gl_id);
+            unsafe { (self.gl.glDrawElements)(opengl::GL_TRIANGLES, shape.indices.len() as i32, opengl::GL_UNSIGNED_INT, ptr::null()) };
+            self.statistics.borrow_mut().add_draw_call(shape.vertices.len() / 9, shape.indices.len());
+
+            if self.shadows != ShadowType::None {
+                let vertices = self.get_shadow_vertices(light);
+
+                if !vertices.is_empty() {
+                    renderer.set_shader_as_active(self.shadow_shader_id)?;
+                    self.state.set_blend(false);
+
+                    let shader = renderer.shaders.get(self.shadow_shader_id)?;
+                    shader.set_parameter("proj", projection_matrix.as_ptr())?;
+                    shader.set_parameter("view", view_matrix.as_ptr())?;
+                    shader.set_parameter("model", Mat4x4::identity().as_ptr())?;
+
+                    self.draw_shadows(&vertices);
+                }
+            }
+
+            if let ShadowType::Soft { radius } = self.shadows {
+                let horizontal = Vec2::new(1.0, 0.0);
+                let vertical = Vec2::new(0.0, 1.0);
+
+                self.draw_blur_pass(renderer, light_buffer, blur_buffer, horizontal, radius)?;
+                self.draw_blur_pass(renderer, blur_buffer, light_buffer, vertical, radius)?;
+            }
+
+            // Every light is added to the light map separately, so shadows of one light don't cover the others
+            renderer.set_shader_as_active(self.composite_shader_id)?;
+            self.state.set_blend_function(opengl::GL_ONE, opengl::GL_ONE);
+            self.draw_fullscreen_pass(renderer, light_buffer, light_map.framebuffer_gl_id)?;
+        }
+
+        // Scene colors are multiplied by the light map
+        renderer.set_shader_as_active(self.composite_shader_id)?;
+        self.state.set_blend_function(opengl::GL_DST_COLOR, opengl::GL_ZERO);
+        self.draw_fullscreen_pass(renderer, light_map, target_framebuffer_gl_id)?;
+
+        Ok(())
+    }
+
+    fn get_shadow_vertices(&self, light: &Light) -> Vec<f32> {
+        let mut vertices = Vec::new();
+
+        for occluder in &self.occluders {
+            // Occluders outside of the light circle can't cast any shadow inside it
+            let min = occluder.points.iter().fold(Vec2::new(f32::MAX, f32::MAX), |acc, p| Vec2::new(acc.x.min(p.x), acc.y.min(p.y)));
+            let max = occluder.points.iter().fold(Vec2::new(f32::MIN, f32::MIN), |acc, p| Vec2::new(acc.x.max(p.x), acc.y.max(p.y)));
+            let closest = light.position.clamp(min, max);
+
+            if closest.distance(light.position) > light.radius {
+                continue;
+            }
+
+            for index in 0..occluder.points.len() {
+                let a = occluder.points[index];
+                let b = occluder.points[(index + 1) % occluder.points.len()];
+                let a_far = a + (a - light.position).normalized() * light.radius * SHADOW_EXTRUSION_FACTOR;
+                let b_far = b + (b - light.position).normalized() * light.radius * SHADOW_EXTRUSION_FACTOR;
+
+                for point in [a, b, b_far, a, b_far, a_far] {
+                    vertices.extend_from_slice(&[point.x, point.y, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+                }
+            }
+        }
+
+        vertices
+    }
+
+    fn draw_shadows(&self, vertices: &[f32]) {
+        unsafe {
+            let data_size = mem::size_of_val(vertices) as i64;
+            let data_ptr = vertices.as_ptr() as *const c_void;
+
+            self.state.bind_vertex_array(self.vao_gl_id);
+            self.state.bind_buffer(opengl::GL_ARRAY_BUFFER, self.vbo_gl_id);
+            (self.gl.glBufferData)(opengl::GL_ARRAY_BUFFER, data_size, data_ptr, opengl::GL_DYNAMIC_DRAW);
+            self.statistics.borrow_mut().add_buffer_upload(data_size);
+
+            (self.gl.glDrawArrays)(opengl::GL_TRIANGLES, 0, (vertices.len() / 9) as i32);
+            self.statistics.borrow_mut().add_draw_call(vertices.len() / 9, 0);
+        }
+    }
+
+    fn draw_blur_pass(&self, renderer: &mut RendererContext, input: &Framebuffer, output: &Framebuffer, direction: Vec2, radius: f32) -> Result<(), CoreError> {
+        renderer.set_shader_as_active(self.blur_shader_id)?;
+        self.state.set_blend(false);
+
+        let shader = renderer.shaders.get(self.blur_shader_id)?;
+        shader.set_parameter("direction", direction.as_ptr())?;
+        shader.set_parameter("radius", &radius)?;
+
+        self.draw_fullscreen_pass(renderer, input, output.framebuffer_gl_id)
+    }
+
+    fn draw_fullscreen_pass(&self, renderer: &RendererContext, input: &Framebuffer, output_gl_id: u32) -> Result<(), CoreError> {
+        self.state.bind_framebuffer(output_gl_id);
+        self.state.bind_texture(0, input.texture_gl_id);
+
+        let shape = renderer.shapes.get(renderer.default_sprite_shape_id)?;
+        self.state.bind_vertex_array(shape.vao_gl_id);
+
+        unsafe { (self.gl.glDrawElements)(opengl::GL_TRIANGLES, shape.indices.len() as i32, opengl::GL_UNSIGNED_INT, ptr::null()) };
+        self.statistics.borrow_mut().add_draw_call(shape.vertices.len() / 9, shape.indices.len());
+
+        Ok(())
+    }
+}
+
+impl Drop for LightingSystem {
+    fn drop(&mut self) {
+        if self.vbo_gl_id != 0 {
+            self.state.delete_buffer(self.vbo_gl_id);
+        }
+
+        if self.vao_gl_id != 0 {
+            self.state.delete_vertex_array(self.vao_gl_id);
+        }
+    }
+}
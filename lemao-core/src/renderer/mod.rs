@@ -1,8 +1,17 @@
 pub mod batcher;
 pub mod cameras;
 pub mod context;
+pub mod debug;
 pub mod drawable;
 pub mod fonts;
+pub mod framebuffer;
+pub mod instancer;
+pub mod lighting;
+pub mod postprocessing;
+pub mod richtext;
 pub mod shaders;
 pub mod shapes;
+pub mod state;
+pub mod statistics;
 pub mod textures;
+pub mod vectors;
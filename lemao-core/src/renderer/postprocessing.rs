@@ -0,0 +1,258 @@
+use super::context::RendererContext;
+use super::framebuffer::Framebuffer;
+use super::shaders::Shader;
+use super::shaders::BLOOM_COMBINE_FRAGMENT_SHADER;
+use super::shaders::BLOOM_THRESHOLD_FRAGMENT_SHADER;
+use super::shaders::BLUR_FRAGMENT_SHADER;
+use super::shaders::COLOR_GRADING_FRAGMENT_SHADER;
+use super::shaders::COPY_FRAGMENT_SHADER;
+use super::shaders::CRT_FRAGMENT_SHADER;
+use super::shaders::FULLSCREEN_VERTEX_SHADER;
+use super::shaders::VIGNETTE_FRAGMENT_SHADER;
+use super::textures::RawTexture;
+use super::textures::Texture;
+use crate::error::CoreError;
+use crate::utils::storage::Handle;
+use lemao_math::vec2::Vec2;
+use lemao_math::vec4::Vec4;
+use lemao_opengl::bindings::opengl;
+use std::ptr;
+
+// Scene is drawn into the first one, passes are switching between all three (bloom needs its input until the end)
+const FRAMEBUFFERS_COUNT: usize = 3;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PostProcessingEffect {
+    Blur { radius: f32 },
+    Bloom { threshold: f32, intensity: f32, radius: f32 },
+    Vignette { intensity: f32, radius: f32, smoothness: f32 },
+    ColorGrading { lut_texture_id: Handle<Texture>, lut_size: u32, intensity: f32 },
+    Crt { curvature: f32, scanlines_intensity: f32 },
+    Custom { shader_id: Handle<Shader>, parameters: Vec<(String, PostProcessingParameter)> },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PostProcessingParameter {
+    Float(f32),
+    Vec2(Vec2),
+    Vec4(Vec4),
+}
+
+pub struct PostProcessor {
+    pub effects: Vec<PostProcessingEffect>,
+
+    framebuffers: Vec<Framebuffer>,
+    copy_shader_id: Handle<Shader>,
+    blur_shader_id: Handle<Shader>,
+    bloom_threshold_shader_id: Handle<Shader>,
+    bloom_combine_shader_id: Handle<Shader>,
+    vignette_shader_id: Handle<Shader>,
+    color_grading_shader_id: Handle<Shader>,
+    crt_shader_id: Handle<Shader>,
+    running: bool,
+    skipped: bool,
+}
+
+impl PostProcessor {
+    pub fn new(renderer: &mut RendererContext) -> Result<Self, CoreError> {
+        let mut create_shader = |fragment_shader: &str| -> Result<Handle<Shader>, CoreError> {
+            let shader = Shader::new(renderer, FULLSCREEN_VERTEX_SHADER, fragment_shader)?;
+            Ok(renderer.shaders.store(shader))
+        };
+
+        Ok(Self {
+            effects: Vec::new(),
+
+            framebuffers: Vec::new(),
+            copy_shader_id: create_shader(COPY_FRAGMENT_SHADER)?,
+            blur_shader_id: create_shader(BLUR_FRAGMENT_SHADER)?,
+            bloom_threshold_shader_id: create_shader(BLOOM_THRESHOLD_FRAGMENT_SHADER)?,
+            bloom_combine_shader_id: create_shader(BLOOM_COMBINE_FRAGMENT_SHADER)?,
+            vignette_shader_id: create_shader(VIGNETTE_FRAGMENT_SHADER)?,
+            color_grading_shader_id: create_shader(COLOR_GRADING_FRAGMENT_SHADER)?,
+            crt_shader_id: create_shader(CRT_FRAGMENT_SHADER)?,
+            running: false,
+            skipped: false,
+        })
+    }
+
+    pub fn begin(&mut self, renderer: &RendererContext) -> Result<(), CoreError> {
+        if self.running {
+            return Err(CoreError::InvalidOperation("post-processing has been already started".to_string()));
+        }
+
+        // Framebuffers can't be created for a minimized window, so the scene is drawn directly until the viewport is restored
+        self.skipped = renderer.viewport_size.x <= 0.0 || renderer.viewport_size.y <= 0.0;
+        if !self.skipped {
+            if self.framebuffers.is_empty() || self.framebuffers[0].size != renderer.viewport_size {
+                self.framebuffers.clear();
+                for _ in 0..FRAMEBUFFERS_COUNT {
+                    self.framebuffers.push(Framebuffer::new(renderer, renderer.viewport_size)?);
+                }
+            }
+
+            renderer.state.bind_framebuffer(self.framebuffers[0].framebuffer_gl_id);
+        }
+
+        self.running = true;
+
+        Ok(())
+    }
+
+    pub fn end(&mut self, renderer: &mut RendererContext) -> Result<(), CoreError> {
+        if !self.running {
+            return Err(CoreError::InvalidOperation("post-processing hasn't been started".to_string()));
+        }
+
+        self.running = false;
+        if self.skipped {
+            return Ok(());
+        }
+
+        renderer.state.set_scissor(false);
+        renderer.state.set_stencil(false);
+
+        let mut current = 0;
+        for effect in &self.effects {
+            let (first, second) = ((current + 1) % FRAMEBUFFERS_COUNT, (current + 2) % FRAMEBUFFERS_COUNT);
+
+            match effect {
+                PostProcessingEffect::Blur { radius } => {
+                    let horizontal = [("direction", PostProcessingParameter::Vec2(Vec2::new(1.0, 0.0))), ("radius", PostProcessingParameter::Float(*radius))];
+                    let vertical = [("direction", PostProcessingParameter::Vec2(Vec2::new(0.0, 1.0))), ("radius", PostProcessingParameter::Float(*radius))];
+
+                    self.draw_pass(renderer, self.blur_shader_id, current, None, Some(first), &horizontal)?;
+                    self.draw_pass(renderer, self.blur_shader_id, first, None, Some(current), &vertical)?;
+                }
+                PostProcessingEffect::Bloom { threshold, intensity, radius } => {
+                    let horizontal = [("direction", PostProcessingParameter::Vec2(Vec2::new(1.0, 0.0))), ("radius", PostProcessingParameter::Float(*radius))];
+                    let vertical = [("direction", PostProcessingParameter::Vec2(Vec2::new(0.0, 1.0))), ("radius", PostProcessingParameter::Float(*radius))];
+
+                    let threshold = [("threshold", PostProcessingParameter::Float(*threshold))];
+                    let intensity = [("intensity", PostProcessingParameter::Float(*intensity))];
+
+                    self.draw_pass(renderer, self.bloom_threshold_shader_id, current, None, Some(first), &threshold)?;
+                    self.draw_pass(renderer, self.blur_shader_id, first, None, Some(second), &horizontal)?;
+                    self.draw_pass(renderer, self.blur_shader_id, second, None, Some(first), &vertical)?;
+                    self.draw_pass(renderer, self.bloom_combine_shader_id, first, Some(current), Some(second), &intensity)?;
+
+                    current = second;
+                }
+                PostProcessingEffect::Vignette { intensity, radius, smoothness } => {
+                    let parameters = [
+                        ("intensity", PostProcessingParameter::Float(*intensity)),
+                        ("radius", PostProcessingParameter::Float(*radius)),
+                        ("smoothness", PostProcessingParameter::Float(*smoothness)),
+                    ];
+
+                    self.draw_pass(renderer, self.vignette_shader_id, current, None, Some(first), &parameters)?;
+                    current = first;
+                }
+                PostProcessingEffect::ColorGrading { lut_texture_id, lut_size, intensity } => {
+                    let lut_texture = renderer.textures.get(*lut_texture_id)?;
+                    if lut_texture.srgb {
+                        return Err(CoreError::InvalidArgument("LUT texture must be created with Texture::new_linear".to_string()));
+                    }
+
+                    let lut_texture_gl_id = lut_texture.texture_gl_id;
+                    let parameters = [("lutSize", PostProcessingParameter::Float(*lut_size as f32)), ("intensity", PostProcessingParameter::Float(*intensity))];
+
+                    renderer.state.bind_texture(1, lut_texture_gl_id);
+                    self.draw_pass(renderer, self.color_grading_shader_id, current, None, Some(first), &parameters)?;
+                    current = first;
+                }
+                PostProcessingEffect::Crt { curvature, scanlines_intensity } => {
+                    let parameters =
+                        [("curvature", PostProcessingParameter::Float(*curvature)), ("scanlinesIntensity", PostProcessingParameter::Float(*scanlines_intensity))];
+
+                    self.draw_pass(renderer, self.crt_shader_id, current, None, Some(first), &parameters)?;
+                    current = first;
+                }
+                PostProcessingEffect::Custom { shader_id, parameters } => {
+                    let parameters = parameters.iter().map(|(name, value)| (name.as_str(), *value)).collect::<Vec<_>>();
+
+                    self.draw_pass(renderer, *shader_id, current, None, Some(first), &parameters)?;
+                    current = first;
+                }
+            }
+        }
+
+        self.draw_pass(renderer, self.copy_shader_id, current, None, None, &[])?;
+        renderer.state.bind_texture(0, 0);
+
+        Ok(())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn draw_pass(
+        &self,
+        renderer: &mut RendererContext,
+        shader_id: Handle<Shader>,
+        input: usize,
+        scene: Option<usize>,
+        target: Option<usize>,
+        parameters: &[(&str, PostProcessingParameter)],
+    ) -> Result<(), CoreError> {
+        renderer.set_shader_as_active(shader_id)?;
+        renderer.state.set_blend(false);
+
+        match target {
+            Some(target) => renderer.state.bind_framebuffer(self.framebuffers[target].framebuffer_gl_id),
+            None => renderer.state.bind_framebuffer(0),
+        }
+
+        // Texture units: 0 - the previous pass output, 1 - LUT (bound by the effect), 2 - input of the effect
+        let shader = renderer.shaders.get(shader_id)?;
+        renderer.state.bind_texture(0, self.framebuffers[input].texture_gl_id);
+
+        if let Some(scene) = scene {
+            renderer.state.bind_texture(2, self.framebuffers[scene].texture_gl_id);
+        }
+
+        for (name, unit) in [("ourTexture", 0.0f32), ("lutTexture", 1.0f32), ("sceneTexture", 2.0f32)] {
+            if shader.uniforms.contains_key(name) {
+                shader.set_parameter(name, &unit)?;
+            }
+        }
+
+        for (name, value) in parameters {
+            match value {
+                PostProcessingParameter::Float(value) => shader.set_parameter(name, value)?,
+                PostProcessingParameter::Vec2(value) => shader.set_parameter(name, value.as_ptr())?,
+                PostProcessingParameter::Vec4(value) => shader.set_parameter(name, value.as_ptr())?,
+            }
+        }
+
+        let shape = renderer.shapes.get(renderer.default_sprite_shape_id)?;
+        renderer.state.bind_vertex_array(shape.vao_gl_id);
+
+        unsafe {
+            (renderer.gl.glDrawElements)(opengl::GL_TRIANGLES, shape.indices.len() as i32, opengl::GL_UNSIGNED_INT, ptr::null());
+        }
+
+        renderer.frame_statistics.borrow_mut().add_draw_call(shape.vertices.len() / 9, shape.indices.len());
+        Ok(())
+    }
+}
+
+// Lookup tables hold data instead of colors, so they have to be uploaded with Texture::new_linear
+pub fn generate_neutral_lut(size: u32) -> RawTexture {
+    let max_value = u32::max(size, 2) - 1;
+    let mut data = Vec::new();
+
+    for green in 0..size {
+        for blue in 0..size {
+            for red in 0..size {
+                data.push((red * 255 / max_value) as u8);
+                data.push((green * 255 / max_value) as u8);
+                data.push((blue * 255 / max_value) as u8);
+                data.push(255);
+            }
+        }
+    }
+
+    RawTexture::new(Vec2::new((size * size) as f32, size as f32), data)
+}
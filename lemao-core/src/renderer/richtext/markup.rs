@@ -0,0 +1,271 @@
+use super::*;
+use crate::error::CoreError;
+
+pub fn parse(text: &str) -> Result<RichText, CoreError> {
+    // Supported tags:
+    // [color=#RRGGBB], [color=#RRGGBBAA] or [color=r,g,b,a] ... [/color]
+    // [outline=#RRGGBBAA,thickness] ... [/outline]
+    // [shadow=#RRGGBBAA,x,y] ... [/shadow]
+    // [u] ... [/u]
+    // [scale=factor] ... [/scale]
+    // Literal bracket has to be escaped as [[
+
+    let chars = text.chars().collect::<Vec<char>>();
+    let mut rich_text = RichText::new();
+    let mut opened_tags: Vec<(String, usize, TextStyle)> = Vec::new();
+    let mut style = TextStyle::default();
+    let mut buffer = String::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        if chars[index] != '[' {
+            buffer.push(chars[index]);
+            index += 1;
+            continue;
+        }
+
+        if chars.get(index + 1) == Some(&'[') {
+            buffer.push('[');
+            index += 2;
+            continue;
+        }
+
+        let tag_end = match chars[index..].iter().position(|p| *p == ']') {
+            Some(length) => index + length,
+            None => return Err(CoreError::parse("Unterminated tag", Some(index))),
+        };
+        let tag = chars[index + 1..tag_end].iter().collect::<String>();
+
+        rich_text.add_span(&buffer, style);
+        buffer.clear();
+
+        if let Some(name) = tag.strip_prefix('/') {
+            match opened_tags.pop() {
+                Some((opened_name, _, previous_style)) if opened_name == name => style = previous_style,
+                _ => return Err(CoreError::parse(&format!("Unexpected closing tag [/{}]", name), Some(index))),
+            }
+        } else {
+            let (name, value) = match tag.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (tag.as_str(), None),
+            };
+
+            opened_tags.push((name.to_string(), index, style));
+            style = apply_tag(style, name, value, index)?;
+        }
+
+        index = tag_end + 1;
+    }
+
+    if let Some((name, position, _)) = opened_tags.last() {
+        return Err(CoreError::parse(&format!("Unclosed tag [{}]", name), Some(*position)));
+    }
+
+    rich_text.add_span(&buffer, style);
+    Ok(rich_text)
+}
+
+pub fn parse_legacy(text: &str) -> Result<RichText, CoreError> {
+    let mut rich_text = RichText::new();
+    let mut style = TextStyle::default();
+    let mut buffer = String::new();
+    let mut color_section_position = None;
+
+    for (index, char) in text.chars().enumerate() {
+        if char != '°' {
+            buffer.push(char);
+            continue;
+        }
+
+        match color_section_position {
+            Some(position) => {
+                style.color = parse_color_at(&buffer, position)?;
+                color_section_position = None;
+            }
+            None => {
+                rich_text.add_span(&buffer, style);
+                color_section_position = Some(index);
+            }
+        }
+
+        buffer.clear();
+    }
+
+    if let Some(position) = color_section_position {
+        return Err(CoreError::parse("Unterminated color definition", Some(position)));
+    }
+
+    rich_text.add_span(&buffer, style);
+    Ok(rich_text)
+}
+
+pub fn parse_color(value: &str) -> Result<SolidColor, CoreError> {
+    parse_color_at(value, 0)
+}
+
+pub fn format_color(color: SolidColor) -> String {
+    let component = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02X}{:02X}{:02X}{:02X}", component(color.r), component(color.g), component(color.b), component(color.a))
+}
+
+fn parse_color_at(value: &str, offset: usize) -> Result<SolidColor, CoreError> {
+    if let Some(hex) = value.strip_prefix('#') {
+        if (hex.len() != 6 && hex.len() != 8) || !hex.chars().all(|p| p.is_ascii_hexdigit()) {
+            return Err(CoreError::parse(&format!("Invalid color {}, expected #RRGGBB or #RRGGBBAA", value), Some(offset)));
+        }
+
+        let component = |index: usize| u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).unwrap_or(0xff);
+        let alpha = if hex.len() == 8 { component(3) } else { 0xff };
+
+        return Ok(SolidColor::new_rgb(component(0), component(1), component(2), alpha));
+    }
+
+    let tokens = value.split(',').collect::<Vec<&str>>();
+    if tokens.len() != 4 {
+        return Err(CoreError::parse(&format!("Invalid color {}, expected r,g,b,a", value), Some(offset)));
+    }
+
+    let mut components = [0; 4];
+    for (index, token) in tokens.iter().enumerate() {
+        components[index] = match token.trim().parse::<u8>() {
+            Ok(value) => value,
+            Err(_) => return Err(CoreError::parse(&format!("Invalid color component {}", token), Some(offset))),
+        };
+    }
+
+    Ok(SolidColor::new_rgb(components[0], components[1], components[2], components[3]))
+}
+
+fn apply_tag(mut style: TextStyle, name: &str, value: Option<&str>, position: usize) -> Result<TextStyle, CoreError> {
+    match (name, value) {
+        ("color", Some(value)) => {
+            style.color = parse_color_at(value, position)?;
+        }
+        ("outline", Some(value)) => {
+            let arguments = value.split(',').collect::<Vec<&str>>();
+            if arguments.len() > 2 || !arguments[0].starts_with('#') {
+                return Err(CoreError::parse(&format!("Invalid outline {}, expected #RRGGBBAA,thickness", value), Some(position)));
+            }
+
+            let thickness = match arguments.get(1) {
+                Some(thickness) => parse_number(thickness, position)?,
+                None => 1.0,
+            };
+
+            style.outline = Some(TextOutline::new(parse_color_at(arguments[0], position)?, thickness));
+        }
+        ("shadow", Some(value)) => {
+            let arguments = value.split(',').collect::<Vec<&str>>();
+            if (arguments.len() != 1 && arguments.len() != 3) || !arguments[0].starts_with('#') {
+                return Err(CoreError::parse(&format!("Invalid shadow {}, expected #RRGGBBAA,x,y", value), Some(position)));
+            }
+
+            let offset = match arguments.len() {
+                3 => Vec2::new(parse_number(arguments[1], position)?, parse_number(arguments[2], position)?),
+                _ => Vec2::new(1.0, -1.0),
+            };
+
+            style.shadow = Some(TextShadow::new(parse_color_at(arguments[0], position)?, offset));
+        }
+        ("u", None) => {
+            style.underline = true;
+        }
+        ("scale", Some(value)) => {
+            let scale = parse_number(value, position)?;
+            if scale <= 0.0 {
+                return Err(CoreError::parse(&format!("Invalid scale {}, expected positive number", value), Some(position)));
+            }
+
+            style.scale = scale;
+        }
+        _ => return Err(CoreError::parse(&format!("Invalid tag [{}]", name), Some(position))),
+    }
+
+    Ok(style)
+}
+
+fn parse_number(value: &str, offset: usize) -> Result<f32, CoreError> {
+    match value.trim().parse::<f32>() {
+        Ok(number) if number.is_finite() => Ok(number),
+        _ => Err(CoreError::parse(&format!("Invalid number {}", value), Some(offset))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_offset(result: Result<RichText, CoreError>) -> Option<usize> {
+        match result {
+            Err(CoreError::AssetParse { offset, .. }) => offset,
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn error_offsets() {
+        assert_eq!(get_offset(parse("abc [color=#12]x[/color]")), Some(4));
+        assert_eq!(get_offset(parse("ab[scale=0]x[/scale]")), Some(2));
+        assert_eq!(get_offset(parse("[u]x[/u] [unknown]")), Some(9));
+        assert_eq!(get_offset(parse_legacy("ab°#zz°c")), Some(2));
+    }
+
+    #[test]
+    fn parse_nested_tags() {
+        let rich_text = parse("a[color=#FF0000]b[outline=#00FF00FF,2]c[u]d[/u][/outline][/color]e").unwrap();
+
+        let red = SolidColor::new_rgb(255, 0, 0, 255);
+        let outline = Some(TextOutline::new(SolidColor::new_rgb(0, 255, 0, 255), 2.0));
+        let outlined = TextStyle { color: red, outline, ..Default::default() };
+
+        assert_eq!(
+            rich_text.spans,
+            vec![
+                TextSpan::new("a", TextStyle::default()),
+                TextSpan::new("b", TextStyle { color: red, ..Default::default() }),
+                TextSpan::new("c", outlined),
+                TextSpan::new("d", TextStyle { underline: true, ..outlined }),
+                TextSpan::new("e", TextStyle::default()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_shadow_and_scale() {
+        let rich_text = parse("[shadow=#000000FF,2,-3][scale=1.5]a[/scale][/shadow]").unwrap();
+        let shadow = Some(TextShadow::new(SolidColor::new_rgb(0, 0, 0, 255), Vec2::new(2.0, -3.0)));
+
+        assert_eq!(rich_text.spans, vec![TextSpan::new("a", TextStyle { shadow, scale: 1.5, ..Default::default() })]);
+    }
+
+    #[test]
+    fn parse_escaped_bracket() {
+        let rich_text = parse("a[[b] [u][[[/u]").unwrap();
+
+        assert_eq!(rich_text.get_plain_text(), "a[b] [");
+        assert_eq!(rich_text.spans[1], TextSpan::new("[", TextStyle { underline: true, ..Default::default() }));
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let text = "a[color=#FF000080]b[[[outline=#00FF00FF,2]c[/outline][/color][shadow=#000000FF,1,-1][u]d[/u][/shadow][scale=2]e[/scale]";
+        let rich_text = parse(text).unwrap();
+
+        assert_eq!(parse(&rich_text.to_string()).unwrap(), rich_text);
+        assert_eq!(RichText::new_plain("a[b").to_string(), "a[[b");
+    }
+
+    #[test]
+    fn parse_legacy_colors() {
+        let rich_text = parse_legacy("a°#FF0000°b°0,0,255,255°c").unwrap();
+
+        assert_eq!(
+            rich_text.spans,
+            vec![
+                TextSpan::new("a", TextStyle::default()),
+                TextSpan::new("b", TextStyle { color: SolidColor::new_rgb(255, 0, 0, 255), ..Default::default() }),
+                TextSpan::new("c", TextStyle { color: SolidColor::new_rgb(0, 0, 255, 255), ..Default::default() }),
+            ]
+        );
+    }
+}
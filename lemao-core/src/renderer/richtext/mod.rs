@@ -0,0 +1,143 @@
+use lemao_math::color::SolidColor;
+use lemao_math::vec2::Vec2;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+pub mod markup;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TextMarkup {
+    None,
+    Rich,
+    Legacy,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RichText {
+    pub spans: Vec<TextSpan>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextSpan {
+    pub text: String,
+    pub style: TextStyle,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TextStyle {
+    pub color: SolidColor,
+    pub outline: Option<TextOutline>,
+    pub shadow: Option<TextShadow>,
+    pub underline: bool,
+    pub scale: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TextOutline {
+    pub color: SolidColor,
+    pub thickness: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TextShadow {
+    pub color: SolidColor,
+    pub offset: Vec2,
+}
+
+impl RichText {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn new_plain(text: &str) -> Self {
+        let mut rich_text = Self::new();
+        rich_text.add_span(text, Default::default());
+
+        rich_text
+    }
+
+    pub fn add_span(&mut self, text: &str, style: TextStyle) {
+        if text.is_empty() {
+            return;
+        }
+
+        if let Some(last_span) = self.spans.last_mut() {
+            if last_span.style == style {
+                last_span.text += text;
+                return;
+            }
+        }
+
+        self.spans.push(TextSpan::new(text, style));
+    }
+
+    pub fn get_plain_text(&self) -> String {
+        self.spans.iter().map(|p| p.text.as_str()).collect()
+    }
+}
+
+impl Display for RichText {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for span in &self.spans {
+            let mut closing_tags = Vec::new();
+
+            if span.style.color != SolidColor::new(1.0, 1.0, 1.0, 1.0) {
+                write!(f, "[color={}]", markup::format_color(span.style.color))?;
+                closing_tags.push("color");
+            }
+
+            if let Some(outline) = span.style.outline {
+                write!(f, "[outline={},{}]", markup::format_color(outline.color), outline.thickness)?;
+                closing_tags.push("outline");
+            }
+
+            if let Some(shadow) = span.style.shadow {
+                write!(f, "[shadow={},{},{}]", markup::format_color(shadow.color), shadow.offset.x, shadow.offset.y)?;
+                closing_tags.push("shadow");
+            }
+
+            if span.style.underline {
+                write!(f, "[u]")?;
+                closing_tags.push("u");
+            }
+
+            if span.style.scale != 1.0 {
+                write!(f, "[scale={}]", span.style.scale)?;
+                closing_tags.push("scale");
+            }
+
+            write!(f, "{}", span.text.replace('[', "[["))?;
+
+            for tag in closing_tags.iter().rev() {
+                write!(f, "[/{}]", tag)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TextSpan {
+    pub fn new(text: &str, style: TextStyle) -> Self {
+        Self { text: text.to_string(), style }
+    }
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self { color: SolidColor::new(1.0, 1.0, 1.0, 1.0), outline: None, shadow: None, underline: false, scale: 1.0 }
+    }
+}
+
+impl TextOutline {
+    pub fn new(color: SolidColor, thickness: f32) -> Self {
+        Self { color, thickness }
+    }
+}
+
+impl TextShadow {
+    pub fn new(color: SolidColor, offset: Vec2) -> Self {
+        Self { color, offset }
+    }
+}
@@ -0,0 +1,15 @@
+#version 330 core
+in vec2 TexCoord;
+out vec4 FragColor;
+
+uniform sampler2D ourTexture;
+uniform sampler2D sceneTexture;
+uniform float intensity;
+
+void main()
+{
+    vec4 scene = texture(sceneTexture, TexCoord);
+    vec4 bloom = texture(ourTexture, TexCoord);
+
+    FragColor = vec4(scene.rgb + bloom.rgb * intensity, scene.a);
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+#version 330 core
+in vec2 TexCoord;
+out vec4 FragColor;
+
+uniform sampler2D ourTexture;
+uniform float threshold;
+
+void main()
+{
+    vec4 color = texture(ourTexture, TexCoord);
+    float brightness = dot(color.rgb, vec3(0.2126, 0.7152, 0.0722));
+    float factor = max(brightness - threshold, 0.0) / max(brightness, 0.0001);
+
+    FragColor = vec4(color.rgb * factor, 1.0);
+}
\ No newline at end of file
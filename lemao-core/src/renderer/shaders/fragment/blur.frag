@@ -0,0 +1,32 @@
+#version 330 core
+in vec2 TexCoord;
+out vec4 FragColor;
+
+uniform sampler2D ourTexture;
+uniform vec2 direction;
+uniform float radius;
+
+void main()
+{
+    vec2 texelSize = 1.0 / vec2(textureSize(ourTexture, 0));
+    float sigma = max(radius / 2.0, 0.0001);
+
+    vec4 sum = texture(ourTexture, TexCoord);
+    float weightsSum = 1.0;
+
+    for (int i = 1; i <= 64; i++)
+    {
+        if (float(i) > radius)
+        {
+            break;
+        }
+
+        float weight = exp(-float(i * i) / (2.0 * sigma * sigma));
+        vec2 offset = direction * texelSize * float(i);
+
+        sum += (texture(ourTexture, TexCoord + offset) + texture(ourTexture, TexCoord - offset)) * weight;
+        weightsSum += 2.0 * weight;
+    }
+
+    FragColor = sum / weightsSum;
+}
\ No newline at end of file
@@ -0,0 +1,32 @@
+#version 330 core
+in vec2 TexCoord;
+out vec4 FragColor;
+
+uniform sampler2D ourTexture;
+uniform sampler2D lutTexture;
+uniform float lutSize;
+uniform float intensity;
+
+// LUT is a horizontal strip of slices, blue selects the slice, red grows along x and green along y
+vec3 fetchLut(vec3 index)
+{
+    return texelFetch(lutTexture, ivec2(int(index.b * lutSize + index.r), int(index.g)), 0).rgb;
+}
+
+void main()
+{
+    vec4 color = texture(ourTexture, TexCoord);
+
+    vec3 scaled = clamp(color.rgb, 0.0, 1.0) * (lutSize - 1.0);
+    vec3 base = floor(scaled);
+    vec3 next = min(base + 1.0, lutSize - 1.0);
+    vec3 factor = scaled - base;
+
+    vec3 bottom = mix(mix(fetchLut(base), fetchLut(vec3(next.r, base.g, base.b)), factor.r),
+                      mix(fetchLut(vec3(base.r, next.g, base.b)), fetchLut(vec3(next.r, next.g, base.b)), factor.r), factor.g);
+    vec3 top = mix(mix(fetchLut(vec3(base.r, base.g, next.b)), fetchLut(vec3(next.r, base.g, next.b)), factor.r),
+                   mix(fetchLut(vec3(base.r, next.g, next.b)), fetchLut(next), factor.r), factor.g);
+    vec3 graded = mix(bottom, top, factor.b);
+
+    FragColor = vec4(mix(color.rgb, graded, intensity), color.a);
+}
\ No newline at end of file
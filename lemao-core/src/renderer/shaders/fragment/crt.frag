@@ -0,0 +1,25 @@
+#version 330 core
+in vec2 TexCoord;
+out vec4 FragColor;
+
+uniform sampler2D ourTexture;
+uniform float curvature;
+uniform float scanlinesIntensity;
+
+void main()
+{
+    vec2 position = TexCoord * 2.0 - 1.0;
+    position += position * (position.yx * position.yx) * curvature;
+
+    vec2 uv = position * 0.5 + 0.5;
+    if (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0)
+    {
+        FragColor = vec4(0.0, 0.0, 0.0, 1.0);
+        return;
+    }
+
+    vec4 color = texture(ourTexture, uv);
+    float scanline = sin(uv.y * float(textureSize(ourTexture, 0).y) * 3.14159265) * 0.5 + 0.5;
+
+    FragColor = vec4(color.rgb * mix(1.0, scanline, scanlinesIntensity), color.a);
+}
\ No newline at end of file
@@ -4,17 +4,30 @@ in vec2 TexCoord;
 out vec4 FragColor;
 
 uniform int gradientPatternType;
+uniform int gradientSpread;
 uniform int gradientStepsCount;
+uniform float gradientAngle;
 uniform vec2 gradientOffset;
 uniform float gradientSteps[16];
 uniform vec4 gradientColors[16];
+uniform sampler2D gradientTexture;
 
 uniform sampler2D ourTexture;
 
+vec4 toLinear(vec4 color)
+{
+    return vec4(mix(color.rgb / 12.92, pow((color.rgb + 0.055) / 1.055, vec3(2.4)), step(0.04045, color.rgb)), color.a);
+}
+
+vec4 toSrgb(vec4 color)
+{
+    return vec4(mix(color.rgb * 12.92, 1.055 * pow(color.rgb, vec3(1.0 / 2.4)) - 0.055, step(0.0031308, color.rgb)), color.a);
+}
+
 void main()
 {
     float phase = 0.0;
-    vec4 color = gradientColors[0];
+    vec4 color = toLinear(gradientColors[0]);
 
     switch (gradientPatternType)
     {
@@ -33,21 +46,62 @@ void main()
         // Radial
         case 2:
         {
-            phase = length((TexCoord + gradientOffset) * 2 - 1);
+            phase = length((TexCoord + gradientOffset) * 2.0 - 1.0);
             break;
         }
         // Rectangular
         case 3:
         {
-            phase = max(abs((TexCoord.x + gradientOffset.x) * 2  - 1), abs((TexCoord.y + gradientOffset.y) * 2  - 1));
+            phase = max(abs((TexCoord.x + gradientOffset.x) * 2.0 - 1.0), abs((TexCoord.y + gradientOffset.y) * 2.0 - 1.0));
+            break;
+        }
+        // Linear
+        case 4:
+        {
+            vec2 direction = vec2(cos(gradientAngle), sin(gradientAngle));
+            phase = dot(TexCoord + gradientOffset - 0.5, direction) / (abs(direction.x) + abs(direction.y)) + 0.5;
+            break;
+        }
+        // Conic
+        case 5:
+        {
+            vec2 position = (TexCoord + gradientOffset) * 2.0 - 1.0;
+            phase = fract((atan(position.y, position.x) - gradientAngle) / 6.28318530718);
+            break;
+        }
+    }
+
+    switch (gradientSpread)
+    {
+        // Repeat
+        case 1:
+        {
+            phase = fract(phase);
             break;
         }
+        // Reflect
+        case 2:
+        {
+            phase = 1.0 - abs(mod(phase, 2.0) - 1.0);
+            break;
+        }
+    }
+
+    // Gradients with more steps than the uniform arrays can hold are baked into the lookup texture
+    if (gradientStepsCount == 0)
+    {
+        color = toLinear(texture(gradientTexture, vec2(clamp(phase, 0.0, 1.0), 0.5)));
     }
 
+    // Steps are mixed in linear space, otherwise transitions between saturated colors get dark in the middle
     for (int i = 0; i < gradientStepsCount - 1; i++)
     {
-        color = mix(color, gradientColors[i + 1], smoothstep(gradientSteps[i], gradientSteps[i + 1], phase));
+        color = mix(color, toLinear(gradientColors[i + 1]), smoothstep(gradientSteps[i], gradientSteps[i + 1], phase));
     }
-    
+
+#ifndef SRGB_FRAMEBUFFER
+    color = toSrgb(color);
+#endif
+
     FragColor = texture(ourTexture, TexCoord) * ourColor * color;
 }
\ No newline at end of file
@@ -0,0 +1,32 @@
+#version 330 core
+in vec4 ourColor;
+in vec2 TexCoord;
+out vec4 FragColor;
+
+uniform vec4 color;
+uniform float intensity;
+uniform float falloff;
+uniform vec2 spotDirection;
+uniform float spotCutoff;
+
+void main()
+{
+    vec2 offset = TexCoord * 2.0 - 1.0;
+    float lightDistance = length(offset);
+
+    if (lightDistance > 1.0)
+    {
+        discard;
+    }
+
+    float attenuation = pow(1.0 - lightDistance, falloff);
+
+    // Point lights have cutoff set to -1, so the whole circle is lit
+    if (spotCutoff > -1.0 && lightDistance > 0.0)
+    {
+        float cosine = dot(offset / lightDistance, spotDirection);
+        attenuation *= smoothstep(spotCutoff, mix(spotCutoff, 1.0, 0.1), cosine);
+    }
+
+    FragColor = vec4(color.rgb * color.a * intensity * attenuation, 1.0);
+}
\ No newline at end of file
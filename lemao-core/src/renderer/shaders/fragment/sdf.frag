@@ -0,0 +1,65 @@
+#version 330 core
+in vec4 ourColor;
+in vec2 TexCoord;
+out vec4 FragColor;
+
+uniform vec4 color;
+uniform int msdf;
+uniform float distanceRange;
+uniform float smoothing;
+uniform float outlineThickness;
+uniform vec4 outlineColor;
+uniform float glowThickness;
+uniform vec4 glowColor;
+
+uniform sampler2D ourTexture;
+
+float median(float r, float g, float b)
+{
+    return max(min(r, g), min(max(r, g), b));
+}
+
+vec4 blend(vec4 top, vec4 bottom)
+{
+    float alpha = top.a + bottom.a * (1.0 - top.a);
+    if (alpha == 0.0)
+    {
+        return vec4(0.0);
+    }
+
+    return vec4((top.rgb * top.a + bottom.rgb * bottom.a * (1.0 - top.a)) / alpha, alpha);
+}
+
+void main()
+{
+    vec4 texel = texture(ourTexture, TexCoord);
+    float fieldDistance = msdf == 1 ? median(texel.r, texel.g, texel.b) : texel.a;
+
+    // Distance expressed in texels of the atlas and in pixels of the screen, so the edge stays sharp at any scale
+    float texelDistance = (fieldDistance - 0.5) * distanceRange;
+    vec2 unitRange = vec2(distanceRange) / vec2(textureSize(ourTexture, 0));
+    float screenRange = max(0.5 * dot(unitRange, vec2(1.0) / fwidth(TexCoord)), 1.0);
+    float pixelsPerTexel = screenRange / distanceRange;
+    float screenDistance = texelDistance * pixelsPerTexel;
+
+    float fillAlpha = clamp(screenDistance / smoothing + 0.5, 0.0, 1.0);
+    float outlineAlpha = 0.0;
+    float glowAlpha = 0.0;
+
+    if (outlineThickness > 0.0)
+    {
+        outlineAlpha = clamp((screenDistance + outlineThickness * pixelsPerTexel) / smoothing + 0.5, 0.0, 1.0);
+    }
+
+    if (glowThickness > 0.0)
+    {
+        glowAlpha = smoothstep(-outlineThickness - glowThickness, -outlineThickness, texelDistance);
+    }
+
+    vec4 fill = ourColor * color;
+    vec4 result = vec4(glowColor.rgb, glowColor.a * glowAlpha);
+    result = blend(vec4(outlineColor.rgb, outlineColor.a * outlineAlpha), result);
+    result = blend(vec4(fill.rgb, fill.a * fillAlpha), result);
+
+    FragColor = result;
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+#version 330 core
+in vec2 TexCoord;
+out vec4 FragColor;
+
+uniform sampler2D ourTexture;
+uniform float intensity;
+uniform float radius;
+uniform float smoothness;
+
+void main()
+{
+    vec4 color = texture(ourTexture, TexCoord);
+    float vignette = smoothstep(radius, radius - smoothness, length(TexCoord - 0.5));
+
+    FragColor = vec4(color.rgb * mix(1.0, vignette, intensity), color.a);
+}
\ No newline at end of file
@@ -1,26 +1,55 @@
 use super::context::RendererContext;
 use super::drawable::Color;
+use super::state::StateCache;
+use crate::error::CoreError;
+use crate::utils::storage::Handle;
 use crate::utils::storage::StorageItem;
+use lemao_math::color::SolidColor;
+use lemao_math::gradient::Gradient;
+use lemao_math::gradient::GradientStep;
+use lemao_math::vec4::Vec4;
 use lemao_opengl::bindings::opengl;
 use lemao_opengl::pointers::OpenGLPointers;
+use lemao_opengl::pointers::OpenGLProfile;
+use std::cell::Cell;
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::ffi::c_void;
 use std::ffi::CString;
 use std::ptr;
 use std::rc::Rc;
 
 pub const MAX_UNIFORM_NAME_LENGTH: usize = 32;
 pub const ERROR_LENGTH: usize = 1024;
+pub const MAX_GRADIENT_STEPS: usize = 16;
+pub const GRADIENT_TEXTURE_WIDTH: usize = 256;
 pub const DEFAULT_VERTEX_SHADER: &str = include_str!("./vertex/default.vert");
+pub const INSTANCED_VERTEX_SHADER: &str = include_str!("./vertex/instanced.vert");
+pub const FULLSCREEN_VERTEX_SHADER: &str = include_str!("./vertex/fullscreen.vert");
 pub const SOLID_FRAGMENT_SHADER: &str = include_str!("./fragment/solid.frag");
 pub const GRADIENT_FRAGMENT_SHADER: &str = include_str!("./fragment/gradient.frag");
+pub const SDF_FRAGMENT_SHADER: &str = include_str!("./fragment/sdf.frag");
+pub const COPY_FRAGMENT_SHADER: &str = include_str!("./fragment/copy.frag");
+pub const BLUR_FRAGMENT_SHADER: &str = include_str!("./fragment/blur.frag");
+pub const BLOOM_THRESHOLD_FRAGMENT_SHADER: &str = include_str!("./fragment/bloom_threshold.frag");
+pub const BLOOM_COMBINE_FRAGMENT_SHADER: &str = include_str!("./fragment/bloom_combine.frag");
+pub const VIGNETTE_FRAGMENT_SHADER: &str = include_str!("./fragment/vignette.frag");
+pub const COLOR_GRADING_FRAGMENT_SHADER: &str = include_str!("./fragment/color_grading.frag");
+pub const CRT_FRAGMENT_SHADER: &str = include_str!("./fragment/crt.frag");
+pub const LIGHT_FRAGMENT_SHADER: &str = include_str!("./fragment/light.frag");
+pub const SHADOW_FRAGMENT_SHADER: &str = include_str!("./fragment/shadow.frag");
 
 pub struct Shader {
-    pub id: usize,
+    pub id: Handle<Shader>,
     pub name: Option<String>,
     pub(crate) program_id: u32,
     gl: Rc<OpenGLPointers>,
+    state: Rc<StateCache>,
+    srgb: bool,
 
     pub uniforms: HashMap<String, ShaderParameter>,
+    gradient_texture_gl_id: Cell<u32>,
+    gradient_texture_steps: RefCell<Vec<GradientStep>>,
 }
 
 pub struct ShaderParameter {
@@ -29,12 +58,13 @@ pub struct ShaderParameter {
 }
 
 impl Shader {
-    pub fn new(renderer: &RendererContext, vertex_shader: &str, fragment_shader: &str) -> Result<Self, String> {
+    pub fn new(renderer: &RendererContext, vertex_shader: &str, fragment_shader: &str) -> Result<Self, CoreError> {
         unsafe {
             let gl = renderer.gl.clone();
+            let state = renderer.state.clone();
 
             let mut success = 0;
-            let vertex_shader_cstr = CString::new(vertex_shader).unwrap();
+            let vertex_shader_cstr = CString::new(preprocess_source(vertex_shader, gl.profile, renderer.is_srgb())).unwrap();
             let vertex_shader_array = [vertex_shader_cstr.as_ptr()];
             let vertex_shader_id = (gl.glCreateShader)(opengl::GL_VERTEX_SHADER);
 
@@ -47,10 +77,10 @@ impl Shader {
                 let log_ptr = log.as_mut_ptr() as *mut i8;
                 (gl.glGetShaderInfoLog)(vertex_shader_id, ERROR_LENGTH as i32, ptr::null_mut(), log_ptr);
 
-                return Err(format!("Vertex shader compilation error: {}", String::from_utf8(log).unwrap()));
+                return Err(CoreError::OpenGL(format!("vertex shader compilation failed, {}", String::from_utf8(log).unwrap())));
             }
 
-            let fragment_shader_cstr = CString::new(fragment_shader).unwrap();
+            let fragment_shader_cstr = CString::new(preprocess_source(fragment_shader, gl.profile, renderer.is_srgb())).unwrap();
             let fragment_shader_array = [fragment_shader_cstr.as_ptr()];
             let fragment_shader_id = (gl.glCreateShader)(opengl::GL_FRAGMENT_SHADER);
 
@@ -63,7 +93,7 @@ impl Shader {
                 let log_ptr = log.as_mut_ptr() as *mut i8;
                 (gl.glGetShaderInfoLog)(fragment_shader_id, ERROR_LENGTH as i32, ptr::null_mut(), log_ptr);
 
-                return Err(format!("Fragment shader compilation error: {}", String::from_utf8(log).unwrap()));
+                return Err(CoreError::OpenGL(format!("fragment shader compilation failed, {}", String::from_utf8(log).unwrap())));
             }
 
             let program_id = (gl.glCreateProgram)();
@@ -78,7 +108,7 @@ impl Shader {
                 let log_ptr = log.as_mut_ptr() as *mut i8;
                 (gl.glGetProgramInfoLog)(program_id, 1024, ptr::null_mut(), log_ptr);
 
-                return Err(format!("Program shader linking error: {}", String::from_utf8(log).unwrap()));
+                return Err(CoreError::OpenGL(format!("program linking failed, {}", String::from_utf8(log).unwrap())));
             }
 
             (gl.glDeleteShader)(vertex_shader_id);
@@ -115,22 +145,32 @@ impl Shader {
                 }
             }
 
-            Ok(Shader { id: 0, name: None, program_id, uniforms, gl })
+            Ok(Shader {
+                id: Default::default(),
+                name: None,
+                program_id,
+                uniforms,
+                gl,
+                state,
+                srgb: renderer.is_srgb(),
+                gradient_texture_gl_id: Cell::new(0),
+                gradient_texture_steps: RefCell::new(Vec::new()),
+            })
         }
     }
 
-    pub fn set_parameter<T>(&self, name: &str, data: *const T) -> Result<(), String>
+    pub fn set_parameter<T>(&self, name: &str, data: *const T) -> Result<(), CoreError>
     where
         T: Copy + Into<f32>,
     {
         unsafe {
             let parameter = match self.uniforms.get(name) {
                 Some(parameter) => parameter,
-                None => return Err(format!("Shader parameter with name {} not found", name)),
+                None => return Err(CoreError::not_found_by_name("Shader parameter", name)),
             };
 
             match parameter.r#type {
-                opengl::GL_INT => {
+                opengl::GL_INT | opengl::GL_SAMPLER_2D => {
                     (self.gl.glUniform1i)(parameter.location as i32, (*data).into() as i32);
                 }
                 opengl::GL_FLOAT => {
@@ -145,26 +185,49 @@ impl Shader {
                 opengl::GL_FLOAT_MAT4 => {
                     (self.gl.glUniformMatrix4fv)(parameter.location as i32, 1, opengl::GL_FALSE as u8, data as *const f32);
                 }
-                _ => return Err("Invalid shader parameter type".to_string()),
+                _ => return Err(CoreError::InvalidOperation(format!("shader parameter {} has unsupported type {}", name, parameter.r#type))),
             };
 
             Ok(())
         }
     }
 
-    pub fn set_color(&self, color: &Color) -> Result<(), String> {
+    // Shaders which don't sample textures have this uniform optimized out, so it's set only when present
+    pub fn set_uv_rect(&self, uv_rect: Vec4) -> Result<(), CoreError> {
+        if self.uniforms.contains_key("uvRect") {
+            self.set_parameter("uvRect", uv_rect.as_ptr())?;
+        }
+
+        Ok(())
+    }
+
+    // Colors are specified in sRGB space, so they have to be linearized when the framebuffer encodes them back
+    pub fn set_solid_color(&self, name: &str, color: &SolidColor) -> Result<(), CoreError> {
+        let color = if self.srgb { color.to_linear() } else { *color };
+        self.set_parameter(name, color.as_ptr())
+    }
+
+    pub fn set_color(&self, color: &Color) -> Result<(), CoreError> {
         match color {
-            Color::SolidColor(solid) => {
-                self.set_parameter("color", solid.as_ptr())?;
-            }
+            Color::SolidColor(solid) => self.set_solid_color("color", solid)?,
             Color::Gradient(gradient) => {
                 self.set_parameter("gradientPatternType", &(gradient.r#type as u32 as f32))?;
-                self.set_parameter("gradientStepsCount", &(gradient.steps.len() as f32))?;
+                self.set_parameter("gradientSpread", &(gradient.spread as u32 as f32))?;
+                self.set_parameter("gradientAngle", &gradient.angle)?;
                 self.set_parameter("gradientOffset", gradient.offset.as_ptr() as *const f32)?;
 
-                for (index, step) in gradient.steps.iter().enumerate() {
-                    self.set_parameter(&format!("gradientSteps[{}]", index), &step.step)?;
-                    self.set_parameter(&format!("gradientColors[{}]", index), step.color.as_ptr())?;
+                if gradient.steps.len() <= MAX_GRADIENT_STEPS {
+                    self.set_parameter("gradientStepsCount", &(gradient.steps.len() as f32))?;
+
+                    for (index, step) in gradient.steps.iter().enumerate() {
+                        self.set_parameter(&format!("gradientSteps[{}]", index), &step.step)?;
+                        self.set_parameter(&format!("gradientColors[{}]", index), step.color.as_ptr())?;
+                    }
+                } else {
+                    // Zero steps count tells the shader to read colors from the lookup texture
+                    self.set_parameter("gradientStepsCount", &0.0)?;
+                    self.set_parameter("gradientTexture", &1.0)?;
+                    self.update_gradient_texture(gradient);
                 }
             }
         }
@@ -173,20 +236,62 @@ impl Shader {
     }
 
     pub fn set_as_active(&self) {
+        self.state.use_program(self.program_id);
+        self.state.set_blend(true);
+        self.state.set_blend_function(opengl::GL_SRC_ALPHA, opengl::GL_ONE_MINUS_SRC_ALPHA);
+    }
+}
+
+impl Shader {
+    fn update_gradient_texture(&self, gradient: &Gradient) {
         unsafe {
-            (self.gl.glUseProgram)(self.program_id);
-            (self.gl.glEnable)(opengl::GL_BLEND);
-            (self.gl.glBlendFunc)(opengl::GL_SRC_ALPHA, opengl::GL_ONE_MINUS_SRC_ALPHA);
+            if self.gradient_texture_gl_id.get() == 0 {
+                let mut texture_gl_id = 0;
+                (self.gl.glGenTextures)(1, &mut texture_gl_id);
+                self.state.bind_texture(1, texture_gl_id);
+
+                (self.gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_WRAP_S, opengl::GL_CLAMP_TO_EDGE as i32);
+                (self.gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_WRAP_T, opengl::GL_CLAMP_TO_EDGE as i32);
+                (self.gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_MIN_FILTER, opengl::GL_LINEAR as i32);
+                (self.gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_MAG_FILTER, opengl::GL_LINEAR as i32);
+
+                self.gradient_texture_gl_id.set(texture_gl_id);
+            }
+
+            self.state.bind_texture(1, self.gradient_texture_gl_id.get());
+
+            // Baking is skipped when the same gradient is drawn again
+            if *self.gradient_texture_steps.borrow() == gradient.steps {
+                return;
+            }
+
+            // Steps are interpolated in linear space and then encoded back, the same way as the shader does it with uniforms
+            let mut linear_gradient = gradient.clone();
+            for step in &mut linear_gradient.steps {
+                step.color = step.color.to_linear();
+            }
+
+            let mut data = Vec::with_capacity(GRADIENT_TEXTURE_WIDTH * 4);
+            for index in 0..GRADIENT_TEXTURE_WIDTH {
+                let color = linear_gradient.get_color(index as f32 / (GRADIENT_TEXTURE_WIDTH - 1) as f32).to_srgb();
+                data.extend_from_slice(&[(color.r * 255.0) as u8, (color.g * 255.0) as u8, (color.b * 255.0) as u8, (color.a * 255.0) as u8]);
+            }
+
+            let format = opengl::GL_RGBA;
+            let data_ptr = data.as_ptr() as *const c_void;
+            (self.gl.glTexImage2D)(opengl::GL_TEXTURE_2D, 0, opengl::GL_RGBA as i32, GRADIENT_TEXTURE_WIDTH as i32, 1, 0, format, opengl::GL_UNSIGNED_BYTE, data_ptr);
+
+            *self.gradient_texture_steps.borrow_mut() = gradient.steps.clone();
         }
     }
 }
 
 impl StorageItem for Shader {
-    fn get_id(&self) -> usize {
+    fn get_id(&self) -> Handle<Self> {
         self.id
     }
 
-    fn set_id(&mut self, id: usize) {
+    fn set_id(&mut self, id: Handle<Self>) {
         self.id = id;
     }
 
@@ -201,10 +306,32 @@ impl StorageItem for Shader {
 
 impl Drop for Shader {
     fn drop(&mut self) {
-        unsafe {
-            if self.program_id != 0 {
-                (self.gl.glDeleteProgram)(self.program_id);
-            }
+        if self.program_id != 0 {
+            self.state.delete_program(self.program_id);
+        }
+
+        if self.gradient_texture_gl_id.get() != 0 {
+            self.state.delete_texture(self.gradient_texture_gl_id.get());
         }
     }
 }
+
+// Shaders are written for desktop OpenGL, in OpenGL ES the version header is replaced and default precisions are declared.
+// Defines are inserted right after the version header, since it has to be the first line of the source
+fn preprocess_source(source: &str, profile: OpenGLProfile, srgb: bool) -> String {
+    let (version, body) = match source.trim_start().strip_prefix("#version") {
+        Some(rest) => match rest.split_once('\n') {
+            Some((version, body)) => (format!("#version{}", version), body),
+            None => (format!("#version{}", rest), ""),
+        },
+        None => ("#version 330 core".to_string(), source),
+    };
+
+    let header = match profile {
+        OpenGLProfile::Core => version,
+        OpenGLProfile::Es => "#version 300 es\nprecision highp float;\nprecision highp int;\nprecision highp sampler2D;".to_string(),
+    };
+    let defines = if srgb { "#define SRGB_FRAMEBUFFER\n" } else { "" };
+
+    format!("{}\n{}{}", header, defines, body)
+}
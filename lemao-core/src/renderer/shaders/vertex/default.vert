@@ -6,13 +6,26 @@ layout (location = 2) in vec2 aTexCoord;
 uniform mat4 model;
 uniform mat4 view;
 uniform mat4 proj;
+uniform vec4 uvRect;
 
 out vec4 ourColor;
 out vec2 TexCoord;
 
+vec4 toLinear(vec4 color)
+{
+    return vec4(mix(color.rgb / 12.92, pow((color.rgb + 0.055) / 1.055, vec3(2.4)), step(0.04045, color.rgb)), color.a);
+}
+
 void main()
 {
     gl_Position = proj * view * model * vec4(aPos, 1.0);
     ourColor = aColor;
-    TexCoord = aTexCoord;
+
+    // Vertex colors are defined in sRGB, but with sRGB framebuffer blending and interpolation happen in linear space
+#ifdef SRGB_FRAMEBUFFER
+    ourColor = toLinear(ourColor);
+#endif
+
+    // Shared shapes always cover the whole texture, so the part of it used by the drawable is selected here
+    TexCoord = uvRect.xy + aTexCoord * uvRect.zw;
 }
\ No newline at end of file
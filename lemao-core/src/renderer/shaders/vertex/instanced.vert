@@ -0,0 +1,31 @@
+#version 330 core
+layout (location = 0) in vec3 aPos;
+layout (location = 1) in vec4 aColor;
+layout (location = 2) in vec2 aTexCoord;
+layout (location = 3) in mat4 aModel;
+layout (location = 7) in vec4 aInstanceColor;
+layout (location = 8) in vec4 aUvRect;
+
+uniform mat4 view;
+uniform mat4 proj;
+
+out vec4 ourColor;
+out vec2 TexCoord;
+
+vec4 toLinear(vec4 color)
+{
+    return vec4(mix(color.rgb / 12.92, pow((color.rgb + 0.055) / 1.055, vec3(2.4)), step(0.04045, color.rgb)), color.a);
+}
+
+void main()
+{
+    gl_Position = proj * view * aModel * vec4(aPos, 1.0);
+    ourColor = aColor * aInstanceColor;
+
+    // Vertex colors are defined in sRGB, but with sRGB framebuffer blending and interpolation happen in linear space
+#ifdef SRGB_FRAMEBUFFER
+    ourColor = toLinear(ourColor);
+#endif
+
+    TexCoord = aUvRect.xy + aTexCoord * aUvRect.zw;
+}
\ No newline at end of file
@@ -1,22 +1,23 @@
 use super::context::RendererContext;
+use super::state::StateCache;
+use crate::utils::storage::Handle;
 use crate::utils::storage::StorageItem;
 use lemao_math::color::SolidColor;
 use lemao_math::vec2::Vec2;
 use lemao_math::vec3::Vec3;
 use lemao_opengl::bindings::opengl;
-use lemao_opengl::pointers::OpenGLPointers;
 use std::ffi::c_void;
 use std::mem;
 use std::ptr;
 use std::rc::Rc;
 
 pub struct Shape {
-    pub id: usize,
+    pub id: Handle<Shape>,
     pub name: Option<String>,
     pub(crate) vao_gl_id: u32,
     pub(crate) vbo_gl_id: u32,
     pub(crate) ebo_gl_id: u32,
-    gl: Rc<OpenGLPointers>,
+    state: Rc<StateCache>,
 
     pub vertices: Vec<f32>,
     pub indices: Vec<u32>,
@@ -26,10 +27,11 @@ impl Shape {
     pub fn new(renderer: &RendererContext, vertices: Vec<Vec3>, indices: Vec<u32>, uv: Vec<Vec2>, colors: Vec<SolidColor>) -> Self {
         unsafe {
             let gl = renderer.gl.clone();
+            let state = renderer.state.clone();
 
             let mut vao_gl_id = 0;
             (gl.glGenVertexArrays)(1, &mut vao_gl_id);
-            (gl.glBindVertexArray)(vao_gl_id);
+            state.bind_vertex_array(vao_gl_id);
 
             let mut data = Vec::new();
             for i in 0..vertices.len() {
@@ -51,7 +53,7 @@ impl Shape {
 
             let mut vbo_gl_id = 0;
             (gl.glGenBuffers)(1, &mut vbo_gl_id);
-            (gl.glBindBuffer)(opengl::GL_ARRAY_BUFFER, vbo_gl_id);
+            state.bind_buffer(opengl::GL_ARRAY_BUFFER, vbo_gl_id);
             (gl.glBufferData)(opengl::GL_ARRAY_BUFFER, data_size, data_ptr, opengl::GL_STATIC_DRAW);
 
             let indices_size = (mem::size_of::<u32>() * indices.len()) as i64;
@@ -59,7 +61,7 @@ impl Shape {
 
             let mut ebo_gl_id = 0;
             (gl.glGenBuffers)(1, &mut ebo_gl_id);
-            (gl.glBindBuffer)(opengl::GL_ELEMENT_ARRAY_BUFFER, ebo_gl_id);
+            state.bind_buffer(opengl::GL_ELEMENT_ARRAY_BUFFER, ebo_gl_id);
             (gl.glBufferData)(opengl::GL_ELEMENT_ARRAY_BUFFER, indices_size, indices_ptr, opengl::GL_STATIC_DRAW);
 
             let attrib_size = (9 * mem::size_of::<f32>()) as i32;
@@ -71,17 +73,17 @@ impl Shape {
             (gl.glEnableVertexAttribArray)(1);
             (gl.glEnableVertexAttribArray)(2);
 
-            Self { id: 0, name: None, vao_gl_id, vbo_gl_id, ebo_gl_id, gl, vertices: data, indices }
+            Self { id: Default::default(), name: None, vao_gl_id, vbo_gl_id, ebo_gl_id, state, vertices: data, indices }
         }
     }
 }
 
 impl StorageItem for Shape {
-    fn get_id(&self) -> usize {
+    fn get_id(&self) -> Handle<Self> {
         self.id
     }
 
-    fn set_id(&mut self, id: usize) {
+    fn set_id(&mut self, id: Handle<Self>) {
         self.id = id;
     }
 
@@ -96,18 +98,16 @@ impl StorageItem for Shape {
 
 impl Drop for Shape {
     fn drop(&mut self) {
-        unsafe {
-            if self.vbo_gl_id != 0 {
-                (self.gl.glDeleteBuffers)(1, &mut self.vbo_gl_id);
-            }
+        if self.vbo_gl_id != 0 {
+            self.state.delete_buffer(self.vbo_gl_id);
+        }
 
-            if self.ebo_gl_id != 0 {
-                (self.gl.glDeleteBuffers)(1, &mut self.ebo_gl_id);
-            }
+        if self.ebo_gl_id != 0 {
+            self.state.delete_buffer(self.ebo_gl_id);
+        }
 
-            if self.vao_gl_id != 0 {
-                (self.gl.glDeleteVertexArrays)(1, &mut self.vao_gl_id);
-            }
+        if self.vao_gl_id != 0 {
+            self.state.delete_vertex_array(self.vao_gl_id);
         }
     }
 }
@@ -0,0 +1,256 @@
+use super::statistics::RendererStatistics;
+use lemao_opengl::bindings::opengl;
+use lemao_opengl::pointers::OpenGLPointers;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const MAX_TEXTURE_UNITS: usize = 16;
+
+// None means that the state is unknown (not set yet or invalidated), so the next call will always reach the driver
+#[derive(Debug)]
+pub struct StateCache {
+    gl: Rc<OpenGLPointers>,
+    statistics: Rc<RefCell<RendererStatistics>>,
+
+    program: Cell<Option<u32>>,
+    framebuffer: Cell<Option<u32>>,
+    vertex_array: Cell<Option<u32>>,
+    array_buffer: Cell<Option<u32>>,
+    element_array_buffer: Cell<Option<u32>>,
+    active_texture_unit: Cell<Option<u32>>,
+    textures: [Cell<Option<u32>>; MAX_TEXTURE_UNITS],
+    blend: Cell<Option<bool>>,
+    blend_function: Cell<Option<(u32, u32)>>,
+    scissor: Cell<Option<bool>>,
+    scissor_box: Cell<Option<(i32, i32, i32, i32)>>,
+    stencil: Cell<Option<bool>>,
+}
+
+impl StateCache {
+    pub fn new(gl: Rc<OpenGLPointers>, statistics: Rc<RefCell<RendererStatistics>>) -> Self {
+        Self {
+            gl,
+            statistics,
+
+            program: Cell::new(None),
+            framebuffer: Cell::new(None),
+            vertex_array: Cell::new(None),
+            array_buffer: Cell::new(None),
+            element_array_buffer: Cell::new(None),
+            active_texture_unit: Cell::new(None),
+            textures: Default::default(),
+            blend: Cell::new(None),
+            blend_function: Cell::new(None),
+            scissor: Cell::new(None),
+            scissor_box: Cell::new(None),
+            stencil: Cell::new(None),
+        }
+    }
+
+    pub fn use_program(&self, program_gl_id: u32) {
+        if self.program.get() != Some(program_gl_id) {
+            unsafe { (self.gl.glUseProgram)(program_gl_id) };
+
+            self.program.set(Some(program_gl_id));
+            self.statistics.borrow_mut().shader_switches += 1;
+        }
+    }
+
+    pub fn bind_framebuffer(&self, framebuffer_gl_id: u32) {
+        if self.framebuffer.get() != Some(framebuffer_gl_id) {
+            unsafe { (self.gl.glBindFramebuffer)(opengl::GL_FRAMEBUFFER, framebuffer_gl_id) };
+            self.framebuffer.set(Some(framebuffer_gl_id));
+        }
+    }
+
+    pub fn get_framebuffer(&self) -> u32 {
+        match self.framebuffer.get() {
+            Some(framebuffer_gl_id) => framebuffer_gl_id,
+            None => {
+                let mut framebuffer_gl_id = 0;
+                unsafe { (self.gl.glGetIntegerv)(opengl::GL_FRAMEBUFFER_BINDING, &mut framebuffer_gl_id) };
+
+                self.framebuffer.set(Some(framebuffer_gl_id as u32));
+                framebuffer_gl_id as u32
+            }
+        }
+    }
+
+    pub fn bind_vertex_array(&self, vao_gl_id: u32) {
+        if self.vertex_array.get() != Some(vao_gl_id) {
+            unsafe { (self.gl.glBindVertexArray)(vao_gl_id) };
+
+            self.vertex_array.set(Some(vao_gl_id));
+
+            // Element array buffer binding is a part of the vertex array state
+            self.element_array_buffer.set(None);
+        }
+    }
+
+    pub fn bind_buffer(&self, target: u32, buffer_gl_id: u32) {
+        let cache = match target {
+            opengl::GL_ARRAY_BUFFER => &self.array_buffer,
+            opengl::GL_ELEMENT_ARRAY_BUFFER => &self.element_array_buffer,
+            _ => {
+                unsafe { (self.gl.glBindBuffer)(target, buffer_gl_id) };
+                return;
+            }
+        };
+
+        if cache.get() != Some(buffer_gl_id) {
+            unsafe { (self.gl.glBindBuffer)(target, buffer_gl_id) };
+            cache.set(Some(buffer_gl_id));
+        }
+    }
+
+    pub fn bind_texture(&self, unit: u32, texture_gl_id: u32) {
+        // Active unit is always switched, texture uploads following this call are applied to it
+        self.set_active_texture_unit(unit);
+
+        // Units above the cache size are still valid for the driver, they are just bound every time
+        let cache = match self.textures.get(unit as usize) {
+            Some(cache) => cache,
+            None => {
+                unsafe { (self.gl.glBindTexture)(opengl::GL_TEXTURE_2D, texture_gl_id) };
+                self.statistics.borrow_mut().texture_switches += 1;
+                return;
+            }
+        };
+
+        if cache.get() != Some(texture_gl_id) {
+            unsafe { (self.gl.glBindTexture)(opengl::GL_TEXTURE_2D, texture_gl_id) };
+
+            cache.set(Some(texture_gl_id));
+            self.statistics.borrow_mut().texture_switches += 1;
+        }
+    }
+
+    pub fn set_blend(&self, enabled: bool) {
+        if self.blend.get() != Some(enabled) {
+            unsafe {
+                if enabled {
+                    (self.gl.glEnable)(opengl::GL_BLEND);
+                } else {
+                    (self.gl.glDisable)(opengl::GL_BLEND);
+                }
+            }
+
+            self.blend.set(Some(enabled));
+        }
+    }
+
+    pub fn set_blend_function(&self, source: u32, destination: u32) {
+        if self.blend_function.get() != Some((source, destination)) {
+            unsafe { (self.gl.glBlendFunc)(source, destination) };
+            self.blend_function.set(Some((source, destination)));
+        }
+    }
+
+    pub fn set_scissor(&self, enabled: bool) {
+        if self.scissor.get() != Some(enabled) {
+            unsafe {
+                if enabled {
+                    (self.gl.glEnable)(opengl::GL_SCISSOR_TEST);
+                } else {
+                    (self.gl.glDisable)(opengl::GL_SCISSOR_TEST);
+                }
+            }
+
+            self.scissor.set(Some(enabled));
+        }
+    }
+
+    pub fn set_scissor_box(&self, x: i32, y: i32, width: i32, height: i32) {
+        if self.scissor_box.get() != Some((x, y, width, height)) {
+            unsafe { (self.gl.glScissor)(x, y, width, height) };
+            self.scissor_box.set(Some((x, y, width, height)));
+        }
+    }
+
+    pub fn set_stencil(&self, enabled: bool) {
+        if self.stencil.get() != Some(enabled) {
+            unsafe {
+                if enabled {
+                    (self.gl.glEnable)(opengl::GL_STENCIL_TEST);
+                } else {
+                    (self.gl.glDisable)(opengl::GL_STENCIL_TEST);
+                }
+            }
+
+            self.stencil.set(Some(enabled));
+        }
+    }
+
+    pub fn delete_program(&self, program_gl_id: u32) {
+        unsafe { (self.gl.glDeleteProgram)(program_gl_id) };
+
+        if self.program.get() == Some(program_gl_id) {
+            self.program.set(None);
+        }
+    }
+
+    pub fn delete_framebuffer(&self, framebuffer_gl_id: u32) {
+        unsafe { (self.gl.glDeleteFramebuffers)(1, &framebuffer_gl_id) };
+
+        // Deleting the bound framebuffer reverts the binding to the default one
+        if self.framebuffer.get() == Some(framebuffer_gl_id) {
+            self.framebuffer.set(Some(0));
+        }
+    }
+
+    pub fn delete_vertex_array(&self, vao_gl_id: u32) {
+        unsafe { (self.gl.glDeleteVertexArrays)(1, &vao_gl_id) };
+
+        // Deleted names can be returned again by glGen*, so the cache can't keep them
+        if self.vertex_array.get() == Some(vao_gl_id) {
+            self.vertex_array.set(None);
+            self.element_array_buffer.set(None);
+        }
+    }
+
+    pub fn delete_buffer(&self, buffer_gl_id: u32) {
+        unsafe { (self.gl.glDeleteBuffers)(1, &buffer_gl_id) };
+
+        for cache in [&self.array_buffer, &self.element_array_buffer] {
+            if cache.get() == Some(buffer_gl_id) {
+                cache.set(None);
+            }
+        }
+    }
+
+    pub fn delete_texture(&self, texture_gl_id: u32) {
+        unsafe { (self.gl.glDeleteTextures)(1, &texture_gl_id) };
+
+        for cache in &self.textures {
+            if cache.get() == Some(texture_gl_id) {
+                cache.set(None);
+            }
+        }
+    }
+
+    pub fn invalidate(&self) {
+        self.program.set(None);
+        self.framebuffer.set(None);
+        self.vertex_array.set(None);
+        self.array_buffer.set(None);
+        self.element_array_buffer.set(None);
+        self.active_texture_unit.set(None);
+        self.blend.set(None);
+        self.blend_function.set(None);
+        self.scissor.set(None);
+        self.scissor_box.set(None);
+        self.stencil.set(None);
+
+        for cache in &self.textures {
+            cache.set(None);
+        }
+    }
+
+    fn set_active_texture_unit(&self, unit: u32) {
+        if self.active_texture_unit.get() != Some(unit) {
+            unsafe { (self.gl.glActiveTexture)(opengl::GL_TEXTURE0 + unit) };
+            self.active_texture_unit.set(Some(unit));
+        }
+    }
+}
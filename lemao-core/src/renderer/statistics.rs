@@ -0,0 +1,112 @@
+use crate::error::CoreError;
+use lemao_opengl::bindings::opengl;
+use lemao_opengl::pointers::OpenGLPointers;
+use std::rc::Rc;
+
+// Results are read a few frames later, so the CPU doesn't have to wait for the GPU
+const GPU_TIMER_QUERIES_COUNT: usize = 4;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct RendererStatistics {
+    pub draw_calls: u32,
+    pub batches: u32,
+    pub vertices: u32,
+    pub indices: u32,
+    pub shader_switches: u32,
+    pub texture_switches: u32,
+    pub buffer_uploads: u32,
+    pub buffer_uploads_size: usize,
+    pub gpu_time: Option<f32>,
+}
+
+pub(crate) struct GpuTimer {
+    gl: Rc<OpenGLPointers>,
+    get_query_object: opengl::PFNGLGETQUERYOBJECTIVPROC,
+    get_query_object_u64: opengl::PFNGLGETQUERYOBJECTUI64VPROC,
+
+    queries: [u32; GPU_TIMER_QUERIES_COUNT],
+    pending: [bool; GPU_TIMER_QUERIES_COUNT],
+    current_query: usize,
+    running: bool,
+    last_time: Option<f32>,
+}
+
+impl RendererStatistics {
+    pub(crate) fn add_draw_call(&mut self, vertices_count: usize, indices_count: usize) {
+        self.draw_calls += 1;
+        self.vertices += vertices_count as u32;
+        self.indices += indices_count as u32;
+    }
+
+    pub(crate) fn add_buffer_upload(&mut self, size: i64) {
+        self.buffer_uploads += 1;
+        self.buffer_uploads_size += size as usize;
+    }
+}
+
+impl GpuTimer {
+    pub fn new(gl: Rc<OpenGLPointers>) -> Result<Self, CoreError> {
+        // Timer queries are not a part of OpenGL ES 3.0
+        let (get_query_object, get_query_object_u64) = match (gl.glGetQueryObjectiv, gl.glGetQueryObjectui64v) {
+            (Some(get_query_object), Some(get_query_object_u64)) => (get_query_object, get_query_object_u64),
+            _ => return Err(CoreError::InvalidOperation("GPU timer is not supported by the current OpenGL profile".to_string())),
+        };
+
+        let mut queries = [0; GPU_TIMER_QUERIES_COUNT];
+        unsafe { (gl.glGenQueries)(GPU_TIMER_QUERIES_COUNT as i32, queries.as_mut_ptr()) };
+
+        Ok(Self { gl, get_query_object, get_query_object_u64, queries, pending: [false; GPU_TIMER_QUERIES_COUNT], current_query: 0, running: false, last_time: None })
+    }
+
+    pub fn begin(&mut self) {
+        if self.running || self.pending[self.current_query] {
+            return;
+        }
+
+        unsafe { (self.gl.glBeginQuery)(opengl::GL_TIME_ELAPSED, self.queries[self.current_query]) };
+        self.running = true;
+    }
+
+    pub fn end(&mut self) {
+        if self.running {
+            unsafe { (self.gl.glEndQuery)(opengl::GL_TIME_ELAPSED) };
+
+            self.pending[self.current_query] = true;
+            self.current_query = (self.current_query + 1) % GPU_TIMER_QUERIES_COUNT;
+            self.running = false;
+        }
+    }
+
+    pub fn get_time(&mut self) -> Option<f32> {
+        unsafe {
+            // The oldest query is the next one to reuse, so it's checked first to keep the results in order
+            for offset in 0..GPU_TIMER_QUERIES_COUNT {
+                let index = (self.current_query + offset) % GPU_TIMER_QUERIES_COUNT;
+                if !self.pending[index] {
+                    continue;
+                }
+
+                let mut available = 0;
+                (self.get_query_object)(self.queries[index], opengl::GL_QUERY_RESULT_AVAILABLE, &mut available);
+
+                if available == 0 {
+                    break;
+                }
+
+                let mut time = 0;
+                (self.get_query_object_u64)(self.queries[index], opengl::GL_QUERY_RESULT, &mut time);
+
+                self.pending[index] = false;
+                self.last_time = Some(time as f32 / 1_000_000.0);
+            }
+
+            self.last_time
+        }
+    }
+}
+
+impl Drop for GpuTimer {
+    fn drop(&mut self) {
+        unsafe { (self.gl.glDeleteQueries)(GPU_TIMER_QUERIES_COUNT as i32, self.queries.as_ptr()) };
+    }
+}
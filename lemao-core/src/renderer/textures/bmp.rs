@@ -1,30 +1,31 @@
 use super::*;
+use crate::error::CoreError;
 use crate::utils::binary;
 use std::fs::File;
 use std::io::Read;
 
-pub fn load(path: &str) -> Result<RawTexture, String> {
+pub fn load(path: &str) -> Result<RawTexture, CoreError> {
     //////////////////////////////////////////////////////////////////////
     // BMP specification: https://en.wikipedia.org/wiki/BMP_file_format //
     //////////////////////////////////////////////////////////////////////
 
     let mut file = match File::open(path) {
         Ok(file) => file,
-        Err(_) => return Err(format!("File {} not found", path)),
+        Err(error) => return Err(CoreError::Io { path: path.to_string(), message: error.to_string() }),
     };
 
     let mut bmp = Vec::new();
-    if let Err(message) = file.read_to_end(&mut bmp) {
-        return Err(format!("Error while reading file: {}", message));
+    if let Err(error) = file.read_to_end(&mut bmp) {
+        return Err(CoreError::Io { path: path.to_string(), message: error.to_string() });
     }
 
     if binary::read_le_u16(&bmp, 0) != 0x4d42 {
-        return Err("Invalid signature, not recognized as BMP file".to_string());
+        return Err(CoreError::parse("Invalid signature, not recognized as BMP file", Some(0)));
     }
 
     let file_size = binary::read_le_u32(&bmp, 2) as usize;
     if file_size != bmp.len() {
-        return Err("Invalid file, expected size doesn't match the real one".to_string());
+        return Err(CoreError::parse("Invalid file, expected size doesn't match the real one", Some(2)));
     }
 
     let data_address = binary::read_le_u32(&bmp, 10);
@@ -80,7 +81,7 @@ pub fn load(path: &str) -> Result<RawTexture, String> {
                 data.push(a as u8);
             }
         }
-        _ => return Err("Unsupported compression method, only BI_RGB and BI_BITFIELDS are supported".to_string()),
+        _ => return Err(CoreError::UnsupportedFormat("only BI_RGB and BI_BITFIELDS compression methods are supported".to_string())),
     }
 
     Ok(RawTexture::new(Vec2::new(width as f32, height as f32), data))
@@ -1,4 +1,7 @@
 use super::context::RendererContext;
+use super::state::StateCache;
+use crate::error::CoreError;
+use crate::utils::storage::Handle;
 use crate::utils::storage::StorageItem;
 use lemao_math::vec2::Vec2;
 use lemao_opengl::bindings::opengl;
@@ -7,6 +10,7 @@ use std::ffi::c_void;
 use std::rc::Rc;
 
 pub mod bmp;
+pub mod tga;
 
 pub struct RawTexture {
     pub size: Vec2,
@@ -14,12 +18,14 @@ pub struct RawTexture {
 }
 
 pub struct Texture {
-    pub id: usize,
+    pub id: Handle<Texture>,
     pub name: Option<String>,
     pub(crate) texture_gl_id: u32,
     gl: Rc<OpenGLPointers>,
+    state: Rc<StateCache>,
 
     pub size: Vec2,
+    pub srgb: bool,
 }
 
 impl RawTexture {
@@ -29,19 +35,29 @@ impl RawTexture {
 }
 
 impl Texture {
-    pub fn new(renderer: &RendererContext, raw: &RawTexture) -> Result<Self, String> {
+    pub fn new(renderer: &RendererContext, raw: &RawTexture) -> Result<Self, CoreError> {
+        Self::new_with_color_space(renderer, raw, renderer.is_srgb())
+    }
+
+    // Textures containing data instead of colors (lookup tables, masks) must not be decoded from sRGB
+    pub fn new_linear(renderer: &RendererContext, raw: &RawTexture) -> Result<Self, CoreError> {
+        Self::new_with_color_space(renderer, raw, false)
+    }
+
+    fn new_with_color_space(renderer: &RendererContext, raw: &RawTexture, srgb: bool) -> Result<Self, CoreError> {
         unsafe {
             let gl = renderer.gl.clone();
+            let state = renderer.state.clone();
             let mut texture_gl_id = 0;
 
             (gl.glGenTextures)(1, &mut texture_gl_id);
-            (gl.glBindTexture)(opengl::GL_TEXTURE_2D, texture_gl_id);
+            state.bind_texture(0, texture_gl_id);
             (gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_WRAP_S, opengl::GL_MIRRORED_REPEAT as i32);
             (gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_WRAP_T, opengl::GL_MIRRORED_REPEAT as i32);
             (gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_MIN_FILTER, opengl::GL_NEAREST_MIPMAP_NEAREST as i32);
             (gl.glTexParameteri)(opengl::GL_TEXTURE_2D, opengl::GL_TEXTURE_MAG_FILTER, opengl::GL_NEAREST as i32);
 
-            let mut texture = Self { id: 0, name: None, texture_gl_id, gl, size: raw.size };
+            let mut texture = Self { id: Default::default(), name: None, texture_gl_id, gl, state, size: raw.size, srgb };
             texture.set_data(raw);
 
             Ok(texture)
@@ -50,14 +66,15 @@ impl Texture {
 
     pub fn set_data(&mut self, raw: &RawTexture) {
         unsafe {
-            (self.gl.glBindTexture)(opengl::GL_TEXTURE_2D, self.texture_gl_id);
+            self.state.bind_texture(0, self.texture_gl_id);
 
             let format = opengl::GL_RGBA;
+            let internal_format = if self.srgb { opengl::GL_SRGB8_ALPHA8 } else { opengl::GL_RGBA };
             let texture_width = raw.size.x as i32;
             let texture_height = raw.size.y as i32;
             let texture_ptr = raw.data.as_ptr() as *const c_void;
 
-            (self.gl.glTexImage2D)(opengl::GL_TEXTURE_2D, 0, format as i32, texture_width, texture_height, 0, format, opengl::GL_UNSIGNED_BYTE, texture_ptr);
+            (self.gl.glTexImage2D)(opengl::GL_TEXTURE_2D, 0, internal_format as i32, texture_width, texture_height, 0, format, opengl::GL_UNSIGNED_BYTE, texture_ptr);
             (self.gl.glGenerateMipmap)(opengl::GL_TEXTURE_2D);
 
             self.size = raw.size;
@@ -66,11 +83,11 @@ impl Texture {
 }
 
 impl StorageItem for Texture {
-    fn get_id(&self) -> usize {
+    fn get_id(&self) -> Handle<Self> {
         self.id
     }
 
-    fn set_id(&mut self, id: usize) {
+    fn set_id(&mut self, id: Handle<Self>) {
         self.id = id;
     }
 
@@ -85,10 +102,8 @@ impl StorageItem for Texture {
 
 impl Drop for Texture {
     fn drop(&mut self) {
-        unsafe {
-            if self.texture_gl_id != 0 {
-                (self.gl.glDeleteTextures)(1, &self.texture_gl_id);
-            }
+        if self.texture_gl_id != 0 {
+            self.state.delete_texture(self.texture_gl_id);
         }
     }
 }
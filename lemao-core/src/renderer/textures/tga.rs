@@ -0,0 +1,107 @@
+use super::*;
+use crate::error::CoreError;
+use crate::utils::binary;
+use std::fs::File;
+use std::io::Read;
+
+pub fn load(path: &str) -> Result<RawTexture, CoreError> {
+    /////////////////////////////////////////////////////////////////////
+    // TGA specification: https://en.wikipedia.org/wiki/Truevision_TGA //
+    /////////////////////////////////////////////////////////////////////
+
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) => return Err(CoreError::Io { path: path.to_string(), message: error.to_string() }),
+    };
+
+    let mut tga = Vec::new();
+    if let Err(error) = file.read_to_end(&mut tga) {
+        return Err(CoreError::Io { path: path.to_string(), message: error.to_string() });
+    }
+
+    if tga.len() < 18 {
+        return Err(CoreError::parse("Invalid file, header is too short", Some(tga.len())));
+    }
+
+    let id_length = binary::read_u8(&tga, 0) as usize;
+    let color_map_type = binary::read_u8(&tga, 1);
+    let image_type = binary::read_u8(&tga, 2);
+    let width = binary::read_le_u16(&tga, 12) as usize;
+    let height = binary::read_le_u16(&tga, 14) as usize;
+    let bits_per_pixel = binary::read_u8(&tga, 16);
+    let descriptor = binary::read_u8(&tga, 17);
+
+    if color_map_type != 0 {
+        return Err(CoreError::UnsupportedFormat("color-mapped images are not allowed".to_string()));
+    }
+
+    let (grayscale, compressed) = match image_type {
+        2 => (false, false),
+        3 => (true, false),
+        10 => (false, true),
+        11 => (true, true),
+        _ => return Err(CoreError::UnsupportedFormat("only truecolor and grayscale images are allowed".to_string())),
+    };
+
+    let bytes_per_pixel = match (grayscale, bits_per_pixel) {
+        (true, 8) => 1,
+        (false, 24) => 3,
+        (false, 32) => 4,
+        _ => return Err(CoreError::UnsupportedFormat("only 8 (grayscale), 24 and 32 bits per pixel are allowed".to_string())),
+    };
+
+    let mut pixels = Vec::new();
+    let mut data_index = 18 + id_length;
+
+    while pixels.len() < width * height * bytes_per_pixel {
+        let (count, repeated) = if compressed {
+            let packet = match tga.get(data_index) {
+                Some(packet) => *packet,
+                None => return Err(CoreError::parse("Invalid file, unexpected end of data", Some(data_index))),
+            };
+
+            data_index += 1;
+            ((packet & 0x7f) as usize + 1, packet & 0x80 != 0)
+        } else {
+            (width * height, false)
+        };
+
+        let length = if repeated { bytes_per_pixel } else { count * bytes_per_pixel };
+        if data_index + length > tga.len() {
+            return Err(CoreError::parse("Invalid file, unexpected end of data", Some(data_index)));
+        }
+
+        if repeated {
+            for _ in 0..count {
+                pixels.extend_from_slice(&tga[data_index..data_index + length]);
+            }
+        } else {
+            pixels.extend_from_slice(&tga[data_index..data_index + length]);
+        }
+
+        data_index += length;
+    }
+
+    // Rows are stored from the bottom unless the fifth bit of the descriptor says otherwise
+    let top_to_bottom = descriptor & 0x20 != 0;
+    let mut data = Vec::new();
+
+    for y in 0..height {
+        let row = if top_to_bottom { height - y - 1 } else { y };
+        for x in 0..width {
+            let index = (x + row * width) * bytes_per_pixel;
+            let (r, g, b, a) = match bytes_per_pixel {
+                1 => (pixels[index], pixels[index], pixels[index], 0xff),
+                3 => (pixels[index + 2], pixels[index + 1], pixels[index], 0xff),
+                _ => (pixels[index + 2], pixels[index + 1], pixels[index], pixels[index + 3]),
+            };
+
+            data.push(r);
+            data.push(g);
+            data.push(b);
+            data.push(a);
+        }
+    }
+
+    Ok(RawTexture::new(Vec2::new(width as f32, height as f32), data))
+}
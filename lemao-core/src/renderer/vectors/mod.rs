@@ -0,0 +1,200 @@
+use super::drawable::mesh::MeshVertex;
+use lemao_math::color::SolidColor;
+use lemao_math::vec2::Vec2;
+use lemao_math::vec3::Vec3;
+
+pub mod path;
+pub mod svg;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FillRule {
+    NonZero,
+    EvenOdd,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RawVectorImage {
+    pub size: Vec2,
+    pub vertices: Vec<MeshVertex>,
+    pub indices: Vec<u32>,
+}
+
+impl RawVectorImage {
+    pub fn new(size: Vec2) -> Self {
+        Self { size, vertices: Vec::new(), indices: Vec::new() }
+    }
+
+    pub fn add_fill(&mut self, contours: &[Vec<Vec2>], rule: FillRule, color: SolidColor) {
+        let mut edges = Vec::new();
+        for contour in contours {
+            for index in 0..contour.len() {
+                let from = contour[index];
+                let to = contour[(index + 1) % contour.len()];
+
+                // Horizontal edges never cross any scanline, so they don't affect the winding
+                if from.y != to.y {
+                    edges.push((from, to));
+                }
+            }
+        }
+
+        if edges.is_empty() {
+            return;
+        }
+
+        // Shape is cut into horizontal slabs at every vertex and edge intersection, so inside of each slab edges don't cross
+        // each other and the area between every pair of them is a single trapezoid
+        let mut scanlines = edges.iter().flat_map(|p| [p.0.y, p.1.y]).collect::<Vec<f32>>();
+        for (index, a) in edges.iter().enumerate() {
+            for b in &edges[index + 1..] {
+                if let Some(y) = get_intersection_y(*a, *b) {
+                    scanlines.push(y);
+                }
+            }
+        }
+
+        scanlines.sort_by(|a, b| a.total_cmp(b));
+        scanlines.dedup_by(|a, b| (*a - *b).abs() < f32::EPSILON);
+
+        for slab in scanlines.windows(2) {
+            let (bottom, top) = (slab[0], slab[1]);
+            let middle = (bottom + top) / 2.0;
+
+            let mut crossings = Vec::new();
+            for (from, to) in &edges {
+                if (from.y < middle) != (to.y < middle) {
+                    let winding = if from.y < to.y { 1 } else { -1 };
+                    crossings.push((get_x_at(*from, *to, bottom), get_x_at(*from, *to, middle), get_x_at(*from, *to, top), winding));
+                }
+            }
+
+            crossings.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].3;
+
+                let inside = match rule {
+                    FillRule::NonZero => winding != 0,
+                    FillRule::EvenOdd => winding % 2 != 0,
+                };
+
+                if inside {
+                    let (left, right) = (pair[0], pair[1]);
+                    let points = [Vec2::new(left.0, bottom), Vec2::new(right.0, bottom), Vec2::new(right.2, top), Vec2::new(left.2, top)];
+                    self.add_quad(points, color);
+                }
+            }
+        }
+    }
+
+    pub fn add_stroke(&mut self, polyline: &[Vec2], closed: bool, width: f32, color: SolidColor) {
+        let mut points = polyline.to_vec();
+        points.dedup();
+
+        if closed && points.len() > 2 {
+            points.push(points[0]);
+        }
+
+        let mut previous_normal: Option<Vec2> = None;
+        for segment in points.windows(2) {
+            let (from, to) = (segment[0], segment[1]);
+            let direction = (to - from).normalized();
+            let normal = Vec2::new(-direction.y, direction.x) * (width / 2.0);
+
+            self.add_quad([from - normal, to - normal, to + normal, from + normal], color);
+
+            // Gaps between consecutive segments are closed with a bevel join on both sides
+            if let Some(previous_normal) = previous_normal {
+                self.add_triangle([from, from + previous_normal, from + normal], color);
+                self.add_triangle([from, from - previous_normal, from - normal], color);
+            }
+
+            previous_normal = Some(normal);
+        }
+
+        if closed && points.len() > 2 {
+            if let Some(previous_normal) = previous_normal {
+                let first_direction = (points[1] - points[0]).normalized();
+                let first_normal = Vec2::new(-first_direction.y, first_direction.x) * (width / 2.0);
+
+                self.add_triangle([points[0], points[0] + previous_normal, points[0] + first_normal], color);
+                self.add_triangle([points[0], points[0] - previous_normal, points[0] - first_normal], color);
+            }
+        }
+    }
+
+    fn add_triangle(&mut self, points: [Vec2; 3], color: SolidColor) {
+        let base_index = self.vertices.len() as u32;
+
+        for point in points {
+            self.vertices.push(MeshVertex::new(Vec3::new(point.x, point.y, 0.0), color, self.get_uv(point)));
+        }
+
+        self.indices.extend_from_slice(&[base_index, base_index + 1, base_index + 2]);
+    }
+
+    fn add_quad(&mut self, points: [Vec2; 4], color: SolidColor) {
+        let base_index = self.vertices.len() as u32;
+
+        for point in points {
+            self.vertices.push(MeshVertex::new(Vec3::new(point.x, point.y, 0.0), color, self.get_uv(point)));
+        }
+
+        self.indices.extend_from_slice(&[base_index, base_index + 1, base_index + 2, base_index, base_index + 2, base_index + 3]);
+    }
+
+    fn get_uv(&self, point: Vec2) -> Vec2 {
+        if self.size.x == 0.0 || self.size.y == 0.0 {
+            return Vec2::new(0.0, 0.0);
+        }
+
+        point / self.size
+    }
+}
+
+fn get_x_at(from: Vec2, to: Vec2, y: f32) -> f32 {
+    from.x + (to.x - from.x) * (y - from.y) / (to.y - from.y)
+}
+
+fn get_intersection_y(a: (Vec2, Vec2), b: (Vec2, Vec2)) -> Option<f32> {
+    let r = a.1 - a.0;
+    let s = b.1 - b.0;
+    let denominator = r.x * s.y - r.y * s.x;
+
+    if denominator.abs() < f32::EPSILON {
+        return None;
+    }
+
+    let t = ((b.0.x - a.0.x) * s.y - (b.0.y - a.0.y) * s.x) / denominator;
+    let u = ((b.0.x - a.0.x) * r.y - (b.0.y - a.0.y) * r.x) / denominator;
+
+    if t > 0.0 && t < 1.0 && u > 0.0 && u < 1.0 {
+        Some(a.0.y + r.y * t)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_with_nan() {
+        let mut image = RawVectorImage::new(Vec2::new(10.0, 10.0));
+        let contour = vec![Vec2::new(0.0, 0.0), Vec2::new(10.0, f32::NAN), Vec2::new(10.0, 10.0), Vec2::new(0.0, 10.0)];
+
+        image.add_fill(&[contour], FillRule::NonZero, SolidColor::WHITE);
+    }
+
+    #[test]
+    fn fill_square() {
+        let mut image = RawVectorImage::new(Vec2::new(10.0, 10.0));
+        let contour = vec![Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0), Vec2::new(0.0, 10.0)];
+
+        image.add_fill(&[contour], FillRule::NonZero, SolidColor::WHITE);
+        assert!(!image.vertices.is_empty());
+        assert_eq!(image.indices.len() % 3, 0);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::error::CoreError;
+use lemao_math::vec2::Vec2;
+use std::f32::consts;
+
+const CURVE_SEGMENTS: usize = 16;
+const ARC_SEGMENT_ANGLE: f32 = consts::PI / 16.0;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Subpath {
+    pub points: Vec<Vec2>,
+    pub closed: bool,
+}
+
+struct PathReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+pub fn parse(data: &str) -> Result<Vec<Subpath>, CoreError> {
+    /////////////////////////////////////////////////////////////////////////////
+    // SVG path specification: https://www.w3.org/TR/SVG11/paths.html#PathData //
+    /////////////////////////////////////////////////////////////////////////////
+
+    let mut reader = PathReader { data: data.as_bytes(), position: 0 };
+    let mut subpaths = Vec::new();
+    let mut subpath = Subpath::default();
+    let mut command = None;
+    let mut current = Vec2::new(0.0, 0.0);
+    let mut start = Vec2::new(0.0, 0.0);
+
+    loop {
+        reader.skip_separators();
+
+        let next = match reader.peek() {
+            Some(next) => next,
+            None => break,
+        };
+
+        if next.is_ascii_alphabetic() {
+            command = Some(next);
+            reader.position += 1;
+        }
+
+        let relative = command.map(|p| p.is_ascii_lowercase()).unwrap_or(false);
+        let origin = if relative { current } else { Vec2::new(0.0, 0.0) };
+
+        match command.map(|p| p.to_ascii_uppercase()) {
+            Some(b'M') => {
+                let point = origin + reader.read_point()?;
+
+                if subpath.points.len() > 1 {
+                    subpaths.push(subpath);
+                }
+
+                subpath = Subpath { points: vec![point], closed: false };
+                current = point;
+                start = point;
+
+                // Coordinates following the first pair are implicit line commands
+                command = Some(if relative { b'l' } else { b'L' });
+            }
+            Some(b'L') => {
+                current = origin + reader.read_point()?;
+                subpath.points.push(current);
+            }
+            Some(b'H') => {
+                current = Vec2::new(origin.x + reader.read_number()?, current.y);
+                subpath.points.push(current);
+            }
+            Some(b'V') => {
+                current = Vec2::new(current.x, origin.y + reader.read_number()?);
+                subpath.points.push(current);
+            }
+            Some(b'C') => {
+                let control1 = origin + reader.read_point()?;
+                let control2 = origin + reader.read_point()?;
+                let end = origin + reader.read_point()?;
+
+                for index in 1..=CURVE_SEGMENTS {
+                    let t = index as f32 / CURVE_SEGMENTS as f32;
+                    let u = 1.0 - t;
+                    subpath.points.push(current * (u * u * u) + control1 * (3.0 * u * u * t) + control2 * (3.0 * u * t * t) + end * (t * t * t));
+                }
+
+                current = end;
+            }
+            Some(b'Q') => {
+                let control = origin + reader.read_point()?;
+                let end = origin + reader.read_point()?;
+
+                for index in 1..=CURVE_SEGMENTS {
+                    let t = index as f32 / CURVE_SEGMENTS as f32;
+                    let u = 1.0 - t;
+                    subpath.points.push(current * (u * u) + control * (2.0 * u * t) + end * (t * t));
+                }
+
+                current = end;
+            }
+            Some(b'A') => {
+                let radius = Vec2::new(reader.read_number()?, reader.read_number()?);
+                let rotation = reader.read_number()?.to_radians();
+                let large_arc = reader.read_flag()?;
+                let sweep = reader.read_flag()?;
+                let end = origin + reader.read_point()?;
+
+                add_arc_points(&mut subpath.points, current, end, radius, rotation, large_arc, sweep);
+                current = end;
+            }
+            Some(b'Z') => {
+                subpath.closed = true;
+                if subpath.points.len() > 1 {
+                    subpaths.push(subpath);
+                }
+
+                // Drawing after close starts a new subpath at the same point as the previous one
+                subpath = Subpath { points: vec![start], closed: false };
+                current = start;
+                command = None;
+            }
+            Some(other) => return Err(CoreError::UnsupportedFormat(format!("path command {}", other as char))),
+            None => return Err(CoreError::parse("Invalid path data, expected command", Some(reader.position))),
+        }
+    }
+
+    if subpath.points.len() > 1 {
+        subpaths.push(subpath);
+    }
+
+    Ok(subpaths)
+}
+
+impl<'a> PathReader<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.position).copied()
+    }
+
+    fn skip_separators(&mut self) {
+        while let Some(b' ' | b'\t' | b'\r' | b'\n' | b',') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn read_number(&mut self) -> Result<f32, CoreError> {
+        self.skip_separators();
+        let start = self.position;
+
+        if let Some(b'+' | b'-') = self.peek() {
+            self.position += 1;
+        }
+
+        let mut dot = false;
+        let mut exponent = false;
+
+        while let Some(next) = self.peek() {
+            match next {
+                b'0'..=b'9' => {}
+                b'.' if !dot && !exponent => dot = true,
+                b'e' | b'E' if !exponent && self.position > start => {
+                    exponent = true;
+                    if let Some(b'+' | b'-') = self.data.get(self.position + 1) {
+                        self.position += 1;
+                    }
+                }
+                _ => break,
+            }
+
+            self.position += 1;
+        }
+
+        match std::str::from_utf8(&self.data[start..self.position]).ok().and_then(|p| p.parse::<f32>().ok()) {
+            Some(number) if number.is_finite() => Ok(number),
+            _ => Err(CoreError::parse("Invalid path data, expected number", Some(start))),
+        }
+    }
+
+    fn read_point(&mut self) -> Result<Vec2, CoreError> {
+        Ok(Vec2::new(self.read_number()?, self.read_number()?))
+    }
+
+    // Flags are single digits, which can be written without any separator after them
+    fn read_flag(&mut self) -> Result<bool, CoreError> {
+        self.skip_separators();
+
+        let flag = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(CoreError::parse("Invalid path data, expected arc flag", Some(self.position))),
+        };
+
+        self.position += 1;
+        Ok(flag)
+    }
+}
+
+fn add_arc_points(points: &mut Vec<Vec2>, from: Vec2, to: Vec2, radius: Vec2, rotation: f32, large_arc: bool, sweep: bool) {
+    /////////////////////////////////////////////////////////////////////////////////////////////
+    // Arc conversion: https://www.w3.org/TR/SVG11/implnote.html#ArcConversionEndpointToCenter //
+    /////////////////////////////////////////////////////////////////////////////////////////////
+
+    // Arcs with identical endpoints are omitted entirely, as required by F.6.2
+    if from == to {
+        return;
+    }
+
+    let mut radius = radius.abs();
+    if radius.x == 0.0 || radius.y == 0.0 {
+        points.push(to);
+        return;
+    }
+
+    let (sin, cos) = rotation.sin_cos();
+    let half_delta = (from - to) / 2.0;
+    let from_prime = Vec2::new(cos * half_delta.x + sin * half_delta.y, -sin * half_delta.x + cos * half_delta.y);
+
+    // Radii too small to connect both points are scaled up uniformly
+    let lambda = (from_prime.x * from_prime.x) / (radius.x * radius.x) + (from_prime.y * from_prime.y) / (radius.y * radius.y);
+    if lambda > 1.0 {
+        radius = radius * lambda.sqrt();
+    }
+
+    let numerator = radius.x * radius.x * radius.y * radius.y - radius.x * radius.x * from_prime.y * from_prime.y - radius.y * radius.y * from_prime.x * from_prime.x;
+    let denominator = radius.x * radius.x * from_prime.y * from_prime.y + radius.y * radius.y * from_prime.x * from_prime.x;
+    let coefficient = (numerator / denominator).max(0.0).sqrt() * if large_arc == sweep { -1.0 } else { 1.0 };
+    let center_prime = Vec2::new(coefficient * radius.x * from_prime.y / radius.y, -coefficient * radius.y * from_prime.x / radius.x);
+
+    let middle = (from + to) / 2.0;
+    let center = Vec2::new(cos * center_prime.x - sin * center_prime.y + middle.x, sin * center_prime.x + cos * center_prime.y + middle.y);
+
+    let start_vector = Vec2::new((from_prime.x - center_prime.x) / radius.x, (from_prime.y - center_prime.y) / radius.y);
+    let end_vector = Vec2::new((-from_prime.x - center_prime.x) / radius.x, (-from_prime.y - center_prime.y) / radius.y);
+    let start_angle = start_vector.y.atan2(start_vector.x);
+    let mut delta_angle = end_vector.y.atan2(end_vector.x) - start_angle;
+
+    if sweep && delta_angle < 0.0 {
+        delta_angle += consts::TAU;
+    } else if !sweep && delta_angle > 0.0 {
+        delta_angle -= consts::TAU;
+    }
+
+    let segments = ((delta_angle.abs() / ARC_SEGMENT_ANGLE).ceil() as usize).max(1);
+    for index in 1..=segments {
+        let angle = start_angle + delta_angle * (index as f32 / segments as f32);
+        let (angle_sin, angle_cos) = angle.sin_cos();
+        let point = Vec2::new(radius.x * angle_cos, radius.y * angle_sin);
+
+        points.push(Vec2::new(cos * point.x - sin * point.y + center.x, sin * point.x + cos * point.y + center.y));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arc_with_identical_endpoints() {
+        assert!(parse("M10 10 A5 5 0 0 1 10 10").unwrap().is_empty());
+
+        let subpaths = parse("M10 10 A5 5 0 0 1 10 10 L20 10").unwrap();
+        assert_eq!(subpaths[0].points, vec![Vec2::new(10.0, 10.0), Vec2::new(20.0, 10.0)]);
+    }
+
+    #[test]
+    fn arc_with_zero_radius() {
+        let subpaths = parse("M10 10 A0 5 0 0 1 20 10").unwrap();
+        assert_eq!(subpaths[0].points, vec![Vec2::new(10.0, 10.0), Vec2::new(20.0, 10.0)]);
+    }
+
+    #[test]
+    fn arc_ends_at_target() {
+        let subpaths = parse("M10 10 A5 5 0 0 1 20 10").unwrap();
+        let last = *subpaths[0].points.last().unwrap();
+
+        assert!((last - Vec2::new(20.0, 10.0)).length() < 1e-4);
+    }
+}
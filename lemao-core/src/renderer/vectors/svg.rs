@@ -0,0 +1,485 @@
+use super::path;
+use super::path::Subpath;
+use super::*;
+use crate::error::CoreError;
+use lemao_math::mat3x3::Mat3x3;
+use std::collections::HashMap;
+use std::f32::consts;
+use std::fs;
+
+const ELLIPSE_SEGMENTS: usize = 64;
+const CORNER_SEGMENTS: usize = 8;
+const SKIPPED_ELEMENTS: [&str; 8] = ["defs", "clipPath", "mask", "symbol", "pattern", "marker", "linearGradient", "radialGradient"];
+
+#[derive(Clone, Debug)]
+struct Style {
+    fill: Option<SolidColor>,
+    fill_rule: FillRule,
+    fill_opacity: f32,
+    stroke: Option<SolidColor>,
+    stroke_width: f32,
+    stroke_opacity: f32,
+    opacity: f32,
+    transform: Mat3x3,
+}
+
+struct Element {
+    name: String,
+    attributes: HashMap<String, String>,
+    closing: bool,
+    self_closing: bool,
+    offset: usize,
+}
+
+pub fn load(path: &str) -> Result<RawVectorImage, CoreError> {
+    let svg = match fs::read_to_string(path) {
+        Ok(svg) => svg,
+        Err(error) => return Err(CoreError::Io { path: path.to_string(), message: error.to_string() }),
+    };
+
+    parse(&svg)
+}
+
+pub fn parse(svg: &str) -> Result<RawVectorImage, CoreError> {
+    //////////////////////////////////////////////////////////////////
+    // SVG specification: https://www.w3.org/TR/SVG11/Overview.html //
+    //////////////////////////////////////////////////////////////////
+
+    let elements = read_elements(svg)?;
+    let mut image: Option<RawVectorImage> = None;
+    let mut styles: Vec<Style> = Vec::new();
+    let mut skip_depth = 0;
+
+    for element in elements {
+        if element.closing {
+            if skip_depth > 0 {
+                skip_depth -= 1;
+            } else {
+                styles.pop();
+            }
+
+            continue;
+        }
+
+        if skip_depth > 0 || SKIPPED_ELEMENTS.contains(&element.name.as_str()) {
+            if !element.self_closing {
+                skip_depth += 1;
+            }
+
+            continue;
+        }
+
+        let style = match (styles.last(), &mut image) {
+            (Some(parent), Some(image)) => {
+                let style = get_style(&element, parent)?;
+                add_element(image, &element, &style)?;
+
+                style
+            }
+            _ => {
+                if element.name != "svg" {
+                    return Err(CoreError::parse("Invalid file, root element must be svg", Some(element.offset)));
+                }
+
+                let (size, root_transform) = get_viewport(&element)?;
+                let root_style = Style {
+                    fill: Some(SolidColor::new(0.0, 0.0, 0.0, 1.0)),
+                    fill_rule: FillRule::NonZero,
+                    fill_opacity: 1.0,
+                    stroke: None,
+                    stroke_width: 1.0,
+                    stroke_opacity: 1.0,
+                    opacity: 1.0,
+                    transform: root_transform,
+                };
+
+                image = Some(RawVectorImage::new(size));
+                get_style(&element, &root_style)?
+            }
+        };
+
+        if !element.self_closing {
+            styles.push(style);
+        }
+    }
+
+    match image {
+        Some(image) => Ok(image),
+        None => Err(CoreError::parse("Invalid file, svg element not found", None)),
+    }
+}
+
+fn read_elements(svg: &str) -> Result<Vec<Element>, CoreError> {
+    let mut elements = Vec::new();
+    let mut position = 0;
+
+    while let Some(start) = svg[position..].find('<').map(|p| p + position) {
+        let rest = &svg[start..];
+
+        // Declarations, comments and CDATA don't affect the geometry
+        let skip_to = if rest.starts_with("<!--") {
+            Some("-->")
+        } else if rest.starts_with("<![CDATA[") {
+            Some("]]>")
+        } else if rest.starts_with("<?") {
+            Some("?>")
+        } else if rest.starts_with("<!") {
+            Some(">")
+        } else {
+            None
+        };
+
+        if let Some(skip_to) = skip_to {
+            position = match rest.find(skip_to) {
+                Some(end) => start + end + skip_to.len(),
+                None => return Err(CoreError::parse("Invalid file, unterminated markup", Some(start))),
+            };
+            continue;
+        }
+
+        let (element, end) = read_element(svg, start)?;
+        elements.push(element);
+        position = end;
+    }
+
+    Ok(elements)
+}
+
+fn read_element(svg: &str, start: usize) -> Result<(Element, usize), CoreError> {
+    let bytes = svg.as_bytes();
+    let mut position = start + 1;
+    let closing = bytes.get(position) == Some(&b'/');
+
+    if closing {
+        position += 1;
+    }
+
+    let name_start = position;
+    while position < bytes.len() && !bytes[position].is_ascii_whitespace() && bytes[position] != b'>' && bytes[position] != b'/' {
+        position += 1;
+    }
+
+    let name = svg[name_start..position].to_string();
+    let mut attributes = HashMap::new();
+
+    loop {
+        while position < bytes.len() && bytes[position].is_ascii_whitespace() {
+            position += 1;
+        }
+
+        match bytes.get(position) {
+            Some(b'>') => {
+                return Ok((Element { name, attributes, closing, self_closing: false, offset: start }, position + 1));
+            }
+            Some(b'/') if bytes.get(position + 1) == Some(&b'>') => {
+                return Ok((Element { name, attributes, closing, self_closing: true, offset: start }, position + 2));
+            }
+            Some(_) => {
+                let attribute_start = position;
+                while position < bytes.len() && bytes[position] != b'=' && !bytes[position].is_ascii_whitespace() && bytes[position] != b'>' {
+                    position += 1;
+                }
+
+                let attribute_name = svg[attribute_start..position].trim().to_string();
+
+                while position < bytes.len() && (bytes[position].is_ascii_whitespace() || bytes[position] == b'=') {
+                    position += 1;
+                }
+
+                let quote = match bytes.get(position) {
+                    Some(quote @ (b'"' | b'\'')) => *quote,
+                    _ => return Err(CoreError::parse("Invalid file, expected quoted attribute value", Some(position))),
+                };
+
+                let value_start = position + 1;
+                let value_end = match bytes[value_start..].iter().position(|p| *p == quote) {
+                    Some(length) => value_start + length,
+                    None => return Err(CoreError::parse("Invalid file, unterminated attribute value", Some(position))),
+                };
+
+                attributes.insert(attribute_name, svg[value_start..value_end].to_string());
+                position = value_end + 1;
+            }
+            None => return Err(CoreError::parse("Invalid file, unterminated element", Some(start))),
+        }
+    }
+}
+
+fn get_viewport(element: &Element) -> Result<(Vec2, Mat3x3), CoreError> {
+    let view_box = match element.attributes.get("viewBox") {
+        Some(view_box) => {
+            let values = parse_numbers(view_box, element.offset)?;
+            if values.len() != 4 || values[2] <= 0.0 || values[3] <= 0.0 {
+                return Err(CoreError::parse("Invalid file, viewBox must have 4 values with positive size", Some(element.offset)));
+            }
+
+            Some((Vec2::new(values[0], values[1]), Vec2::new(values[2], values[3])))
+        }
+        None => None,
+    };
+
+    let width = element.attributes.get("width").map(|p| parse_length(p, element.offset)).transpose()?;
+    let height = element.attributes.get("height").map(|p| parse_length(p, element.offset)).transpose()?;
+    let size = match (width, height, view_box) {
+        (Some(width), Some(height), _) => Vec2::new(width, height),
+        (_, _, Some((_, view_box_size))) => Vec2::new(width.unwrap_or(view_box_size.x), height.unwrap_or(view_box_size.y)),
+        _ => return Err(CoreError::parse("Invalid file, size of the image is not defined", Some(element.offset))),
+    };
+
+    // SVG Y axis points down, so the whole image is flipped to match the renderer coordinates
+    let flip = Mat3x3::translate(Vec2::new(0.0, size.y)) * Mat3x3::scale(Vec2::new(1.0, -1.0));
+    let transform = match view_box {
+        Some((position, view_box_size)) => {
+            let scale = size / view_box_size;
+            flip * Mat3x3::scale(scale) * Mat3x3::translate(-position)
+        }
+        None => flip,
+    };
+
+    Ok((size, transform))
+}
+
+fn get_style(element: &Element, parent: &Style) -> Result<Style, CoreError> {
+    let mut style = parent.clone();
+
+    // Opacity isn't inherited directly, but multiplies with the parent one
+    style.opacity = 1.0;
+
+    let mut properties = element.attributes.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect::<Vec<(&str, &str)>>();
+    if let Some(inline) = element.attributes.get("style") {
+        for declaration in inline.split(';') {
+            if let Some((name, value)) = declaration.split_once(':') {
+                properties.push((name.trim(), value.trim()));
+            }
+        }
+    }
+
+    for (name, value) in properties {
+        match name {
+            "fill" => style.fill = parse_color(value, element.offset)?,
+            "fill-rule" => style.fill_rule = if value == "evenodd" { FillRule::EvenOdd } else { FillRule::NonZero },
+            "fill-opacity" => style.fill_opacity = parse_number(value, element.offset)?,
+            "stroke" => style.stroke = parse_color(value, element.offset)?,
+            "stroke-width" => style.stroke_width = parse_length(value, element.offset)?,
+            "stroke-opacity" => style.stroke_opacity = parse_number(value, element.offset)?,
+            "opacity" => style.opacity = parse_number(value, element.offset)?,
+            "transform" => style.transform = style.transform * parse_transform(value, element.offset)?,
+            _ => {}
+        }
+    }
+
+    style.opacity *= parent.opacity;
+    Ok(style)
+}
+
+fn add_element(image: &mut RawVectorImage, element: &Element, style: &Style) -> Result<(), CoreError> {
+    let attribute = |name: &str| -> Result<f32, CoreError> { element.attributes.get(name).map(|p| parse_length(p, element.offset)).unwrap_or(Ok(0.0)) };
+
+    let subpaths = match element.name.as_str() {
+        "path" => path::parse(element.attributes.get("d").map(|p| p.as_str()).unwrap_or(""))?,
+        "rect" => {
+            let position = Vec2::new(attribute("x")?, attribute("y")?);
+            let size = Vec2::new(attribute("width")?, attribute("height")?);
+            let radius = match (element.attributes.contains_key("rx"), element.attributes.contains_key("ry")) {
+                (true, true) => Vec2::new(attribute("rx")?, attribute("ry")?),
+                (true, false) => Vec2::new(attribute("rx")?, attribute("rx")?),
+                (false, true) => Vec2::new(attribute("ry")?, attribute("ry")?),
+                (false, false) => Vec2::new(0.0, 0.0),
+            };
+
+            vec![get_rectangle(position, size, radius.clamp(Vec2::new(0.0, 0.0), size / 2.0))]
+        }
+        "circle" => {
+            let radius = attribute("r")?;
+            vec![get_ellipse(Vec2::new(attribute("cx")?, attribute("cy")?), Vec2::new(radius, radius))]
+        }
+        "ellipse" => vec![get_ellipse(Vec2::new(attribute("cx")?, attribute("cy")?), Vec2::new(attribute("rx")?, attribute("ry")?))],
+        "line" => vec![Subpath { points: vec![Vec2::new(attribute("x1")?, attribute("y1")?), Vec2::new(attribute("x2")?, attribute("y2")?)], closed: false }],
+        "polygon" | "polyline" => {
+            let values = parse_numbers(element.attributes.get("points").map(|p| p.as_str()).unwrap_or(""), element.offset)?;
+            let points = values.chunks_exact(2).map(|p| Vec2::new(p[0], p[1])).collect();
+
+            vec![Subpath { points, closed: element.name == "polygon" }]
+        }
+        _ => return Ok(()),
+    };
+
+    let contours = subpaths.iter().map(|p| p.points.iter().map(|p| style.transform.transform_point(*p)).collect()).collect::<Vec<Vec<Vec2>>>();
+
+    // Lines have no area, so they can only be stroked
+    if let Some(fill) = style.fill {
+        if element.name != "line" {
+            image.add_fill(&contours, style.fill_rule, get_color_with_opacity(fill, style.fill_opacity * style.opacity));
+        }
+    }
+
+    if let Some(stroke) = style.stroke {
+        // Non-uniform transformations would require the stroke to vary along the path, so the average scale is used instead
+        let width = style.stroke_width * style.transform.determinant().abs().sqrt();
+        let color = get_color_with_opacity(stroke, style.stroke_opacity * style.opacity);
+
+        for (subpath, contour) in subpaths.iter().zip(contours.iter()) {
+            image.add_stroke(contour, subpath.closed, width, color);
+        }
+    }
+
+    Ok(())
+}
+
+fn get_rectangle(position: Vec2, size: Vec2, radius: Vec2) -> Subpath {
+    if radius.x == 0.0 || radius.y == 0.0 {
+        let points = vec![position, position + Vec2::new(size.x, 0.0), position + size, position + Vec2::new(0.0, size.y)];
+        return Subpath { points, closed: true };
+    }
+
+    let centers = [position + Vec2::new(size.x - radius.x, radius.y), position + size - radius, position + Vec2::new(radius.x, size.y - radius.y), position + radius];
+
+    let mut points = Vec::new();
+    for (corner, center) in centers.iter().enumerate() {
+        for index in 0..=CORNER_SEGMENTS {
+            let angle = (corner as f32 - 1.0 + index as f32 / CORNER_SEGMENTS as f32) * consts::FRAC_PI_2;
+            points.push(*center + Vec2::new(angle.cos(), angle.sin()) * radius);
+        }
+    }
+
+    Subpath { points, closed: true }
+}
+
+fn get_ellipse(center: Vec2, radius: Vec2) -> Subpath {
+    let points = (0..ELLIPSE_SEGMENTS).map(|p| center + Vec2::new_from_angle(p as f32 / ELLIPSE_SEGMENTS as f32 * consts::TAU) * radius).collect();
+    Subpath { points, closed: true }
+}
+
+fn get_color_with_opacity(color: SolidColor, opacity: f32) -> SolidColor {
+    SolidColor::new(color.r, color.g, color.b, color.a * opacity.clamp(0.0, 1.0))
+}
+
+fn parse_number(value: &str, offset: usize) -> Result<f32, CoreError> {
+    match value.trim().parse::<f32>() {
+        Ok(number) if number.is_finite() => Ok(number),
+        _ => Err(CoreError::parse(&format!("Invalid file, {} is not a number", value), Some(offset))),
+    }
+}
+
+fn parse_numbers(value: &str, offset: usize) -> Result<Vec<f32>, CoreError> {
+    value.split(|p: char| p.is_ascii_whitespace() || p == ',').filter(|p| !p.is_empty()).map(|p| parse_number(p, offset)).collect()
+}
+
+fn parse_length(value: &str, offset: usize) -> Result<f32, CoreError> {
+    let value = value.trim();
+    let number = value.strip_suffix("px").unwrap_or(value);
+
+    if number.ends_with(|p: char| p.is_ascii_alphabetic() || p == '%') {
+        return Err(CoreError::UnsupportedFormat(format!("length unit in {}", value)));
+    }
+
+    parse_number(number, offset)
+}
+
+fn parse_color(value: &str, offset: usize) -> Result<Option<SolidColor>, CoreError> {
+    let value = value.trim();
+    let rgb = |r: u8, g: u8, b: u8| Ok(Some(SolidColor::new_rgb(r, g, b, 255)));
+
+    if value.starts_with('#') {
+        return match SolidColor::new_hex(value) {
+            Some(color) => Ok(Some(color)),
+            None => Err(CoreError::parse(&format!("Invalid file, {} is not a valid color", value), Some(offset))),
+        };
+    }
+
+    if let Some(arguments) = value.strip_prefix("rgb(").and_then(|p| p.strip_suffix(')')) {
+        let mut channels = Vec::new();
+        for channel in arguments.split(',') {
+            let channel = channel.trim();
+            channels.push(match channel.strip_suffix('%') {
+                Some(percent) => parse_number(percent, offset)? * 2.55,
+                None => parse_number(channel, offset)?,
+            });
+        }
+
+        return match channels.as_slice() {
+            [r, g, b] => rgb(r.clamp(0.0, 255.0) as u8, g.clamp(0.0, 255.0) as u8, b.clamp(0.0, 255.0) as u8),
+            _ => Err(CoreError::parse(&format!("Invalid file, {} is not a valid color", value), Some(offset))),
+        };
+    }
+
+    match value {
+        "none" | "transparent" => Ok(None),
+        "currentColor" => Ok(Some(SolidColor::BLACK)),
+        _ => match SolidColor::new_named(value) {
+            Some(color) => Ok(Some(color)),
+            None => Err(CoreError::UnsupportedFormat(format!("paint {}", value))),
+        },
+    }
+}
+
+fn parse_transform(value: &str, offset: usize) -> Result<Mat3x3, CoreError> {
+    let mut transform = Mat3x3::identity();
+
+    for function in value.split(')').map(|p| p.trim().trim_start_matches(',').trim()).filter(|p| !p.is_empty()) {
+        let (name, arguments) = match function.split_once('(') {
+            Some((name, arguments)) => (name.trim(), parse_numbers(arguments, offset)?),
+            None => return Err(CoreError::parse(&format!("Invalid file, {} is not a valid transform", function), Some(offset))),
+        };
+
+        let next = match (name, arguments.as_slice()) {
+            ("matrix", [a, b, c, d, e, f]) => Mat3x3::new_affine(*a, *b, *c, *d, *e, *f),
+            ("translate", [x]) => Mat3x3::translate(Vec2::new(*x, 0.0)),
+            ("translate", [x, y]) => Mat3x3::translate(Vec2::new(*x, *y)),
+            ("scale", [x]) => Mat3x3::scale(Vec2::new(*x, *x)),
+            ("scale", [x, y]) => Mat3x3::scale(Vec2::new(*x, *y)),
+            ("rotate", [angle]) => Mat3x3::rotate(angle.to_radians()),
+            ("rotate", [angle, x, y]) => Mat3x3::translate(Vec2::new(*x, *y)) * Mat3x3::rotate(angle.to_radians()) * Mat3x3::translate(Vec2::new(-x, -y)),
+            ("skewX", [angle]) => Mat3x3::skew(Vec2::new(angle.to_radians(), 0.0)),
+            ("skewY", [angle]) => Mat3x3::skew(Vec2::new(0.0, angle.to_radians())),
+            _ => return Err(CoreError::parse(&format!("Invalid file, {}) is not a valid transform", function), Some(offset))),
+        };
+
+        transform = transform * next;
+    }
+
+    Ok(transform)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_point_eq(a: Vec2, b: Vec2) {
+        assert!((a - b).length() < 1e-4, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn transform_functions() {
+        let point = Vec2::new(1.0, 2.0);
+
+        assert_point_eq(parse_transform("matrix(1 2 3 4 5 6)", 0).unwrap().transform_point(point), Vec2::new(12.0, 16.0));
+        assert_point_eq(parse_transform("translate(5)", 0).unwrap().transform_point(point), Vec2::new(6.0, 2.0));
+        assert_point_eq(parse_transform("translate(5, -1) scale(2)", 0).unwrap().transform_point(point), Vec2::new(7.0, 3.0));
+        assert_point_eq(parse_transform("rotate(90)", 0).unwrap().transform_point(point), Vec2::new(-2.0, 1.0));
+        assert_point_eq(parse_transform("rotate(90 1 1)", 0).unwrap().transform_point(point), Vec2::new(0.0, 1.0));
+        assert_point_eq(parse_transform("skewX(45)", 0).unwrap().transform_point(point), Vec2::new(3.0, 2.0));
+        assert_point_eq(parse_transform("skewY(45)", 0).unwrap().transform_point(point), Vec2::new(1.0, 3.0));
+    }
+
+    #[test]
+    fn viewport_flip() {
+        let svg = r#"<svg width="200" height="100" viewBox="10 10 100 50"><rect x="10" y="10" width="100" height="50"/></svg>"#;
+        let image = parse(svg).unwrap();
+
+        assert_eq!(image.size, Vec2::new(200.0, 100.0));
+        for vertex in &image.vertices {
+            assert!(vertex.position.x >= -1e-4 && vertex.position.x <= 200.0 + 1e-4);
+            assert!(vertex.position.y >= -1e-4 && vertex.position.y <= 100.0 + 1e-4);
+        }
+    }
+
+    #[test]
+    fn named_colors() {
+        assert_eq!(parse_color("red", 0).unwrap(), Some(SolidColor::RED));
+        assert_eq!(parse_color("orange", 0).unwrap(), Some(SolidColor::new_rgb(255, 165, 0, 255)));
+        assert_eq!(parse_color("currentColor", 0).unwrap(), Some(SolidColor::BLACK));
+        assert_eq!(parse_color("none", 0).unwrap(), None);
+        assert!(parse_color("chartreuse-ish", 0).is_err());
+    }
+}
@@ -1,16 +1,29 @@
+use crate::error::CoreError;
 use std::collections::HashMap;
 use std::collections::VecDeque;
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::marker::PhantomData;
 
 pub struct Storage<T> {
     data: Vec<Option<T>>,
-    name_to_id_hashmap: HashMap<String, usize>,
+    generations: Vec<u32>,
+    name_to_id_hashmap: HashMap<String, Handle<T>>,
     id_to_name_hashmap: HashMap<usize, String>,
     removed_ids: VecDeque<usize>,
 }
 
-pub trait StorageItem {
-    fn get_id(&self) -> usize;
-    fn set_id(&mut self, id: usize);
+// Generation is increased every time the slot is released, so handles to removed items never point at their successors
+pub struct Handle<T> {
+    index: usize,
+    generation: u32,
+    phantom: PhantomData<fn() -> T>,
+}
+
+pub trait StorageItem: Sized {
+    fn get_id(&self) -> Handle<Self>;
+    fn set_id(&mut self, id: Handle<Self>);
 
     fn get_name(&self) -> Option<String>;
     fn set_name(&mut self, name: Option<String>);
@@ -20,86 +33,87 @@ impl<T> Storage<T>
 where
     T: StorageItem,
 {
-    pub fn store(&mut self, mut item: T) -> usize {
+    pub fn store(&mut self, mut item: T) -> Handle<T> {
         let id = self.get_new_id();
         item.set_id(id);
-        self.data[id] = Some(item);
+        self.data[id.index] = Some(item);
 
         id
     }
 
-    pub fn store_with_name(&mut self, name: &str, mut item: T) -> Result<usize, String> {
+    pub fn store_with_name(&mut self, name: &str, mut item: T) -> Result<Handle<T>, CoreError> {
         if self.name_to_id_hashmap.contains_key(name) {
-            return Err("Name already exists".to_string());
+            return Err(CoreError::AlreadyExists { item: "Storage item", name: name.to_string() });
         }
 
         let id = self.get_new_id();
         item.set_id(id);
         item.set_name(Some(name.to_string()));
-        self.data[id] = Some(item);
+        self.data[id.index] = Some(item);
 
         self.name_to_id_hashmap.insert(name.to_string(), id);
-        self.id_to_name_hashmap.insert(id, name.to_string());
+        self.id_to_name_hashmap.insert(id.index, name.to_string());
 
         Ok(id)
     }
 
-    pub fn get(&self, id: usize) -> Result<&T, String> {
-        match self.data.get(id) {
-            Some(item) => Ok(item.as_ref().unwrap()),
-            None => Err(format!("Storage item {} not found", id)),
-        }
+    pub fn get(&self, id: Handle<T>) -> Result<&T, CoreError> {
+        self.check_id(id)?;
+
+        Ok(self.data[id.index].as_ref().unwrap())
     }
 
-    pub fn get_by_name(&self, name: &str) -> Result<&T, String> {
+    pub fn get_by_name(&self, name: &str) -> Result<&T, CoreError> {
         match self.name_to_id_hashmap.get(name) {
-            Some(id) => Ok(self.data[*id].as_ref().unwrap()),
-            None => Err(format!("Storage item {} not found", name)),
+            Some(id) => Ok(self.data[id.index].as_ref().unwrap()),
+            None => Err(CoreError::not_found_by_name("Storage item", name)),
         }
     }
 
-    pub fn get_mut(&mut self, id: usize) -> Result<&mut T, String> {
-        match self.data.get_mut(id) {
-            Some(item) => Ok(item.as_mut().unwrap()),
-            None => Err(format!("Storage item {} not found", id)),
-        }
+    pub fn get_mut(&mut self, id: Handle<T>) -> Result<&mut T, CoreError> {
+        self.check_id(id)?;
+
+        Ok(self.data[id.index].as_mut().unwrap())
     }
 
-    pub fn get_by_name_mut(&mut self, name: &str) -> Result<&mut T, String> {
-        match self.name_to_id_hashmap.get_mut(name) {
-            Some(id) => Ok(self.data[*id].as_mut().unwrap()),
-            None => Err(format!("Storage item {} not found", name)),
+    pub fn get_by_name_mut(&mut self, name: &str) -> Result<&mut T, CoreError> {
+        match self.name_to_id_hashmap.get(name) {
+            Some(id) => Ok(self.data[id.index].as_mut().unwrap()),
+            None => Err(CoreError::not_found_by_name("Storage item", name)),
         }
     }
 
-    pub fn remove(&mut self, id: usize) -> Result<(), String> {
-        if id >= self.data.len() || self.data[id].is_none() {
-            return Err(format!("Storage item {} not found", id));
+    pub fn get_id_by_name(&self, name: &str) -> Result<Handle<T>, CoreError> {
+        match self.name_to_id_hashmap.get(name) {
+            Some(id) => Ok(*id),
+            None => Err(CoreError::not_found_by_name("Storage item", name)),
         }
+    }
 
-        self.data[id] = None;
-        self.removed_ids.push_back(id);
+    pub fn contains(&self, id: Handle<T>) -> bool {
+        matches!(self.data.get(id.index), Some(Some(_))) && self.generations[id.index] == id.generation
+    }
 
-        if let Some(name) = self.id_to_name_hashmap.get(&id) {
-            self.name_to_id_hashmap.remove(name);
-            self.id_to_name_hashmap.remove(&id);
-        }
+    pub fn remove(&mut self, id: Handle<T>) -> Result<(), CoreError> {
+        self.check_id(id)?;
 
-        Ok(())
-    }
+        self.release(id.index);
 
-    pub fn remove_by_name(&mut self, name: &str) -> Result<(), String> {
-        if !self.name_to_id_hashmap.contains_key(name) {
-            return Err("Name doesn't exist".to_string());
+        if let Some(name) = self.id_to_name_hashmap.remove(&id.index) {
+            self.name_to_id_hashmap.remove(&name);
         }
 
-        let id = self.name_to_id_hashmap[name];
+        Ok(())
+    }
 
-        self.data[id] = None;
-        self.removed_ids.push_back(id);
+    pub fn remove_by_name(&mut self, name: &str) -> Result<(), CoreError> {
+        let id = match self.name_to_id_hashmap.remove(name) {
+            Some(id) => id,
+            None => return Err(CoreError::not_found_by_name("Storage item", name)),
+        };
 
-        self.name_to_id_hashmap.remove(name);
-        self.id_to_name_hashmap.remove(&id);
+        self.release(id.index);
+        self.id_to_name_hashmap.remove(&id.index);
 
         Ok(())
     }
@@ -112,18 +126,139 @@ where
         self.data.iter_mut().filter_map(|p| p.as_mut())
     }
 
-    fn get_new_id(&mut self) -> usize {
-        if let Some(id) = self.removed_ids.pop_front() {
-            id
+    fn get_new_id(&mut self) -> Handle<T> {
+        let index = if let Some(index) = self.removed_ids.pop_front() {
+            index
         } else {
             self.data.push(None);
+            self.generations.push(1);
             self.data.len() - 1
+        };
+
+        Handle::new(index, self.generations[index])
+    }
+
+    fn check_id(&self, id: Handle<T>) -> Result<(), CoreError> {
+        if self.contains(id) {
+            Ok(())
+        } else if id.index < self.generations.len() && id.generation != 0 && id.generation < self.generations[id.index] {
+            Err(CoreError::Removed { item: "Storage item", id: id.index, generation: id.generation })
+        } else {
+            Err(CoreError::not_found_by_id("Storage item", id.index))
         }
     }
+
+    fn release(&mut self, index: usize) {
+        self.data[index] = None;
+        self.generations[index] += 1;
+        self.removed_ids.push_back(index);
+    }
 }
 
 impl<T> Default for Storage<T> {
     fn default() -> Self {
-        Self { data: Default::default(), name_to_id_hashmap: Default::default(), id_to_name_hashmap: Default::default(), removed_ids: Default::default() }
+        Self {
+            data: Default::default(),
+            generations: Default::default(),
+            name_to_id_hashmap: Default::default(),
+            id_to_name_hashmap: Default::default(),
+            removed_ids: Default::default(),
+        }
+    }
+}
+
+impl<T> Handle<T> {
+    pub(crate) fn new(index: usize, generation: u32) -> Self {
+        Self { index, generation, phantom: PhantomData }
+    }
+
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    pub fn get_generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+// Zero generation is never assigned by the storage, so the default handle is always invalid
+impl<T> Default for Handle<T> {
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> Hash for Handle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Handle({}, {})", self.index, self.generation)
+    }
+}
+
+impl<T> fmt::Display for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.index, self.generation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Item {
+        id: Handle<Item>,
+        name: Option<String>,
+    }
+
+    impl StorageItem for Item {
+        fn get_id(&self) -> Handle<Self> {
+            self.id
+        }
+
+        fn set_id(&mut self, id: Handle<Self>) {
+            self.id = id;
+        }
+
+        fn get_name(&self) -> Option<String> {
+            self.name.clone()
+        }
+
+        fn set_name(&mut self, name: Option<String>) {
+            self.name = name;
+        }
+    }
+
+    #[test]
+    fn removed_handle() {
+        let mut storage = Storage::default();
+        let first = storage.store(Item { id: Default::default(), name: None });
+        storage.remove(first).unwrap();
+        let second = storage.store(Item { id: Default::default(), name: None });
+
+        assert_eq!(storage.get(second).unwrap().get_id(), second);
+        assert_eq!(storage.get(first).err(), Some(CoreError::Removed { item: "Storage item", id: 0, generation: 1 }));
+        assert_eq!(storage.get(Default::default()).err(), Some(CoreError::not_found_by_id("Storage item", 0)));
     }
 }
@@ -1,8 +1,10 @@
+use crate::error::CoreError;
 use crate::renderer::cameras::Camera;
 use crate::renderer::context::RendererContext;
 use lemao_common_platform::input::InputEvent;
 use lemao_common_platform::input::Key;
 use lemao_common_platform::input::MouseButton;
+use lemao_common_platform::renderer::ContextOptions;
 use lemao_common_platform::window::WindowPlatformSpecific;
 use lemao_common_platform::window::WindowStyle;
 use lemao_math::vec2::Vec2;
@@ -19,12 +21,12 @@ pub enum CoordinationSystem<'a> {
 }
 
 impl WindowContext {
-    pub fn new(title: &str, style: WindowStyle) -> Result<Self, String> {
+    pub fn new(title: &str, style: WindowStyle, options: ContextOptions) -> Result<Self, CoreError> {
         #[cfg(windows)]
-        return Ok(Self { window: lemao_windows_winapi::window::WindowWinAPI::new(title, style)?, events: VecDeque::new() });
+        return Ok(Self { window: lemao_windows_winapi::window::WindowWinAPI::new(title, style, options).map_err(CoreError::Platform)?, events: VecDeque::new() });
 
         #[cfg(unix)]
-        return Ok(Self { window: lemao_linux_x11::window::WindowX11::new(title, style)?, events: VecDeque::new() });
+        return Ok(Self { window: lemao_linux_x11::window::WindowX11::new(title, style, options).map_err(CoreError::Platform)?, events: VecDeque::new() });
     }
 
     pub fn poll_event(&mut self) -> Option<InputEvent> {
@@ -36,8 +38,8 @@ impl WindowContext {
         self.events.push_back(event);
     }
 
-    pub fn create_renderer(&mut self) -> Result<RendererContext, String> {
-        let renderer_platform_specific = self.window.create_renderer()?;
+    pub fn create_renderer(&mut self) -> Result<RendererContext, CoreError> {
+        let renderer_platform_specific = self.window.create_renderer().map_err(CoreError::Platform)?;
         let mut renderer = RendererContext::new(renderer_platform_specific, self.window.get_size())?;
         renderer.init()?;
 
@@ -52,12 +54,16 @@ impl WindowContext {
         self.window.get_size()
     }
 
+    pub fn get_scale_factor(&self) -> f32 {
+        self.window.get_scale_factor()
+    }
+
     pub fn get_style(&self) -> WindowStyle {
         self.window.get_style()
     }
 
-    pub fn set_style(&mut self, style: WindowStyle) -> Result<(), String> {
-        self.window.set_style(style)
+    pub fn set_style(&mut self, style: WindowStyle) -> Result<(), CoreError> {
+        self.window.set_style(style).map_err(CoreError::Platform)
     }
 
     pub fn swap_buffers(&self) {
@@ -81,7 +87,7 @@ impl WindowContext {
 
         match coordination_system {
             CoordinationSystem::Window => Vec2::new(cursor_position.x, cursor_position.y),
-            CoordinationSystem::Camera(camera) => Vec2::new(cursor_position.x, cursor_position.y) + camera.position,
+            CoordinationSystem::Camera(camera) => Vec2::new(cursor_position.x, cursor_position.y) / camera.scale_factor + camera.position,
         }
     }
 
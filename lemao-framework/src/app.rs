@@ -1,10 +1,13 @@
+use crate::error::FrameworkError;
 use crate::utils::storage::SceneStorage;
 use lemao_core::audio::context::AudioContext;
 use lemao_core::lemao_common_platform::input::InputEvent;
+use lemao_core::lemao_common_platform::renderer::ContextOptions;
 use lemao_core::lemao_common_platform::window::WindowStyle;
 use lemao_core::renderer::context::RendererContext;
 use lemao_core::window::context::WindowContext;
 use std::any::Any;
+use std::error::Error;
 use std::sync::Arc;
 use std::sync::RwLock;
 use std::time::SystemTime;
@@ -28,10 +31,10 @@ pub struct Application<G> {
 }
 
 pub trait Scene<G> {
-    fn on_init(&mut self, app: &mut Application<G>) -> Result<(), String>;
-    fn on_activation(&mut self, app: &mut Application<G>) -> Result<(), String>;
-    fn on_deactivation(&mut self, app: &mut Application<G>) -> Result<(), String>;
-    fn on_tick(&mut self, app: &mut Application<G>) -> Result<(), String>;
+    fn on_init(&mut self, app: &mut Application<G>) -> Result<(), Box<dyn Error>>;
+    fn on_activation(&mut self, app: &mut Application<G>) -> Result<(), Box<dyn Error>>;
+    fn on_deactivation(&mut self, app: &mut Application<G>) -> Result<(), Box<dyn Error>>;
+    fn on_tick(&mut self, app: &mut Application<G>) -> Result<(), Box<dyn Error>>;
 
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
@@ -41,8 +44,8 @@ impl<G> Application<G>
 where
     G: Default,
 {
-    pub fn new(window_title: &str, window_style: WindowStyle) -> Result<Self, String> {
-        let mut window = WindowContext::new(window_title, window_style)?;
+    pub fn new(window_title: &str, window_style: WindowStyle, context_options: ContextOptions) -> Result<Self, FrameworkError> {
+        let mut window = WindowContext::new(window_title, window_style, context_options)?;
         let mut renderer = window.create_renderer()?;
         renderer.set_swap_interval(1);
 
@@ -65,7 +68,7 @@ where
         })
     }
 
-    pub fn register_scene(mut self, name: &str, scene_factory: fn(&mut Self) -> Box<dyn Scene<G>>, default: bool) -> Result<Self, String> {
+    pub fn register_scene(mut self, name: &str, scene_factory: fn(&mut Self) -> Box<dyn Scene<G>>, default: bool) -> Result<Self, FrameworkError> {
         if default {
             self.default_scene = name.to_string();
             self.pending_scene = name.to_string();
@@ -81,7 +84,7 @@ where
         Ok(self)
     }
 
-    pub fn run(mut self) -> Result<(), String> {
+    pub fn run(mut self) -> Result<(), FrameworkError> {
         let mut dt_timestamp = SystemTime::now();
         let mut first_iteration = true;
         self.current_scene = self.default_scene.clone();
@@ -106,7 +109,10 @@ where
             self.delta_time = dt_timestamp.elapsed().unwrap().as_secs_f32();
             dt_timestamp = SystemTime::now();
 
+            self.renderer.begin_frame();
             scene_storage_lock.get_mut(&self.current_scene)?.on_tick(&mut self)?;
+            self.renderer.debug_draw()?;
+            self.renderer.end_frame();
 
             if self.fps_timestamp.elapsed().unwrap().as_secs() >= 1 {
                 self.fps = self.fps_frames + 1;
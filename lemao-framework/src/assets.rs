@@ -1,5 +1,7 @@
+use crate::error::FrameworkError;
 use lemao_core::audio::samples::wav;
 use lemao_core::audio::samples::RawSample;
+use lemao_core::error::CoreError;
 use lemao_core::renderer::fonts::bff;
 use lemao_core::renderer::fonts::RawFont;
 use lemao_core::renderer::textures::bmp;
@@ -29,9 +31,9 @@ pub struct Asset<T> {
 }
 
 impl AssetsLoader {
-    pub fn set_queue(&mut self, path: &str) -> Result<(), String> {
+    pub fn set_queue(&mut self, path: &str) -> Result<(), FrameworkError> {
         if !Path::new(path).is_dir() {
-            return Err("Directory not found".to_string());
+            return Err(FrameworkError::NotFound { item: "Directory", id: None, name: Some(path.to_string()) });
         }
 
         self.add_to_queue(path);
@@ -68,7 +70,7 @@ impl AssetsLoader {
                     "wav" => {
                         samples.write().unwrap().push(Asset::new(name.to_str().unwrap().to_string(), wav::load(&asset_to_load)?));
                     }
-                    _ => return Err("Unsupported extension".to_string()),
+                    _ => return Err(CoreError::UnsupportedFormat(format!("extension of {}", asset_to_load))),
                 };
 
                 *loaded_assets.write().unwrap() += 1;
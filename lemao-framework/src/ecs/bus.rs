@@ -1,3 +1,5 @@
+use crate::error::FrameworkError;
+use std::any::type_name;
 use std::any::TypeId;
 use std::collections::hash_map::Iter;
 use std::collections::hash_map::IterMut;
@@ -20,22 +22,22 @@ where
         Self { queues: Default::default() }
     }
 
-    pub fn register_receiver<R>(&mut self) -> Result<(), String>
+    pub fn register_receiver<R>(&mut self) -> Result<(), FrameworkError>
     where
         R: 'static,
     {
         if self.queues.contains_key(&TypeId::of::<R>()) {
-            return Err("Receiver already registered".to_string());
+            return Err(FrameworkError::AlreadyExists { item: "Receiver", name: type_name::<R>().to_string() });
         }
 
         self.queues.insert(TypeId::of::<R>(), VecDeque::new());
         Ok(())
     }
 
-    pub fn broadcast(&mut self, message: M) -> Result<(), String> {
+    pub fn broadcast(&mut self, message: M) -> Result<(), FrameworkError> {
         for queue in &mut self.queues {
             if queue.1.len() > MAX_EVENTS_PER_QUEUE {
-                return Err("Too many messages".to_string());
+                return Err(FrameworkError::CapacityExceeded { resource: "messages", capacity: MAX_EVENTS_PER_QUEUE });
             }
 
             queue.1.push_back(message);
@@ -44,25 +46,25 @@ where
         Ok(())
     }
 
-    pub fn send_to_1<R1>(&mut self, messsage: M) -> Result<(), String>
+    pub fn send_to_1<R1>(&mut self, messsage: M) -> Result<(), FrameworkError>
     where
         R1: 'static,
     {
         match self.queues.get_mut(&TypeId::of::<R1>()) {
             Some(queue) => {
                 if queue.len() > MAX_EVENTS_PER_QUEUE {
-                    return Err("Too many messages".to_string());
+                    return Err(FrameworkError::CapacityExceeded { resource: "messages", capacity: MAX_EVENTS_PER_QUEUE });
                 }
 
                 queue.push_back(messsage);
             }
-            None => return Err("Received doesn't exist".to_string()),
+            None => return Err(FrameworkError::NotFound { item: "Receiver", id: None, name: Some(type_name::<R1>().to_string()) }),
         }
 
         Ok(())
     }
 
-    pub fn send_to_2<R1, R2>(&mut self, messsage: M) -> Result<(), String>
+    pub fn send_to_2<R1, R2>(&mut self, messsage: M) -> Result<(), FrameworkError>
     where
         R1: 'static,
         R2: 'static,
@@ -72,7 +74,7 @@ where
         Ok(())
     }
 
-    pub fn send_to_3<R1, R2, R3>(&mut self, messsage: M) -> Result<(), String>
+    pub fn send_to_3<R1, R2, R3>(&mut self, messsage: M) -> Result<(), FrameworkError>
     where
         R1: 'static,
         R2: 'static,
@@ -84,7 +86,7 @@ where
         Ok(())
     }
 
-    pub fn send_to_4<R1, R2, R3, R4>(&mut self, messsage: M) -> Result<(), String>
+    pub fn send_to_4<R1, R2, R3, R4>(&mut self, messsage: M) -> Result<(), FrameworkError>
     where
         R1: 'static,
         R2: 'static,
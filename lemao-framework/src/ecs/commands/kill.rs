@@ -1,5 +1,6 @@
 use super::Command;
 use crate::ecs::world::World;
+use crate::error::FrameworkError;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct KillCommand {
@@ -13,12 +14,12 @@ impl KillCommand {
 }
 
 impl<G, S, M> Command<G, S, M> for KillCommand {
-    fn execute(self: Box<Self>, world: &mut World<G, S, M>) -> Result<(), String>
+    fn execute(self: Box<Self>, world: &mut World<G, S, M>) -> Result<(), FrameworkError>
     where
         M: Copy,
     {
         if !world.entities.contains(self.entity_id) {
-            return Err("Entity not found".to_string());
+            return Err(FrameworkError::NotFound { item: "Entity", id: Some(self.entity_id), name: None });
         }
 
         world.entities.remove(self.entity_id)?;
@@ -1,11 +1,12 @@
 use super::world::World;
+use crate::error::FrameworkError;
 use std::collections::VecDeque;
 
 pub mod kill;
 pub mod spawn;
 
 pub trait Command<G, S, M> {
-    fn execute(self: Box<Self>, world: &mut World<G, S, M>) -> Result<(), String>
+    fn execute(self: Box<Self>, world: &mut World<G, S, M>) -> Result<(), FrameworkError>
     where
         M: Copy;
 }
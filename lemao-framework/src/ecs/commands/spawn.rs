@@ -1,6 +1,7 @@
 use super::Command;
 use crate::ecs::components::Component;
 use crate::ecs::world::World;
+use crate::error::FrameworkError;
 
 #[derive(Copy, Clone, Debug)]
 pub struct SpawnCommand<C>
@@ -24,7 +25,7 @@ impl<G, S, M, C> Command<G, S, M> for SpawnCommand<C>
 where
     C: Component + 'static,
 {
-    fn execute(self: Box<Self>, world: &mut World<G, S, M>) -> Result<(), String>
+    fn execute(self: Box<Self>, world: &mut World<G, S, M>) -> Result<(), FrameworkError>
     where
         M: Copy,
     {
@@ -1,4 +1,5 @@
 use super::Component;
+use crate::error::FrameworkError;
 use std::any::Any;
 use std::slice::Iter;
 use std::slice::IterMut;
@@ -13,7 +14,7 @@ where
 
 pub trait ComponentListTrait {
     fn contains(&self, id: usize) -> bool;
-    fn remove(&mut self, entity_id: usize) -> Result<(), String>;
+    fn remove(&mut self, entity_id: usize) -> Result<(), FrameworkError>;
 
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
@@ -23,9 +24,9 @@ impl<C> ComponentList<C>
 where
     C: Component + 'static,
 {
-    pub fn store(&mut self, entity_id: usize, item: C) -> Result<(), String> {
+    pub fn store(&mut self, entity_id: usize, item: C) -> Result<(), FrameworkError> {
         if self.contains(entity_id) {
-            return Err("Component already exists".to_string());
+            return Err(FrameworkError::AlreadyExists { item: "Component", name: entity_id.to_string() });
         }
 
         self.data.push(item);
@@ -38,32 +39,32 @@ where
         Ok(())
     }
 
-    pub fn get(&self, entity_id: usize) -> Result<&C, String> {
+    pub fn get(&self, entity_id: usize) -> Result<&C, FrameworkError> {
         match self.id_lookup.get(entity_id) {
             Some(Some(component_id)) => match self.data.get(*component_id) {
                 Some(component) => Ok(component),
-                None => Err(format!("Component {} not found", entity_id)),
+                None => Err(FrameworkError::NotFound { item: "Component", id: Some(entity_id), name: None }),
             },
-            _ => Err(format!("Component {} not found", entity_id)),
+            _ => Err(FrameworkError::NotFound { item: "Component", id: Some(entity_id), name: None }),
         }
     }
 
-    pub fn get_first(&self) -> Result<&C, String> {
-        self.data.first().ok_or_else(|| "No component available".to_string())
+    pub fn get_first(&self) -> Result<&C, FrameworkError> {
+        self.data.first().ok_or(FrameworkError::NotFound { item: "Component", id: None, name: None })
     }
 
-    pub fn get_mut(&mut self, entity_id: usize) -> Result<&mut C, String> {
+    pub fn get_mut(&mut self, entity_id: usize) -> Result<&mut C, FrameworkError> {
         match self.id_lookup.get_mut(entity_id) {
             Some(Some(component_id)) => match self.data.get_mut(*component_id) {
                 Some(component) => Ok(component),
-                None => Err(format!("Component {} not found", entity_id)),
+                None => Err(FrameworkError::NotFound { item: "Component", id: Some(entity_id), name: None }),
             },
-            _ => Err(format!("Component {} not found", entity_id)),
+            _ => Err(FrameworkError::NotFound { item: "Component", id: Some(entity_id), name: None }),
         }
     }
 
-    pub fn get_mut_first(&mut self) -> Result<&mut C, String> {
-        self.data.first_mut().ok_or_else(|| "No component available".to_string())
+    pub fn get_mut_first(&mut self) -> Result<&mut C, FrameworkError> {
+        self.data.first_mut().ok_or(FrameworkError::NotFound { item: "Component", id: None, name: None })
     }
 
     pub fn iter(&self) -> Iter<C> {
@@ -91,9 +92,9 @@ where
         matches!(self.id_lookup.get(id), Some(Some(_)))
     }
 
-    fn remove(&mut self, entity_id: usize) -> Result<(), String> {
+    fn remove(&mut self, entity_id: usize) -> Result<(), FrameworkError> {
         if self.id_lookup[entity_id].is_none() {
-            return Err("Component not found".to_string());
+            return Err(FrameworkError::NotFound { item: "Component", id: Some(entity_id), name: None });
         }
 
         let component_index = self.id_lookup[entity_id].unwrap();
@@ -1,6 +1,8 @@
 use super::list::ComponentList;
 use super::list::ComponentListTrait;
 use super::Component;
+use crate::error::FrameworkError;
+use std::any::type_name;
 use std::any::TypeId;
 use std::collections::hash_map::Values;
 use std::collections::hash_map::ValuesMut;
@@ -16,12 +18,12 @@ pub struct ComponentManager {
 }
 
 impl ComponentManager {
-    pub fn store<C>(&mut self, item: Box<dyn ComponentListTrait>) -> Result<(), String>
+    pub fn store<C>(&mut self, item: Box<dyn ComponentListTrait>) -> Result<(), FrameworkError>
     where
         C: 'static,
     {
         if self.data.contains_key(&TypeId::of::<C>()) {
-            return Err("Storage item already exists".to_string());
+            return Err(FrameworkError::AlreadyExists { item: "Component list", name: type_name::<C>().to_string() });
         }
 
         self.data.insert(TypeId::of::<C>(), item);
@@ -35,47 +37,47 @@ impl ComponentManager {
         self.data.contains_key(&TypeId::of::<C>())
     }
 
-    pub fn get<C>(&self) -> Result<&dyn ComponentListTrait, String>
+    pub fn get<C>(&self) -> Result<&dyn ComponentListTrait, FrameworkError>
     where
         C: 'static,
     {
         match self.data.get(&TypeId::of::<C>()) {
             Some(item) => Ok(item.as_ref()),
-            _ => Err(format!("Component list {} not found", 0)),
+            _ => Err(FrameworkError::NotFound { item: "Component list", id: None, name: Some(type_name::<C>().to_string()) }),
         }
     }
 
-    pub fn get_and_cast<C>(&self) -> Result<&ComponentList<C>, String>
+    pub fn get_and_cast<C>(&self) -> Result<&ComponentList<C>, FrameworkError>
     where
         C: Component + 'static,
     {
         match self.data.get(&TypeId::of::<C>()) {
             Some(item) => Ok(item.as_any().downcast_ref::<ComponentList<C>>().unwrap()),
-            _ => Err(format!("Component list {} not found", 0)),
+            _ => Err(FrameworkError::NotFound { item: "Component list", id: None, name: Some(type_name::<C>().to_string()) }),
         }
     }
 
-    pub fn get_mut<C>(&mut self) -> Result<&mut Box<dyn ComponentListTrait>, String>
+    pub fn get_mut<C>(&mut self) -> Result<&mut Box<dyn ComponentListTrait>, FrameworkError>
     where
         C: 'static,
     {
         match self.data.get_mut(&TypeId::of::<C>()) {
             Some(item) => Ok(item),
-            _ => Err(format!("Component list {} not found", 0)),
+            _ => Err(FrameworkError::NotFound { item: "Component list", id: None, name: Some(type_name::<C>().to_string()) }),
         }
     }
 
-    pub fn get_and_cast_mut<C>(&mut self) -> Result<&mut ComponentList<C>, String>
+    pub fn get_and_cast_mut<C>(&mut self) -> Result<&mut ComponentList<C>, FrameworkError>
     where
         C: Component + 'static,
     {
         match self.data.get_mut(&TypeId::of::<C>()) {
             Some(item) => Ok(item.as_any_mut().downcast_mut::<ComponentList<C>>().unwrap()),
-            _ => Err(format!("Component list {} not found", 0)),
+            _ => Err(FrameworkError::NotFound { item: "Component list", id: None, name: Some(type_name::<C>().to_string()) }),
         }
     }
 
-    pub fn get_and_cast_mut_2<C1, C2>(&mut self) -> Result<ComponentTuple2<C1, C2>, String>
+    pub fn get_and_cast_mut_2<C1, C2>(&mut self) -> Result<ComponentTuple2<C1, C2>, FrameworkError>
     where
         C1: Component + 'static,
         C2: Component + 'static,
@@ -88,7 +90,7 @@ impl ComponentManager {
         }
     }
 
-    pub fn get_and_cast_mut_3<C1, C2, C3>(&mut self) -> Result<ComponentTuple3<C1, C2, C3>, String>
+    pub fn get_and_cast_mut_3<C1, C2, C3>(&mut self) -> Result<ComponentTuple3<C1, C2, C3>, FrameworkError>
     where
         C1: Component + 'static,
         C2: Component + 'static,
@@ -107,7 +109,7 @@ impl ComponentManager {
         }
     }
 
-    pub fn get_and_cast_mut_4<C1, C2, C3, C4>(&mut self) -> Result<ComponentTuple4<C1, C2, C3, C4>, String>
+    pub fn get_and_cast_mut_4<C1, C2, C3, C4>(&mut self) -> Result<ComponentTuple4<C1, C2, C3, C4>, FrameworkError>
     where
         C1: Component + 'static,
         C2: Component + 'static,
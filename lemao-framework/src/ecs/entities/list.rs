@@ -1,4 +1,5 @@
 use super::Entity;
+use crate::error::FrameworkError;
 use std::collections::VecDeque;
 use std::slice::Iter;
 use std::slice::IterMut;
@@ -21,17 +22,17 @@ impl EntityList {
         matches!(self.data.get(id), Some(Some(_)))
     }
 
-    pub fn get(&self, id: usize) -> Result<&Entity, String> {
+    pub fn get(&self, id: usize) -> Result<&Entity, FrameworkError> {
         match self.data.get(id) {
             Some(Some(item)) => Ok(item),
-            _ => Err(format!("Storage item {} not found", id)),
+            _ => Err(FrameworkError::NotFound { item: "Entity", id: Some(id), name: None }),
         }
     }
 
-    pub fn get_mut(&mut self, id: usize) -> Result<&mut Entity, String> {
+    pub fn get_mut(&mut self, id: usize) -> Result<&mut Entity, FrameworkError> {
         match self.data.get_mut(id) {
             Some(Some(item)) => Ok(item),
-            _ => Err(format!("Storage item {} not found", id)),
+            _ => Err(FrameworkError::NotFound { item: "Entity", id: Some(id), name: None }),
         }
     }
 
@@ -43,9 +44,9 @@ impl EntityList {
         self.data.iter_mut()
     }
 
-    pub fn remove(&mut self, id: usize) -> Result<(), String> {
+    pub fn remove(&mut self, id: usize) -> Result<(), FrameworkError> {
         if id >= self.data.len() || self.data[id].is_none() {
-            return Err(format!("Storage item {} not found", id));
+            return Err(FrameworkError::NotFound { item: "Entity", id: Some(id), name: None });
         }
 
         self.data[id] = None;
@@ -1,4 +1,5 @@
 use super::System;
+use crate::error::FrameworkError;
 use std::slice::Iter;
 use std::slice::IterMut;
 
@@ -12,7 +13,7 @@ impl<G, S, M> SystemList<G, S, M> {
         Self { data: Default::default() }
     }
 
-    pub fn store<T>(&mut self, item: Box<dyn System<G, S, M>>) -> Result<(), String>
+    pub fn store<T>(&mut self, item: Box<dyn System<G, S, M>>) -> Result<(), FrameworkError>
     where
         T: 'static,
     {
@@ -2,6 +2,7 @@ use std::any::TypeId;
 
 use super::world::World;
 use crate::app::Application;
+use std::error::Error;
 
 pub mod list;
 
@@ -24,5 +25,5 @@ where
 {
     fn get_stage(&self) -> SystemStage;
     fn get_type(&self) -> TypeId;
-    fn update(&mut self, app: &mut Application<G>, scene: &mut S, world: &mut World<G, S, M>) -> Result<(), String>;
+    fn update(&mut self, app: &mut Application<G>, scene: &mut S, world: &mut World<G, S, M>) -> Result<(), Box<dyn Error>>;
 }
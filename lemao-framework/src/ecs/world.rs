@@ -5,6 +5,7 @@ use super::entities::list::EntityList;
 use super::systems::list::SystemList;
 use super::systems::SystemStage;
 use crate::app::Application;
+use crate::error::FrameworkError;
 use std::fmt::Debug;
 use std::sync::Arc;
 use std::sync::RwLock;
@@ -26,7 +27,7 @@ impl<G, S, M> World<G, S, M>
 where
     M: Copy + Debug,
 {
-    pub fn update(&mut self, app: &mut Application<G>, scene: &mut S) -> Result<(), String> {
+    pub fn update(&mut self, app: &mut Application<G>, scene: &mut S) -> Result<(), FrameworkError> {
         let systems = self.systems.clone();
         let mut systems = systems.write().unwrap();
 
@@ -0,0 +1,57 @@
+use lemao_core::error::CoreError;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum FrameworkError {
+    Core(CoreError),
+    NotFound { item: &'static str, id: Option<usize>, name: Option<String> },
+    AlreadyExists { item: &'static str, name: String },
+    CapacityExceeded { resource: &'static str, capacity: usize },
+    InvalidCast { name: String },
+    // Errors returned by scenes and systems, which can come from any crate used by the application
+    Application(Box<dyn Error>),
+}
+
+impl fmt::Display for FrameworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Core(error) => write!(f, "{}", error),
+            Self::NotFound { item, id: Some(id), name: _ } => write!(f, "{} {} not found", item, id),
+            Self::NotFound { item, id: None, name: Some(name) } => write!(f, "{} {} not found", item, name),
+            Self::NotFound { item, id: None, name: None } => write!(f, "{} not found", item),
+            Self::AlreadyExists { item, name } => write!(f, "{} {} already exists", item, name),
+            Self::CapacityExceeded { resource, capacity } => write!(f, "Too many {}, capacity is {}", resource, capacity),
+            Self::InvalidCast { name } => write!(f, "Storage item {} cannot be downcasted", name),
+            Self::Application(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for FrameworkError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Core(error) => Some(error),
+            Self::Application(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<CoreError> for FrameworkError {
+    fn from(error: CoreError) -> Self {
+        Self::Core(error)
+    }
+}
+
+impl From<Box<dyn Error>> for FrameworkError {
+    fn from(error: Box<dyn Error>) -> Self {
+        Self::Application(error)
+    }
+}
+
+impl From<FrameworkError> for String {
+    fn from(error: FrameworkError) -> Self {
+        error.to_string()
+    }
+}
@@ -3,4 +3,5 @@
 pub mod app;
 pub mod assets;
 pub mod ecs;
+pub mod error;
 pub mod utils;
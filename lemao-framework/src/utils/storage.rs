@@ -1,4 +1,5 @@
 use crate::app::Scene;
+use crate::error::FrameworkError;
 use std::collections::hash_map::Values;
 use std::collections::hash_map::ValuesMut;
 use std::collections::HashMap;
@@ -13,32 +14,32 @@ impl<G> SceneStorage<G> {
         self.data.insert(name.to_string(), item);
     }
 
-    pub fn get(&self, name: &str) -> Result<&dyn Scene<G>, String> {
+    pub fn get(&self, name: &str) -> Result<&dyn Scene<G>, FrameworkError> {
         match self.data.get(&name.to_string()) {
             Some(item) => Ok(item.as_ref()),
-            None => Err(format!("Storage item {} not found", name)),
+            None => Err(FrameworkError::NotFound { item: "Scene", id: None, name: Some(name.to_string()) }),
         }
     }
 
-    pub fn get_and_cast<C>(&self, name: &str) -> Result<&C, String>
+    pub fn get_and_cast<C>(&self, name: &str) -> Result<&C, FrameworkError>
     where
         C: 'static,
     {
-        self.get(name)?.as_any().downcast_ref::<C>().ok_or_else(|| format!("Storage item {} cannot be downcasted", name))
+        self.get(name)?.as_any().downcast_ref::<C>().ok_or_else(|| FrameworkError::InvalidCast { name: name.to_string() })
     }
 
-    pub fn get_mut(&mut self, name: &str) -> Result<&mut dyn Scene<G>, String> {
+    pub fn get_mut(&mut self, name: &str) -> Result<&mut dyn Scene<G>, FrameworkError> {
         match self.data.get_mut(&name.to_string()) {
             Some(drawable) => Ok(drawable.as_mut()),
-            None => Err(format!("Storage item {} not found", name)),
+            None => Err(FrameworkError::NotFound { item: "Scene", id: None, name: Some(name.to_string()) }),
         }
     }
 
-    pub fn get_and_cast_mut<C>(&mut self, name: &str) -> Result<&mut C, String>
+    pub fn get_and_cast_mut<C>(&mut self, name: &str) -> Result<&mut C, FrameworkError>
     where
         C: 'static,
     {
-        self.get_mut(name)?.as_any_mut().downcast_mut::<C>().ok_or_else(|| format!("Storage item {} cannot be downcasted", name))
+        self.get_mut(name)?.as_any_mut().downcast_mut::<C>().ok_or_else(|| FrameworkError::InvalidCast { name: name.to_string() })
     }
 
     pub fn iter(&self) -> Values<String, Box<dyn Scene<G>>> {
@@ -49,9 +50,9 @@ impl<G> SceneStorage<G> {
         self.data.values_mut()
     }
 
-    pub fn remove(&mut self, name: &str) -> Result<(), String> {
+    pub fn remove(&mut self, name: &str) -> Result<(), FrameworkError> {
         if self.data.remove(&name.to_string()).is_none() {
-            return Err(format!("Storage item {} not found", name));
+            return Err(FrameworkError::NotFound { item: "Scene", id: None, name: Some(name.to_string()) });
         }
 
         Ok(())
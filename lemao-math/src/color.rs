@@ -1,6 +1,30 @@
 use std::ops::Add;
+use std::ops::Mul;
 use std::ops::Sub;
 
+const NAMED_COLORS: [(&str, u8, u8, u8); 20] = [
+    ("black", 0, 0, 0),
+    ("white", 255, 255, 255),
+    ("red", 255, 0, 0),
+    ("lime", 0, 255, 0),
+    ("green", 0, 128, 0),
+    ("blue", 0, 0, 255),
+    ("yellow", 255, 255, 0),
+    ("cyan", 0, 255, 255),
+    ("aqua", 0, 255, 255),
+    ("magenta", 255, 0, 255),
+    ("fuchsia", 255, 0, 255),
+    ("gray", 128, 128, 128),
+    ("grey", 128, 128, 128),
+    ("silver", 192, 192, 192),
+    ("maroon", 128, 0, 0),
+    ("olive", 128, 128, 0),
+    ("navy", 0, 0, 128),
+    ("purple", 128, 0, 128),
+    ("teal", 0, 128, 128),
+    ("orange", 255, 165, 0),
+];
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
 pub struct SolidColor {
@@ -11,7 +35,20 @@ pub struct SolidColor {
 }
 
 impl SolidColor {
-    pub fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+    pub const TRANSPARENT: Self = Self::new(0.0, 0.0, 0.0, 0.0);
+    pub const BLACK: Self = Self::new(0.0, 0.0, 0.0, 1.0);
+    pub const WHITE: Self = Self::new(1.0, 1.0, 1.0, 1.0);
+    pub const GRAY: Self = Self::new(0.5, 0.5, 0.5, 1.0);
+    pub const RED: Self = Self::new(1.0, 0.0, 0.0, 1.0);
+    pub const GREEN: Self = Self::new(0.0, 1.0, 0.0, 1.0);
+    pub const BLUE: Self = Self::new(0.0, 0.0, 1.0, 1.0);
+    pub const YELLOW: Self = Self::new(1.0, 1.0, 0.0, 1.0);
+    pub const CYAN: Self = Self::new(0.0, 1.0, 1.0, 1.0);
+    pub const MAGENTA: Self = Self::new(1.0, 0.0, 1.0, 1.0);
+    pub const ORANGE: Self = Self::new(1.0, 0.5, 0.0, 1.0);
+    pub const PURPLE: Self = Self::new(0.5, 0.0, 0.5, 1.0);
+
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
         Self { r, g, b, a }
     }
 
@@ -19,9 +56,131 @@ impl SolidColor {
         Self { r: (r as f32) / 255.0, g: (g as f32) / 255.0, b: (b as f32) / 255.0, a: (a as f32) / 255.0 }
     }
 
+    // Accepts #RGB, #RGBA, #RRGGBB and #RRGGBBAA, the leading hash is optional
+    pub fn new_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim();
+        let digits = hex.strip_prefix('#').unwrap_or(hex).chars().map(|p| p.to_digit(16).map(|p| p as u8)).collect::<Option<Vec<u8>>>()?;
+
+        match digits.as_slice() {
+            [r, g, b] => Some(Self::new_rgb(r * 17, g * 17, b * 17, 255)),
+            [r, g, b, a] => Some(Self::new_rgb(r * 17, g * 17, b * 17, a * 17)),
+            [r1, r2, g1, g2, b1, b2] => Some(Self::new_rgb(r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2, 255)),
+            [r1, r2, g1, g2, b1, b2, a1, a2] => Some(Self::new_rgb(r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2, a1 * 16 + a2)),
+            _ => None,
+        }
+    }
+
+    // Basic CSS color keywords, names are case-insensitive
+    pub fn new_named(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+        NAMED_COLORS.iter().find(|p| p.0 == name).map(|p| Self::new_rgb(p.1, p.2, p.3, 255))
+    }
+
+    // Hue is in degrees, saturation and value in range 0.0 - 1.0
+    pub fn new_hsv(h: f32, s: f32, v: f32, a: f32) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let c = v * s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = Self::get_hue_components(h, c, x);
+        let m = v - c;
+
+        Self::new(r + m, g + m, b + m, a)
+    }
+
+    // Hue is in degrees, saturation and lightness in range 0.0 - 1.0
+    pub fn new_hsl(h: f32, s: f32, l: f32, a: f32) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = Self::get_hue_components(h, c, x);
+        let m = l - c / 2.0;
+
+        Self::new(r + m, g + m, b + m, a)
+    }
+
+    pub fn to_hex(&self) -> String {
+        let [r, g, b, a] = [self.r, self.g, self.b, self.a].map(|p| (p.clamp(0.0, 1.0) * 255.0).round() as u8);
+        format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+    }
+
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        let (max, min, hue) = self.get_hue();
+        let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+
+        (hue, saturation, max)
+    }
+
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let (max, min, hue) = self.get_hue();
+        let lightness = (max + min) / 2.0;
+        let saturation = if max == min { 0.0 } else { (max - min) / (1.0 - (2.0 * lightness - 1.0).abs()) };
+
+        (hue, saturation, lightness)
+    }
+
+    // Alpha is always linear, so only color channels are converted
+    pub fn to_linear(&self) -> Self {
+        let convert = |p: f32| if p <= 0.04045 { p / 12.92 } else { ((p + 0.055) / 1.055).powf(2.4) };
+        Self::new(convert(self.r), convert(self.g), convert(self.b), self.a)
+    }
+
+    pub fn to_srgb(&self) -> Self {
+        let convert = |p: f32| if p <= 0.0031308 { p * 12.92 } else { 1.055 * p.powf(1.0 / 2.4) - 0.055 };
+        Self::new(convert(self.r), convert(self.g), convert(self.b), self.a)
+    }
+
+    pub fn premultiplied(&self) -> Self {
+        Self::new(self.r * self.a, self.g * self.a, self.b * self.a, self.a)
+    }
+
+    pub fn unpremultiplied(&self) -> Self {
+        if self.a == 0.0 {
+            return Self::TRANSPARENT;
+        }
+
+        Self::new(self.r / self.a, self.g / self.a, self.b / self.a, self.a)
+    }
+
+    pub fn lerp(&self, other: Self, t: f32) -> Self {
+        Self::new(self.r + (other.r - self.r) * t, self.g + (other.g - self.g) * t, self.b + (other.b - self.b) * t, self.a + (other.a - self.a) * t)
+    }
+
+    pub fn with_alpha(&self, a: f32) -> Self {
+        Self::new(self.r, self.g, self.b, a)
+    }
+
     pub fn as_ptr(&self) -> *const f32 {
         self as *const _ as *const f32
     }
+
+    fn get_hue(&self) -> (f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let delta = max - min;
+
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == self.r {
+            60.0 * ((self.g - self.b) / delta).rem_euclid(6.0)
+        } else if max == self.g {
+            60.0 * ((self.b - self.r) / delta + 2.0)
+        } else {
+            60.0 * ((self.r - self.g) / delta + 4.0)
+        };
+
+        (max, min, hue)
+    }
+
+    fn get_hue_components(h: f32, c: f32, x: f32) -> (f32, f32, f32) {
+        match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        }
+    }
 }
 
 impl Add for SolidColor {
@@ -39,3 +198,103 @@ impl Sub for SolidColor {
         Self { r: (self.r - rhs.r).clamp(0.0, 1.0), g: (self.g - rhs.g).clamp(0.0, 1.0), b: (self.b - rhs.b).clamp(0.0, 1.0), a: (self.a - rhs.a).clamp(0.0, 1.0) }
     }
 }
+
+impl Mul for SolidColor {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self { r: self.r * rhs.r, g: self.g * rhs.g, b: self.b * rhs.b, a: self.a * rhs.a }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_named() {
+        assert_eq!(SolidColor::new_named("black"), Some(SolidColor::BLACK));
+        assert_eq!(SolidColor::new_named("White"), Some(SolidColor::WHITE));
+        assert_eq!(SolidColor::new_named("lime"), Some(SolidColor::new_rgb(0, 255, 0, 255)));
+        assert_eq!(SolidColor::new_named("grey"), SolidColor::new_named("gray"));
+        assert_eq!(SolidColor::new_named("unknown"), None);
+    }
+
+    fn assert_color_eq(a: SolidColor, b: SolidColor) {
+        assert!((a.r - b.r).abs() < 1e-4 && (a.g - b.g).abs() < 1e-4 && (a.b - b.b).abs() < 1e-4 && (a.a - b.a).abs() < 1e-4, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn new_hex() {
+        assert_eq!(SolidColor::new_hex("#F80"), Some(SolidColor::new_rgb(0xff, 0x88, 0x00, 0xff)));
+        assert_eq!(SolidColor::new_hex("F808"), Some(SolidColor::new_rgb(0xff, 0x88, 0x00, 0x88)));
+        assert_eq!(SolidColor::new_hex("#12AB34"), Some(SolidColor::new_rgb(0x12, 0xab, 0x34, 0xff)));
+        assert_eq!(SolidColor::new_hex(" #12ab34cd "), Some(SolidColor::new_rgb(0x12, 0xab, 0x34, 0xcd)));
+
+        assert_eq!(SolidColor::new_hex(""), None);
+        assert_eq!(SolidColor::new_hex("#12"), None);
+        assert_eq!(SolidColor::new_hex("#12345"), None);
+        assert_eq!(SolidColor::new_hex("#12345G"), None);
+        assert_eq!(SolidColor::new_hex("#123456789"), None);
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        for hex in ["#00000000", "#FFFFFFFF", "#12AB34CD", "#80808080"] {
+            assert_eq!(SolidColor::new_hex(hex).unwrap().to_hex(), hex);
+        }
+
+        assert_eq!(SolidColor::new(2.0, -1.0, 0.5, 1.0).to_hex(), "#FF0080FF");
+    }
+
+    #[test]
+    fn hsv_round_trip() {
+        for color in [SolidColor::RED, SolidColor::CYAN, SolidColor::ORANGE, SolidColor::new(0.2, 0.4, 0.9, 0.5), SolidColor::new(0.7, 0.1, 0.3, 1.0)] {
+            let (h, s, v) = color.to_hsv();
+            assert_color_eq(SolidColor::new_hsv(h, s, v, color.a), color);
+        }
+
+        assert_eq!(SolidColor::GRAY.to_hsv(), (0.0, 0.0, 0.5));
+        assert_color_eq(SolidColor::new_hsv(480.0, 1.0, 1.0, 1.0), SolidColor::GREEN);
+    }
+
+    #[test]
+    fn hsl_round_trip() {
+        for color in [SolidColor::BLUE, SolidColor::MAGENTA, SolidColor::PURPLE, SolidColor::new(0.2, 0.4, 0.9, 0.5), SolidColor::new(0.9, 0.8, 0.1, 1.0)] {
+            let (h, s, l) = color.to_hsl();
+            assert_color_eq(SolidColor::new_hsl(h, s, l, color.a), color);
+        }
+
+        assert_eq!(SolidColor::WHITE.to_hsl(), (0.0, 0.0, 1.0));
+        assert_color_eq(SolidColor::new_hsl(-120.0, 1.0, 0.5, 1.0), SolidColor::BLUE);
+    }
+
+    #[test]
+    fn linear_round_trip() {
+        for color in [SolidColor::BLACK, SolidColor::WHITE, SolidColor::new(0.01, 0.2, 0.5, 0.5), SolidColor::new(0.04045, 0.8, 0.99, 1.0)] {
+            assert_color_eq(color.to_linear().to_srgb(), color);
+            assert_color_eq(color.to_srgb().to_linear(), color);
+        }
+
+        assert_color_eq(SolidColor::new(0.5, 0.5, 0.5, 0.5).to_linear(), SolidColor::new(0.21404, 0.21404, 0.21404, 0.5));
+    }
+
+    #[test]
+    fn premultiplied_round_trip() {
+        let color = SolidColor::new(0.8, 0.4, 0.2, 0.5);
+
+        assert_color_eq(color.premultiplied(), SolidColor::new(0.4, 0.2, 0.1, 0.5));
+        assert_color_eq(color.premultiplied().unpremultiplied(), color);
+        assert_eq!(SolidColor::new(0.8, 0.4, 0.2, 0.0).unpremultiplied(), SolidColor::TRANSPARENT);
+    }
+
+    #[test]
+    fn lerp() {
+        let from = SolidColor::new(0.0, 0.2, 1.0, 0.0);
+        let to = SolidColor::new(1.0, 0.6, 0.0, 1.0);
+
+        assert_color_eq(from.lerp(to, 0.0), from);
+        assert_color_eq(from.lerp(to, 1.0), to);
+        assert_color_eq(from.lerp(to, 0.25), SolidColor::new(0.25, 0.3, 0.75, 0.25));
+    }
+}
@@ -0,0 +1,96 @@
+use super::rect::Rect;
+use crate::vec2::Vec2;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Circle {
+    pub center: Vec2,
+    pub radius: f32,
+}
+
+impl Circle {
+    pub fn new(center: Vec2, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    pub fn get_bounds(&self) -> Rect {
+        Rect::new(self.center - Vec2::new(self.radius, self.radius), Vec2::new(self.radius, self.radius) * 2.0)
+    }
+
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        self.center.distance(point) <= self.radius
+    }
+
+    pub fn overlaps(&self, circle: &Circle) -> bool {
+        self.center.distance(circle.center) <= self.radius + circle.radius
+    }
+
+    pub fn overlaps_rect(&self, rect: &Rect) -> bool {
+        rect.distance_to_point(self.center) <= self.radius
+    }
+
+    // Points inside the circle are returned without any change
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        if self.contains_point(point) {
+            return point;
+        }
+
+        self.center + (point - self.center).normalized() * self.radius
+    }
+
+    // Circles touching at a single point return it twice
+    pub fn intersection(&self, circle: &Circle) -> Option<(Vec2, Vec2)> {
+        ////////////////////////////////////////////////////////////////////////
+        // Circle intersection: https://paulbourke.net/geometry/circlesphere/ //
+        ////////////////////////////////////////////////////////////////////////
+
+        let distance = self.center.distance(circle.center);
+        if distance == 0.0 || distance > self.radius + circle.radius || distance < (self.radius - circle.radius).abs() {
+            return None;
+        }
+
+        let a = (self.radius * self.radius - circle.radius * circle.radius + distance * distance) / (2.0 * distance);
+        let h = (self.radius * self.radius - a * a).max(0.0).sqrt();
+        let direction = (circle.center - self.center) / distance;
+        let middle = self.center + direction * a;
+        let offset = Vec2::new(-direction.y, direction.x) * h;
+
+        Some((middle + offset, middle - offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_and_overlaps() {
+        let circle = Circle::new(Vec2::new(0.0, 0.0), 5.0);
+
+        assert!(circle.contains_point(Vec2::new(3.0, 4.0)));
+        assert!(!circle.contains_point(Vec2::new(4.0, 4.0)));
+        assert!(circle.overlaps(&Circle::new(Vec2::new(8.0, 0.0), 3.0)));
+        assert!(!circle.overlaps(&Circle::new(Vec2::new(8.0, 0.0), 2.9)));
+        assert!(circle.overlaps_rect(&Rect::new(Vec2::new(3.0, 3.0), Vec2::new(2.0, 2.0))));
+        assert!(!circle.overlaps_rect(&Rect::new(Vec2::new(4.0, 4.0), Vec2::new(2.0, 2.0))));
+        assert_eq!(circle.get_bounds(), Rect::new(Vec2::new(-5.0, -5.0), Vec2::new(10.0, 10.0)));
+    }
+
+    #[test]
+    fn closest_point() {
+        let circle = Circle::new(Vec2::new(1.0, 1.0), 2.0);
+
+        assert_eq!(circle.closest_point(Vec2::new(11.0, 1.0)), Vec2::new(3.0, 1.0));
+        assert_eq!(circle.closest_point(Vec2::new(1.5, 1.5)), Vec2::new(1.5, 1.5));
+    }
+
+    #[test]
+    fn intersection() {
+        let circle = Circle::new(Vec2::new(0.0, 0.0), 5.0);
+
+        assert_eq!(circle.intersection(&Circle::new(Vec2::new(8.0, 0.0), 5.0)), Some((Vec2::new(4.0, 3.0), Vec2::new(4.0, -3.0))));
+        assert_eq!(circle.intersection(&Circle::new(Vec2::new(10.0, 0.0), 5.0)), Some((Vec2::new(5.0, 0.0), Vec2::new(5.0, 0.0))));
+        assert_eq!(circle.intersection(&Circle::new(Vec2::new(20.0, 0.0), 5.0)), None);
+        assert_eq!(circle.intersection(&Circle::new(Vec2::new(0.0, 0.0), 5.0)), None);
+        assert_eq!(circle.intersection(&Circle::new(Vec2::new(1.0, 0.0), 1.0)), None);
+    }
+}
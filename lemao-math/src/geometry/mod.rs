@@ -0,0 +1,5 @@
+pub mod circle;
+pub mod polygon;
+pub mod ray;
+pub mod rect;
+pub mod segment;
@@ -0,0 +1,150 @@
+use super::rect::Rect;
+use super::segment::Segment;
+use crate::vec2::Vec2;
+
+// Convex polygon, points can be ordered both clockwise and counterclockwise
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polygon {
+    pub points: Vec<Vec2>,
+}
+
+impl Polygon {
+    pub fn new(points: Vec<Vec2>) -> Self {
+        Self { points }
+    }
+
+    pub fn get_bounds(&self) -> Option<Rect> {
+        Rect::new_from_points(&self.points)
+    }
+
+    pub fn get_edges(&self) -> impl Iterator<Item = Segment> + '_ {
+        (0..self.points.len()).map(|p| Segment::new(self.points[p], self.points[(p + 1) % self.points.len()]))
+    }
+
+    // Point is inside when it lies on the same side of every edge
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        if self.points.len() < 3 {
+            return false;
+        }
+
+        let mut positive = false;
+        let mut negative = false;
+
+        for edge in self.get_edges() {
+            let cross = (edge.to - edge.from).x * (point - edge.from).y - (edge.to - edge.from).y * (point - edge.from).x;
+            positive |= cross > 0.0;
+            negative |= cross < 0.0;
+
+            // NaN appears only for degenerate polygons, for which there is no inside at all
+            if (positive && negative) || cross.is_nan() {
+                return false;
+            }
+        }
+
+        // Polygons without area (all points at the same place or on a single line) don't contain anything
+        positive || negative
+    }
+
+    pub fn overlaps(&self, polygon: &Polygon) -> bool {
+        //////////////////////////////////////////////////////////////////////////////////////////
+        // Separating axis theorem: https://en.wikipedia.org/wiki/Hyperplane_separation_theorem //
+        //////////////////////////////////////////////////////////////////////////////////////////
+
+        if self.points.is_empty() || polygon.points.is_empty() {
+            return false;
+        }
+
+        for edge in self.get_edges().chain(polygon.get_edges()) {
+            let delta = edge.to - edge.from;
+            let axis = Vec2::new(-delta.y, delta.x);
+
+            let (min1, max1) = get_projection(&self.points, axis);
+            let (min2, max2) = get_projection(&polygon.points, axis);
+
+            if max1 < min2 || max2 < min1 {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    // Points inside the polygon are returned without any change
+    pub fn closest_point(&self, point: Vec2) -> Option<Vec2> {
+        if self.contains_point(point) {
+            return Some(point);
+        }
+
+        self.get_edges().map(|p| p.closest_point(point)).min_by(|a, b| a.distance(point).total_cmp(&b.distance(point)))
+    }
+}
+
+impl From<Rect> for Polygon {
+    fn from(value: Rect) -> Self {
+        Polygon::new(value.get_corners().to_vec())
+    }
+}
+
+fn get_projection(points: &[Vec2], axis: Vec2) -> (f32, f32) {
+    let mut min = f32::MAX;
+    let mut max = f32::MIN;
+
+    for point in points {
+        let projection = point.dot(axis);
+        min = min.min(projection);
+        max = max.max(projection);
+    }
+
+    (min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_point() {
+        let clockwise = Polygon::new(vec![Vec2::new(0.0, 0.0), Vec2::new(0.0, 10.0), Vec2::new(10.0, 0.0)]);
+        let counterclockwise = Polygon::new(vec![Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(0.0, 10.0)]);
+
+        for polygon in [clockwise, counterclockwise] {
+            assert!(polygon.contains_point(Vec2::new(2.0, 2.0)));
+            assert!(polygon.contains_point(Vec2::new(5.0, 5.0)));
+            assert!(!polygon.contains_point(Vec2::new(6.0, 6.0)));
+            assert!(!polygon.contains_point(Vec2::new(-1.0, 2.0)));
+        }
+
+        assert!(!Polygon::new(vec![Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0)]).contains_point(Vec2::new(5.0, 0.0)));
+    }
+
+    #[test]
+    fn overlaps() {
+        let square = Polygon::from(Rect::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0)));
+
+        assert!(square.overlaps(&Polygon::new(vec![Vec2::new(9.0, 0.0), Vec2::new(20.0, 0.0), Vec2::new(9.0, 8.0)])));
+        assert!(!square.overlaps(&Polygon::new(vec![Vec2::new(12.0, 0.0), Vec2::new(20.0, 0.0), Vec2::new(12.0, 8.0)])));
+
+        // Bounding boxes overlap here, but the triangle is separated by its hypotenuse
+        assert!(!square.overlaps(&Polygon::new(vec![Vec2::new(11.0, 11.0), Vec2::new(30.0, 11.0), Vec2::new(11.0, -8.5)])));
+        assert!(!square.overlaps(&Polygon::default()));
+    }
+
+    #[test]
+    fn closest_point() {
+        let triangle = Polygon::new(vec![Vec2::new(12.0, 0.0), Vec2::new(20.0, 0.0), Vec2::new(12.0, 8.0)]);
+
+        assert_eq!(triangle.closest_point(Vec2::new(20.0, 8.0)), Some(Vec2::new(16.0, 4.0)));
+        assert_eq!(triangle.closest_point(Vec2::new(13.0, 1.0)), Some(Vec2::new(13.0, 1.0)));
+        assert_eq!(Polygon::default().closest_point(Vec2::new(1.0, 1.0)), None);
+        assert_eq!(triangle.get_bounds(), Some(Rect::new(Vec2::new(12.0, 0.0), Vec2::new(8.0, 8.0))));
+    }
+
+    #[test]
+    fn closest_point_degenerate() {
+        let polygon = Polygon::new(vec![Vec2::new(f32::NAN, 0.0), Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0)]);
+        assert_eq!(polygon.closest_point(Vec2::new(12.0, 5.0)), Some(Vec2::new(10.0, 5.0)));
+
+        let point = Polygon::new(vec![Vec2::new(1.0, 1.0); 3]);
+        assert_eq!(point.closest_point(Vec2::new(4.0, 5.0)), Some(Vec2::new(1.0, 1.0)));
+    }
+}
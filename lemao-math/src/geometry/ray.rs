@@ -0,0 +1,157 @@
+use super::circle::Circle;
+use super::polygon::Polygon;
+use super::rect::Rect;
+use super::segment;
+use super::segment::Segment;
+use crate::vec2::Vec2;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Ray {
+    pub origin: Vec2,
+    pub direction: Vec2,
+}
+
+// All intersection methods return the distance from the origin to the nearest hit (zero if the origin is inside the shape),
+// the point itself can be obtained with get_point
+impl Ray {
+    pub fn new(origin: Vec2, direction: Vec2) -> Self {
+        Self { origin, direction: direction.normalized() }
+    }
+
+    pub fn get_point(&self, distance: f32) -> Vec2 {
+        self.origin + self.direction * distance
+    }
+
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        self.get_point((point - self.origin).dot(self.direction).max(0.0))
+    }
+
+    pub fn intersect_segment(&self, segment: &Segment) -> Option<f32> {
+        let (t, u) = segment::get_intersection_factors(self.origin, self.direction, segment.from, segment.to - segment.from)?;
+
+        if t >= 0.0 && (0.0..=1.0).contains(&u) {
+            Some(t)
+        } else {
+            None
+        }
+    }
+
+    pub fn intersect_rect(&self, rect: &Rect) -> Option<f32> {
+        ///////////////////////////////////////////////////////////
+        // Slab method: https://tavianator.com/2011/ray_box.html //
+        ///////////////////////////////////////////////////////////
+
+        let min = rect.get_min();
+        let max = rect.get_max();
+        let mut near = f32::MIN;
+        let mut far = f32::MAX;
+
+        for (origin, direction, min, max) in [(self.origin.x, self.direction.x, min.x, max.x), (self.origin.y, self.direction.y, min.y, max.y)] {
+            if direction == 0.0 {
+                if origin < min || origin > max {
+                    return None;
+                }
+            } else {
+                let t1 = (min - origin) / direction;
+                let t2 = (max - origin) / direction;
+
+                near = near.max(t1.min(t2));
+                far = far.min(t1.max(t2));
+            }
+        }
+
+        if near > far || far < 0.0 {
+            return None;
+        }
+
+        Some(near.max(0.0))
+    }
+
+    pub fn intersect_circle(&self, circle: &Circle) -> Option<f32> {
+        let offset = self.origin - circle.center;
+        let b = offset.dot(self.direction);
+        let c = offset.dot(offset) - circle.radius * circle.radius;
+        let discriminant = b * b - c;
+
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        let root = discriminant.sqrt();
+        let near = -b - root;
+        let far = -b + root;
+
+        if far < 0.0 {
+            None
+        } else {
+            Some(near.max(0.0))
+        }
+    }
+
+    pub fn intersect_polygon(&self, polygon: &Polygon) -> Option<f32> {
+        if polygon.contains_point(self.origin) {
+            return Some(0.0);
+        }
+
+        polygon.get_edges().filter_map(|p| self.intersect_segment(&p)).min_by(|a, b| a.total_cmp(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_point() {
+        let ray = Ray::new(Vec2::new(1.0, 1.0), Vec2::new(0.0, 5.0));
+
+        assert_eq!(ray.direction, Vec2::new(0.0, 1.0));
+        assert_eq!(ray.get_point(3.0), Vec2::new(1.0, 4.0));
+        assert_eq!(ray.closest_point(Vec2::new(5.0, 3.0)), Vec2::new(1.0, 3.0));
+        assert_eq!(ray.closest_point(Vec2::new(5.0, -3.0)), Vec2::new(1.0, 1.0));
+    }
+
+    #[test]
+    fn intersect_segment() {
+        let ray = Ray::new(Vec2::new(-5.0, 5.0), Vec2::new(1.0, 0.0));
+
+        assert_eq!(ray.intersect_segment(&Segment::new(Vec2::new(3.0, 0.0), Vec2::new(3.0, 10.0))), Some(8.0));
+        assert_eq!(ray.intersect_segment(&Segment::new(Vec2::new(-8.0, 0.0), Vec2::new(-8.0, 10.0))), None);
+        assert_eq!(ray.intersect_segment(&Segment::new(Vec2::new(3.0, 6.0), Vec2::new(3.0, 10.0))), None);
+    }
+
+    #[test]
+    fn intersect_rect() {
+        let rect = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0));
+
+        assert_eq!(Ray::new(Vec2::new(-5.0, 5.0), Vec2::new(1.0, 0.0)).intersect_rect(&rect), Some(5.0));
+        assert_eq!(Ray::new(Vec2::new(5.0, 5.0), Vec2::new(1.0, 0.0)).intersect_rect(&rect), Some(0.0));
+        assert_eq!(Ray::new(Vec2::new(-5.0, 5.0), Vec2::new(-1.0, 0.0)).intersect_rect(&rect), None);
+        assert_eq!(Ray::new(Vec2::new(-5.0, 15.0), Vec2::new(1.0, 0.0)).intersect_rect(&rect), None);
+    }
+
+    #[test]
+    fn intersect_circle() {
+        let circle = Circle::new(Vec2::new(5.0, 5.0), 2.0);
+
+        assert_eq!(Ray::new(Vec2::new(-5.0, 5.0), Vec2::new(1.0, 0.0)).intersect_circle(&circle), Some(8.0));
+        assert_eq!(Ray::new(Vec2::new(5.0, 5.0), Vec2::new(1.0, 0.0)).intersect_circle(&circle), Some(0.0));
+        assert_eq!(Ray::new(Vec2::new(-5.0, 5.0), Vec2::new(0.0, 1.0)).intersect_circle(&circle), None);
+        assert_eq!(Ray::new(Vec2::new(10.0, 5.0), Vec2::new(1.0, 0.0)).intersect_circle(&circle), None);
+    }
+
+    #[test]
+    fn intersect_polygon() {
+        let polygon = Polygon::from(Rect::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0)));
+
+        assert_eq!(Ray::new(Vec2::new(-5.0, 5.0), Vec2::new(1.0, 0.0)).intersect_polygon(&polygon), Some(5.0));
+        assert_eq!(Ray::new(Vec2::new(5.0, 5.0), Vec2::new(1.0, 0.0)).intersect_polygon(&polygon), Some(0.0));
+        assert_eq!(Ray::new(Vec2::new(-5.0, 5.0), Vec2::new(-1.0, 0.0)).intersect_polygon(&polygon), None);
+    }
+
+    #[test]
+    fn intersect_degenerate_polygon() {
+        let polygon = Polygon::new(vec![Vec2::new(f32::NAN, 0.0), Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0), Vec2::new(0.0, 10.0)]);
+        assert_eq!(Ray::new(Vec2::new(5.0, -5.0), Vec2::new(0.0, 1.0)).intersect_polygon(&polygon), Some(15.0));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::vec2::Vec2;
+
+// Axis-aligned rectangle, position is the left-bottom corner
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Rect {
+    pub position: Vec2,
+    pub size: Vec2,
+}
+
+impl Rect {
+    pub fn new(position: Vec2, size: Vec2) -> Self {
+        Self { position, size }
+    }
+
+    pub fn new_from_corners(a: Vec2, b: Vec2) -> Self {
+        let min = Vec2::new(a.x.min(b.x), a.y.min(b.y));
+        let max = Vec2::new(a.x.max(b.x), a.y.max(b.y));
+
+        Self { position: min, size: max - min }
+    }
+
+    pub fn new_from_points(points: &[Vec2]) -> Option<Self> {
+        let first = *points.first()?;
+        let mut min = first;
+        let mut max = first;
+
+        for point in &points[1..] {
+            min = Vec2::new(min.x.min(point.x), min.y.min(point.y));
+            max = Vec2::new(max.x.max(point.x), max.y.max(point.y));
+        }
+
+        Some(Self { position: min, size: max - min })
+    }
+
+    pub fn get_min(&self) -> Vec2 {
+        self.position
+    }
+
+    pub fn get_max(&self) -> Vec2 {
+        self.position + self.size
+    }
+
+    pub fn get_center(&self) -> Vec2 {
+        self.position + self.size / 2.0
+    }
+
+    pub fn get_corners(&self) -> [Vec2; 4] {
+        let min = self.get_min();
+        let max = self.get_max();
+
+        [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)]
+    }
+
+    // Edges are treated as a part of the rectangle
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        let min = self.get_min();
+        let max = self.get_max();
+
+        point.x >= min.x && point.y >= min.y && point.x <= max.x && point.y <= max.y
+    }
+
+    pub fn contains_rect(&self, rect: &Rect) -> bool {
+        self.contains_point(rect.get_min()) && self.contains_point(rect.get_max())
+    }
+
+    pub fn overlaps(&self, rect: &Rect) -> bool {
+        let min = self.get_min();
+        let max = self.get_max();
+        let rect_min = rect.get_min();
+        let rect_max = rect.get_max();
+
+        min.x <= rect_max.x && rect_min.x <= max.x && min.y <= rect_max.y && rect_min.y <= max.y
+    }
+
+    pub fn intersection(&self, rect: &Rect) -> Option<Rect> {
+        if !self.overlaps(rect) {
+            return None;
+        }
+
+        let min = self.get_min();
+        let max = self.get_max();
+        let rect_min = rect.get_min();
+        let rect_max = rect.get_max();
+
+        let intersection_min = Vec2::new(min.x.max(rect_min.x), min.y.max(rect_min.y));
+        let intersection_max = Vec2::new(max.x.min(rect_max.x), max.y.min(rect_max.y));
+
+        Some(Rect::new(intersection_min, intersection_max - intersection_min))
+    }
+
+    pub fn union(&self, rect: &Rect) -> Rect {
+        let min = self.get_min();
+        let max = self.get_max();
+        let rect_min = rect.get_min();
+        let rect_max = rect.get_max();
+
+        Rect::new_from_corners(Vec2::new(min.x.min(rect_min.x), min.y.min(rect_min.y)), Vec2::new(max.x.max(rect_max.x), max.y.max(rect_max.y)))
+    }
+
+    // Points inside the rectangle are returned without any change
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        point.clamp(self.get_min(), self.get_max())
+    }
+
+    pub fn distance_to_point(&self, point: Vec2) -> f32 {
+        self.closest_point(point).distance(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_from_corners() {
+        let rect = Rect::new_from_corners(Vec2::new(10.0, 2.0), Vec2::new(4.0, 8.0));
+
+        assert_eq!(rect.position, Vec2::new(4.0, 2.0));
+        assert_eq!(rect.size, Vec2::new(6.0, 6.0));
+        assert_eq!(Rect::new_from_points(&[]), None);
+        assert_eq!(Rect::new_from_points(&[Vec2::new(1.0, 5.0), Vec2::new(-1.0, 2.0), Vec2::new(3.0, 0.0)]), Some(Rect::new(Vec2::new(-1.0, 0.0), Vec2::new(4.0, 5.0))));
+    }
+
+    #[test]
+    fn contains_point() {
+        let rect = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0));
+
+        assert!(rect.contains_point(Vec2::new(5.0, 5.0)));
+        assert!(rect.contains_point(Vec2::new(0.0, 0.0)));
+        assert!(rect.contains_point(Vec2::new(10.0, 10.0)));
+        assert!(!rect.contains_point(Vec2::new(10.1, 5.0)));
+        assert!(!rect.contains_point(Vec2::new(5.0, -0.1)));
+        assert!(rect.contains_rect(&Rect::new(Vec2::new(2.0, 2.0), Vec2::new(3.0, 3.0))));
+        assert!(!rect.contains_rect(&Rect::new(Vec2::new(8.0, 8.0), Vec2::new(3.0, 3.0))));
+    }
+
+    #[test]
+    fn overlaps_and_intersection() {
+        let a = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0));
+        let b = Rect::new(Vec2::new(5.0, 5.0), Vec2::new(10.0, 2.0));
+        let c = Rect::new(Vec2::new(11.0, 0.0), Vec2::new(1.0, 1.0));
+
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&c));
+        assert_eq!(a.intersection(&b), Some(Rect::new(Vec2::new(5.0, 5.0), Vec2::new(5.0, 2.0))));
+        assert_eq!(a.intersection(&c), None);
+    }
+
+    #[test]
+    fn union() {
+        let a = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0));
+        let b = Rect::new(Vec2::new(5.0, -5.0), Vec2::new(10.0, 2.0));
+
+        assert_eq!(a.union(&b), Rect::new(Vec2::new(0.0, -5.0), Vec2::new(15.0, 15.0)));
+        assert_eq!(a.union(&a), a);
+    }
+
+    #[test]
+    fn closest_point() {
+        let rect = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0));
+
+        assert_eq!(rect.closest_point(Vec2::new(15.0, -3.0)), Vec2::new(10.0, 0.0));
+        assert_eq!(rect.closest_point(Vec2::new(3.0, 4.0)), Vec2::new(3.0, 4.0));
+        assert_eq!(rect.distance_to_point(Vec2::new(13.0, 14.0)), 5.0);
+    }
+}
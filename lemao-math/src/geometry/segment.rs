@@ -0,0 +1,92 @@
+use crate::vec2::Vec2;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Segment {
+    pub from: Vec2,
+    pub to: Vec2,
+}
+
+impl Segment {
+    pub fn new(from: Vec2, to: Vec2) -> Self {
+        Self { from, to }
+    }
+
+    pub fn length(&self) -> f32 {
+        self.from.distance(self.to)
+    }
+
+    pub fn get_direction(&self) -> Vec2 {
+        (self.to - self.from).normalized()
+    }
+
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        let delta = self.to - self.from;
+        let length_squared = delta.dot(delta);
+
+        if length_squared == 0.0 {
+            return self.from;
+        }
+
+        let t = ((point - self.from).dot(delta) / length_squared).clamp(0.0, 1.0);
+        self.from + delta * t
+    }
+
+    pub fn distance_to_point(&self, point: Vec2) -> f32 {
+        self.closest_point(point).distance(point)
+    }
+
+    // Parallel and collinear segments are never treated as intersecting
+    pub fn intersection(&self, segment: &Segment) -> Option<Vec2> {
+        let (t, u) = get_intersection_factors(self.from, self.to - self.from, segment.from, segment.to - segment.from)?;
+
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            Some(self.from + (self.to - self.from) * t)
+        } else {
+            None
+        }
+    }
+
+    pub fn intersects(&self, segment: &Segment) -> bool {
+        self.intersection(segment).is_some()
+    }
+}
+
+// Returns factors t and u, for which a + r * t == b + s * u
+pub(crate) fn get_intersection_factors(a: Vec2, r: Vec2, b: Vec2, s: Vec2) -> Option<(f32, f32)> {
+    let denominator = r.x * s.y - r.y * s.x;
+    if denominator.abs() < f32::EPSILON {
+        return None;
+    }
+
+    let delta = b - a;
+    let t = (delta.x * s.y - delta.y * s.x) / denominator;
+    let u = (delta.x * r.y - delta.y * r.x) / denominator;
+
+    Some((t, u))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closest_point() {
+        let segment = Segment::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0));
+
+        assert_eq!(segment.closest_point(Vec2::new(10.0, 0.0)), Vec2::new(5.0, 5.0));
+        assert_eq!(segment.closest_point(Vec2::new(-5.0, -1.0)), Vec2::new(0.0, 0.0));
+        assert_eq!(segment.closest_point(Vec2::new(20.0, 12.0)), Vec2::new(10.0, 10.0));
+        assert_eq!(Segment::new(Vec2::new(1.0, 1.0), Vec2::new(1.0, 1.0)).closest_point(Vec2::new(5.0, 5.0)), Vec2::new(1.0, 1.0));
+        assert_eq!(Segment::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0)).distance_to_point(Vec2::new(5.0, 3.0)), 3.0);
+    }
+
+    #[test]
+    fn intersection() {
+        let segment = Segment::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0));
+
+        assert_eq!(segment.intersection(&Segment::new(Vec2::new(0.0, 10.0), Vec2::new(10.0, 0.0))), Some(Vec2::new(5.0, 5.0)));
+        assert_eq!(segment.intersection(&Segment::new(Vec2::new(0.0, 10.0), Vec2::new(4.0, 6.0))), None);
+        assert_eq!(segment.intersection(&Segment::new(Vec2::new(0.0, 1.0), Vec2::new(10.0, 11.0))), None);
+        assert!(segment.intersects(&Segment::new(Vec2::new(10.0, 10.0), Vec2::new(20.0, 0.0))));
+    }
+}
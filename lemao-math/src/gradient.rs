@@ -4,7 +4,9 @@ use crate::vec2::Vec2;
 #[derive(Clone, Debug, PartialEq)]
 pub struct Gradient {
     pub r#type: GradientType,
+    pub spread: GradientSpread,
     pub offset: Vec2,
+    pub angle: f32,
     pub steps: Vec<GradientStep>,
 }
 
@@ -14,6 +16,15 @@ pub enum GradientType {
     Vertical,
     Radial,
     Rectangular,
+    Linear,
+    Conic,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GradientSpread {
+    Pad,
+    Repeat,
+    Reflect,
 }
 
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
@@ -24,7 +35,59 @@ pub struct GradientStep {
 
 impl Gradient {
     pub fn new(r#type: GradientType, offset: Vec2) -> Self {
-        Self { r#type, offset, steps: Default::default() }
+        Self { r#type, spread: GradientSpread::Pad, offset, angle: 0.0, steps: Default::default() }
+    }
+
+    pub fn new_linear(angle: f32, offset: Vec2) -> Self {
+        Self { r#type: GradientType::Linear, spread: GradientSpread::Pad, offset, angle, steps: Default::default() }
+    }
+
+    pub fn new_conic(angle: f32, offset: Vec2) -> Self {
+        Self { r#type: GradientType::Conic, spread: GradientSpread::Pad, offset, angle, steps: Default::default() }
+    }
+
+    // Same interpolation as in the gradient shader, steps are blended with smoothstep
+    pub fn get_color(&self, phase: f32) -> SolidColor {
+        let mut color = match self.steps.first() {
+            Some(step) => step.color,
+            None => return Default::default(),
+        };
+
+        for index in 0..self.steps.len() - 1 {
+            let from = self.steps[index].step;
+            let to = self.steps[index + 1].step;
+            let ratio = if from >= to {
+                if phase >= to {
+                    1.0
+                } else {
+                    0.0
+                }
+            } else {
+                let x = ((phase - from) / (to - from)).clamp(0.0, 1.0);
+                x * x * (3.0 - 2.0 * x)
+            };
+
+            let next = self.steps[index + 1].color;
+            color = SolidColor::new(
+                color.r + (next.r - color.r) * ratio,
+                color.g + (next.g - color.g) * ratio,
+                color.b + (next.b - color.b) * ratio,
+                color.a + (next.a - color.a) * ratio,
+            );
+        }
+
+        color
+    }
+}
+
+impl GradientSpread {
+    // Same as in the gradient shader, phase outside of 0..1 is mapped back to this range
+    pub fn apply(&self, phase: f32) -> f32 {
+        match self {
+            Self::Pad => phase,
+            Self::Repeat => phase - phase.floor(),
+            Self::Reflect => 1.0 - ((phase % 2.0 + 2.0) % 2.0 - 1.0).abs(),
+        }
     }
 }
 
@@ -33,3 +96,66 @@ impl GradientStep {
         Self { color, step }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_color_eq(a: SolidColor, b: SolidColor) {
+        assert!((a.r - b.r).abs() < 1e-4 && (a.g - b.g).abs() < 1e-4 && (a.b - b.b).abs() < 1e-4 && (a.a - b.a).abs() < 1e-4, "{:?} != {:?}", a, b);
+    }
+
+    fn get_gradient() -> Gradient {
+        let mut gradient = Gradient::new(GradientType::Horizontal, Vec2::new(0.0, 0.0));
+        gradient.steps.push(GradientStep::new(SolidColor::new(1.0, 0.0, 0.0, 1.0), 0.2));
+        gradient.steps.push(GradientStep::new(SolidColor::new(0.0, 1.0, 0.0, 1.0), 0.5));
+        gradient.steps.push(GradientStep::new(SolidColor::new(0.0, 0.0, 1.0, 0.0), 0.8));
+        gradient
+    }
+
+    #[test]
+    fn get_color() {
+        let gradient = get_gradient();
+
+        assert_color_eq(gradient.get_color(0.0), SolidColor::new(1.0, 0.0, 0.0, 1.0));
+        assert_color_eq(gradient.get_color(0.2), SolidColor::new(1.0, 0.0, 0.0, 1.0));
+        assert_color_eq(gradient.get_color(0.35), SolidColor::new(0.5, 0.5, 0.0, 1.0));
+        assert_color_eq(gradient.get_color(0.5), SolidColor::new(0.0, 1.0, 0.0, 1.0));
+        assert_color_eq(gradient.get_color(0.65), SolidColor::new(0.0, 0.5, 0.5, 0.5));
+        assert_color_eq(gradient.get_color(1.0), SolidColor::new(0.0, 0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn get_color_without_steps() {
+        let gradient = Gradient::new(GradientType::Radial, Vec2::new(0.0, 0.0));
+        assert_eq!(gradient.get_color(0.5), SolidColor::default());
+    }
+
+    #[test]
+    fn get_color_hard_stop() {
+        let mut gradient = Gradient::new(GradientType::Vertical, Vec2::new(0.0, 0.0));
+        gradient.steps.push(GradientStep::new(SolidColor::new(1.0, 0.0, 0.0, 1.0), 0.5));
+        gradient.steps.push(GradientStep::new(SolidColor::new(0.0, 0.0, 1.0, 1.0), 0.5));
+
+        assert_color_eq(gradient.get_color(0.49), SolidColor::new(1.0, 0.0, 0.0, 1.0));
+        assert_color_eq(gradient.get_color(0.5), SolidColor::new(0.0, 0.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn spread() {
+        assert_eq!(GradientSpread::Pad.apply(1.25), 1.25);
+        assert_eq!(GradientSpread::Pad.apply(-0.25), -0.25);
+
+        assert!((GradientSpread::Repeat.apply(1.25) - 0.25).abs() < 1e-6);
+        assert!((GradientSpread::Repeat.apply(-0.25) - 0.75).abs() < 1e-6);
+
+        assert!((GradientSpread::Reflect.apply(0.25) - 0.25).abs() < 1e-6);
+        assert!((GradientSpread::Reflect.apply(1.25) - 0.75).abs() < 1e-6);
+        assert!((GradientSpread::Reflect.apply(2.25) - 0.25).abs() < 1e-6);
+        assert!((GradientSpread::Reflect.apply(-0.25) - 0.25).abs() < 1e-6);
+
+        let gradient = get_gradient();
+        assert_color_eq(gradient.get_color(GradientSpread::Repeat.apply(1.35)), SolidColor::new(0.5, 0.5, 0.0, 1.0));
+        assert_color_eq(gradient.get_color(GradientSpread::Reflect.apply(1.65)), SolidColor::new(0.5, 0.5, 0.0, 1.0));
+    }
+}
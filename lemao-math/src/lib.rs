@@ -1,8 +1,12 @@
 #![allow(clippy::identity_op, clippy::erasing_op, clippy::needless_range_loop)]
 
 pub mod color;
+pub mod geometry;
 pub mod gradient;
+pub mod mat3x3;
 pub mod mat4x4;
+pub mod simd;
+pub mod transform2d;
 pub mod vec2;
 pub mod vec3;
 pub mod vec4;
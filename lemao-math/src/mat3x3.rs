@@ -0,0 +1,243 @@
+use crate::mat4x4::Mat4x4;
+use crate::vec2::Vec2;
+use std::ops::Index;
+use std::ops::IndexMut;
+use std::ops::Mul;
+
+// 2D affine transformation stored in the same column-major order as Mat4x4
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Mat3x3 {
+    data: [f32; 9],
+}
+
+impl Mat3x3 {
+    #[rustfmt::skip]
+    pub fn identity() -> Self {
+        Self { data: [
+            1.0, 0.0, 0.0,
+            0.0, 1.0, 0.0,
+            0.0, 0.0, 1.0
+        ]}
+    }
+
+    // Same order as the SVG matrix(a, b, c, d, e, f) transform, where e and f are the translation
+    #[rustfmt::skip]
+    pub fn new_affine(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Self {
+        Self { data: [
+            a, b, 0.0,
+            c, d, 0.0,
+            e, f, 1.0
+        ]}
+    }
+
+    pub fn translate(translation: Vec2) -> Self {
+        let mut matrix = Mat3x3::identity();
+        matrix[6] = translation.x;
+        matrix[7] = translation.y;
+
+        matrix
+    }
+
+    pub fn rotate(rotation: f32) -> Self {
+        let mut matrix = Mat3x3::identity();
+        matrix[0] = rotation.cos();
+        matrix[3] = -rotation.sin();
+        matrix[1] = rotation.sin();
+        matrix[4] = rotation.cos();
+
+        matrix
+    }
+
+    pub fn scale(scale: Vec2) -> Self {
+        let mut matrix = Mat3x3::identity();
+        matrix[0] = scale.x;
+        matrix[4] = scale.y;
+
+        matrix
+    }
+
+    // Skew angles are in radians, x moves points horizontally proportionally to their y and vice versa
+    pub fn skew(skew: Vec2) -> Self {
+        let mut matrix = Mat3x3::identity();
+        matrix[3] = skew.x.tan();
+        matrix[1] = skew.y.tan();
+
+        matrix
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut matrix = Mat3x3::default();
+        for col in 0..3 {
+            for row in 0..3 {
+                matrix[(row * 3) + col] = self[(col * 3) + row];
+            }
+        }
+
+        matrix
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let m = &self.data;
+        m[0] * (m[4] * m[8] - m[7] * m[5]) - m[3] * (m[1] * m[8] - m[7] * m[2]) + m[6] * (m[1] * m[5] - m[4] * m[2])
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+        // Only singular matrices are rejected, projections like ortho have very small but still valid determinants
+        if determinant == 0.0 {
+            return None;
+        }
+
+        let m = &self.data;
+        let mut matrix = Mat3x3::default();
+        matrix[0] = m[4] * m[8] - m[5] * m[7];
+        matrix[1] = m[2] * m[7] - m[1] * m[8];
+        matrix[2] = m[1] * m[5] - m[2] * m[4];
+        matrix[3] = m[5] * m[6] - m[3] * m[8];
+        matrix[4] = m[0] * m[8] - m[2] * m[6];
+        matrix[5] = m[2] * m[3] - m[0] * m[5];
+        matrix[6] = m[3] * m[7] - m[4] * m[6];
+        matrix[7] = m[1] * m[6] - m[0] * m[7];
+        matrix[8] = m[0] * m[4] - m[1] * m[3];
+
+        for index in 0..9 {
+            matrix[index] /= determinant;
+        }
+
+        Some(matrix)
+    }
+
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        Vec2::new(self[0] * point.x + self[3] * point.y + self[6], self[1] * point.x + self[4] * point.y + self[7])
+    }
+
+    // Vectors are directions, so they are not affected by the translation
+    pub fn transform_vector(&self, vector: Vec2) -> Vec2 {
+        Vec2::new(self[0] * vector.x + self[3] * vector.y, self[1] * vector.x + self[4] * vector.y)
+    }
+
+    pub fn as_ptr(&self) -> *const f32 {
+        self.data.as_ptr()
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut f32 {
+        self.data.as_mut_ptr()
+    }
+}
+
+impl Mul for Mat3x3 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut matrix = Mat3x3::default();
+
+        for col in 0..3 {
+            for row in 0..3 {
+                let mut sum = 0.0;
+
+                for p in 0..3 {
+                    sum += self[(p * 3) + row] * rhs[(col * 3) + p];
+                }
+
+                matrix[(col * 3) + row] = sum;
+            }
+        }
+
+        matrix
+    }
+}
+
+impl Index<usize> for Mat3x3 {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.data[index]
+    }
+}
+
+impl IndexMut<usize> for Mat3x3 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.data[index]
+    }
+}
+
+impl From<Mat3x3> for Mat4x4 {
+    fn from(value: Mat3x3) -> Self {
+        let mut matrix = Mat4x4::identity();
+        matrix[0] = value[0];
+        matrix[1] = value[1];
+        matrix[4] = value[3];
+        matrix[5] = value[4];
+        matrix[12] = value[6];
+        matrix[13] = value[7];
+
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_matrix_eq(a: &Mat3x3, b: &Mat3x3, epsilon: f32) {
+        for index in 0..9 {
+            assert!((a[index] - b[index]).abs() <= epsilon, "index {}: {} != {}", index, a[index], b[index]);
+        }
+    }
+
+    #[test]
+    fn new_affine() {
+        let matrix = Mat3x3::new_affine(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+
+        assert_eq!(matrix.transform_point(Vec2::new(1.0, 2.0)), Vec2::new(12.0, 16.0));
+        assert_eq!(Mat3x3::new_affine(1.0, 0.0, 0.0, 1.0, 0.0, 0.0), Mat3x3::identity());
+    }
+
+    #[test]
+    fn transpose() {
+        let matrix = Mat3x3::translate(Vec2::new(1.0, 2.0));
+        let transposed = matrix.transpose();
+
+        assert_eq!(transposed[2], 1.0);
+        assert_eq!(transposed[5], 2.0);
+        assert_eq!(transposed.transpose(), matrix);
+    }
+
+    #[test]
+    fn determinant() {
+        assert_eq!(Mat3x3::identity().determinant(), 1.0);
+        assert!((Mat3x3::scale(Vec2::new(2.0, 3.0)).determinant() - 6.0).abs() < 1e-5);
+        assert!((Mat3x3::rotate(1.2).determinant() - 1.0).abs() < 1e-5);
+        assert_eq!(Mat3x3::scale(Vec2::new(0.0, 3.0)).determinant(), 0.0);
+    }
+
+    #[test]
+    fn inverse() {
+        let matrix = Mat3x3::translate(Vec2::new(5.0, -3.0)) * Mat3x3::rotate(0.4) * Mat3x3::skew(Vec2::new(0.2, 0.1)) * Mat3x3::scale(Vec2::new(2.0, 0.5));
+        let inverse = matrix.inverse().unwrap();
+
+        assert_matrix_eq(&(matrix * inverse), &Mat3x3::identity(), 1e-5);
+        assert!(Mat3x3::scale(Vec2::new(0.0, 1.0)).inverse().is_none());
+    }
+
+    #[test]
+    fn inverse_small_scale() {
+        let matrix = Mat3x3::scale(Vec2::new(1.0 / 1920.0, 1.0 / 1080.0));
+        let point = matrix.inverse().unwrap().transform_point(Vec2::new(0.5, 0.5));
+
+        assert!((point.x - 960.0).abs() < 1e-2);
+        assert!((point.y - 540.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn to_mat4x4() {
+        let matrix = Mat3x3::translate(Vec2::new(5.0, -3.0)) * Mat3x3::rotate(0.4);
+        let expected = Mat4x4::translate(crate::vec3::Vec3::new(5.0, -3.0, 0.0)) * Mat4x4::rotate(0.4);
+        let converted = Mat4x4::from(matrix);
+
+        for index in 0..16 {
+            assert!((converted[index] - expected[index]).abs() < 1e-6);
+        }
+    }
+}
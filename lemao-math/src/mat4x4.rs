@@ -1,7 +1,6 @@
+use crate::simd::backend;
 use crate::vec3::Vec3;
 use crate::vec4::Vec4;
-use std::arch::x86_64;
-use std::mem;
 use std::ops::Index;
 use std::ops::IndexMut;
 use std::ops::Mul;
@@ -66,6 +65,42 @@ impl Mat4x4 {
         matrix
     }
 
+    pub fn transpose(&self) -> Self {
+        let mut matrix = Mat4x4::default();
+        for col in 0..4 {
+            for row in 0..4 {
+                matrix[(row * 4) + col] = self[(col * 4) + row];
+            }
+        }
+
+        matrix
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let cofactors = self.get_cofactors();
+        let m = &self.data;
+
+        m[0] * cofactors[0] + m[1] * cofactors[4] + m[2] * cofactors[8] + m[3] * cofactors[12]
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let cofactors = self.get_cofactors();
+        let m = &self.data;
+        let determinant = m[0] * cofactors[0] + m[1] * cofactors[4] + m[2] * cofactors[8] + m[3] * cofactors[12];
+
+        // Only singular matrices are rejected, projections like ortho have very small but still valid determinants
+        if determinant == 0.0 {
+            return None;
+        }
+
+        let mut matrix = Mat4x4::default();
+        for index in 0..16 {
+            matrix[index] = cofactors[index] / determinant;
+        }
+
+        Some(matrix)
+    }
+
     pub fn as_ptr(&self) -> *const f32 {
         self.data.as_ptr() as *const f32
     }
@@ -73,64 +108,38 @@ impl Mat4x4 {
     pub fn as_mut_ptr(&mut self) -> *mut f32 {
         self.data.as_mut_ptr() as *mut f32
     }
+
+    // Transposed matrix of cofactors (adjugate), based on https://stackoverflow.com/a/1148405
+    fn get_cofactors(&self) -> [f32; 16] {
+        let m = &self.data;
+        let mut inv = [0.0; 16];
+
+        inv[0] = m[5] * m[10] * m[15] - m[5] * m[11] * m[14] - m[9] * m[6] * m[15] + m[9] * m[7] * m[14] + m[13] * m[6] * m[11] - m[13] * m[7] * m[10];
+        inv[4] = -m[4] * m[10] * m[15] + m[4] * m[11] * m[14] + m[8] * m[6] * m[15] - m[8] * m[7] * m[14] - m[12] * m[6] * m[11] + m[12] * m[7] * m[10];
+        inv[8] = m[4] * m[9] * m[15] - m[4] * m[11] * m[13] - m[8] * m[5] * m[15] + m[8] * m[7] * m[13] + m[12] * m[5] * m[11] - m[12] * m[7] * m[9];
+        inv[12] = -m[4] * m[9] * m[14] + m[4] * m[10] * m[13] + m[8] * m[5] * m[14] - m[8] * m[6] * m[13] - m[12] * m[5] * m[10] + m[12] * m[6] * m[9];
+        inv[1] = -m[1] * m[10] * m[15] + m[1] * m[11] * m[14] + m[9] * m[2] * m[15] - m[9] * m[3] * m[14] - m[13] * m[2] * m[11] + m[13] * m[3] * m[10];
+        inv[5] = m[0] * m[10] * m[15] - m[0] * m[11] * m[14] - m[8] * m[2] * m[15] + m[8] * m[3] * m[14] + m[12] * m[2] * m[11] - m[12] * m[3] * m[10];
+        inv[9] = -m[0] * m[9] * m[15] + m[0] * m[11] * m[13] + m[8] * m[1] * m[15] - m[8] * m[3] * m[13] - m[12] * m[1] * m[11] + m[12] * m[3] * m[9];
+        inv[13] = m[0] * m[9] * m[14] - m[0] * m[10] * m[13] - m[8] * m[1] * m[14] + m[8] * m[2] * m[13] + m[12] * m[1] * m[10] - m[12] * m[2] * m[9];
+        inv[2] = m[1] * m[6] * m[15] - m[1] * m[7] * m[14] - m[5] * m[2] * m[15] + m[5] * m[3] * m[14] + m[13] * m[2] * m[7] - m[13] * m[3] * m[6];
+        inv[6] = -m[0] * m[6] * m[15] + m[0] * m[7] * m[14] + m[4] * m[2] * m[15] - m[4] * m[3] * m[14] - m[12] * m[2] * m[7] + m[12] * m[3] * m[6];
+        inv[10] = m[0] * m[5] * m[15] - m[0] * m[7] * m[13] - m[4] * m[1] * m[15] + m[4] * m[3] * m[13] + m[12] * m[1] * m[7] - m[12] * m[3] * m[5];
+        inv[14] = -m[0] * m[5] * m[14] + m[0] * m[6] * m[13] + m[4] * m[1] * m[14] - m[4] * m[2] * m[13] - m[12] * m[1] * m[6] + m[12] * m[2] * m[5];
+        inv[3] = -m[1] * m[6] * m[11] + m[1] * m[7] * m[10] + m[5] * m[2] * m[11] - m[5] * m[3] * m[10] - m[9] * m[2] * m[7] + m[9] * m[3] * m[6];
+        inv[7] = m[0] * m[6] * m[11] - m[0] * m[7] * m[10] - m[4] * m[2] * m[11] + m[4] * m[3] * m[10] + m[8] * m[2] * m[7] - m[8] * m[3] * m[6];
+        inv[11] = -m[0] * m[5] * m[11] + m[0] * m[7] * m[9] + m[4] * m[1] * m[11] - m[4] * m[3] * m[9] - m[8] * m[1] * m[7] + m[8] * m[3] * m[5];
+        inv[15] = m[0] * m[5] * m[10] - m[0] * m[6] * m[9] - m[4] * m[1] * m[10] + m[4] * m[2] * m[9] + m[8] * m[1] * m[6] - m[8] * m[2] * m[5];
+
+        inv
+    }
 }
 
 impl Mul for Mat4x4 {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        /*
-            1x + 2y + 3z   1hx + 2hy + 3hz
-            6x + 5y + 4z   6hx + 5hy + 4hz
-            7x + 8y + 9z   7hx + 8hy + 9hz
-
-            (1)  + (2)  + (3)    (1)   + (2)   + (3)
-            (6)x + (5)y + (4)z   (6)hx + (5)hy + (4)hz
-            (7)  + (8)  + (9)    (7)   + (8)   + (9)
-        */
-        // https://math.stackexchange.com/a/64639
-        unsafe {
-            let mut matrix = Mat4x4::default();
-            let col1 = x86_64::_mm_load_ps(self.as_ptr().add(0));
-            let col2 = x86_64::_mm_load_ps(self.as_ptr().add(4));
-            let col3 = x86_64::_mm_load_ps(self.as_ptr().add(8));
-            let col4 = x86_64::_mm_load_ps(self.as_ptr().add(12));
-
-            for col in 0..4 {
-                let col_m128 = x86_64::_mm_load_ps(rhs.as_ptr().add(col * 4));
-                let x_m128 = x86_64::_mm_shuffle_ps::<0x00>(col_m128, col_m128);
-                let y_m128 = x86_64::_mm_shuffle_ps::<0x55>(col_m128, col_m128);
-                let z_m128 = x86_64::_mm_shuffle_ps::<0xaa>(col_m128, col_m128);
-                let w_m128 = x86_64::_mm_shuffle_ps::<0xff>(col_m128, col_m128);
-
-                let r1 = x86_64::_mm_mul_ps(x_m128, col1);
-                let r2 = x86_64::_mm_add_ps(r1, x86_64::_mm_mul_ps(y_m128, col2));
-                let r3 = x86_64::_mm_add_ps(r2, x86_64::_mm_mul_ps(z_m128, col3));
-                let r4 = x86_64::_mm_add_ps(r3, x86_64::_mm_mul_ps(w_m128, col4));
-
-                x86_64::_mm_store_ps(matrix.as_mut_ptr().add(col * 4), r4);
-            }
-
-            matrix
-        }
-
-        /*
-        let mut matrix = Mat4x4::default();
-
-        for i in 0..16 {
-            let row = i / 4;
-            let col = i % 4;
-            let mut sum = 0.0;
-
-            for p in 0..4 {
-                sum += self[(row * 4) + p] * rhs[col + (p * 4)];
-            }
-
-            matrix[i] = sum;
-        }
-
-        matrix
-        */
+        Mat4x4 { data: backend::mat4_mul(&self.data, &rhs.data) }
     }
 }
 
@@ -138,35 +147,7 @@ impl Mul<Vec4> for Mat4x4 {
     type Output = Vec4;
 
     fn mul(self, rhs: Vec4) -> Self::Output {
-        // https://math.stackexchange.com/a/64639
-        unsafe {
-            let col1 = x86_64::_mm_load_ps(self.as_ptr().add(0));
-            let col2 = x86_64::_mm_load_ps(self.as_ptr().add(4));
-            let col3 = x86_64::_mm_load_ps(self.as_ptr().add(8));
-            let col4 = x86_64::_mm_load_ps(self.as_ptr().add(12));
-
-            let rhs_m128 = x86_64::_mm_load_ps(rhs.as_ptr());
-            let x_m128 = x86_64::_mm_shuffle_ps::<0x00>(rhs_m128, rhs_m128);
-            let y_m128 = x86_64::_mm_shuffle_ps::<0x55>(rhs_m128, rhs_m128);
-            let z_m128 = x86_64::_mm_shuffle_ps::<0xaa>(rhs_m128, rhs_m128);
-            let w_m128 = x86_64::_mm_shuffle_ps::<0xff>(rhs_m128, rhs_m128);
-
-            let r1 = x86_64::_mm_mul_ps(x_m128, col1);
-            let r2 = x86_64::_mm_add_ps(r1, x86_64::_mm_mul_ps(y_m128, col2));
-            let r3 = x86_64::_mm_add_ps(r2, x86_64::_mm_mul_ps(z_m128, col3));
-            let r4 = x86_64::_mm_add_ps(r3, x86_64::_mm_mul_ps(w_m128, col4));
-
-            mem::transmute(r4)
-        }
-
-        /*
-        Vec4::new(
-            self[(0 * 4) + 0] * rhs.x + self[(0 * 4) + 1] * rhs.y + self[(0 * 4) + 2] * rhs.z + self[(0 * 4) + 3] * rhs.w,
-            self[(1 * 4) + 0] * rhs.x + self[(1 * 4) + 1] * rhs.y + self[(1 * 4) + 2] * rhs.z + self[(1 * 4) + 3] * rhs.w,
-            self[(2 * 4) + 0] * rhs.x + self[(2 * 4) + 1] * rhs.y + self[(2 * 4) + 2] * rhs.z + self[(2 * 4) + 3] * rhs.w,
-            self[(3 * 4) + 0] * rhs.x + self[(3 * 4) + 1] * rhs.y + self[(3 * 4) + 2] * rhs.z + self[(3 * 4) + 3] * rhs.w,
-        )
-        */
+        Vec4::from_array(backend::mat4_mul_vec4(&self.data, rhs.to_array()))
     }
 }
 
@@ -183,3 +164,55 @@ impl IndexMut<usize> for Mat4x4 {
         &mut self.data[index]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_matrix_eq(a: &Mat4x4, b: &Mat4x4, epsilon: f32) {
+        for index in 0..16 {
+            assert!((a[index] - b[index]).abs() <= epsilon, "index {}: {} != {}", index, a[index], b[index]);
+        }
+    }
+
+    #[test]
+    fn transpose() {
+        let matrix = Mat4x4::translate(Vec3::new(1.0, 2.0, 3.0));
+        let transposed = matrix.transpose();
+
+        assert_eq!(transposed[3], 1.0);
+        assert_eq!(transposed[7], 2.0);
+        assert_eq!(transposed[11], 3.0);
+        assert_eq!(transposed.transpose(), matrix);
+    }
+
+    #[test]
+    fn determinant() {
+        assert_eq!(Mat4x4::identity().determinant(), 1.0);
+        assert!((Mat4x4::scale(Vec3::new(2.0, 3.0, 4.0)).determinant() - 24.0).abs() < 1e-5);
+        assert!((Mat4x4::rotate(0.7).determinant() - 1.0).abs() < 1e-5);
+        assert_eq!(Mat4x4::scale(Vec3::new(1.0, 0.0, 1.0)).determinant(), 0.0);
+    }
+
+    #[test]
+    fn inverse() {
+        let matrix = Mat4x4::translate(Vec3::new(3.0, -2.0, 1.0)) * Mat4x4::rotate(0.7) * Mat4x4::scale(Vec3::new(2.0, 5.0, 1.5));
+        let inverse = matrix.inverse().unwrap();
+
+        assert_matrix_eq(&(matrix * inverse), &Mat4x4::identity(), 1e-5);
+        assert_matrix_eq(&(inverse * matrix), &Mat4x4::identity(), 1e-5);
+        assert!(Mat4x4::scale(Vec3::new(1.0, 0.0, 1.0)).inverse().is_none());
+    }
+
+    #[test]
+    fn inverse_ortho() {
+        let projection = Mat4x4::ortho(1920.0, 1080.0, 0.1, 100.0);
+        let inverse = projection.inverse().unwrap();
+        assert_matrix_eq(&(projection * inverse), &Mat4x4::identity(), 1e-5);
+
+        // Unprojecting the center of the screen gives the center of the viewport
+        let world = inverse * Vec4::new(0.0, 0.0, 0.0, 1.0);
+        assert!((world.x - 960.0).abs() < 1e-2);
+        assert!((world.y - 540.0).abs() < 1e-2);
+    }
+}
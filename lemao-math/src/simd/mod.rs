@@ -0,0 +1,121 @@
+// Backend is selected at compile time: SSE on x86_64, NEON on aarch64 and plain scalar code everywhere else.
+// The scalar backend is always compiled, so it can be also forced with the "scalar" feature
+pub mod scalar;
+
+#[cfg(all(target_arch = "x86_64", target_feature = "sse", not(feature = "scalar")))]
+pub mod sse;
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon", not(feature = "scalar")))]
+pub mod neon;
+
+#[cfg(all(target_arch = "x86_64", target_feature = "sse", not(feature = "scalar")))]
+pub use sse as backend;
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon", not(feature = "scalar")))]
+pub use neon as backend;
+
+#[cfg(not(any(
+    all(target_arch = "x86_64", target_feature = "sse", not(feature = "scalar")),
+    all(target_arch = "aarch64", target_feature = "neon", not(feature = "scalar"))
+)))]
+pub use scalar as backend;
+
+#[cfg(test)]
+mod tests {
+    use super::backend;
+    use super::scalar;
+
+    // Simple LCG, so every run compares the backends on the same values
+    fn get_values<const N: usize>(seed: &mut u32) -> [f32; N] {
+        let mut values = [0.0; N];
+        for value in &mut values {
+            *seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            *value = (*seed >> 8) as f32 / 65536.0 - 128.0;
+        }
+
+        values
+    }
+
+    // Backends can sum products in a different order or use fused multiply-add, so only relative error is checked
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() <= a.abs().max(1.0) * 1e-4, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn add_sub_mul_div() {
+        let mut seed = 1;
+        for _ in 0..1000 {
+            let a = get_values::<4>(&mut seed);
+            let b = get_values::<4>(&mut seed);
+
+            assert_eq!(backend::add(a, b), scalar::add(a, b));
+            assert_eq!(backend::sub(a, b), scalar::sub(a, b));
+            assert_eq!(backend::mul(a, b), scalar::mul(a, b));
+            assert_eq!(backend::div(a, b), scalar::div(a, b));
+        }
+    }
+
+    #[test]
+    fn mul_scalar() {
+        let mut seed = 2;
+        for _ in 0..1000 {
+            let a = get_values::<4>(&mut seed);
+            let b = get_values::<1>(&mut seed)[0];
+
+            assert_eq!(backend::mul_scalar(a, b), scalar::mul_scalar(a, b));
+        }
+    }
+
+    #[test]
+    fn dot() {
+        let mut seed = 3;
+        for _ in 0..1000 {
+            let a = get_values::<4>(&mut seed);
+            let b = get_values::<4>(&mut seed);
+
+            assert_close(backend::dot(a, b), scalar::dot(a, b));
+        }
+    }
+
+    #[test]
+    fn mat4_mul() {
+        let mut seed = 4;
+        for _ in 0..1000 {
+            let a = get_values::<16>(&mut seed);
+            let b = get_values::<16>(&mut seed);
+            let expected = scalar::mat4_mul(&a, &b);
+            let result = backend::mat4_mul(&a, &b);
+
+            for index in 0..16 {
+                assert_close(result[index], expected[index]);
+            }
+        }
+    }
+
+    #[test]
+    fn mat4_mul_vec4() {
+        let mut seed = 5;
+        for _ in 0..1000 {
+            let a = get_values::<16>(&mut seed);
+            let b = get_values::<4>(&mut seed);
+            let expected = scalar::mat4_mul_vec4(&a, b);
+            let result = backend::mat4_mul_vec4(&a, b);
+
+            for index in 0..4 {
+                assert_close(result[index], expected[index]);
+            }
+        }
+    }
+
+    #[test]
+    fn identity() {
+        let identity = [1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0];
+        let mut seed = 6;
+        let a = get_values::<16>(&mut seed);
+        let b = get_values::<4>(&mut seed);
+
+        assert_eq!(backend::mat4_mul(&a, &identity), a);
+        assert_eq!(backend::mat4_mul(&identity, &a), a);
+        assert_eq!(backend::mat4_mul_vec4(&identity, b), b);
+    }
+}
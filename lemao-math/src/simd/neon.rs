@@ -0,0 +1,78 @@
+use std::arch::aarch64;
+
+pub fn add(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    unsafe { store(aarch64::vaddq_f32(load(&a), load(&b))) }
+}
+
+pub fn sub(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    unsafe { store(aarch64::vsubq_f32(load(&a), load(&b))) }
+}
+
+pub fn mul(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    unsafe { store(aarch64::vmulq_f32(load(&a), load(&b))) }
+}
+
+pub fn div(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    unsafe { store(aarch64::vdivq_f32(load(&a), load(&b))) }
+}
+
+pub fn mul_scalar(a: [f32; 4], b: f32) -> [f32; 4] {
+    unsafe { store(aarch64::vmulq_n_f32(load(&a), b)) }
+}
+
+pub fn dot(a: [f32; 4], b: [f32; 4]) -> f32 {
+    unsafe { aarch64::vaddvq_f32(aarch64::vmulq_f32(load(&a), load(&b))) }
+}
+
+pub fn mat4_mul(a: &[f32; 16], b: &[f32; 16]) -> [f32; 16] {
+    // Same column combination as in the SSE backend, https://math.stackexchange.com/a/64639
+    unsafe {
+        let mut result = [0.0; 16];
+        let col1 = aarch64::vld1q_f32(a.as_ptr().add(0));
+        let col2 = aarch64::vld1q_f32(a.as_ptr().add(4));
+        let col3 = aarch64::vld1q_f32(a.as_ptr().add(8));
+        let col4 = aarch64::vld1q_f32(a.as_ptr().add(12));
+
+        for col in 0..4 {
+            let rhs = aarch64::vld1q_f32(b.as_ptr().add(col * 4));
+            aarch64::vst1q_f32(result.as_mut_ptr().add(col * 4), combine(col1, col2, col3, col4, rhs));
+        }
+
+        result
+    }
+}
+
+pub fn mat4_mul_vec4(a: &[f32; 16], b: [f32; 4]) -> [f32; 4] {
+    unsafe {
+        let col1 = aarch64::vld1q_f32(a.as_ptr().add(0));
+        let col2 = aarch64::vld1q_f32(a.as_ptr().add(4));
+        let col3 = aarch64::vld1q_f32(a.as_ptr().add(8));
+        let col4 = aarch64::vld1q_f32(a.as_ptr().add(12));
+
+        store(combine(col1, col2, col3, col4, load(&b)))
+    }
+}
+
+unsafe fn load(data: &[f32; 4]) -> aarch64::float32x4_t {
+    aarch64::vld1q_f32(data.as_ptr())
+}
+
+unsafe fn store(data: aarch64::float32x4_t) -> [f32; 4] {
+    let mut result = [0.0; 4];
+    aarch64::vst1q_f32(result.as_mut_ptr(), data);
+
+    result
+}
+
+unsafe fn combine(
+    col1: aarch64::float32x4_t,
+    col2: aarch64::float32x4_t,
+    col3: aarch64::float32x4_t,
+    col4: aarch64::float32x4_t,
+    rhs: aarch64::float32x4_t,
+) -> aarch64::float32x4_t {
+    let r1 = aarch64::vmulq_laneq_f32::<0>(col1, rhs);
+    let r2 = aarch64::vfmaq_laneq_f32::<1>(r1, col2, rhs);
+    let r3 = aarch64::vfmaq_laneq_f32::<2>(r2, col3, rhs);
+    aarch64::vfmaq_laneq_f32::<3>(r3, col4, rhs)
+}
@@ -0,0 +1,51 @@
+pub fn add(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2], a[3] + b[3]]
+}
+
+pub fn sub(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2], a[3] - b[3]]
+}
+
+pub fn mul(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    [a[0] * b[0], a[1] * b[1], a[2] * b[2], a[3] * b[3]]
+}
+
+pub fn div(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    [a[0] / b[0], a[1] / b[1], a[2] / b[2], a[3] / b[3]]
+}
+
+pub fn mul_scalar(a: [f32; 4], b: f32) -> [f32; 4] {
+    [a[0] * b, a[1] * b, a[2] * b, a[3] * b]
+}
+
+pub fn dot(a: [f32; 4], b: [f32; 4]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2] + a[3] * b[3]
+}
+
+pub fn mat4_mul(a: &[f32; 16], b: &[f32; 16]) -> [f32; 16] {
+    let mut result = [0.0; 16];
+
+    for col in 0..4 {
+        for row in 0..4 {
+            let mut sum = 0.0;
+
+            for p in 0..4 {
+                sum += a[(p * 4) + row] * b[(col * 4) + p];
+            }
+
+            result[(col * 4) + row] = sum;
+        }
+    }
+
+    result
+}
+
+pub fn mat4_mul_vec4(a: &[f32; 16], b: [f32; 4]) -> [f32; 4] {
+    let mut result = [0.0; 4];
+
+    for row in 0..4 {
+        result[row] = a[row] * b[0] + a[4 + row] * b[1] + a[8 + row] * b[2] + a[12 + row] * b[3];
+    }
+
+    result
+}
@@ -0,0 +1,91 @@
+use std::arch::x86_64;
+use std::mem;
+
+pub fn add(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    unsafe { mem::transmute(x86_64::_mm_add_ps(load(&a), load(&b))) }
+}
+
+pub fn sub(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    unsafe { mem::transmute(x86_64::_mm_sub_ps(load(&a), load(&b))) }
+}
+
+pub fn mul(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    unsafe { mem::transmute(x86_64::_mm_mul_ps(load(&a), load(&b))) }
+}
+
+pub fn div(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    unsafe { mem::transmute(x86_64::_mm_div_ps(load(&a), load(&b))) }
+}
+
+pub fn mul_scalar(a: [f32; 4], b: f32) -> [f32; 4] {
+    unsafe { mem::transmute(x86_64::_mm_mul_ps(load(&a), x86_64::_mm_set1_ps(b))) }
+}
+
+pub fn dot(a: [f32; 4], b: [f32; 4]) -> f32 {
+    unsafe {
+        // Horizontal sum without SSE3: (x + z, y + w) and then the sum of both halves
+        let product = x86_64::_mm_mul_ps(load(&a), load(&b));
+        let shuffled = x86_64::_mm_movehl_ps(product, product);
+        let sums = x86_64::_mm_add_ps(product, shuffled);
+        let result = x86_64::_mm_add_ss(sums, x86_64::_mm_shuffle_ps::<0x55>(sums, sums));
+
+        x86_64::_mm_cvtss_f32(result)
+    }
+}
+
+pub fn mat4_mul(a: &[f32; 16], b: &[f32; 16]) -> [f32; 16] {
+    /*
+        1x + 2y + 3z   1hx + 2hy + 3hz
+        6x + 5y + 4z   6hx + 5hy + 4hz
+        7x + 8y + 9z   7hx + 8hy + 9hz
+
+        (1)  + (2)  + (3)    (1)   + (2)   + (3)
+        (6)x + (5)y + (4)z   (6)hx + (5)hy + (4)hz
+        (7)  + (8)  + (9)    (7)   + (8)   + (9)
+    */
+    // https://math.stackexchange.com/a/64639
+    unsafe {
+        let mut result = [0.0; 16];
+        let col1 = x86_64::_mm_loadu_ps(a.as_ptr().add(0));
+        let col2 = x86_64::_mm_loadu_ps(a.as_ptr().add(4));
+        let col3 = x86_64::_mm_loadu_ps(a.as_ptr().add(8));
+        let col4 = x86_64::_mm_loadu_ps(a.as_ptr().add(12));
+
+        for col in 0..4 {
+            let col_m128 = x86_64::_mm_loadu_ps(b.as_ptr().add(col * 4));
+            let r4 = combine(col1, col2, col3, col4, col_m128);
+
+            x86_64::_mm_storeu_ps(result.as_mut_ptr().add(col * 4), r4);
+        }
+
+        result
+    }
+}
+
+pub fn mat4_mul_vec4(a: &[f32; 16], b: [f32; 4]) -> [f32; 4] {
+    // https://math.stackexchange.com/a/64639
+    unsafe {
+        let col1 = x86_64::_mm_loadu_ps(a.as_ptr().add(0));
+        let col2 = x86_64::_mm_loadu_ps(a.as_ptr().add(4));
+        let col3 = x86_64::_mm_loadu_ps(a.as_ptr().add(8));
+        let col4 = x86_64::_mm_loadu_ps(a.as_ptr().add(12));
+
+        mem::transmute(combine(col1, col2, col3, col4, load(&b)))
+    }
+}
+
+unsafe fn load(data: &[f32; 4]) -> x86_64::__m128 {
+    x86_64::_mm_loadu_ps(data.as_ptr())
+}
+
+unsafe fn combine(col1: x86_64::__m128, col2: x86_64::__m128, col3: x86_64::__m128, col4: x86_64::__m128, rhs: x86_64::__m128) -> x86_64::__m128 {
+    let x_m128 = x86_64::_mm_shuffle_ps::<0x00>(rhs, rhs);
+    let y_m128 = x86_64::_mm_shuffle_ps::<0x55>(rhs, rhs);
+    let z_m128 = x86_64::_mm_shuffle_ps::<0xaa>(rhs, rhs);
+    let w_m128 = x86_64::_mm_shuffle_ps::<0xff>(rhs, rhs);
+
+    let r1 = x86_64::_mm_mul_ps(x_m128, col1);
+    let r2 = x86_64::_mm_add_ps(r1, x86_64::_mm_mul_ps(y_m128, col2));
+    let r3 = x86_64::_mm_add_ps(r2, x86_64::_mm_mul_ps(z_m128, col3));
+    x86_64::_mm_add_ps(r3, x86_64::_mm_mul_ps(w_m128, col4))
+}
@@ -0,0 +1,116 @@
+use crate::mat3x3::Mat3x3;
+use crate::mat4x4::Mat4x4;
+use crate::vec2::Vec2;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform2D {
+    pub position: Vec2,
+    pub rotation: f32,
+    pub scale: Vec2,
+    pub skew: Vec2,
+    pub pivot: Vec2,
+}
+
+impl Transform2D {
+    pub fn new(position: Vec2, rotation: f32, scale: Vec2) -> Self {
+        Self { position, rotation, scale, skew: Vec2::new(0.0, 0.0), pivot: Vec2::new(0.0, 0.0) }
+    }
+
+    // Pivot is expressed in local (unscaled) units, it's the point around which the object is scaled, skewed and rotated,
+    // and which ends up exactly at the position
+    pub fn get_matrix(&self) -> Mat3x3 {
+        let translation = Mat3x3::translate(self.position);
+        let rotation = Mat3x3::rotate(self.rotation);
+        let skew = Mat3x3::skew(self.skew);
+        let scale = Mat3x3::scale(self.scale);
+        let pivot_offset = Mat3x3::translate(-self.pivot);
+
+        translation * rotation * skew * scale * pivot_offset
+    }
+
+    // Children are positioned relatively to their parent, so the world matrix of the child is the parent's world matrix
+    // combined with the local one
+    pub fn get_world_matrix(&self, parent: &Mat3x3) -> Mat3x3 {
+        *parent * self.get_matrix()
+    }
+
+    pub fn combine(&self, child: &Transform2D) -> Mat3x3 {
+        child.get_world_matrix(&self.get_matrix())
+    }
+
+    pub fn get_mat4x4(&self) -> Mat4x4 {
+        Mat4x4::from(self.get_matrix())
+    }
+
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        self.get_matrix().transform_point(point)
+    }
+
+    pub fn inverse_transform_point(&self, point: Vec2) -> Option<Vec2> {
+        self.get_matrix().inverse().map(|p| p.transform_point(point))
+    }
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Self::new(Vec2::new(0.0, 0.0), 0.0, Vec2::new(1.0, 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec3::Vec3;
+
+    fn assert_point_eq(a: Vec2, b: Vec2) {
+        assert!((a.x - b.x).abs() < 1e-3 && (a.y - b.y).abs() < 1e-3, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn pivot_is_placed_at_position() {
+        let transform = Transform2D { pivot: Vec2::new(4.0, 5.0), ..Transform2D::new(Vec2::new(10.0, 20.0), 0.3, Vec2::new(2.0, 3.0)) };
+        assert_point_eq(transform.transform_point(Vec2::new(4.0, 5.0)), Vec2::new(10.0, 20.0));
+    }
+
+    #[test]
+    fn matches_drawable_matrix() {
+        let transform = Transform2D { pivot: Vec2::new(4.0, 5.0), ..Transform2D::new(Vec2::new(10.0, 20.0), 0.3, Vec2::new(2.0, 3.0)) };
+        let expected =
+            Mat4x4::translate(Vec3::new(10.0, 20.0, 0.0)) * Mat4x4::rotate(0.3) * Mat4x4::scale(Vec3::new(2.0, 3.0, 1.0)) * Mat4x4::translate(Vec3::new(-4.0, -5.0, 0.0));
+        let matrix = transform.get_mat4x4();
+
+        for index in 0..16 {
+            assert!((matrix[index] - expected[index]).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn compose() {
+        let parent = Transform2D::new(Vec2::new(100.0, 0.0), std::f32::consts::FRAC_PI_2, Vec2::new(2.0, 2.0));
+        let child = Transform2D::new(Vec2::new(10.0, 0.0), 0.0, Vec2::new(1.0, 1.0));
+        let world = parent.combine(&child);
+
+        // Child offset is scaled and rotated by the parent, then moved to its position
+        assert_point_eq(world.transform_point(Vec2::new(0.0, 0.0)), Vec2::new(100.0, 20.0));
+        assert_eq!(world, child.get_world_matrix(&parent.get_matrix()));
+    }
+
+    #[test]
+    fn inverse_round_trip() {
+        let transform = Transform2D { position: Vec2::new(-7.0, 3.0), rotation: 1.1, scale: Vec2::new(0.5, 4.0), skew: Vec2::new(0.3, -0.2), pivot: Vec2::new(2.0, 1.0) };
+
+        for point in [Vec2::new(0.0, 0.0), Vec2::new(12.5, -3.0), Vec2::new(-100.0, 250.0)] {
+            let transformed = transform.transform_point(point);
+            assert_point_eq(transform.inverse_transform_point(transformed).unwrap(), point);
+        }
+
+        let world = Transform2D::default().combine(&transform);
+        assert_point_eq(world.inverse().unwrap().transform_point(world.transform_point(Vec2::new(1.0, 2.0))), Vec2::new(1.0, 2.0));
+    }
+
+    #[test]
+    fn inverse_of_degenerate_scale() {
+        let transform = Transform2D::new(Vec2::new(1.0, 1.0), 0.0, Vec2::new(0.0, 1.0));
+        assert!(transform.inverse_transform_point(Vec2::new(1.0, 1.0)).is_none());
+    }
+}
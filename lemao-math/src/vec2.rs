@@ -1,3 +1,4 @@
+use crate::simd::backend;
 use std::f32::consts::PI;
 use std::ops::Add;
 use std::ops::AddAssign;
@@ -45,7 +46,7 @@ impl Vec2 {
     }
 
     pub fn dot(&self, rhs: Vec2) -> f32 {
-        self.x * rhs.x + self.y * rhs.y
+        backend::dot(self.to_array(), rhs.to_array())
     }
 
     pub fn signed_angle(&self, rhs: Vec2) -> f32 {
@@ -74,12 +75,25 @@ impl Vec2 {
     pub fn as_ptr(&self) -> *const f32 {
         self as *const _ as *const f32
     }
+
+    // Unused lanes are filled with zeros, except divisors which have to be non-zero to not produce NaNs
+    fn to_array(self) -> [f32; 4] {
+        [self.x, self.y, 0.0, 0.0]
+    }
+
+    fn to_divisor_array(self) -> [f32; 4] {
+        [self.x, self.y, 1.0, 1.0]
+    }
+
+    fn from_array(data: [f32; 4]) -> Self {
+        Self { x: data[0], y: data[1] }
+    }
 }
 
 impl Add for Vec2 {
     type Output = Self;
     fn add(self, rhs: Self) -> Self::Output {
-        Self::Output { x: self.x + rhs.x, y: self.y + rhs.y }
+        Self::from_array(backend::add(self.to_array(), rhs.to_array()))
     }
 }
 
@@ -92,7 +106,7 @@ impl AddAssign for Vec2 {
 impl Sub for Vec2 {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self::Output {
-        Self::Output { x: self.x - rhs.x, y: self.y - rhs.y }
+        Self::from_array(backend::sub(self.to_array(), rhs.to_array()))
     }
 }
 
@@ -105,35 +119,35 @@ impl SubAssign for Vec2 {
 impl Mul for Vec2 {
     type Output = Self;
     fn mul(self, rhs: Self) -> Self::Output {
-        Self::Output { x: self.x * rhs.x, y: self.y * rhs.y }
+        Self::from_array(backend::mul(self.to_array(), rhs.to_array()))
     }
 }
 
 impl Mul<f32> for Vec2 {
     type Output = Vec2;
     fn mul(self, rhs: f32) -> Self::Output {
-        Self::Output { x: self.x * rhs, y: self.y * rhs }
+        Vec2::from_array(backend::mul_scalar(self.to_array(), rhs))
     }
 }
 
 impl Mul<Vec2> for f32 {
     type Output = Vec2;
     fn mul(self, rhs: Vec2) -> Self::Output {
-        Self::Output { x: self * rhs.x, y: self * rhs.y }
+        Vec2::from_array(backend::mul_scalar(rhs.to_array(), self))
     }
 }
 
 impl Div<f32> for Vec2 {
     type Output = Vec2;
     fn div(self, rhs: f32) -> Self::Output {
-        Self::Output { x: self.x / rhs, y: self.y / rhs }
+        Vec2::from_array(backend::div(self.to_array(), [rhs; 4]))
     }
 }
 
 impl Div<Vec2> for Vec2 {
     type Output = Vec2;
     fn div(self, rhs: Vec2) -> Self::Output {
-        Self::Output { x: self.x / rhs.x, y: self.y / rhs.y }
+        Vec2::from_array(backend::div(self.to_array(), rhs.to_divisor_array()))
     }
 }
 
@@ -1,3 +1,4 @@
+use crate::simd::backend;
 use crate::vec2::Vec2;
 use std::ops::Add;
 use std::ops::AddAssign;
@@ -24,15 +25,27 @@ impl Vec3 {
         Vec3::new(self.x.floor(), self.y.floor(), self.z.floor())
     }
 
+    pub fn dot(&self, rhs: Vec3) -> f32 {
+        backend::dot(self.to_array(), rhs.to_array())
+    }
+
     pub fn as_ptr(&self) -> *const f32 {
         self as *const _ as *const f32
     }
+
+    fn to_array(self) -> [f32; 4] {
+        [self.x, self.y, self.z, 0.0]
+    }
+
+    fn from_array(data: [f32; 4]) -> Self {
+        Self { x: data[0], y: data[1], z: data[2] }
+    }
 }
 
 impl Add for Vec3 {
     type Output = Self;
     fn add(self, rhs: Self) -> Self::Output {
-        Self::Output { x: self.x + rhs.x, y: self.y + rhs.y, z: self.z + rhs.z }
+        Self::from_array(backend::add(self.to_array(), rhs.to_array()))
     }
 }
 
@@ -45,7 +58,7 @@ impl AddAssign for Vec3 {
 impl Sub for Vec3 {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self::Output {
-        Self::Output { x: self.x - rhs.x, y: self.y - rhs.y, z: self.z - rhs.z }
+        Self::from_array(backend::sub(self.to_array(), rhs.to_array()))
     }
 }
 
@@ -58,28 +71,28 @@ impl SubAssign for Vec3 {
 impl Mul for Vec3 {
     type Output = Self;
     fn mul(self, rhs: Self) -> Self::Output {
-        Self::Output { x: self.x * rhs.x, y: self.y * rhs.y, z: self.z * rhs.z }
+        Self::from_array(backend::mul(self.to_array(), rhs.to_array()))
     }
 }
 
 impl Mul<f32> for Vec3 {
     type Output = Vec3;
     fn mul(self, rhs: f32) -> Self::Output {
-        Self::Output { x: self.x * rhs, y: self.y * rhs, z: self.z * rhs }
+        Vec3::from_array(backend::mul_scalar(self.to_array(), rhs))
     }
 }
 
 impl Mul<Vec3> for f32 {
     type Output = Vec3;
     fn mul(self, rhs: Vec3) -> Self::Output {
-        Self::Output { x: self * rhs.x, y: self * rhs.y, z: self * rhs.z }
+        Vec3::from_array(backend::mul_scalar(rhs.to_array(), self))
     }
 }
 
 impl Div<f32> for Vec3 {
     type Output = Vec3;
     fn div(self, rhs: f32) -> Self::Output {
-        Self::Output { x: self.x / rhs, y: self.y / rhs, z: self.z / rhs }
+        Vec3::from_array(backend::div(self.to_array(), [rhs; 4]))
     }
 }
 
@@ -1,3 +1,12 @@
+use crate::simd::backend;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Div;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+
 #[repr(C)]
 #[repr(align(16))]
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
@@ -13,6 +22,10 @@ impl Vec4 {
         Self { x, y, z, w }
     }
 
+    pub fn dot(&self, rhs: Vec4) -> f32 {
+        backend::dot(self.to_array(), rhs.to_array())
+    }
+
     pub fn as_ptr(&self) -> *const f32 {
         self as *const _ as *const f32
     }
@@ -20,4 +33,80 @@ impl Vec4 {
     pub fn as_mut_ptr(&mut self) -> *mut f32 {
         self as *mut _ as *mut f32
     }
+
+    pub(crate) fn to_array(self) -> [f32; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    pub(crate) fn from_array(data: [f32; 4]) -> Self {
+        Self { x: data[0], y: data[1], z: data[2], w: data[3] }
+    }
+}
+
+impl Add for Vec4 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_array(backend::add(self.to_array(), rhs.to_array()))
+    }
+}
+
+impl AddAssign for Vec4 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vec4 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::from_array(backend::sub(self.to_array(), rhs.to_array()))
+    }
+}
+
+impl SubAssign for Vec4 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for Vec4 {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::from_array(backend::mul(self.to_array(), rhs.to_array()))
+    }
+}
+
+impl Mul<f32> for Vec4 {
+    type Output = Vec4;
+    fn mul(self, rhs: f32) -> Self::Output {
+        Vec4::from_array(backend::mul_scalar(self.to_array(), rhs))
+    }
+}
+
+impl Mul<Vec4> for f32 {
+    type Output = Vec4;
+    fn mul(self, rhs: Vec4) -> Self::Output {
+        Vec4::from_array(backend::mul_scalar(rhs.to_array(), self))
+    }
+}
+
+impl Div<f32> for Vec4 {
+    type Output = Vec4;
+    fn div(self, rhs: f32) -> Self::Output {
+        Vec4::from_array(backend::div(self.to_array(), [rhs; 4]))
+    }
+}
+
+impl Div<Vec4> for Vec4 {
+    type Output = Vec4;
+    fn div(self, rhs: Vec4) -> Self::Output {
+        Vec4::from_array(backend::div(self.to_array(), rhs.to_array()))
+    }
+}
+
+impl Neg for Vec4 {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::Output { x: -self.x, y: -self.y, z: -self.z, w: -self.w }
+    }
 }
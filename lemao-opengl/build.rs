@@ -80,9 +80,26 @@ fn build_linux_binding() {
             .write_to_file(glx_binging_path)
             .unwrap();
     }
+
+    let egl_binging_path = "./src/bindings/egl.rs";
+    if !Path::new(egl_binging_path).exists() {
+        lemao_bindgen::Builder::default()
+            .header("/usr/include/EGL/egl.h")
+            .header("/usr/include/EGL/eglext.h")
+            .parse_callbacks(Box::new(lemao_bindgen::CargoCallbacks))
+            .layout_tests(false)
+            .allowlist_file("/usr/include/EGL/egl.h")
+            .allowlist_file("/usr/include/EGL/eglext.h")
+            .allowlist_file("/usr/include/EGL/eglplatform.h")
+            .generate()
+            .unwrap()
+            .write_to_file(egl_binging_path)
+            .unwrap();
+    }
 }
 
 #[cfg(unix)]
 fn link_linux_libs() {
     println!("cargo:rustc-link-lib=dylib=GLX");
+    println!("cargo:rustc-link-lib=dylib=EGL");
 }
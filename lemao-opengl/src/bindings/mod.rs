@@ -9,3 +9,6 @@ pub mod winapi;
 
 #[cfg(unix)]
 pub mod glx;
+
+#[cfg(unix)]
+pub mod egl;
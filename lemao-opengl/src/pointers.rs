@@ -2,47 +2,77 @@ use crate::bindings::opengl;
 use std::ffi::CString;
 use std::mem;
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum OpenGLProfile {
+    Core,
+    Es,
+}
+
 #[allow(non_snake_case)]
 #[derive(Debug)]
 pub struct OpenGLPointers {
+    pub profile: OpenGLProfile,
+
+    pub glActiveTexture: opengl::PFNGLACTIVETEXTUREPROC,
     pub glAttachShader: opengl::PFNGLATTACHSHADERPROC,
+    pub glBeginQuery: opengl::PFNGLBEGINQUERYPROC,
     pub glBindBuffer: opengl::PFNGLBINDBUFFERPROC,
+    pub glBindFramebuffer: opengl::PFNGLBINDFRAMEBUFFERPROC,
+    pub glBindRenderbuffer: opengl::PFNGLBINDRENDERBUFFERPROC,
     pub glBindTexture: opengl::PFNGLBINDTEXTUREPROC,
     pub glBindVertexArray: opengl::PFNGLBINDVERTEXARRAYPROC,
     pub glBlendFunc: opengl::PFNGLBLENDFUNCPROC,
     pub glBufferData: opengl::PFNGLBUFFERDATAPROC,
     pub glBufferSubData: opengl::PFNGLBUFFERSUBDATAPROC,
+    pub glCheckFramebufferStatus: opengl::PFNGLCHECKFRAMEBUFFERSTATUSPROC,
     pub glClear: opengl::PFNGLCLEARPROC,
     pub glClearColor: opengl::PFNGLCLEARCOLORPROC,
+    pub glColorMask: opengl::PFNGLCOLORMASKPROC,
     pub glCompileShader: opengl::PFNGLCOMPILESHADERPROC,
     pub glCreateProgram: opengl::PFNGLCREATEPROGRAMPROC,
     pub glCreateShader: opengl::PFNGLCREATESHADERPROC,
-    pub glDebugMessageCallback: opengl::PFNGLDEBUGMESSAGECALLBACKPROC,
+    pub glDebugMessageCallback: Option<opengl::PFNGLDEBUGMESSAGECALLBACKPROC>,
     pub glDeleteBuffers: opengl::PFNGLDELETEBUFFERSPROC,
+    pub glDeleteFramebuffers: opengl::PFNGLDELETEFRAMEBUFFERSPROC,
     pub glDeleteProgram: opengl::PFNGLDELETEPROGRAMPROC,
+    pub glDeleteQueries: opengl::PFNGLDELETEQUERIESPROC,
+    pub glDeleteRenderbuffers: opengl::PFNGLDELETERENDERBUFFERSPROC,
     pub glDeleteTextures: opengl::PFNGLDELETETEXTURESPROC,
     pub glDeleteShader: opengl::PFNGLDELETESHADERPROC,
     pub glDeleteVertexArrays: opengl::PFNGLDELETEVERTEXARRAYSPROC,
     pub glDisable: opengl::PFNGLDISABLEPROC,
     pub glDrawArrays: opengl::PFNGLDRAWARRAYSPROC,
     pub glDrawElements: opengl::PFNGLDRAWELEMENTSPROC,
+    pub glDrawElementsInstanced: opengl::PFNGLDRAWELEMENTSINSTANCEDPROC,
+    pub glEndQuery: opengl::PFNGLENDQUERYPROC,
     pub glEnable: opengl::PFNGLENABLEPROC,
     pub glEnableVertexAttribArray: opengl::PFNGLENABLEVERTEXATTRIBARRAYPROC,
+    pub glFramebufferRenderbuffer: opengl::PFNGLFRAMEBUFFERRENDERBUFFERPROC,
+    pub glFramebufferTexture2D: opengl::PFNGLFRAMEBUFFERTEXTURE2DPROC,
     pub glGenBuffers: opengl::PFNGLGENBUFFERSPROC,
+    pub glGenFramebuffers: opengl::PFNGLGENFRAMEBUFFERSPROC,
     pub glGenerateMipmap: opengl::PFNGLGENERATEMIPMAPPROC,
+    pub glGenQueries: opengl::PFNGLGENQUERIESPROC,
+    pub glGenRenderbuffers: opengl::PFNGLGENRENDERBUFFERSPROC,
     pub glGenTextures: opengl::PFNGLGENTEXTURESPROC,
     pub glGenVertexArrays: opengl::PFNGLGENVERTEXARRAYSPROC,
     pub glGetActiveUniform: opengl::PFNGLGETACTIVEUNIFORMPROC,
     pub glGetError: opengl::PFNGLGETERRORPROC,
+    pub glGetIntegerv: opengl::PFNGLGETINTEGERVPROC,
     pub glGetProgramiv: opengl::PFNGLGETPROGRAMIVPROC,
     pub glGetProgramInfoLog: opengl::PFNGLGETPROGRAMINFOLOGPROC,
+    pub glGetQueryObjectiv: Option<opengl::PFNGLGETQUERYOBJECTIVPROC>,
+    pub glGetQueryObjectui64v: Option<opengl::PFNGLGETQUERYOBJECTUI64VPROC>,
     pub glGetShaderInfoLog: opengl::PFNGLGETSHADERINFOLOGPROC,
     pub glGetShaderiv: opengl::PFNGLGETSHADERIVPROC,
     pub glGetString: opengl::PFNGLGETSTRINGPROC,
     pub glGetUniformLocation: opengl::PFNGLGETUNIFORMLOCATIONPROC,
     pub glLinkProgram: opengl::PFNGLLINKPROGRAMPROC,
+    pub glRenderbufferStorage: opengl::PFNGLRENDERBUFFERSTORAGEPROC,
     pub glScissor: opengl::PFNGLSCISSORPROC,
     pub glShaderSource: opengl::PFNGLSHADERSOURCEPROC,
+    pub glStencilFunc: opengl::PFNGLSTENCILFUNCPROC,
+    pub glStencilOp: opengl::PFNGLSTENCILOPPROC,
     pub glTexImage2D: opengl::PFNGLTEXIMAGE2DPROC,
     pub glTexParameteri: opengl::PFNGLTEXPARAMETERIPROC,
     pub glUniformMatrix4fv: opengl::PFNGLUNIFORMMATRIX4FVPROC,
@@ -51,6 +81,7 @@ pub struct OpenGLPointers {
     pub glUniform2fv: opengl::PFNGLUNIFORM2FVPROC,
     pub glUniform4fv: opengl::PFNGLUNIFORM4FVPROC,
     pub glUseProgram: opengl::PFNGLUSEPROGRAMPROC,
+    pub glVertexAttribDivisor: opengl::PFNGLVERTEXATTRIBDIVISORPROC,
     pub glVertexAttribPointer: opengl::PFNGLVERTEXATTRIBPOINTERPROC,
     pub glViewport: opengl::PFNGLVIEWPORTPROC,
 
@@ -64,84 +95,123 @@ pub struct OpenGLPointers {
     pub wglSwapIntervalEXT: crate::bindings::wgl::PFNWGLSWAPINTERVALEXTPROC,
 
     #[cfg(unix)]
-    pub glXCreateContextAttribsARB: crate::bindings::glx::PFNGLXCREATECONTEXTATTRIBSARBPROC,
+    pub glXCreateContextAttribsARB: Option<crate::bindings::glx::PFNGLXCREATECONTEXTATTRIBSARBPROC>,
 
     #[cfg(unix)]
-    pub glXSwapIntervalEXT: crate::bindings::glx::PFNGLXSWAPINTERVALEXTPROC,
+    pub glXSwapIntervalEXT: Option<crate::bindings::glx::PFNGLXSWAPINTERVALEXTPROC>,
 }
 
-impl Default for OpenGLPointers {
-    fn default() -> Self {
+impl OpenGLPointers {
+    pub fn new(profile: OpenGLProfile) -> Self {
         Self {
-            glAttachShader: get_proc_address::<opengl::PFNGLATTACHSHADERPROC>("glAttachShader"),
-            glBindBuffer: get_proc_address::<opengl::PFNGLBINDBUFFERPROC>("glBindBuffer"),
-            glBindTexture: get_proc_address::<opengl::PFNGLBINDTEXTUREPROC>("glBindTexture"),
-            glBindVertexArray: get_proc_address::<opengl::PFNGLBINDVERTEXARRAYPROC>("glBindVertexArray"),
-            glBlendFunc: get_proc_address::<opengl::PFNGLBLENDFUNCPROC>("glBlendFunc"),
-            glBufferData: get_proc_address::<opengl::PFNGLBUFFERDATAPROC>("glBufferData"),
-            glBufferSubData: get_proc_address::<opengl::PFNGLBUFFERSUBDATAPROC>("glBufferSubData"),
-            glClear: get_proc_address::<opengl::PFNGLCLEARPROC>("glClear"),
-            glClearColor: get_proc_address::<opengl::PFNGLCLEARCOLORPROC>("glClearColor"),
-            glCreateProgram: get_proc_address::<opengl::PFNGLCREATEPROGRAMPROC>("glCreateProgram"),
-            glCompileShader: get_proc_address::<opengl::PFNGLCOMPILESHADERPROC>("glCompileShader"),
-            glCreateShader: get_proc_address::<opengl::PFNGLCREATESHADERPROC>("glCreateShader"),
-            glDebugMessageCallback: get_proc_address::<opengl::PFNGLDEBUGMESSAGECALLBACKPROC>("glDebugMessageCallback"),
-            glDeleteBuffers: get_proc_address::<opengl::PFNGLDELETEBUFFERSPROC>("glDeleteBuffers"),
-            glDeleteProgram: get_proc_address::<opengl::PFNGLDELETEPROGRAMPROC>("glDeleteProgram"),
-            glDeleteTextures: get_proc_address::<opengl::PFNGLDELETETEXTURESPROC>("glDeleteTextures"),
-            glDeleteShader: get_proc_address::<opengl::PFNGLDELETESHADERPROC>("glDeleteShader"),
-            glDeleteVertexArrays: get_proc_address::<opengl::PFNGLDELETEVERTEXARRAYSPROC>("glDeleteVertexArrays"),
-            glDisable: get_proc_address::<opengl::PFNGLDISABLEPROC>("glDisable"),
-            glDrawArrays: get_proc_address::<opengl::PFNGLDRAWARRAYSPROC>("glDrawArrays"),
-            glDrawElements: get_proc_address::<opengl::PFNGLDRAWELEMENTSPROC>("glDrawElements"),
-            glEnable: get_proc_address::<opengl::PFNGLENABLEPROC>("glEnable"),
-            glEnableVertexAttribArray: get_proc_address::<opengl::PFNGLENABLEVERTEXATTRIBARRAYPROC>("glEnableVertexAttribArray"),
-            glGenBuffers: get_proc_address::<opengl::PFNGLGENBUFFERSPROC>("glGenBuffers"),
-            glGenerateMipmap: get_proc_address::<opengl::PFNGLGENERATEMIPMAPPROC>("glGenerateMipmap"),
-            glGenTextures: get_proc_address::<opengl::PFNGLGENTEXTURESPROC>("glGenTextures"),
-            glGenVertexArrays: get_proc_address::<opengl::PFNGLGENBUFFERSPROC>("glGenVertexArrays"),
-            glGetActiveUniform: get_proc_address::<opengl::PFNGLGETACTIVEUNIFORMPROC>("glGetActiveUniform"),
-            glGetError: get_proc_address::<opengl::PFNGLGETERRORPROC>("glGetError"),
-            glGetProgramiv: get_proc_address::<opengl::PFNGLGETPROGRAMIVPROC>("glGetProgramiv"),
-            glGetProgramInfoLog: get_proc_address::<opengl::PFNGLGETPROGRAMINFOLOGPROC>("glGetProgramInfoLog"),
-            glGetShaderInfoLog: get_proc_address::<opengl::PFNGLGETSHADERINFOLOGPROC>("glGetShaderInfoLog"),
-            glGetShaderiv: get_proc_address::<opengl::PFNGLGETSHADERIVPROC>("glGetShaderiv"),
-            glGetString: get_proc_address::<opengl::PFNGLGETSTRINGPROC>("glGetString"),
-            glGetUniformLocation: get_proc_address::<opengl::PFNGLGETUNIFORMLOCATIONPROC>("glGetUniformLocation"),
-            glLinkProgram: get_proc_address::<opengl::PFNGLLINKPROGRAMPROC>("glLinkProgram"),
-            glScissor: get_proc_address::<opengl::PFNGLSCISSORPROC>("glScissor"),
-            glShaderSource: get_proc_address::<opengl::PFNGLSHADERSOURCEPROC>("glShaderSource"),
-            glTexImage2D: get_proc_address::<opengl::PFNGLTEXIMAGE2DPROC>("glTexImage2D"),
-            glTexParameteri: get_proc_address::<opengl::PFNGLTEXPARAMETERIPROC>("glTexParameteri"),
-            glUniformMatrix4fv: get_proc_address::<opengl::PFNGLUNIFORMMATRIX4FVPROC>("glUniformMatrix4fv"),
-            glUniform1f: get_proc_address::<opengl::PFNGLUNIFORM1FPROC>("glUniform1f"),
-            glUniform1i: get_proc_address::<opengl::PFNGLUNIFORM1IPROC>("glUniform1i"),
-            glUniform2fv: get_proc_address::<opengl::PFNGLUNIFORM2FVPROC>("glUniform2fv"),
-            glUniform4fv: get_proc_address::<opengl::PFNGLUNIFORM4FVPROC>("glUniform4fv"),
-            glUseProgram: get_proc_address::<opengl::PFNGLUSEPROGRAMPROC>("glUseProgram"),
-            glVertexAttribPointer: get_proc_address::<opengl::PFNGLVERTEXATTRIBPOINTERPROC>("glVertexAttribPointer"),
-            glViewport: get_proc_address::<opengl::PFNGLVIEWPORTPROC>("glViewport"),
+            profile,
+
+            glActiveTexture: get_proc_address::<opengl::PFNGLACTIVETEXTUREPROC>(profile, "glActiveTexture"),
+            glAttachShader: get_proc_address::<opengl::PFNGLATTACHSHADERPROC>(profile, "glAttachShader"),
+            glBeginQuery: get_proc_address::<opengl::PFNGLBEGINQUERYPROC>(profile, "glBeginQuery"),
+            glBindBuffer: get_proc_address::<opengl::PFNGLBINDBUFFERPROC>(profile, "glBindBuffer"),
+            glBindFramebuffer: get_proc_address::<opengl::PFNGLBINDFRAMEBUFFERPROC>(profile, "glBindFramebuffer"),
+            glBindRenderbuffer: get_proc_address::<opengl::PFNGLBINDRENDERBUFFERPROC>(profile, "glBindRenderbuffer"),
+            glBindTexture: get_proc_address::<opengl::PFNGLBINDTEXTUREPROC>(profile, "glBindTexture"),
+            glBindVertexArray: get_proc_address::<opengl::PFNGLBINDVERTEXARRAYPROC>(profile, "glBindVertexArray"),
+            glBlendFunc: get_proc_address::<opengl::PFNGLBLENDFUNCPROC>(profile, "glBlendFunc"),
+            glBufferData: get_proc_address::<opengl::PFNGLBUFFERDATAPROC>(profile, "glBufferData"),
+            glBufferSubData: get_proc_address::<opengl::PFNGLBUFFERSUBDATAPROC>(profile, "glBufferSubData"),
+            glCheckFramebufferStatus: get_proc_address::<opengl::PFNGLCHECKFRAMEBUFFERSTATUSPROC>(profile, "glCheckFramebufferStatus"),
+            glClear: get_proc_address::<opengl::PFNGLCLEARPROC>(profile, "glClear"),
+            glClearColor: get_proc_address::<opengl::PFNGLCLEARCOLORPROC>(profile, "glClearColor"),
+            glColorMask: get_proc_address::<opengl::PFNGLCOLORMASKPROC>(profile, "glColorMask"),
+            glCreateProgram: get_proc_address::<opengl::PFNGLCREATEPROGRAMPROC>(profile, "glCreateProgram"),
+            glCompileShader: get_proc_address::<opengl::PFNGLCOMPILESHADERPROC>(profile, "glCompileShader"),
+            glCreateShader: get_proc_address::<opengl::PFNGLCREATESHADERPROC>(profile, "glCreateShader"),
+            glDebugMessageCallback: get_desktop_proc_address::<opengl::PFNGLDEBUGMESSAGECALLBACKPROC>(profile, "glDebugMessageCallback"),
+            glDeleteBuffers: get_proc_address::<opengl::PFNGLDELETEBUFFERSPROC>(profile, "glDeleteBuffers"),
+            glDeleteFramebuffers: get_proc_address::<opengl::PFNGLDELETEFRAMEBUFFERSPROC>(profile, "glDeleteFramebuffers"),
+            glDeleteProgram: get_proc_address::<opengl::PFNGLDELETEPROGRAMPROC>(profile, "glDeleteProgram"),
+            glDeleteQueries: get_proc_address::<opengl::PFNGLDELETEQUERIESPROC>(profile, "glDeleteQueries"),
+            glDeleteRenderbuffers: get_proc_address::<opengl::PFNGLDELETERENDERBUFFERSPROC>(profile, "glDeleteRenderbuffers"),
+            glDeleteTextures: get_proc_address::<opengl::PFNGLDELETETEXTURESPROC>(profile, "glDeleteTextures"),
+            glDeleteShader: get_proc_address::<opengl::PFNGLDELETESHADERPROC>(profile, "glDeleteShader"),
+            glDeleteVertexArrays: get_proc_address::<opengl::PFNGLDELETEVERTEXARRAYSPROC>(profile, "glDeleteVertexArrays"),
+            glDisable: get_proc_address::<opengl::PFNGLDISABLEPROC>(profile, "glDisable"),
+            glDrawArrays: get_proc_address::<opengl::PFNGLDRAWARRAYSPROC>(profile, "glDrawArrays"),
+            glDrawElements: get_proc_address::<opengl::PFNGLDRAWELEMENTSPROC>(profile, "glDrawElements"),
+            glDrawElementsInstanced: get_proc_address::<opengl::PFNGLDRAWELEMENTSINSTANCEDPROC>(profile, "glDrawElementsInstanced"),
+            glEndQuery: get_proc_address::<opengl::PFNGLENDQUERYPROC>(profile, "glEndQuery"),
+            glEnable: get_proc_address::<opengl::PFNGLENABLEPROC>(profile, "glEnable"),
+            glEnableVertexAttribArray: get_proc_address::<opengl::PFNGLENABLEVERTEXATTRIBARRAYPROC>(profile, "glEnableVertexAttribArray"),
+            glFramebufferRenderbuffer: get_proc_address::<opengl::PFNGLFRAMEBUFFERRENDERBUFFERPROC>(profile, "glFramebufferRenderbuffer"),
+            glFramebufferTexture2D: get_proc_address::<opengl::PFNGLFRAMEBUFFERTEXTURE2DPROC>(profile, "glFramebufferTexture2D"),
+            glGenBuffers: get_proc_address::<opengl::PFNGLGENBUFFERSPROC>(profile, "glGenBuffers"),
+            glGenFramebuffers: get_proc_address::<opengl::PFNGLGENFRAMEBUFFERSPROC>(profile, "glGenFramebuffers"),
+            glGenerateMipmap: get_proc_address::<opengl::PFNGLGENERATEMIPMAPPROC>(profile, "glGenerateMipmap"),
+            glGenQueries: get_proc_address::<opengl::PFNGLGENQUERIESPROC>(profile, "glGenQueries"),
+            glGenRenderbuffers: get_proc_address::<opengl::PFNGLGENRENDERBUFFERSPROC>(profile, "glGenRenderbuffers"),
+            glGenTextures: get_proc_address::<opengl::PFNGLGENTEXTURESPROC>(profile, "glGenTextures"),
+            glGenVertexArrays: get_proc_address::<opengl::PFNGLGENBUFFERSPROC>(profile, "glGenVertexArrays"),
+            glGetActiveUniform: get_proc_address::<opengl::PFNGLGETACTIVEUNIFORMPROC>(profile, "glGetActiveUniform"),
+            glGetError: get_proc_address::<opengl::PFNGLGETERRORPROC>(profile, "glGetError"),
+            glGetIntegerv: get_proc_address::<opengl::PFNGLGETINTEGERVPROC>(profile, "glGetIntegerv"),
+            glGetProgramiv: get_proc_address::<opengl::PFNGLGETPROGRAMIVPROC>(profile, "glGetProgramiv"),
+            glGetProgramInfoLog: get_proc_address::<opengl::PFNGLGETPROGRAMINFOLOGPROC>(profile, "glGetProgramInfoLog"),
+            glGetQueryObjectiv: get_desktop_proc_address::<opengl::PFNGLGETQUERYOBJECTIVPROC>(profile, "glGetQueryObjectiv"),
+            glGetQueryObjectui64v: get_desktop_proc_address::<opengl::PFNGLGETQUERYOBJECTUI64VPROC>(profile, "glGetQueryObjectui64v"),
+            glGetShaderInfoLog: get_proc_address::<opengl::PFNGLGETSHADERINFOLOGPROC>(profile, "glGetShaderInfoLog"),
+            glGetShaderiv: get_proc_address::<opengl::PFNGLGETSHADERIVPROC>(profile, "glGetShaderiv"),
+            glGetString: get_proc_address::<opengl::PFNGLGETSTRINGPROC>(profile, "glGetString"),
+            glGetUniformLocation: get_proc_address::<opengl::PFNGLGETUNIFORMLOCATIONPROC>(profile, "glGetUniformLocation"),
+            glLinkProgram: get_proc_address::<opengl::PFNGLLINKPROGRAMPROC>(profile, "glLinkProgram"),
+            glRenderbufferStorage: get_proc_address::<opengl::PFNGLRENDERBUFFERSTORAGEPROC>(profile, "glRenderbufferStorage"),
+            glScissor: get_proc_address::<opengl::PFNGLSCISSORPROC>(profile, "glScissor"),
+            glShaderSource: get_proc_address::<opengl::PFNGLSHADERSOURCEPROC>(profile, "glShaderSource"),
+            glStencilFunc: get_proc_address::<opengl::PFNGLSTENCILFUNCPROC>(profile, "glStencilFunc"),
+            glStencilOp: get_proc_address::<opengl::PFNGLSTENCILOPPROC>(profile, "glStencilOp"),
+            glTexImage2D: get_proc_address::<opengl::PFNGLTEXIMAGE2DPROC>(profile, "glTexImage2D"),
+            glTexParameteri: get_proc_address::<opengl::PFNGLTEXPARAMETERIPROC>(profile, "glTexParameteri"),
+            glUniformMatrix4fv: get_proc_address::<opengl::PFNGLUNIFORMMATRIX4FVPROC>(profile, "glUniformMatrix4fv"),
+            glUniform1f: get_proc_address::<opengl::PFNGLUNIFORM1FPROC>(profile, "glUniform1f"),
+            glUniform1i: get_proc_address::<opengl::PFNGLUNIFORM1IPROC>(profile, "glUniform1i"),
+            glUniform2fv: get_proc_address::<opengl::PFNGLUNIFORM2FVPROC>(profile, "glUniform2fv"),
+            glUniform4fv: get_proc_address::<opengl::PFNGLUNIFORM4FVPROC>(profile, "glUniform4fv"),
+            glUseProgram: get_proc_address::<opengl::PFNGLUSEPROGRAMPROC>(profile, "glUseProgram"),
+            glVertexAttribDivisor: get_proc_address::<opengl::PFNGLVERTEXATTRIBDIVISORPROC>(profile, "glVertexAttribDivisor"),
+            glVertexAttribPointer: get_proc_address::<opengl::PFNGLVERTEXATTRIBPOINTERPROC>(profile, "glVertexAttribPointer"),
+            glViewport: get_proc_address::<opengl::PFNGLVIEWPORTPROC>(profile, "glViewport"),
 
             #[cfg(windows)]
-            wglChoosePixelFormatARB: get_proc_address::<crate::bindings::wgl::PFNWGLCHOOSEPIXELFORMATARBPROC>("wglChoosePixelFormatARB"),
+            wglChoosePixelFormatARB: get_proc_address::<crate::bindings::wgl::PFNWGLCHOOSEPIXELFORMATARBPROC>(profile, "wglChoosePixelFormatARB"),
 
             #[cfg(windows)]
-            wglCreateContextAttribsARB: get_proc_address::<crate::bindings::wgl::PFNWGLCREATECONTEXTATTRIBSARBPROC>("wglCreateContextAttribsARB"),
+            wglCreateContextAttribsARB: get_proc_address::<crate::bindings::wgl::PFNWGLCREATECONTEXTATTRIBSARBPROC>(profile, "wglCreateContextAttribsARB"),
 
             #[cfg(windows)]
-            wglSwapIntervalEXT: get_proc_address::<crate::bindings::wgl::PFNWGLSWAPINTERVALEXTPROC>("wglSwapIntervalEXT"),
+            wglSwapIntervalEXT: get_proc_address::<crate::bindings::wgl::PFNWGLSWAPINTERVALEXTPROC>(profile, "wglSwapIntervalEXT"),
 
             #[cfg(unix)]
-            glXCreateContextAttribsARB: get_proc_address::<crate::bindings::glx::PFNGLXCREATECONTEXTATTRIBSARBPROC>("glXCreateContextAttribsARB"),
+            glXCreateContextAttribsARB: get_desktop_proc_address::<crate::bindings::glx::PFNGLXCREATECONTEXTATTRIBSARBPROC>(profile, "glXCreateContextAttribsARB"),
 
             #[cfg(unix)]
-            glXSwapIntervalEXT: get_proc_address::<crate::bindings::glx::PFNGLXSWAPINTERVALEXTPROC>("glXSwapIntervalEXT"),
+            glXSwapIntervalEXT: get_desktop_proc_address::<crate::bindings::glx::PFNGLXSWAPINTERVALEXTPROC>(profile, "glXSwapIntervalEXT"),
         }
     }
 }
 
+impl Default for OpenGLPointers {
+    fn default() -> Self {
+        Self::new(OpenGLProfile::Core)
+    }
+}
+
+// Entry points which don't exist in OpenGL ES are not loaded at all there, as the loader would return null for them
+fn get_desktop_proc_address<T>(profile: OpenGLProfile, name: &str) -> Option<T> {
+    match profile {
+        OpenGLProfile::Core => Some(get_proc_address(profile, name)),
+        OpenGLProfile::Es => None,
+    }
+}
+
 #[cfg(windows)]
-fn get_proc_address<T>(name: &str) -> T {
+fn get_proc_address<T>(_profile: OpenGLProfile, name: &str) -> T {
     use crate::bindings::winapi;
 
     unsafe {
@@ -150,6 +220,7 @@ fn get_proc_address<T>(name: &str) -> T {
             "glBlendFunc",
             "glClear",
             "glClearColor",
+            "glColorMask",
             "glDeleteTextures",
             "glDisable",
             "glDrawArrays",
@@ -157,7 +228,10 @@ fn get_proc_address<T>(name: &str) -> T {
             "glEnable",
             "glGenTextures",
             "glGetError",
+            "glGetIntegerv",
             "glScissor",
+            "glStencilFunc",
+            "glStencilOp",
             "glTexImage2D",
             "glTexParameteri",
             "glViewport",
@@ -181,10 +255,19 @@ fn get_proc_address<T>(name: &str) -> T {
 }
 
 #[cfg(unix)]
-fn get_proc_address<T>(name: &str) -> T {
+fn get_proc_address<T>(profile: OpenGLProfile, name: &str) -> T {
     unsafe {
         let function_cstr = CString::new(name).unwrap();
-        let function_handle = crate::bindings::glx::glXGetProcAddressARB(function_cstr.as_ptr() as *const u8);
-        mem::transmute_copy::<crate::bindings::glx::__GLXextFuncPtr, T>(&function_handle)
+
+        match profile {
+            OpenGLProfile::Core => {
+                let function_handle = crate::bindings::glx::glXGetProcAddressARB(function_cstr.as_ptr() as *const u8);
+                mem::transmute_copy::<crate::bindings::glx::__GLXextFuncPtr, T>(&function_handle)
+            }
+            OpenGLProfile::Es => {
+                let function_handle = crate::bindings::egl::eglGetProcAddress(function_cstr.as_ptr());
+                mem::transmute_copy::<crate::bindings::egl::__eglMustCastToProperFunctionPointerType, T>(&function_handle)
+            }
+        }
     }
 }
@@ -1,22 +1,14 @@
 use crate::body::BodyShape;
 use crate::contacts::Contact;
+use crate::error::PhysicsError;
 use crate::storage::PhysicsStorage;
 use crate::{collisions, contacts, solvers};
 use core::f32;
 use lemao_core::renderer::context::RendererContext;
-use lemao_core::renderer::drawable::circle::Circle;
-use lemao_core::renderer::drawable::frame::Frame;
-use lemao_core::renderer::drawable::line::Line;
-use lemao_core::renderer::drawable::rectangle::Rectangle;
-use lemao_core::renderer::drawable::Color;
 use lemao_math::color::SolidColor;
 use lemao_math::vec2::Vec2;
 
 pub struct PhysicsContext {
-    pub debug_circle: Circle,
-    pub debug_frame: Frame,
-    pub debug_rectangle: Rectangle,
-    pub debug_line: Line,
     pub pixels_per_meter: f32,
     pub gravity: Vec2,
     pub substeps: usize,
@@ -26,24 +18,13 @@ pub struct PhysicsContext {
 }
 
 impl PhysicsContext {
-    pub fn new(renderer: &mut RendererContext) -> Result<Self, String> {
-        let physics = Self {
-            debug_circle: renderer.create_circle()?,
-            debug_frame: renderer.create_frame()?,
-            debug_rectangle: renderer.create_rectangle()?,
-            debug_line: renderer.create_line()?,
-            pixels_per_meter: 100.0,
-            gravity: Vec2::new(0.0, -9.81),
-            substeps: 1,
-
-            contacts: Default::default(),
-            bodies: Default::default(),
-        };
+    pub fn new() -> Result<Self, PhysicsError> {
+        let physics = Self { pixels_per_meter: 100.0, gravity: Vec2::new(0.0, -9.81), substeps: 1, contacts: Default::default(), bodies: Default::default() };
 
         Ok(physics)
     }
 
-    pub fn update(&mut self, mut delta_time: f32) -> Result<(), String> {
+    pub fn update(&mut self, mut delta_time: f32) -> Result<(), PhysicsError> {
         self.contacts.clear();
 
         delta_time /= self.substeps as f32;
@@ -107,52 +88,35 @@ impl PhysicsContext {
         Ok(())
     }
 
-    pub fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), String> {
+    // Only queues debug shapes, they are flushed by renderer.debug_draw()
+    pub fn draw(&self, renderer: &mut RendererContext) -> Result<(), PhysicsError> {
+        let body_color = SolidColor::new(1.0, 1.0, 1.0, 1.0);
+        let contact_color = SolidColor::new(1.0, 0.0, 0.0, 1.0);
+
         for body in self.bodies.iter() {
+            let position = body.position * self.pixels_per_meter;
+            let size = body.size * self.pixels_per_meter;
+
             match body.shape {
                 BodyShape::Box => {
-                    self.debug_frame.position = body.position * self.pixels_per_meter;
-                    self.debug_frame.rotation = body.rotation;
-                    self.debug_frame.size = body.size * self.pixels_per_meter;
-                    self.debug_frame.anchor = Vec2::new(0.5, 0.5);
-                    self.debug_frame.color = Color::SolidColor(SolidColor::new(1.0, 1.0, 1.0, 1.0));
-                    self.debug_frame.update();
-
-                    self.debug_line.from = body.position * self.pixels_per_meter;
-                    self.debug_line.to = (body.position + (Vec2::new_from_angle(body.rotation) * body.size.y / 2.0)) * self.pixels_per_meter;
-                    self.debug_line.color = Color::SolidColor(SolidColor::new(1.0, 1.0, 1.0, 1.0));
-                    self.debug_line.update();
-
-                    renderer.draw(&mut self.debug_frame)?;
-                    renderer.draw(&mut self.debug_line)?;
+                    let axis_x = Vec2::new_from_angle(body.rotation) * (size.x / 2.0);
+                    let axis_y = Vec2::new_from_angle(body.rotation + f32::consts::FRAC_PI_2) * (size.y / 2.0);
+                    let points = vec![position - axis_x - axis_y, position + axis_x - axis_y, position + axis_x + axis_y, position - axis_x + axis_y];
+
+                    renderer.debug_add_polygon(points, body_color, None);
                 }
                 BodyShape::Circle => {
-                    self.debug_circle.position = body.position * self.pixels_per_meter;
-                    self.debug_circle.rotation = body.rotation;
-                    self.debug_circle.size = body.size * self.pixels_per_meter;
-                    self.debug_circle.anchor = Vec2::new(0.5, 0.5);
-                    self.debug_circle.color = Color::SolidColor(SolidColor::new(1.0, 1.0, 1.0, 1.0));
-                    self.debug_circle.update();
-
-                    self.debug_line.from = body.position * self.pixels_per_meter;
-                    self.debug_line.to = (body.position + (Vec2::new_from_angle(body.rotation) * body.size.y / 2.0)) * self.pixels_per_meter;
-                    self.debug_line.color = Color::SolidColor(SolidColor::new(1.0, 1.0, 1.0, 1.0));
-                    self.debug_line.update();
-
-                    renderer.draw(&mut self.debug_circle)?;
-                    renderer.draw(&mut self.debug_line)?;
+                    renderer.debug_add_circle(position, size.x / 2.0, body_color, None);
                 }
             }
+
+            let direction = (body.position + (Vec2::new_from_angle(body.rotation) * body.size.y / 2.0)) * self.pixels_per_meter;
+            renderer.debug_add_line(position, direction, body_color, None);
         }
 
         for contact in &self.contacts {
-            self.debug_rectangle.position = contact.position * self.pixels_per_meter;
-            self.debug_rectangle.size = Vec2::new(10.0, 10.0);
-            self.debug_rectangle.anchor = Vec2::new(0.5, 0.5);
-            self.debug_rectangle.color = Color::SolidColor(SolidColor::new(1.0, 0.0, 0.0, 1.0));
-            self.debug_rectangle.update();
-
-            renderer.draw(&mut self.debug_rectangle)?;
+            let position = contact.position * self.pixels_per_meter;
+            renderer.debug_add_rectangle(position - Vec2::new(5.0, 5.0), Vec2::new(10.0, 10.0), contact_color, None);
         }
 
         Ok(())
@@ -0,0 +1,39 @@
+use lemao_core::error::CoreError;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PhysicsError {
+    Core(CoreError),
+    BodyNotFound { id: usize },
+}
+
+impl fmt::Display for PhysicsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Core(error) => write!(f, "{}", error),
+            Self::BodyNotFound { id } => write!(f, "Body {} not found", id),
+        }
+    }
+}
+
+impl Error for PhysicsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Core(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<CoreError> for PhysicsError {
+    fn from(error: CoreError) -> Self {
+        Self::Core(error)
+    }
+}
+
+impl From<PhysicsError> for String {
+    fn from(error: PhysicsError) -> Self {
+        error.to_string()
+    }
+}
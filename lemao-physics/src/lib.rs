@@ -3,5 +3,6 @@ pub mod collisions;
 pub mod constants;
 pub mod contacts;
 pub mod context;
+pub mod error;
 pub mod solvers;
 pub mod storage;
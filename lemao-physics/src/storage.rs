@@ -1,4 +1,5 @@
 use crate::body::Body;
+use crate::error::PhysicsError;
 use std::collections::VecDeque;
 use std::slice::Iter;
 use std::slice::IterMut;
@@ -11,7 +12,7 @@ pub struct PhysicsStorage {
 }
 
 impl PhysicsStorage {
-    pub fn store(&mut self, mut body: Body) -> Result<(), String> {
+    pub fn store(&mut self, mut body: Body) -> Result<(), PhysicsError> {
         let body_id = self.get_new_id();
         body.id = body_id;
         self.data.push(body);
@@ -24,27 +25,27 @@ impl PhysicsStorage {
         Ok(())
     }
 
-    pub fn get(&self, entity_id: usize) -> Result<&Body, String> {
+    pub fn get(&self, entity_id: usize) -> Result<&Body, PhysicsError> {
         match self.id_lookup.get(entity_id) {
             Some(Some(body_id)) => match self.data.get(*body_id) {
                 Some(body) => Ok(body),
-                None => Err(format!("Body {} not found", entity_id)),
+                None => Err(PhysicsError::BodyNotFound { id: entity_id }),
             },
-            _ => Err(format!("Body {} not found", entity_id)),
+            _ => Err(PhysicsError::BodyNotFound { id: entity_id }),
         }
     }
 
-    pub fn get_mut(&mut self, body_id: usize) -> Result<&mut Body, String> {
+    pub fn get_mut(&mut self, body_id: usize) -> Result<&mut Body, PhysicsError> {
         match self.id_lookup.get_mut(body_id) {
-            Some(Some(body_id)) => match self.data.get_mut(*body_id) {
+            Some(Some(index)) => match self.data.get_mut(*index) {
                 Some(body) => Ok(body),
-                None => Err(format!("Body {} not found", body_id)),
+                None => Err(PhysicsError::BodyNotFound { id: body_id }),
             },
-            _ => Err(format!("Body {} not found", body_id)),
+            _ => Err(PhysicsError::BodyNotFound { id: body_id }),
         }
     }
 
-    pub fn get_mut_2(&mut self, body1_id: usize, body2_id: usize) -> Result<(&mut Body, &mut Body), String> {
+    pub fn get_mut_2(&mut self, body1_id: usize, body2_id: usize) -> Result<(&mut Body, &mut Body), PhysicsError> {
         unsafe {
             let body1 = self.get_mut(body1_id)? as *mut _ as *mut Body;
             let body2 = self.get_mut(body2_id)? as *mut _ as *mut Body;
@@ -5,6 +5,7 @@ pub enum InputEvent {
     Unknown,
     WindowMoved(Vec2),
     WindowSizeChanged(Vec2),
+    ScaleFactorChanged(f32),
     WindowClosed,
     KeyPressed(Key),
     KeyReleased(Key),
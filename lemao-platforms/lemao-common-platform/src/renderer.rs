@@ -1,4 +1,34 @@
+use lemao_opengl::pointers::OpenGLProfile;
+
+pub const DEFAULT_SAMPLES: u32 = 4;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ContextOptions {
+    // None requests multisampling on a best-effort basis, an explicit count (0 disables it) has to be supported by the driver
+    pub samples: Option<u32>,
+    pub depth_bits: u32,
+    pub stencil_bits: u32,
+    pub srgb: bool,
+    pub profile: OpenGLProfile,
+    pub gl_version: (u32, u32),
+    pub debug: bool,
+}
+
+impl Default for ContextOptions {
+    fn default() -> Self {
+        Self { samples: None, depth_bits: 24, stencil_bits: 8, srgb: false, profile: OpenGLProfile::Core, gl_version: (3, 3), debug: false }
+    }
+}
+
+impl ContextOptions {
+    pub fn get_samples(&self) -> u32 {
+        self.samples.unwrap_or(DEFAULT_SAMPLES)
+    }
+}
+
 pub trait RendererPlatformSpecific {
     fn set_swap_interval(&self, interval: u32);
+    fn get_profile(&self) -> OpenGLProfile;
+    fn is_srgb(&self) -> bool;
     fn close(&self);
 }
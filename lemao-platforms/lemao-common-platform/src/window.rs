@@ -16,6 +16,7 @@ pub trait WindowPlatformSpecific {
     fn create_renderer(&mut self) -> Result<Box<dyn RendererPlatformSpecific>, String>;
     fn get_position(&self) -> Vec2;
     fn get_size(&self) -> Vec2;
+    fn get_scale_factor(&self) -> f32;
     fn get_style(&self) -> WindowStyle;
     fn set_style(&mut self, style: WindowStyle) -> Result<(), String>;
     fn swap_buffers(&self);
@@ -9,6 +9,7 @@ fn main() {
             .header("/usr/include/X11/Xutil.h")
             .header("/usr/include/X11/keysym.h")
             .header("/usr/include/X11/extensions/Xfixes.h")
+            .header("/usr/include/X11/extensions/Xrandr.h")
             .parse_callbacks(Box::new(lemao_bindgen::CargoCallbacks))
             .layout_tests(false)
             .generate()
@@ -25,4 +26,5 @@ fn main() {
 fn link_linux_libs() {
     println!("cargo:rustc-link-lib=dylib=X11");
     println!("cargo:rustc-link-lib=dylib=Xfixes");
+    println!("cargo:rustc-link-lib=dylib=Xrandr");
 }
@@ -1,7 +1,11 @@
 use crate::bindings::x11;
+use lemao_common_platform::renderer::ContextOptions;
 use lemao_common_platform::renderer::RendererPlatformSpecific;
+use lemao_opengl::bindings::egl;
 use lemao_opengl::bindings::glx::{self, __GLXcontextRec};
+use lemao_opengl::bindings::opengl;
 use lemao_opengl::pointers::OpenGLPointers;
+use lemao_opengl::pointers::OpenGLProfile;
 use std::{mem, ptr};
 
 pub struct LinuxX11Renderer {
@@ -9,39 +13,143 @@ pub struct LinuxX11Renderer {
     window: u64,
     gl: OpenGLPointers,
     gl_context: *mut __GLXcontextRec,
+    srgb: bool,
+}
+
+pub struct LinuxX11EglRenderer {
+    egl_display: egl::EGLDisplay,
+    egl_context: egl::EGLContext,
+    srgb: bool,
 }
 
 impl LinuxX11Renderer {
-    pub unsafe fn new(display: *mut x11::_XDisplay, frame_buffer_config: *mut glx::__GLXFBConfigRec, window: u64) -> Self {
+    pub unsafe fn new(display: *mut x11::_XDisplay, frame_buffer_config: *mut glx::__GLXFBConfigRec, window: u64, options: ContextOptions) -> Result<Self, String> {
         unsafe {
+            let mut flags = glx::GLX_CONTEXT_FORWARD_COMPATIBLE_BIT_ARB;
+            if options.debug {
+                flags |= glx::GLX_CONTEXT_DEBUG_BIT_ARB;
+            }
+
             let context_attributes = [
                 glx::GLX_CONTEXT_MAJOR_VERSION_ARB,
-                3,
+                options.gl_version.0,
                 glx::GLX_CONTEXT_MINOR_VERSION_ARB,
-                3,
+                options.gl_version.1,
+                glx::GLX_CONTEXT_PROFILE_MASK_ARB,
+                glx::GLX_CONTEXT_CORE_PROFILE_BIT_ARB,
                 glx::GLX_CONTEXT_FLAGS_ARB,
-                glx::GLX_CONTEXT_FORWARD_COMPATIBLE_BIT_ARB,
+                flags,
                 0,
             ];
             let context_attributes_ptr = context_attributes.as_ptr() as *const i32;
 
-            let gl: OpenGLPointers = Default::default();
-            let gl_context = (gl.glXCreateContextAttribsARB)(mem::transmute(display), frame_buffer_config, ptr::null_mut(), 1, context_attributes_ptr);
+            // Unsupported version generates X error (which terminates the application by default) instead of just returning null
+            let previous_error_handler = x11::XSetErrorHandler(Some(ignore_error));
+
+            let gl = OpenGLPointers::new(OpenGLProfile::Core);
+            let create_context = gl.glXCreateContextAttribsARB.ok_or("Error while creating OpenGL context, GLX_ARB_create_context is not available")?;
+            let gl_context = create_context(mem::transmute(display), frame_buffer_config, ptr::null_mut(), 1, context_attributes_ptr);
 
             x11::XSync(display, 0);
+            x11::XSetErrorHandler(previous_error_handler);
+
+            if gl_context.is_null() {
+                return Err(format!(
+                    "Error while creating OpenGL {}.{} context{}, the version is not supported by the driver",
+                    options.gl_version.0,
+                    options.gl_version.1,
+                    if options.debug { " with debug flag" } else { "" }
+                ));
+            }
+
             glx::glXMakeCurrent(mem::transmute(display), window, gl_context);
 
-            Self { display, window, gl, gl_context }
+            if options.srgb {
+                (gl.glEnable)(opengl::GL_FRAMEBUFFER_SRGB);
+            }
+
+            Ok(Self { display, window, gl, gl_context, srgb: options.srgb })
+        }
+    }
+}
+
+impl LinuxX11EglRenderer {
+    pub unsafe fn new(egl_display: egl::EGLDisplay, egl_config: egl::EGLConfig, egl_surface: egl::EGLSurface, options: ContextOptions) -> Result<Self, String> {
+        unsafe {
+            if egl::eglBindAPI(egl::EGL_OPENGL_ES_API) == 0 {
+                return Err(format!("Error while binding OpenGL ES API, eglGetError()={}", egl::eglGetError()));
+            }
+
+            let mut context_attributes = vec![egl::EGL_CONTEXT_MAJOR_VERSION, options.gl_version.0, egl::EGL_CONTEXT_MINOR_VERSION, options.gl_version.1];
+            if options.debug {
+                context_attributes.extend_from_slice(&[egl::EGL_CONTEXT_OPENGL_DEBUG, egl::EGL_TRUE]);
+            }
+            context_attributes.push(egl::EGL_NONE);
+
+            let egl_context = egl::eglCreateContext(egl_display, egl_config, ptr::null_mut(), context_attributes.as_ptr() as *const i32);
+            if egl_context.is_null() {
+                return Err(format!(
+                    "Error while creating OpenGL ES {}.{} context{}, eglGetError()={}",
+                    options.gl_version.0,
+                    options.gl_version.1,
+                    if options.debug { " with debug flag" } else { "" },
+                    egl::eglGetError()
+                ));
+            }
+
+            if egl::eglMakeCurrent(egl_display, egl_surface, egl_surface, egl_context) == 0 {
+                return Err(format!("Error while making OpenGL ES context current, eglGetError()={}", egl::eglGetError()));
+            }
+
+            // sRGB conversion is enabled by the surface color space, GL_FRAMEBUFFER_SRGB doesn't exist in OpenGL ES
+            Ok(Self { egl_display, egl_context, srgb: options.srgb })
         }
     }
 }
 
 impl RendererPlatformSpecific for LinuxX11Renderer {
     fn set_swap_interval(&self, interval: u32) {
-        unsafe { (self.gl.glXSwapIntervalEXT)(mem::transmute(self.display), self.window, interval as i32) }
+        if let Some(swap_interval) = self.gl.glXSwapIntervalEXT {
+            unsafe { swap_interval(mem::transmute(self.display), self.window, interval as i32) }
+        }
+    }
+
+    fn get_profile(&self) -> OpenGLProfile {
+        OpenGLProfile::Core
+    }
+
+    fn is_srgb(&self) -> bool {
+        self.srgb
     }
 
     fn close(&self) {
         unsafe { glx::glXDestroyContext(mem::transmute(self.display), self.gl_context) }
     }
 }
+
+impl RendererPlatformSpecific for LinuxX11EglRenderer {
+    fn set_swap_interval(&self, interval: u32) {
+        unsafe {
+            egl::eglSwapInterval(self.egl_display, interval as i32);
+        }
+    }
+
+    fn get_profile(&self) -> OpenGLProfile {
+        OpenGLProfile::Es
+    }
+
+    fn is_srgb(&self) -> bool {
+        self.srgb
+    }
+
+    fn close(&self) {
+        unsafe {
+            egl::eglMakeCurrent(self.egl_display, ptr::null_mut(), ptr::null_mut(), ptr::null_mut());
+            egl::eglDestroyContext(self.egl_display, self.egl_context);
+        }
+    }
+}
+
+extern "C" fn ignore_error(_: *mut x11::_XDisplay, _: *mut x11::XErrorEvent) -> i32 {
+    0
+}
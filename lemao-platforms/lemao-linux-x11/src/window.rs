@@ -1,24 +1,36 @@
 use crate::bindings::x11;
 use crate::input;
+use crate::renderer::LinuxX11EglRenderer;
 use crate::renderer::LinuxX11Renderer;
 use lemao_common_platform::input::InputEvent;
 use lemao_common_platform::input::Key;
 use lemao_common_platform::input::MouseButton;
 use lemao_common_platform::input::MouseWheelDirection;
+use lemao_common_platform::renderer::ContextOptions;
 use lemao_common_platform::renderer::RendererPlatformSpecific;
 use lemao_common_platform::window::WindowPlatformSpecific;
 use lemao_common_platform::window::WindowStyle;
 use lemao_math::vec2::Vec2;
+use lemao_opengl::bindings::egl;
 use lemao_opengl::bindings::glx;
+use lemao_opengl::pointers::OpenGLProfile;
+use std::ffi::CStr;
 use std::ffi::CString;
 use std::mem;
 use std::os::raw::c_void;
 use std::ptr;
 
+// Density which is considered as 100% scale by X11 desktop environments
+const BASE_DPI: f32 = 96.0;
+
 pub struct WindowX11 {
     pub(crate) display: *mut x11::_XDisplay,
     pub(crate) frame_buffer_config: *mut glx::__GLXFBConfigRec,
+    pub(crate) egl_display: egl::EGLDisplay,
+    pub(crate) egl_config: egl::EGLConfig,
+    pub(crate) egl_surface: egl::EGLSurface,
     pub(crate) window: u64,
+    pub(crate) options: ContextOptions,
 
     delete_window_atom: u64,
     keyboard_state: [bool; 256],
@@ -27,11 +39,13 @@ pub struct WindowX11 {
     style: WindowStyle,
     position: Vec2,
     size: Vec2,
+    scale_factor: f32,
+    scale_factor_area: (i32, i32, i32, i32),
     last_cursor_position: Vec2,
 }
 
 impl WindowX11 {
-    pub fn new(title: &str, style: WindowStyle) -> Result<Box<Self>, String> {
+    pub fn new(title: &str, style: WindowStyle, options: ContextOptions) -> Result<Box<Self>, String> {
         unsafe {
             let display = x11::XOpenDisplay(ptr::null());
             if display.is_null() {
@@ -40,80 +54,23 @@ impl WindowX11 {
 
             let screen_id = x11::XDefaultScreen(display);
 
-            let attributes = [
-                glx::GLX_X_RENDERABLE,
-                1,
-                glx::GLX_DRAWABLE_TYPE,
-                glx::GLX_WINDOW_BIT,
-                glx::GLX_RENDER_TYPE,
-                glx::GLX_RGBA_BIT,
-                glx::GLX_X_VISUAL_TYPE,
-                glx::GLX_TRUE_COLOR,
-                glx::GLX_RED_SIZE,
-                8,
-                glx::GLX_GREEN_SIZE,
-                8,
-                glx::GLX_BLUE_SIZE,
-                8,
-                glx::GLX_ALPHA_SIZE,
-                8,
-                glx::GLX_DEPTH_SIZE,
-                24,
-                glx::GLX_STENCIL_SIZE,
-                8,
-                glx::GLX_DOUBLEBUFFER,
-                1,
-                0,
-            ];
-            let attributes_ptr = attributes.as_ptr() as *const i32;
-
-            let mut frame_buffers_count = 0;
-            let frame_buffer_config = glx::glXChooseFBConfig(mem::transmute(display), screen_id, attributes_ptr, &mut frame_buffers_count);
-            if frame_buffer_config.is_null() {
-                x11::XCloseDisplay(display);
-                return Err("Error while creating a new display".to_string());
-            }
-            let frame_buffer_config_slice = std::slice::from_raw_parts_mut(frame_buffer_config, frame_buffers_count as usize);
-
-            let mut best_frame_buffer_config_index = -1;
-            let mut worst_frame_buffer_config_index = -1;
-            let mut best_samples = -1;
-            let mut worst_samples = 999;
-
-            for i in 0..frame_buffers_count {
-                let config = frame_buffer_config_slice[i as usize];
-                let visual_info = glx::glXGetVisualFromFBConfig(mem::transmute(display), config);
-
-                if !visual_info.is_null() {
-                    let mut samp_buf = 0;
-                    let mut samples = 0;
-
-                    glx::glXGetFBConfigAttrib(mem::transmute(display), config, glx::GLX_SAMPLE_BUFFERS as i32, &mut samp_buf);
-                    glx::glXGetFBConfigAttrib(mem::transmute(display), config, glx::GLX_SAMPLES as i32, &mut samples);
-
-                    if best_frame_buffer_config_index < 0 || (samp_buf != 0 && samples > best_samples) {
-                        best_frame_buffer_config_index = i;
-                        best_samples = samples;
-                    }
-
-                    if worst_frame_buffer_config_index < 0 || samp_buf == 0 || samples < worst_samples {
-                        worst_frame_buffer_config_index = i;
-                    }
-
-                    worst_samples = samples;
+            let choose_visual = |options: &ContextOptions| match options.profile {
+                OpenGLProfile::Core => {
+                    Self::choose_glx_visual(display, screen_id, options).map(|(config, visual_info)| (config, ptr::null_mut(), ptr::null_mut(), visual_info))
                 }
+                OpenGLProfile::Es => {
+                    Self::choose_egl_visual(display, options).map(|(egl_display, config, visual_info)| (ptr::null_mut(), egl_display, config, visual_info))
+                }
+            };
 
-                x11::XFree(visual_info as *mut c_void);
+            // Multisampling is only a preference when the sample count hasn't been set, so configurations without it are accepted too
+            let (frame_buffer_config, egl_display, egl_config, visual_info) = match choose_visual(&options) {
+                Err(_) if options.samples.is_none() => choose_visual(&ContextOptions { samples: Some(0), ..options }),
+                result => result,
             }
-
-            let best_frame_buffer_config = frame_buffer_config_slice[best_frame_buffer_config_index as usize];
-            x11::XFree(frame_buffer_config as *mut c_void);
-
-            let visual_info = glx::glXGetVisualFromFBConfig(mem::transmute(display), best_frame_buffer_config);
-            if visual_info.is_null() {
+            .inspect_err(|_| {
                 x11::XCloseDisplay(display);
-                return Err("Error while creating a new display".to_string());
-            }
+            })?;
 
             if screen_id != (*visual_info).screen {
                 x11::XCloseDisplay(display);
@@ -140,7 +97,7 @@ impl WindowX11 {
                     | x11::PointerMotionMask as i64,
                 do_not_propagate_mask: 0,
                 override_redirect: 1,
-                colormap: x11::XCreateColormap(display, x11::XRootWindow(display, screen_id), mem::transmute((*visual_info).visual), x11::AllocNone as i32),
+                colormap: x11::XCreateColormap(display, x11::XRootWindow(display, screen_id), (*visual_info).visual, x11::AllocNone as i32),
                 cursor: 0,
             };
 
@@ -155,7 +112,7 @@ impl WindowX11 {
                 0,
                 (*visual_info).depth,
                 x11::InputOutput,
-                mem::transmute((*visual_info).visual),
+                (*visual_info).visual,
                 x11::CWBackPixel as u64 | x11::CWColormap as u64 | x11::CWBorderPixel as u64 | x11::CWEventMask as u64,
                 &mut window_attributes,
             );
@@ -170,24 +127,270 @@ impl WindowX11 {
             x11::XClearWindow(display, window);
             x11::XMapRaised(display, window);
 
+            // EGL renders into a separate surface, GLX uses the window directly
+            let mut egl_surface = ptr::null_mut();
+            if options.profile == OpenGLProfile::Es {
+                let mut surface_attributes = Vec::new();
+                if options.srgb {
+                    surface_attributes.extend_from_slice(&[egl::EGL_GL_COLORSPACE, egl::EGL_GL_COLORSPACE_SRGB]);
+                }
+                surface_attributes.push(egl::EGL_NONE);
+
+                egl_surface = egl::eglCreateWindowSurface(egl_display, egl_config, window, surface_attributes.as_ptr() as *const i32);
+                if egl_surface.is_null() {
+                    return Err(format!("Error while creating EGL window surface, eglGetError()={}", egl::eglGetError()));
+                }
+            }
+
             let mut context = Box::new(Self {
                 display,
-                frame_buffer_config: best_frame_buffer_config,
+                frame_buffer_config,
+                egl_display,
+                egl_config,
+                egl_surface,
                 delete_window_atom,
                 keyboard_state: [false; 256],
                 mouse_state: [false; 16],
                 cursor_visible: true,
                 window,
+                options,
                 style,
                 position: Default::default(),
                 size: Default::default(),
+                scale_factor: 1.0,
+                scale_factor_area: (0, 0, 0, 0),
                 last_cursor_position: Default::default(),
             });
 
             context.set_style(style)?;
+            context.scale_factor = context.read_scale_factor();
+
             Ok(context)
         }
     }
+
+    unsafe fn choose_glx_visual(
+        display: *mut x11::_XDisplay,
+        screen_id: i32,
+        options: &ContextOptions,
+    ) -> Result<(*mut glx::__GLXFBConfigRec, *mut x11::XVisualInfo), String> {
+        unsafe {
+            let mut attributes = vec![
+                glx::GLX_X_RENDERABLE,
+                1,
+                glx::GLX_DRAWABLE_TYPE,
+                glx::GLX_WINDOW_BIT,
+                glx::GLX_RENDER_TYPE,
+                glx::GLX_RGBA_BIT,
+                glx::GLX_X_VISUAL_TYPE,
+                glx::GLX_TRUE_COLOR,
+                glx::GLX_RED_SIZE,
+                8,
+                glx::GLX_GREEN_SIZE,
+                8,
+                glx::GLX_BLUE_SIZE,
+                8,
+                glx::GLX_ALPHA_SIZE,
+                8,
+                glx::GLX_DEPTH_SIZE,
+                options.depth_bits,
+                glx::GLX_STENCIL_SIZE,
+                options.stencil_bits,
+                glx::GLX_DOUBLEBUFFER,
+                1,
+            ];
+
+            let samples = options.get_samples();
+            if samples > 0 {
+                attributes.extend_from_slice(&[glx::GLX_SAMPLE_BUFFERS, 1, glx::GLX_SAMPLES, samples]);
+            }
+
+            if options.srgb {
+                attributes.extend_from_slice(&[glx::GLX_FRAMEBUFFER_SRGB_CAPABLE_ARB, 1]);
+            }
+
+            attributes.push(0);
+            let attributes_ptr = attributes.as_ptr() as *const i32;
+
+            let mut frame_buffers_count = 0;
+            let frame_buffer_config = glx::glXChooseFBConfig(mem::transmute(display), screen_id, attributes_ptr, &mut frame_buffers_count);
+            if frame_buffer_config.is_null() || frame_buffers_count == 0 {
+                return Err(format!(
+                    "No framebuffer configuration supports the requested options: {} samples, {} depth bits, {} stencil bits, sRGB {}",
+                    samples, options.depth_bits, options.stencil_bits, options.srgb
+                ));
+            }
+            let frame_buffer_config_slice = std::slice::from_raw_parts_mut(frame_buffer_config, frame_buffers_count as usize);
+
+            // Returned configurations are sorted from the smallest number of samples, so the first one with a visual is the closest to the request
+            let mut best_frame_buffer_config = ptr::null_mut();
+            for config in frame_buffer_config_slice.iter() {
+                let visual_info = glx::glXGetVisualFromFBConfig(mem::transmute(display), *config);
+                if !visual_info.is_null() {
+                    best_frame_buffer_config = *config;
+                    x11::XFree(visual_info as *mut c_void);
+                    break;
+                }
+            }
+
+            x11::XFree(frame_buffer_config as *mut c_void);
+
+            if best_frame_buffer_config.is_null() {
+                return Err("No framebuffer configuration with a visual supports the requested options".to_string());
+            }
+
+            let visual_info = glx::glXGetVisualFromFBConfig(mem::transmute(display), best_frame_buffer_config);
+            if visual_info.is_null() {
+                return Err("Error while creating a new display".to_string());
+            }
+
+            Ok((best_frame_buffer_config, visual_info as *mut x11::XVisualInfo))
+        }
+    }
+
+    unsafe fn choose_egl_visual(display: *mut x11::_XDisplay, options: &ContextOptions) -> Result<(egl::EGLDisplay, egl::EGLConfig, *mut x11::XVisualInfo), String> {
+        unsafe {
+            if options.gl_version.0 < 3 {
+                return Err(format!("OpenGL ES {}.{} is not supported, at least 3.0 is required", options.gl_version.0, options.gl_version.1));
+            }
+
+            let egl_display = egl::eglGetDisplay(mem::transmute(display));
+            if egl_display.is_null() || egl::eglInitialize(egl_display, ptr::null_mut(), ptr::null_mut()) == 0 {
+                return Err(format!("Error while initializing EGL display, eglGetError()={}", egl::eglGetError()));
+            }
+
+            let mut attributes = vec![
+                egl::EGL_SURFACE_TYPE,
+                egl::EGL_WINDOW_BIT,
+                egl::EGL_RENDERABLE_TYPE,
+                egl::EGL_OPENGL_ES3_BIT,
+                egl::EGL_RED_SIZE,
+                8,
+                egl::EGL_GREEN_SIZE,
+                8,
+                egl::EGL_BLUE_SIZE,
+                8,
+                egl::EGL_ALPHA_SIZE,
+                8,
+                egl::EGL_DEPTH_SIZE,
+                options.depth_bits,
+                egl::EGL_STENCIL_SIZE,
+                options.stencil_bits,
+            ];
+
+            let samples = options.get_samples();
+            if samples > 0 {
+                attributes.extend_from_slice(&[egl::EGL_SAMPLE_BUFFERS, 1, egl::EGL_SAMPLES, samples]);
+            }
+
+            attributes.push(egl::EGL_NONE);
+            let attributes_ptr = attributes.as_ptr() as *const i32;
+
+            let mut egl_config = ptr::null_mut();
+            let mut configs_count = 0;
+
+            if egl::eglChooseConfig(egl_display, attributes_ptr, &mut egl_config, 1, &mut configs_count) == 0 || configs_count == 0 {
+                egl::eglTerminate(egl_display);
+                return Err(format!(
+                    "No EGL configuration supports the requested options: {} samples, {} depth bits, {} stencil bits",
+                    samples, options.depth_bits, options.stencil_bits
+                ));
+            }
+
+            // Window has to be created with the same visual as the configuration, otherwise the surface can't be attached to it
+            let mut visual_id = 0;
+            egl::eglGetConfigAttrib(egl_display, egl_config, egl::EGL_NATIVE_VISUAL_ID as i32, &mut visual_id);
+
+            let mut visual_template: x11::XVisualInfo = mem::zeroed();
+            visual_template.visualid = visual_id as u64;
+
+            let mut visuals_count = 0;
+            let visual_info = x11::XGetVisualInfo(display, x11::VisualIDMask as i64, &mut visual_template, &mut visuals_count);
+            if visual_info.is_null() {
+                egl::eglTerminate(egl_display);
+                return Err("EGL configuration doesn't have a matching X11 visual".to_string());
+            }
+
+            Ok((egl_display, egl_config, visual_info))
+        }
+    }
+
+    // Area (left, top, right, bottom) where the scale factor stays the same is remembered, so XRandR is queried again only after leaving it
+    fn read_scale_factor(&mut self) -> f32 {
+        unsafe {
+            self.scale_factor_area = (0, 0, 0, 0);
+
+            // Xft.dpi is set by desktop environments when the user chooses the scale, so it takes precedence over the physical size
+            let resources = x11::XResourceManagerString(self.display);
+            if !resources.is_null() {
+                for line in CStr::from_ptr(resources).to_string_lossy().lines() {
+                    if let Some(Ok(dpi)) = line.strip_prefix("Xft.dpi:").map(|p| p.trim().parse::<f32>()) {
+                        if dpi > 0.0 {
+                            self.scale_factor_area = (i32::MIN, i32::MIN, i32::MAX, i32::MAX);
+                            return dpi / BASE_DPI;
+                        }
+                    }
+                }
+            }
+
+            // Servers without RandR (some VNC and remote setups) would raise BadRequest, which aborts the process by default
+            let (mut event_base, mut error_base) = (0, 0);
+            if x11::XRRQueryExtension(self.display, &mut event_base, &mut error_base) == 0 {
+                self.scale_factor_area = (i32::MIN, i32::MIN, i32::MAX, i32::MAX);
+                return 1.0;
+            }
+
+            let root = x11::XDefaultRootWindow(self.display);
+            let (x, y) = self.get_window_center();
+
+            let resources = x11::XRRGetScreenResourcesCurrent(self.display, root);
+            if resources.is_null() {
+                return 1.0;
+            }
+
+            // Physical size is reported per output, the one displaying the center of the window is used
+            let mut scale_factor = 1.0;
+            for index in 0..(*resources).noutput as usize {
+                let output = x11::XRRGetOutputInfo(self.display, resources, *(*resources).outputs.add(index));
+                if output.is_null() {
+                    continue;
+                }
+
+                if (*output).connection as u32 == x11::RR_Connected && (*output).crtc != 0 && (*output).mm_width > 0 {
+                    let crtc = x11::XRRGetCrtcInfo(self.display, resources, (*output).crtc);
+                    if !crtc.is_null() {
+                        let (left, top, width, height) = ((*crtc).x, (*crtc).y, (*crtc).width as i32, (*crtc).height as i32);
+                        if x >= left && x < left + width && y >= top && y < top + height {
+                            let dpi = width as f32 / ((*output).mm_width as f32 / 25.4);
+
+                            // Physical density is only an approximation, so it's snapped to the nearest quarter and never makes the content smaller
+                            scale_factor = f32::max(1.0, (dpi / BASE_DPI * 4.0).round() / 4.0);
+                            self.scale_factor_area = (left, top, left + width, top + height);
+                        }
+
+                        x11::XRRFreeCrtcInfo(crtc);
+                    }
+                }
+
+                x11::XRRFreeOutputInfo(output);
+            }
+
+            x11::XRRFreeScreenResources(resources);
+            scale_factor
+        }
+    }
+
+    fn get_window_center(&self) -> (i32, i32) {
+        unsafe {
+            let root = x11::XDefaultRootWindow(self.display);
+            let mut child = 0;
+            let mut x = 0;
+            let mut y = 0;
+            x11::XTranslateCoordinates(self.display, self.window, root, self.size.x as i32 / 2, self.size.y as i32 / 2, &mut x, &mut y, &mut child);
+
+            (x, y)
+        }
+    }
 }
 
 impl WindowPlatformSpecific for WindowX11 {
@@ -199,10 +402,25 @@ impl WindowPlatformSpecific for WindowX11 {
 
                 match event.type_ as u32 {
                     x11::ConfigureNotify => {
+                        let mut events = Vec::new();
                         if event.xconfigure.width != (self.size.x as i32) || event.xconfigure.height != (self.size.y as i32) {
                             self.size = Vec2::new(event.xconfigure.width as f32, event.xconfigure.height as f32);
-                            return vec![InputEvent::WindowSizeChanged(Vec2::new(event.xconfigure.width as f32, event.xconfigure.height as f32))];
+                            events.push(InputEvent::WindowSizeChanged(Vec2::new(event.xconfigure.width as f32, event.xconfigure.height as f32)));
                         }
+
+                        // Window could be moved to a monitor with different density, which is possible only after leaving the current one
+                        let (x, y) = self.get_window_center();
+                        let (left, top, right, bottom) = self.scale_factor_area;
+
+                        if x < left || x >= right || y < top || y >= bottom {
+                            let scale_factor = self.read_scale_factor();
+                            if scale_factor != self.scale_factor {
+                                self.scale_factor = scale_factor;
+                                events.push(InputEvent::ScaleFactorChanged(scale_factor));
+                            }
+                        }
+
+                        return events;
                     }
                     x11::KeyPress => {
                         let keysym = x11::XLookupKeysym(&event.xkey as *const _ as *mut x11::XKeyEvent, 0);
@@ -267,7 +485,12 @@ impl WindowPlatformSpecific for WindowX11 {
     }
 
     fn create_renderer(&mut self) -> Result<Box<dyn RendererPlatformSpecific>, String> {
-        unsafe { Ok(Box::new(LinuxX11Renderer::new(self.display, self.frame_buffer_config, self.window))) }
+        unsafe {
+            match self.options.profile {
+                OpenGLProfile::Core => Ok(Box::new(LinuxX11Renderer::new(self.display, self.frame_buffer_config, self.window, self.options)?)),
+                OpenGLProfile::Es => Ok(Box::new(LinuxX11EglRenderer::new(self.egl_display, self.egl_config, self.egl_surface, self.options)?)),
+            }
+        }
     }
 
     fn get_position(&self) -> Vec2 {
@@ -278,6 +501,10 @@ impl WindowPlatformSpecific for WindowX11 {
         self.size
     }
 
+    fn get_scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
     fn get_style(&self) -> WindowStyle {
         self.style
     }
@@ -348,7 +575,12 @@ impl WindowPlatformSpecific for WindowX11 {
 
     fn swap_buffers(&self) {
         unsafe {
-            glx::glXSwapBuffers(mem::transmute(self.display), self.window);
+            match self.options.profile {
+                OpenGLProfile::Core => glx::glXSwapBuffers(mem::transmute(self.display), self.window),
+                OpenGLProfile::Es => {
+                    egl::eglSwapBuffers(self.egl_display, self.egl_surface);
+                }
+            }
         }
     }
 
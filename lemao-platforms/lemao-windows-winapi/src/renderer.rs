@@ -1,21 +1,28 @@
 use crate::bindings::winapi;
 use crate::window::WindowWinAPI;
+use lemao_common_platform::renderer::ContextOptions;
 use lemao_common_platform::renderer::RendererPlatformSpecific;
 use lemao_common_platform::window::WindowPlatformSpecific;
 use lemao_opengl::bindings::opengl;
 use lemao_opengl::bindings::wgl;
 use lemao_opengl::pointers::OpenGLPointers;
+use lemao_opengl::pointers::OpenGLProfile;
 use std::mem;
 use std::ptr;
 
 pub struct WindowsWinAPIRenderer {
     gl_context: winapi::HGLRC,
     gl: OpenGLPointers,
+    srgb: bool,
 }
 
 impl WindowsWinAPIRenderer {
-    pub unsafe fn new(hdc: winapi::HDC) -> Result<WindowsWinAPIRenderer, String> {
+    pub unsafe fn new(hdc: winapi::HDC, options: ContextOptions) -> Result<WindowsWinAPIRenderer, String> {
         unsafe {
+            if options.profile == OpenGLProfile::Es {
+                return Err("OpenGL ES profile is not supported on Windows".to_string());
+            }
+
             let fake_window = WindowWinAPI::new_fake()?;
             let fake_window_hdc = fake_window.hdc;
 
@@ -38,8 +45,8 @@ impl WindowsWinAPIRenderer {
                 cAccumGreenBits: 0,
                 cAccumBlueBits: 0,
                 cAccumAlphaBits: 0,
-                cDepthBits: 24,
-                cStencilBits: 8,
+                cDepthBits: options.depth_bits as u8,
+                cStencilBits: options.stencil_bits as u8,
                 cAuxBuffers: 0,
                 iLayerType: winapi::PFD_MAIN_PLANE as u8,
                 bReserved: 0,
@@ -63,49 +70,95 @@ impl WindowsWinAPIRenderer {
             winapi::wglDeleteContext(fake_gl_context);
             fake_window.close();
 
-            let mut attributes = [
-                wgl::WGL_DRAW_TO_WINDOW_ARB,
-                opengl::GL_TRUE,
-                wgl::WGL_SUPPORT_OPENGL_ARB,
-                opengl::GL_TRUE,
-                wgl::WGL_DOUBLE_BUFFER_ARB,
-                opengl::GL_TRUE,
-                wgl::WGL_PIXEL_TYPE_ARB,
-                wgl::WGL_TYPE_RGBA_ARB,
-                wgl::WGL_COLOR_BITS_ARB,
-                32,
-                wgl::WGL_DEPTH_BITS_ARB,
-                24,
-                wgl::WGL_STENCIL_BITS_ARB,
-                8,
-                wgl::WGL_SAMPLE_BUFFERS_ARB,
-                opengl::GL_TRUE,
-                wgl::WGL_SAMPLES_ARB,
-                16,
-                0,
-            ];
+            // Multisampling is only a preference when the sample count hasn't been set, so formats without it are accepted too
+            let requested_samples = options.get_samples();
+            let samples_candidates = if options.samples.is_some() { vec![requested_samples] } else { vec![requested_samples, 0] };
 
             let mut pixel_format = 0;
             let mut formats_count = 0;
-            let attributes_ptr = attributes.as_mut_ptr() as *const i32;
 
-            if (gl.wglChoosePixelFormatARB)(hdc as wgl::HDC, attributes_ptr, ptr::null_mut(), 1, &mut pixel_format, &mut formats_count) == 0 {
-                return Err(format!("Error while loading available pixel formats for desired window, GetLastError()={}", winapi::GetLastError()));
+            for samples in samples_candidates {
+                let mut attributes = vec![
+                    wgl::WGL_DRAW_TO_WINDOW_ARB,
+                    opengl::GL_TRUE,
+                    wgl::WGL_SUPPORT_OPENGL_ARB,
+                    opengl::GL_TRUE,
+                    wgl::WGL_DOUBLE_BUFFER_ARB,
+                    opengl::GL_TRUE,
+                    wgl::WGL_PIXEL_TYPE_ARB,
+                    wgl::WGL_TYPE_RGBA_ARB,
+                    wgl::WGL_COLOR_BITS_ARB,
+                    32,
+                    wgl::WGL_DEPTH_BITS_ARB,
+                    options.depth_bits,
+                    wgl::WGL_STENCIL_BITS_ARB,
+                    options.stencil_bits,
+                ];
+
+                if samples > 0 {
+                    attributes.extend_from_slice(&[wgl::WGL_SAMPLE_BUFFERS_ARB, opengl::GL_TRUE, wgl::WGL_SAMPLES_ARB, samples]);
+                }
+
+                if options.srgb {
+                    attributes.extend_from_slice(&[wgl::WGL_FRAMEBUFFER_SRGB_CAPABLE_ARB, opengl::GL_TRUE]);
+                }
+
+                attributes.push(0);
+                let attributes_ptr = attributes.as_mut_ptr() as *const i32;
+
+                if (gl.wglChoosePixelFormatARB)(hdc as wgl::HDC, attributes_ptr, ptr::null_mut(), 1, &mut pixel_format, &mut formats_count) == 0 {
+                    return Err(format!("Error while loading available pixel formats for desired window, GetLastError()={}", winapi::GetLastError()));
+                }
+
+                if formats_count > 0 {
+                    break;
+                }
+            }
+
+            if formats_count == 0 {
+                return Err(format!(
+                    "No pixel format supports the requested options: {} samples, {} depth bits, {} stencil bits, sRGB {}",
+                    requested_samples, options.depth_bits, options.stencil_bits, options.srgb
+                ));
             }
 
             if winapi::SetPixelFormat(hdc, pixel_format, &pixel_format_descriptor) == 0 {
                 return Err(format!("Error while setting pixel format for desired window, GetLastError()={}", winapi::GetLastError()));
             }
 
-            let mut attributes = [wgl::WGL_CONTEXT_MAJOR_VERSION_ARB, 3, wgl::WGL_CONTEXT_MINOR_VERSION_ARB, 3, 0];
+            let flags = if options.debug { wgl::WGL_CONTEXT_DEBUG_BIT_ARB } else { 0 };
+            let mut attributes = [
+                wgl::WGL_CONTEXT_MAJOR_VERSION_ARB,
+                options.gl_version.0,
+                wgl::WGL_CONTEXT_MINOR_VERSION_ARB,
+                options.gl_version.1,
+                wgl::WGL_CONTEXT_PROFILE_MASK_ARB,
+                wgl::WGL_CONTEXT_CORE_PROFILE_BIT_ARB,
+                wgl::WGL_CONTEXT_FLAGS_ARB,
+                flags,
+                0,
+            ];
             let attributes_ptr = attributes.as_mut_ptr() as *const i32;
 
             let gl_context = (gl.wglCreateContextAttribsARB)(hdc as wgl::HDC, ptr::null_mut(), attributes_ptr) as winapi::HGLRC;
+            if gl_context.is_null() {
+                return Err(format!(
+                    "Error while creating OpenGL {}.{} context{}, the version is not supported by the driver",
+                    options.gl_version.0,
+                    options.gl_version.1,
+                    if options.debug { " with debug flag" } else { "" }
+                ));
+            }
+
             if winapi::wglMakeCurrent(hdc, gl_context) == 0 {
                 return Err(format!("Error while creating OpenGL context for desired window, GetLastError()={}", winapi::GetLastError()));
             }
 
-            Ok(Self { gl_context, gl })
+            if options.srgb {
+                (gl.glEnable)(opengl::GL_FRAMEBUFFER_SRGB);
+            }
+
+            Ok(Self { gl_context, gl, srgb: options.srgb })
         }
     }
 }
@@ -117,6 +170,14 @@ impl RendererPlatformSpecific for WindowsWinAPIRenderer {
         }
     }
 
+    fn get_profile(&self) -> OpenGLProfile {
+        OpenGLProfile::Core
+    }
+
+    fn is_srgb(&self) -> bool {
+        self.srgb
+    }
+
     fn close(&self) {
         unsafe {
             winapi::wglDeleteContext(self.gl_context);
@@ -5,6 +5,7 @@ use lemao_common_platform::input::InputEvent;
 use lemao_common_platform::input::Key;
 use lemao_common_platform::input::MouseButton;
 use lemao_common_platform::input::MouseWheelDirection;
+use lemao_common_platform::renderer::ContextOptions;
 use lemao_common_platform::renderer::RendererPlatformSpecific;
 use lemao_common_platform::window::WindowPlatformSpecific;
 use lemao_common_platform::window::WindowStyle;
@@ -16,9 +17,13 @@ use std::ptr;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
+// Density which is considered as 100% scale by Windows
+const BASE_DPI: f32 = 96.0;
+
 pub struct WindowWinAPI {
     pub(crate) hwnd: winapi::HWND,
     pub(crate) hdc: winapi::HDC,
+    options: ContextOptions,
     wnd_proc_events: VecDeque<WndProcEvent>,
     initialized: bool,
     fake: bool,
@@ -26,16 +31,18 @@ pub struct WindowWinAPI {
     style: WindowStyle,
     position: Vec2,
     size: Vec2,
+    scale_factor: f32,
     last_cursor_position: Vec2,
 }
 
 pub struct WndProcEvent {
     message: winapi::UINT,
+    w_param: winapi::WPARAM,
     l_param: winapi::LPARAM,
 }
 
 impl WindowWinAPI {
-    pub fn new(title: &str, style: WindowStyle) -> Result<Box<Self>, String> {
+    pub fn new(title: &str, style: WindowStyle, options: ContextOptions) -> Result<Box<Self>, String> {
         unsafe {
             let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis();
             let class_cstr = CString::new(format!("LemaoWindow_{}", timestamp)).unwrap();
@@ -66,13 +73,18 @@ impl WindowWinAPI {
                 wnd_proc_events: VecDeque::new(),
                 initialized: false,
                 fake: false,
+                options,
                 style,
                 position: Default::default(),
                 size: Default::default(),
+                scale_factor: 1.0,
                 last_cursor_position: Default::default(),
             });
             let title_cstr = CString::new(title).unwrap();
 
+            // Without awareness the system scales the window bitmap itself and always reports 96 DPI, DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2 is a macro
+            winapi::SetProcessDpiAwarenessContext(-4isize as winapi::DPI_AWARENESS_CONTEXT);
+
             let hwnd = winapi::CreateWindowExA(
                 0,
                 wnd_class.lpszClassName,
@@ -96,6 +108,8 @@ impl WindowWinAPI {
             while !context.initialized {}
 
             context.set_style(context.style)?;
+            context.scale_factor = winapi::GetDpiForWindow(context.hwnd) as f32 / BASE_DPI;
+
             Ok(context)
         }
     }
@@ -129,9 +143,11 @@ impl WindowWinAPI {
                 wnd_proc_events: VecDeque::new(),
                 initialized: false,
                 fake: true,
+                options: Default::default(),
                 style: WindowStyle::Window { position: Vec2::new(0.0, 0.0), size: Vec2::new(0.0, 0.0) },
                 position: Default::default(),
                 size: Default::default(),
+                scale_factor: 1.0,
                 last_cursor_position: Default::default(),
             });
             let title_cstr = CString::new("LemaoWindowInit").unwrap();
@@ -218,6 +234,13 @@ impl WindowPlatformSpecific for WindowWinAPI {
 
                         return vec![InputEvent::WindowSizeChanged(self.size)];
                     }
+                    winapi::WM_DPICHANGED => {
+                        // Horizontal and vertical DPI are always the same for Windows applications
+                        let dpi = (event.w_param & 0xffff) as u32;
+                        self.scale_factor = dpi as f32 / BASE_DPI;
+
+                        return vec![InputEvent::ScaleFactorChanged(self.scale_factor)];
+                    }
                     _ => return Vec::new(),
                 }
             }
@@ -227,7 +250,7 @@ impl WindowPlatformSpecific for WindowWinAPI {
     }
 
     fn create_renderer(&mut self) -> Result<Box<dyn RendererPlatformSpecific>, String> {
-        unsafe { Ok(Box::new(WindowsWinAPIRenderer::new(self.hdc)?)) }
+        unsafe { Ok(Box::new(WindowsWinAPIRenderer::new(self.hdc, self.options)?)) }
     }
 
     fn get_position(&self) -> Vec2 {
@@ -238,6 +261,10 @@ impl WindowPlatformSpecific for WindowWinAPI {
         self.size
     }
 
+    fn get_scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
     fn get_style(&self) -> lemao_common_platform::window::WindowStyle {
         self.style
     }
@@ -364,11 +391,20 @@ extern "C" fn wnd_proc(hwnd: winapi::HWND, message: winapi::UINT, w_param: winap
                 window.hdc = hdc;
                 window.initialized = true;
             }
-            winapi::WM_MOVE | winapi::WM_SIZE => {
+            winapi::WM_MOVE | winapi::WM_SIZE | winapi::WM_DPICHANGED => {
                 let window_ptr = winapi::GetWindowLongPtrA(hwnd, winapi::GWLP_USERDATA);
                 let window = &mut *(window_ptr as *mut WindowWinAPI);
 
-                window.wnd_proc_events.push_front(WndProcEvent { message, l_param });
+                window.wnd_proc_events.push_front(WndProcEvent { message, w_param, l_param });
+
+                // Suggested window rectangle is valid only during this message, so apply it immediately
+                if message == winapi::WM_DPICHANGED {
+                    let rect = &*(l_param as *const winapi::tagRECT);
+                    let width = rect.right - rect.left;
+                    let height = rect.bottom - rect.top;
+
+                    winapi::SetWindowPos(hwnd, ptr::null_mut(), rect.left, rect.top, width, height, winapi::SWP_NOZORDER | winapi::SWP_NOACTIVATE);
+                }
             }
             winapi::WM_CLOSE => {
                 if winapi::DestroyWindow(hwnd) == 0 {
@@ -1,3 +1,5 @@
+use super::is_point_inside_ellipse;
+use super::is_point_inside_mask;
 use super::Component;
 use super::ComponentBorderShape;
 use super::ComponentBorderThickness;
@@ -10,16 +12,19 @@ use super::ComponentSize;
 use super::EventMask;
 use super::HorizontalAlignment;
 use super::VerticalAlignment;
+use crate::error::UiError;
 use crate::events::UiEvent;
 use lemao_core::lemao_common_platform::input::InputEvent;
 use lemao_core::lemao_common_platform::input::MouseButton;
 use lemao_core::lemao_math::color::SolidColor;
+use lemao_core::lemao_math::geometry::rect::Rect;
 use lemao_core::lemao_math::vec2::Vec2;
 use lemao_core::renderer::context::RendererContext;
 use lemao_core::renderer::drawable::text::Text;
 use lemao_core::renderer::drawable::Color;
 use lemao_core::renderer::fonts::Font;
 use lemao_core::renderer::textures::Texture;
+use lemao_core::utils::storage::Handle;
 use std::any::Any;
 
 pub struct Button {
@@ -46,7 +51,7 @@ pub struct Button {
     pub shape: ComponentShape,
     pub color: Color,
     pub corner_rounding: ComponentCornerRounding,
-    pub texture_id: Option<usize>,
+    pub texture_id: Option<Handle<Texture>>,
     pub texture_original_size: Vec2,
 
     // Border properties
@@ -56,7 +61,7 @@ pub struct Button {
 
     // Label properties
     pub label: Text,
-    pub label_font_id: usize,
+    pub label_font_id: Handle<Font>,
     pub label_text: String,
     pub label_horizontal_alignment: HorizontalAlignment,
     pub label_vertical_alignment: VerticalAlignment,
@@ -91,7 +96,7 @@ pub struct Button {
 }
 
 impl Button {
-    pub fn new(renderer: &mut RendererContext, shape: ComponentShape, label_font_id: usize) -> Result<Self, String> {
+    pub fn new(renderer: &mut RendererContext, shape: ComponentShape, label_font_id: Handle<Font>) -> Result<Self, UiError> {
         Ok(Self {
             id: 0,
 
@@ -187,29 +192,14 @@ impl Button {
             return false;
         }
 
-        if let Some(event_mask) = self.event_mask {
-            let event_mask_left_bottom = event_mask.position;
-            let event_mask_right_top = event_mask.position + event_mask.size;
-
-            if point.x < event_mask_left_bottom.x || point.y < event_mask_left_bottom.y || point.x > event_mask_right_top.x || point.y > event_mask_right_top.y {
-                return false;
-            }
+        if !is_point_inside_mask(point, self.event_mask) {
+            return false;
         }
 
         if self.shape == ComponentShape::Rectangle {
-            let x1 = self.screen_position.x;
-            let y1 = self.screen_position.y;
-            let x2 = self.screen_position.x + self.screen_size.x;
-            let y2 = self.screen_position.y + self.screen_size.y;
-
-            point.x >= x1 && point.y >= y1 && point.x <= x2 && point.y <= y2
+            Rect::new(self.screen_position, self.screen_size).contains_point(point)
         } else {
-            let scale = self.screen_size.x / self.screen_size.y;
-            let component_center = self.screen_position + self.screen_size / 2.0;
-            let normalized_point = point - component_center;
-            let scaled_point = normalized_point * Vec2::new(1.0, scale);
-
-            scaled_point.distance(Vec2::new(0.0, 0.0)) <= self.screen_size.x / 2.0
+            is_point_inside_ellipse(point, &Rect::new(self.screen_position, self.screen_size))
         }
     }
 }
@@ -381,7 +371,7 @@ impl Component for Button {
         events
     }
 
-    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), String> {
+    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), UiError> {
         self.screen_size = match self.size {
             ComponentSize::Absolute(size) => size,
             ComponentSize::Relative(size) => area_size * size,
@@ -500,20 +490,21 @@ impl Component for Button {
             VerticalAlignment::Bottom => (Vec2::new(0.0, self.screen_position.y + self.screen_size.y), Vec2::new(0.0, 1.0)),
         };
 
+        renderer.fonts.get_mut(self.label_font_id)?.add_characters(&self.label_text)?;
         let font = renderer.fonts.get(self.label_font_id)?;
         self.label.set_font(font);
         self.label.text = self.label_text.clone();
         self.label.color = self.label_color.clone();
         self.label.position = horizontal_position + vertical_position + self.label_offset;
         self.label.anchor = horizontal_anchor + vertical_anchor;
-        self.label.update();
+        self.label.update()?;
 
         self.dirty = false;
 
         Ok(())
     }
 
-    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), String> {
+    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), UiError> {
         if self.shadow_enabled {
             match &mut self.shadow {
                 ComponentFillingShape::Rectangle(shadow) => renderer.draw(shadow)?,
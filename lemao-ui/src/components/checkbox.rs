@@ -1,12 +1,15 @@
+use super::is_point_inside_area;
 use super::Component;
 use super::ComponentMargin;
 use super::ComponentPosition;
 use super::ComponentSize;
 use super::EventMask;
+use crate::error::UiError;
 use crate::events::UiEvent;
 use lemao_core::lemao_common_platform::input::InputEvent;
 use lemao_core::lemao_common_platform::input::MouseButton;
 use lemao_core::lemao_math::color::SolidColor;
+use lemao_core::lemao_math::geometry::rect::Rect;
 use lemao_core::lemao_math::vec2::Vec2;
 use lemao_core::renderer::context::RendererContext;
 use lemao_core::renderer::drawable::rectangle::Rectangle;
@@ -14,6 +17,7 @@ use lemao_core::renderer::drawable::text::Text;
 use lemao_core::renderer::drawable::Color;
 use lemao_core::renderer::fonts::Font;
 use lemao_core::renderer::textures::Texture;
+use lemao_core::utils::storage::Handle;
 use std::any::Any;
 
 pub struct Checkbox {
@@ -40,12 +44,12 @@ pub struct Checkbox {
     pub box_color: Color,
     pub box_offset: Vec2,
     pub box_size: Vec2,
-    pub box_checked_texture_id: usize,
-    pub box_unchecked_texture_id: usize,
+    pub box_checked_texture_id: Handle<Texture>,
+    pub box_unchecked_texture_id: Handle<Texture>,
 
     // Label properties
     pub label: Text,
-    pub label_font_id: usize,
+    pub label_font_id: Handle<Font>,
     pub label_text: String,
     pub label_offset: Vec2,
     pub label_color: Color,
@@ -70,7 +74,12 @@ pub struct Checkbox {
 }
 
 impl Checkbox {
-    pub fn new(renderer: &mut RendererContext, label_font_id: usize, box_checked_texture_id: usize, box_unchecked_texture_id: usize) -> Result<Self, String> {
+    pub fn new(
+        renderer: &mut RendererContext,
+        label_font_id: Handle<Font>,
+        box_checked_texture_id: Handle<Texture>,
+        box_unchecked_texture_id: Handle<Texture>,
+    ) -> Result<Self, UiError> {
         Ok(Self {
             id: 0,
 
@@ -147,21 +156,7 @@ impl Checkbox {
             return false;
         }
 
-        if let Some(event_mask) = self.event_mask {
-            let event_mask_left_bottom = event_mask.position;
-            let event_mask_right_top = event_mask.position + event_mask.size;
-
-            if point.x < event_mask_left_bottom.x || point.y < event_mask_left_bottom.y || point.x > event_mask_right_top.x || point.y > event_mask_right_top.y {
-                return false;
-            }
-        }
-
-        let x1 = self.screen_position.x;
-        let y1 = self.screen_position.y;
-        let x2 = self.screen_position.x + self.label_offset.x + self.screen_size.x;
-        let y2 = self.screen_position.y + self.label_offset.y + self.screen_size.y;
-
-        point.x >= x1 && point.y >= y1 && point.x <= x2 && point.y <= y2
+        is_point_inside_area(point, &Rect::new(self.screen_position, self.label_offset + self.screen_size), self.event_mask)
     }
 }
 
@@ -314,12 +309,13 @@ impl Component for Checkbox {
         events
     }
 
-    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), String> {
+    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), UiError> {
         // We have to set text first, to get the size used later
+        renderer.fonts.get_mut(self.label_font_id)?.add_characters(&self.label_text)?;
         let font = renderer.fonts.get(self.label_font_id)?;
         self.label.set_font(font);
         self.label.text = self.label_text.clone();
-        self.label.update();
+        self.label.update()?;
 
         self.screen_size = self.label.size + self.label_offset;
         self.size = ComponentSize::Absolute(self.screen_size);
@@ -351,14 +347,14 @@ impl Component for Checkbox {
 
         self.label.position = self.screen_position + self.label_offset;
         self.label.color = self.label_color.clone();
-        self.label.update();
+        self.label.update()?;
 
         self.dirty = false;
 
         Ok(())
     }
 
-    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), String> {
+    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), UiError> {
         if self.label_shadow_enabled {
             let original_position = self.label.position;
             let original_color = self.label.color.clone();
@@ -1,17 +1,22 @@
+use super::is_point_inside_area;
 use super::Component;
 use super::ComponentMargin;
 use super::ComponentPosition;
 use super::ComponentSize;
 use super::EventMask;
+use crate::error::UiError;
 use crate::events::UiEvent;
 use lemao_core::lemao_common_platform::input::InputEvent;
 use lemao_core::lemao_common_platform::input::MouseButton;
 use lemao_core::lemao_math::color::SolidColor;
+use lemao_core::lemao_math::geometry::rect::Rect;
 use lemao_core::lemao_math::vec2::Vec2;
 use lemao_core::renderer::context::RendererContext;
 use lemao_core::renderer::drawable::text::Text;
 use lemao_core::renderer::drawable::Color;
 use lemao_core::renderer::fonts::Font;
+use lemao_core::renderer::richtext::TextMarkup;
+use lemao_core::utils::storage::Handle;
 use std::any::Any;
 
 pub struct Label {
@@ -35,8 +40,9 @@ pub struct Label {
 
     // Label properties
     pub label: Text,
-    pub label_font_id: usize,
+    pub label_font_id: Handle<Font>,
     pub label_text: String,
+    pub label_markup: TextMarkup,
     pub label_color: Color,
     pub label_line_height: u32,
     pub multiline: bool,
@@ -55,7 +61,7 @@ pub struct Label {
 }
 
 impl Label {
-    pub fn new(renderer: &mut RendererContext, label_font_id: usize) -> Result<Self, String> {
+    pub fn new(renderer: &mut RendererContext, label_font_id: Handle<Font>) -> Result<Self, UiError> {
         Ok(Self {
             id: 0,
 
@@ -79,8 +85,9 @@ impl Label {
             label: renderer.create_text(label_font_id)?,
             label_font_id,
             label_text: Default::default(),
+            label_markup: TextMarkup::None,
             label_color: Color::SolidColor(SolidColor::new(1.0, 1.0, 1.0, 1.0)),
-            label_line_height: renderer.fonts.get(label_font_id)?.cell_size.y as u32,
+            label_line_height: renderer.fonts.get(label_font_id)?.line_height as u32,
             multiline: false,
             max_multiline_width: 0.0,
 
@@ -114,21 +121,7 @@ impl Label {
             return false;
         }
 
-        if let Some(event_mask) = self.event_mask {
-            let event_mask_left_bottom = event_mask.position;
-            let event_mask_right_top = event_mask.position + event_mask.size;
-
-            if point.x < event_mask_left_bottom.x || point.y < event_mask_left_bottom.y || point.x > event_mask_right_top.x || point.y > event_mask_right_top.y {
-                return false;
-            }
-        }
-
-        let x1 = self.screen_position.x;
-        let y1 = self.screen_position.y;
-        let x2 = self.screen_position.x + self.screen_size.x;
-        let y2 = self.screen_position.y + self.screen_size.y;
-
-        point.x >= x1 && point.y >= y1 && point.x <= x2 && point.y <= y2
+        is_point_inside_area(point, &Rect::new(self.screen_position, self.screen_size), self.event_mask)
     }
 }
 
@@ -257,32 +250,22 @@ impl Component for Label {
         events
     }
 
-    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), String> {
+    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), UiError> {
         // We have to set text first, to get the size used later
-        let mut label_text_processed = self.label_text.clone();
+        renderer.fonts.get_mut(self.label_font_id)?.add_characters(&self.label_text)?;
+        let font = renderer.fonts.get(self.label_font_id)?;
+        self.label.set_font(font);
+        self.label.line_height = self.label_line_height;
+        self.label.markup = self.label_markup;
+        self.label.text = self.label_text.clone();
 
         if self.multiline {
-            let mut line = String::new();
-            let mut result = String::new();
-
-            for token in label_text_processed.split(' ') {
-                if self.label.calculate_text_size(line.clone() + token).x > self.max_multiline_width {
-                    result += &(line.trim().to_string() + "\n");
-                    line.clear();
-                }
-
-                line += token;
-                line += " ";
-            }
-
-            label_text_processed = result + &line;
+            let rich_text = self.label.parse(&self.label_text)?;
+            let wrapped_rich_text = self.label.wrap(&rich_text, self.max_multiline_width);
+            self.label.set_rich_text(&wrapped_rich_text);
         }
 
-        let font = renderer.fonts.get(self.label_font_id)?;
-        self.label.set_font(font);
-        self.label.text = label_text_processed;
-        self.label.line_height = self.label_line_height;
-        self.label.update();
+        self.label.update()?;
 
         self.screen_size = self.label.size;
         self.size = ComponentSize::Absolute(self.screen_size);
@@ -303,14 +286,14 @@ impl Component for Label {
 
         self.label.position = self.screen_position;
         self.label.color = self.label_color.clone();
-        self.label.update();
+        self.label.update()?;
 
         self.dirty = false;
 
         Ok(())
     }
 
-    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), String> {
+    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), UiError> {
         if self.shadow_enabled {
             let original_position = self.label.position;
             let original_color = self.label.color.clone();
@@ -1,5 +1,7 @@
+use crate::error::UiError;
 use crate::events::UiEvent;
 use lemao_core::lemao_common_platform::input::InputEvent;
+use lemao_core::lemao_math::geometry::rect::Rect;
 use lemao_core::lemao_math::vec2::Vec2;
 use lemao_core::renderer::context::RendererContext;
 use lemao_core::renderer::drawable::circle::Circle;
@@ -87,11 +89,8 @@ pub enum VerticalAlignment {
     Bottom,
 }
 
-#[derive(Copy, Clone, Debug, Default, PartialEq)]
-pub struct EventMask {
-    pub position: Vec2,
-    pub size: Vec2,
-}
+// Area outside of which component doesn't react on mouse events (e.g. part of the scrollbox content which is not visible)
+pub type EventMask = Rect;
 
 pub trait Component {
     fn get_id(&self) -> usize;
@@ -113,8 +112,8 @@ pub trait Component {
     fn get_children(&self) -> &Vec<usize>;
 
     fn process_window_event(&mut self, event: &InputEvent) -> Vec<UiEvent>;
-    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), String>;
-    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), String>;
+    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), UiError>;
+    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), UiError>;
 
     fn is_active(&self) -> bool;
     fn set_active_flag(&mut self, active: bool);
@@ -164,8 +163,57 @@ impl From<ComponentCornerRounding> for CornerRounding {
     }
 }
 
-impl EventMask {
-    pub fn new(position: Vec2, size: Vec2) -> Self {
-        Self { position, size }
+// Points cut off by the event mask are never inside the component, no matter what its shape is
+pub(crate) fn is_point_inside_mask(point: Vec2, event_mask: Option<EventMask>) -> bool {
+    event_mask.map(|p| p.contains_point(point)).unwrap_or(true)
+}
+
+pub(crate) fn is_point_inside_area(point: Vec2, area: &Rect, event_mask: Option<EventMask>) -> bool {
+    is_point_inside_mask(point, event_mask) && area.contains_point(point)
+}
+
+pub(crate) fn is_point_inside_ellipse(point: Vec2, area: &Rect) -> bool {
+    let scale = area.size.x / area.size.y;
+    let scaled_point = (point - area.get_center()) * Vec2::new(1.0, scale);
+
+    scaled_point.distance(Vec2::new(0.0, 0.0)) <= area.size.x / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_inside_area() {
+        let area = Rect::new(Vec2::new(10.0, 20.0), Vec2::new(100.0, 50.0));
+
+        assert!(is_point_inside_area(Vec2::new(10.0, 20.0), &area, None));
+        assert!(is_point_inside_area(Vec2::new(110.0, 70.0), &area, None));
+        assert!(is_point_inside_area(Vec2::new(60.0, 40.0), &area, None));
+        assert!(!is_point_inside_area(Vec2::new(9.9, 40.0), &area, None));
+        assert!(!is_point_inside_area(Vec2::new(60.0, 70.1), &area, None));
+    }
+
+    #[test]
+    fn point_inside_area_with_event_mask() {
+        let area = Rect::new(Vec2::new(10.0, 20.0), Vec2::new(100.0, 50.0));
+        let event_mask = EventMask::new(Vec2::new(0.0, 0.0), Vec2::new(50.0, 50.0));
+
+        assert!(is_point_inside_area(Vec2::new(40.0, 40.0), &area, Some(event_mask)));
+        assert!(!is_point_inside_area(Vec2::new(60.0, 40.0), &area, Some(event_mask)));
+        assert!(!is_point_inside_area(Vec2::new(5.0, 5.0), &area, Some(event_mask)));
+        assert!(is_point_inside_mask(Vec2::new(5.0, 5.0), Some(event_mask)));
+        assert!(is_point_inside_mask(Vec2::new(500.0, 500.0), None));
+    }
+
+    #[test]
+    fn point_inside_ellipse() {
+        let area = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(100.0, 50.0));
+
+        assert!(is_point_inside_ellipse(Vec2::new(50.0, 25.0), &area));
+        assert!(is_point_inside_ellipse(Vec2::new(0.0, 25.0), &area));
+        assert!(is_point_inside_ellipse(Vec2::new(50.0, 50.0), &area));
+        assert!(!is_point_inside_ellipse(Vec2::new(50.0, 50.1), &area));
+        assert!(!is_point_inside_ellipse(Vec2::new(5.0, 5.0), &area));
     }
 }
@@ -1,3 +1,5 @@
+use super::is_point_inside_ellipse;
+use super::is_point_inside_mask;
 use super::Component;
 use super::ComponentBorderShape;
 use super::ComponentBorderThickness;
@@ -8,14 +10,17 @@ use super::ComponentPosition;
 use super::ComponentShape;
 use super::ComponentSize;
 use super::EventMask;
+use crate::error::UiError;
 use crate::events::UiEvent;
 use lemao_core::lemao_common_platform::input::InputEvent;
 use lemao_core::lemao_common_platform::input::MouseButton;
 use lemao_core::lemao_math::color::SolidColor;
+use lemao_core::lemao_math::geometry::rect::Rect;
 use lemao_core::lemao_math::vec2::Vec2;
 use lemao_core::renderer::context::RendererContext;
 use lemao_core::renderer::drawable::Color;
 use lemao_core::renderer::textures::Texture;
+use lemao_core::utils::storage::Handle;
 use std::any::Any;
 
 pub struct Panel {
@@ -44,7 +49,7 @@ pub struct Panel {
     pub corner_rounding: ComponentCornerRounding,
     pub start_angle: f32,
     pub end_angle: f32,
-    pub texture_id: Option<usize>,
+    pub texture_id: Option<Handle<Texture>>,
     pub texture_original_size: Vec2,
 
     // Border properties
@@ -68,7 +73,7 @@ pub struct Panel {
 }
 
 impl Panel {
-    pub fn new(renderer: &mut RendererContext, shape: ComponentShape) -> Result<Self, String> {
+    pub fn new(renderer: &mut RendererContext, shape: ComponentShape) -> Result<Self, UiError> {
         Ok(Self {
             id: 0,
 
@@ -140,35 +145,21 @@ impl Panel {
             return false;
         }
 
-        if let Some(event_mask) = self.event_mask {
-            let event_mask_left_bottom = event_mask.position;
-            let event_mask_right_top = event_mask.position + event_mask.size;
-
-            if point.x < event_mask_left_bottom.x || point.y < event_mask_left_bottom.y || point.x > event_mask_right_top.x || point.y > event_mask_right_top.y {
-                return false;
-            }
+        if !is_point_inside_mask(point, self.event_mask) {
+            return false;
         }
 
         if self.shape == ComponentShape::Rectangle {
-            let x1 = self.screen_position.x;
-            let y1 = self.screen_position.y;
-            let x2 = self.screen_position.x + self.screen_size.x;
-            let y2 = self.screen_position.y + self.screen_size.y;
-
-            point.x >= x1 && point.y >= y1 && point.x <= x2 && point.y <= y2
+            Rect::new(self.screen_position, self.screen_size).contains_point(point)
         } else {
-            let scale = self.screen_size.x / self.screen_size.y;
             let component_center = self.screen_position + self.screen_size / 2.0;
-            let normalized_point = point - component_center;
-            let scaled_point = normalized_point * Vec2::new(1.0, scale);
-
             let mut angle = Vec2::new_from_angle(self.start_angle).signed_angle(point - component_center);
             if angle < 0.0 {
                 angle += std::f32::consts::PI * 2.0;
             }
 
             let within_angle = angle >= 0.0 && angle <= self.end_angle - self.start_angle;
-            within_angle && scaled_point.distance(Vec2::new(0.0, 0.0)) <= self.screen_size.x / 2.0
+            within_angle && is_point_inside_ellipse(point, &Rect::new(self.screen_position, self.screen_size))
         }
     }
 }
@@ -298,7 +289,7 @@ impl Component for Panel {
         events
     }
 
-    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), String> {
+    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), UiError> {
         self.screen_size = match self.size {
             ComponentSize::Absolute(size) => size,
             ComponentSize::Relative(size) => area_size * size,
@@ -415,7 +406,7 @@ impl Component for Panel {
         Ok(())
     }
 
-    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), String> {
+    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), UiError> {
         if self.shadow_enabled {
             match &mut self.shadow {
                 ComponentFillingShape::Rectangle(shadow) => renderer.draw(shadow)?,
@@ -1,3 +1,4 @@
+use super::is_point_inside_area;
 use super::Component;
 use super::ComponentBorderThickness;
 use super::ComponentCornerRounding;
@@ -7,10 +8,12 @@ use super::ComponentSize;
 use super::EventMask;
 use super::HorizontalAlignment;
 use super::VerticalAlignment;
+use crate::error::UiError;
 use crate::events::UiEvent;
 use lemao_core::lemao_common_platform::input::InputEvent;
 use lemao_core::lemao_common_platform::input::MouseButton;
 use lemao_core::lemao_math::color::SolidColor;
+use lemao_core::lemao_math::geometry::rect::Rect;
 use lemao_core::lemao_math::vec2::Vec2;
 use lemao_core::renderer::context::RendererContext;
 use lemao_core::renderer::drawable::frame::Frame;
@@ -19,6 +22,7 @@ use lemao_core::renderer::drawable::text::Text;
 use lemao_core::renderer::drawable::Color;
 use lemao_core::renderer::fonts::Font;
 use lemao_core::renderer::textures::Texture;
+use lemao_core::utils::storage::Handle;
 use std::any::Any;
 
 const MAX_BARS_COUNT: usize = 8;
@@ -46,7 +50,7 @@ pub struct ProgressBar {
     pub filling: Rectangle,
     pub color: Color,
     pub corner_rounding: ComponentCornerRounding,
-    pub texture_id: Option<usize>,
+    pub texture_id: Option<Handle<Texture>>,
     pub texture_original_size: Vec2,
 
     // Border properties
@@ -56,7 +60,7 @@ pub struct ProgressBar {
 
     // Label properties
     pub label: Text,
-    pub label_font_id: usize,
+    pub label_font_id: Handle<Font>,
     pub label_text: String,
     pub label_horizontal_alignment: HorizontalAlignment,
     pub label_vertical_alignment: VerticalAlignment,
@@ -96,7 +100,7 @@ pub struct Bar {
     pub from: f32,
     pub to: f32,
     pub corner_rounding: ComponentCornerRounding,
-    pub texture_id: Option<usize>,
+    pub texture_id: Option<Handle<Texture>>,
 
     // Border properties
     pub border: Frame,
@@ -105,7 +109,7 @@ pub struct Bar {
 }
 
 impl ProgressBar {
-    pub fn new(renderer: &mut RendererContext, label_font_id: usize) -> Result<Self, String> {
+    pub fn new(renderer: &mut RendererContext, label_font_id: Handle<Font>) -> Result<Self, UiError> {
         Ok(Self {
             id: 0,
 
@@ -192,21 +196,7 @@ impl ProgressBar {
             return false;
         }
 
-        if let Some(event_mask) = self.event_mask {
-            let event_mask_left_bottom = event_mask.position;
-            let event_mask_right_top = event_mask.position + event_mask.size;
-
-            if point.x < event_mask_left_bottom.x || point.y < event_mask_left_bottom.y || point.x > event_mask_right_top.x || point.y > event_mask_right_top.y {
-                return false;
-            }
-        }
-
-        let x1 = self.screen_position.x;
-        let y1 = self.screen_position.y;
-        let x2 = self.screen_position.x + self.screen_size.x;
-        let y2 = self.screen_position.y + self.screen_size.y;
-
-        point.x >= x1 && point.y >= y1 && point.x <= x2 && point.y <= y2
+        is_point_inside_area(point, &Rect::new(self.screen_position, self.screen_size), self.event_mask)
     }
 }
 
@@ -335,7 +325,7 @@ impl Component for ProgressBar {
         events
     }
 
-    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), String> {
+    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), UiError> {
         self.screen_size = match self.size {
             ComponentSize::Absolute(size) => size,
             ComponentSize::Relative(size) => area_size * size,
@@ -402,13 +392,14 @@ impl Component for ProgressBar {
             VerticalAlignment::Bottom => (Vec2::new(0.0, self.screen_position.y + self.screen_size.y), Vec2::new(0.0, 1.0)),
         };
 
+        renderer.fonts.get_mut(self.label_font_id)?.add_characters(&self.label_text)?;
         let font = renderer.fonts.get(self.label_font_id)?;
         self.label.set_font(font);
         self.label.text = self.label_text.clone();
         self.label.color = self.label_color.clone();
         self.label.position = (horizontal_position + vertical_position + self.label_offset).floor();
         self.label.anchor = horizontal_anchor + vertical_anchor;
-        self.label.update();
+        self.label.update()?;
 
         if self.shadow_enabled {
             self.shadow.position = self.screen_position + self.screen_size / 2.0 + self.shadow_offset;
@@ -434,7 +425,7 @@ impl Component for ProgressBar {
         Ok(())
     }
 
-    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), String> {
+    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), UiError> {
         if self.shadow_enabled {
             renderer.draw(&mut self.shadow)?;
         }
@@ -490,7 +481,7 @@ impl Component for ProgressBar {
 }
 
 impl Bar {
-    pub fn new(renderer: &mut RendererContext) -> Result<Self, String> {
+    pub fn new(renderer: &mut RendererContext) -> Result<Self, UiError> {
         Ok(Self {
             // Common properties
             visible: false,
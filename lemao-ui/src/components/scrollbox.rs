@@ -1,3 +1,4 @@
+use super::is_point_inside_area;
 use super::Component;
 use super::ComponentBorderThickness;
 use super::ComponentCornerRounding;
@@ -5,10 +6,12 @@ use super::ComponentMargin;
 use super::ComponentPosition;
 use super::ComponentSize;
 use super::EventMask;
+use crate::error::UiError;
 use crate::events::UiEvent;
 use lemao_core::lemao_common_platform::input::InputEvent;
 use lemao_core::lemao_common_platform::input::MouseButton;
 use lemao_core::lemao_common_platform::input::MouseWheelDirection;
+use lemao_core::lemao_math::geometry::rect::Rect;
 use lemao_core::lemao_math::vec2::Vec2;
 use lemao_core::renderer::context::RendererContext;
 use lemao_core::renderer::drawable::frame::Frame;
@@ -111,7 +114,7 @@ pub struct Scrollbox {
 }
 
 impl Scrollbox {
-    pub fn new(renderer: &mut RendererContext) -> Result<Self, String> {
+    pub fn new(renderer: &mut RendererContext) -> Result<Self, UiError> {
         Ok(Self {
             id: 0,
 
@@ -214,21 +217,7 @@ impl Scrollbox {
             return false;
         }
 
-        if let Some(event_mask) = self.event_mask {
-            let event_mask_left_bottom = event_mask.position;
-            let event_mask_right_top = event_mask.position + event_mask.size;
-
-            if point.x < event_mask_left_bottom.x || point.y < event_mask_left_bottom.y || point.x > event_mask_right_top.x || point.y > event_mask_right_top.y {
-                return false;
-            }
-        }
-
-        let x1 = self.screen_position.x;
-        let y1 = self.screen_position.y;
-        let x2 = self.screen_position.x + self.screen_size.x;
-        let y2 = self.screen_position.y + self.screen_size.y;
-
-        point.x >= x1 && point.y >= y1 && point.x <= x2 && point.y <= y2
+        is_point_inside_area(point, &Rect::new(self.screen_position, self.screen_size), self.event_mask)
     }
 
     fn is_point_inside_vertical_scroll(&self, point: Vec2) -> bool {
@@ -236,12 +225,7 @@ impl Scrollbox {
             return false;
         }
 
-        let x1 = self.vertical_scroll_position.x - self.vertical_scroll_size.x;
-        let y1 = self.vertical_scroll_position.y - self.vertical_scroll_size.y;
-        let x2 = self.vertical_scroll_position.x;
-        let y2 = self.vertical_scroll_position.y;
-
-        point.x >= x1 && point.y >= y1 && point.x <= x2 && point.y <= y2
+        Rect::new(self.vertical_scroll_position - self.vertical_scroll_size, self.vertical_scroll_size).contains_point(point)
     }
 
     fn is_point_inside_horizontal_scroll(&self, point: Vec2) -> bool {
@@ -249,12 +233,7 @@ impl Scrollbox {
             return false;
         }
 
-        let x1 = self.horizontal_scroll_position.x;
-        let y1 = self.horizontal_scroll_position.y;
-        let x2 = self.horizontal_scroll_position.x + self.horizontal_scroll_size.x;
-        let y2 = self.horizontal_scroll_position.y + self.horizontal_scroll_size.y;
-
-        point.x >= x1 && point.y >= y1 && point.x <= x2 && point.y <= y2
+        Rect::new(self.horizontal_scroll_position, self.horizontal_scroll_size).contains_point(point)
     }
 }
 
@@ -558,7 +537,7 @@ impl Component for Scrollbox {
         events
     }
 
-    fn update(&mut self, _renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), String> {
+    fn update(&mut self, _renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), UiError> {
         self.screen_size = match self.size {
             ComponentSize::Absolute(size) => size,
             ComponentSize::Relative(size) => area_size * size,
@@ -714,7 +693,7 @@ impl Component for Scrollbox {
         Ok(())
     }
 
-    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), String> {
+    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), UiError> {
         if self.scroll_width.x > 0.0 {
             renderer.draw(&mut self.vertical_scroll_background)?;
             renderer.draw(&mut self.vertical_scroll)?;
@@ -1,3 +1,4 @@
+use super::is_point_inside_area;
 use super::Component;
 use super::ComponentBorderShape;
 use super::ComponentBorderThickness;
@@ -8,17 +9,20 @@ use super::ComponentPosition;
 use super::ComponentShape;
 use super::ComponentSize;
 use super::EventMask;
+use crate::error::UiError;
 use crate::events::UiEvent;
 use lemao_core::lemao_common_platform::input::InputEvent;
 use lemao_core::lemao_common_platform::input::MouseButton;
 use lemao_core::lemao_common_platform::input::MouseWheelDirection;
 use lemao_core::lemao_math::color::SolidColor;
+use lemao_core::lemao_math::geometry::rect::Rect;
 use lemao_core::lemao_math::vec2::Vec2;
 use lemao_core::renderer::context::RendererContext;
 use lemao_core::renderer::drawable::frame::Frame;
 use lemao_core::renderer::drawable::rectangle::Rectangle;
 use lemao_core::renderer::drawable::Color;
 use lemao_core::renderer::textures::Texture;
+use lemao_core::utils::storage::Handle;
 use std::any::Any;
 
 pub struct Slider {
@@ -44,7 +48,7 @@ pub struct Slider {
     pub filling: Rectangle,
     pub color: Color,
     pub corner_rounding: ComponentCornerRounding,
-    pub texture_id: Option<usize>,
+    pub texture_id: Option<Handle<Texture>>,
     pub texture_original_size: Vec2,
 
     // Border properties
@@ -96,7 +100,7 @@ pub struct Slider {
 }
 
 impl Slider {
-    pub fn new(renderer: &mut RendererContext, selector_shape: ComponentShape) -> Result<Self, String> {
+    pub fn new(renderer: &mut RendererContext, selector_shape: ComponentShape) -> Result<Self, UiError> {
         Ok(Self {
             id: 0,
 
@@ -196,21 +200,7 @@ impl Slider {
             return false;
         }
 
-        if let Some(event_mask) = self.event_mask {
-            let event_mask_left_bottom = event_mask.position;
-            let event_mask_right_top = event_mask.position + event_mask.size;
-
-            if point.x < event_mask_left_bottom.x || point.y < event_mask_left_bottom.y || point.x > event_mask_right_top.x || point.y > event_mask_right_top.y {
-                return false;
-            }
-        }
-
-        let x1 = self.screen_position.x - self.selector_size.x / 2.0;
-        let y1 = self.screen_position.y - self.selector_size.y / 2.0;
-        let x2 = self.screen_position.x + self.screen_size.x + self.selector_size.x / 2.0;
-        let y2 = self.screen_position.y + self.screen_size.y + self.selector_size.y / 2.0;
-
-        point.x >= x1 && point.y >= y1 && point.x <= x2 && point.y <= y2
+        is_point_inside_area(point, &Rect::new(self.screen_position - self.selector_size / 2.0, self.screen_size + self.selector_size), self.event_mask)
     }
 
     fn is_point_inside_selector(&self, point: Vec2) -> bool {
@@ -218,21 +208,7 @@ impl Slider {
             return false;
         }
 
-        if let Some(event_mask) = self.event_mask {
-            let event_mask_left_bottom = event_mask.position;
-            let event_mask_right_top = event_mask.position + event_mask.size;
-
-            if point.x < event_mask_left_bottom.x || point.y < event_mask_left_bottom.y || point.x > event_mask_right_top.x || point.y > event_mask_right_top.y {
-                return false;
-            }
-        }
-
-        let x1 = self.selector_position.x - self.selector_size.x / 2.0;
-        let y1 = self.selector_position.y - self.selector_size.y / 2.0;
-        let x2 = self.selector_position.x + self.selector_size.x / 2.0;
-        let y2 = self.selector_position.y + self.selector_size.y / 2.0;
-
-        point.x >= x1 && point.y >= y1 && point.x <= x2 && point.y <= y2
+        is_point_inside_area(point, &Rect::new(self.selector_position - self.selector_size / 2.0, self.selector_size), self.event_mask)
     }
 
     fn update_selector(&mut self, new_phase: f32, events: &mut Vec<UiEvent>) {
@@ -464,7 +440,7 @@ impl Component for Slider {
         events
     }
 
-    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), String> {
+    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), UiError> {
         self.screen_size = match self.size {
             ComponentSize::Absolute(size) => size,
             ComponentSize::Relative(size) => area_size * size,
@@ -585,7 +561,7 @@ impl Component for Slider {
         Ok(())
     }
 
-    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), String> {
+    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), UiError> {
         if self.shadow_enabled {
             renderer.draw(&mut self.shadow)?;
         }
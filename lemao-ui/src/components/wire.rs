@@ -1,11 +1,14 @@
+use super::is_point_inside_area;
 use super::Component;
 use super::ComponentMargin;
 use super::ComponentPosition;
 use super::ComponentSize;
 use super::EventMask;
+use crate::error::UiError;
 use crate::events::UiEvent;
 use lemao_core::lemao_common_platform::input::InputEvent;
 use lemao_core::lemao_common_platform::input::MouseButton;
+use lemao_core::lemao_math::geometry::rect::Rect;
 use lemao_core::lemao_math::vec2::Vec2;
 use lemao_core::renderer::context::RendererContext;
 use lemao_core::renderer::drawable::line::Line;
@@ -55,7 +58,7 @@ pub struct WireChunk {
 }
 
 impl Wire {
-    pub fn new(_renderer: &mut RendererContext) -> Result<Self, String> {
+    pub fn new(_renderer: &mut RendererContext) -> Result<Self, UiError> {
         Ok(Self {
             id: 0,
 
@@ -92,21 +95,7 @@ impl Wire {
             return false;
         }
 
-        if let Some(event_mask) = self.event_mask {
-            let event_mask_left_bottom = event_mask.position;
-            let event_mask_right_top = event_mask.position + event_mask.size;
-
-            if point.x < event_mask_left_bottom.x || point.y < event_mask_left_bottom.y || point.x > event_mask_right_top.x || point.y > event_mask_right_top.y {
-                return false;
-            }
-        }
-
-        let x1 = self.screen_position.x;
-        let y1 = self.screen_position.y;
-        let x2 = self.screen_position.x + self.screen_size.x;
-        let y2 = self.screen_position.y + self.screen_size.y;
-
-        point.x >= x1 && point.y >= y1 && point.x <= x2 && point.y <= y2
+        is_point_inside_area(point, &Rect::new(self.screen_position, self.screen_size), self.event_mask)
     }
 }
 
@@ -235,7 +224,7 @@ impl Component for Wire {
         events
     }
 
-    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), String> {
+    fn update(&mut self, renderer: &mut RendererContext, area_position: Vec2, area_size: Vec2) -> Result<(), UiError> {
         self.screen_size = match self.size {
             ComponentSize::Absolute(size) => size,
             ComponentSize::Relative(size) => area_size * size,
@@ -274,7 +263,7 @@ impl Component for Wire {
         Ok(())
     }
 
-    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), String> {
+    fn draw(&mut self, renderer: &mut RendererContext) -> Result<(), UiError> {
         for chunk in &mut self.chunks {
             renderer.draw(&mut chunk.line)?;
         }
@@ -300,7 +289,7 @@ impl Component for Wire {
 }
 
 impl WireChunk {
-    pub fn new(renderer: &mut RendererContext) -> Result<Self, String> {
+    pub fn new(renderer: &mut RendererContext) -> Result<Self, UiError> {
         Ok(Self { line: renderer.create_line()? })
     }
 }
@@ -4,40 +4,45 @@ use crate::components::Component;
 use crate::components::ComponentPosition;
 use crate::components::ComponentSize;
 use crate::components::EventMask;
+use crate::error::UiError;
 use crate::events::UiEvent;
 use crate::utils::storage::UiStorage;
 use lemao_core::lemao_common_platform::input::InputEvent;
+use lemao_core::lemao_math::geometry::rect::Rect;
 use lemao_core::lemao_math::vec2::Vec2;
 use lemao_core::renderer::cameras::Camera;
 use lemao_core::renderer::context::RendererContext;
-use lemao_core::renderer::drawable::frame::Frame;
-use lemao_core::renderer::drawable::Color;
+use lemao_core::utils::storage::Handle;
 use lemao_math::color::SolidColor;
 use std::collections::VecDeque;
 
 pub struct UiContext {
-    pub ui_camera_id: usize,
+    pub ui_camera_id: Handle<Camera>,
     pub main_canvas_id: usize,
-    pub debug_frame: Frame,
     pub debug: bool,
 
     pub components: UiStorage,
     events: VecDeque<UiEvent>,
+    logical_pixels: bool,
+    scale_factor: f32,
+    window_size: Vec2,
 }
 
 impl UiContext {
-    pub fn new(renderer: &mut RendererContext) -> Result<Self, String> {
+    pub fn new(renderer: &mut RendererContext) -> Result<Self, UiError> {
         let main_camera = renderer.cameras.get(renderer.active_camera_id)?;
         let ui_camera_id = renderer.cameras.store(Camera::new(Default::default(), main_camera.size));
 
         let mut ui = Self {
             main_canvas_id: 0,
             ui_camera_id,
-            debug_frame: renderer.create_frame()?,
             debug: false,
 
             components: Default::default(),
             events: Default::default(),
+            logical_pixels: false,
+            scale_factor: 1.0,
+            window_size: renderer.viewport_size,
         };
         ui.main_canvas_id = ui.components.store(Canvas::new()?);
 
@@ -47,50 +52,80 @@ impl UiContext {
         Ok(ui)
     }
 
-    pub fn process_window_event(&mut self, renderer: &mut RendererContext, event: &InputEvent) -> Result<(), String> {
-        if let InputEvent::WindowSizeChanged(size) = event {
-            let ui_camera = renderer.cameras.get_mut(self.ui_camera_id)?;
-            let main_canvas = self.components.get_mut(self.main_canvas_id)?;
-
-            ui_camera.size = *size;
-            ui_camera.dirty = true;
-            main_canvas.set_size(ComponentSize::Absolute(*size));
-
-            for component in self.components.iter_mut() {
-                component.set_dirty_flag(true);
+    pub fn process_window_event(&mut self, renderer: &mut RendererContext, event: &InputEvent) -> Result<(), UiError> {
+        match event {
+            InputEvent::WindowSizeChanged(size) => {
+                self.window_size = *size;
+                self.apply_scale(renderer)?;
             }
-        } else {
-            for (component_id, component) in self.components.iter_mut().enumerate() {
-                if component_id == self.main_canvas_id {
-                    continue;
+            InputEvent::ScaleFactorChanged(scale_factor) => {
+                if self.logical_pixels {
+                    self.scale_factor = *scale_factor;
+                    self.apply_scale(renderer)?;
+                }
+            }
+            _ => {
+                // Components are laid out in logical pixels, so the cursor has to be converted before hit tests
+                let event = match *event {
+                    InputEvent::MouseButtonPressed(button, position) => InputEvent::MouseButtonPressed(button, position / self.scale_factor),
+                    InputEvent::MouseButtonReleased(button, position) => InputEvent::MouseButtonReleased(button, position / self.scale_factor),
+                    InputEvent::MouseMoved(position, previous_position) => InputEvent::MouseMoved(position / self.scale_factor, previous_position / self.scale_factor),
+                    InputEvent::MouseWheelRotated(direction, position) => InputEvent::MouseWheelRotated(direction, position / self.scale_factor),
+                    _ => *event,
+                };
+
+                for (component_id, component) in self.components.iter_mut().enumerate() {
+                    if component_id == self.main_canvas_id {
+                        continue;
+                    }
+
+                    self.events.extend(component.process_window_event(&event));
                 }
-
-                self.events.extend(component.process_window_event(event));
             }
         }
 
         Ok(())
     }
 
+    pub fn enable_logical_pixels(&mut self, renderer: &mut RendererContext, scale_factor: f32) -> Result<(), UiError> {
+        self.logical_pixels = true;
+        self.scale_factor = scale_factor;
+        self.apply_scale(renderer)
+    }
+
+    pub fn disable_logical_pixels(&mut self, renderer: &mut RendererContext) -> Result<(), UiError> {
+        self.logical_pixels = false;
+        self.scale_factor = 1.0;
+        self.apply_scale(renderer)
+    }
+
+    pub fn is_logical_pixels_enabled(&self) -> bool {
+        self.logical_pixels
+    }
+
+    pub fn get_scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
     pub fn poll_event(&mut self) -> Option<UiEvent> {
         self.events.pop_front()
     }
 
-    pub fn begin_scrollbox(&self, scrollbox_id: usize, renderer: &RendererContext) -> Result<(), String> {
+    pub fn begin_scrollbox(&self, scrollbox_id: usize, renderer: &mut RendererContext) -> Result<(), UiError> {
         let scrollbox = self.components.get(scrollbox_id)?;
         let position = scrollbox.get_work_area_position();
         let size = scrollbox.get_work_area_size();
 
-        renderer.enable_scissor(position, size);
+        renderer.push_clip_rectangle(position, size);
 
         Ok(())
     }
 
-    pub fn end_scrollbox(&self, renderer: &RendererContext) {
-        renderer.disable_scissor();
+    pub fn end_scrollbox(&self, renderer: &mut RendererContext) -> Result<(), UiError> {
+        Ok(renderer.pop_clip_rectangle()?)
     }
 
-    pub fn set_active_flag_for_tree(&mut self, root_component: usize, active: bool) -> Result<(), String> {
+    pub fn set_active_flag_for_tree(&mut self, root_component: usize, active: bool) -> Result<(), UiError> {
         let component = self.components.get_mut(root_component)?;
         component.set_active_flag(active);
 
@@ -101,15 +136,15 @@ impl UiContext {
         Ok(())
     }
 
-    pub fn update(&mut self, renderer: &mut RendererContext) -> Result<(), String> {
+    pub fn update(&mut self, renderer: &mut RendererContext) -> Result<(), UiError> {
         let main_canvas = self.components.get_mut(self.main_canvas_id)?;
         let area_position = match main_canvas.get_position() {
             ComponentPosition::AbsoluteToParent(position) => position,
-            _ => return Err("Invalid canvas".to_string()),
+            _ => return Err(UiError::InvalidOperation("main canvas must have absolute position".to_string())),
         };
         let area_size = match main_canvas.get_size() {
             ComponentSize::Absolute(size) => size,
-            _ => return Err("Invalid canvas".to_string()),
+            _ => return Err(UiError::InvalidOperation("main canvas must have absolute size".to_string())),
         };
         let updated_components = self.update_internal(renderer, self.main_canvas_id, area_position, area_size, None, Default::default(), false)?;
 
@@ -129,7 +164,7 @@ impl UiContext {
         event_mask: Option<EventMask>,
         scroll_offset: Option<Vec2>,
         force: bool,
-    ) -> Result<u32, String> {
+    ) -> Result<u32, UiError> {
         let component = self.components.get_mut(component_id)?;
         let update = force || component.is_dirty();
         let mut updated_components = 0;
@@ -159,28 +194,24 @@ impl UiContext {
 
         // Scrollbox needs to be updated second time, after all children are refreshed
         if self.components.get_and_cast::<Scrollbox>(component_id).is_ok() && updated_components > 1 {
-            let mut left_bottom_corner: Vec2 = Vec2::new(f32::MAX, f32::MAX);
-            let mut right_top_corner: Vec2 = Vec2::new(f32::MIN, f32::MIN);
+            let mut content_area: Option<Rect> = None;
 
             for child_id in self.components.get(component_id)?.get_children().clone() {
                 let child = self.components.get(child_id)?;
-                let child_area_position = child.get_work_area_position();
-                let child_area_size = child.get_work_area_size();
+                let child_area = Rect::new(child.get_work_area_position(), child.get_work_area_size());
 
-                left_bottom_corner.x = f32::min(left_bottom_corner.x, child_area_position.x);
-                left_bottom_corner.y = f32::min(left_bottom_corner.y, child_area_position.y);
-                right_top_corner.x = f32::max(right_top_corner.x, child_area_position.x + child_area_size.x);
-                right_top_corner.y = f32::max(right_top_corner.y, child_area_position.y + child_area_size.y);
+                content_area = Some(content_area.map(|p| p.union(&child_area)).unwrap_or(child_area));
             }
 
-            self.components.get_and_cast_mut::<Scrollbox>(component_id)?.set_total_size(right_top_corner - left_bottom_corner);
+            let total_size = content_area.map(|p| p.size).unwrap_or_default();
+            self.components.get_and_cast_mut::<Scrollbox>(component_id)?.set_total_size(total_size);
             self.components.get_and_cast_mut::<Scrollbox>(component_id)?.update(renderer, area_position, area_size)?;
         }
 
         Ok(updated_components)
     }
 
-    pub fn draw(&mut self, renderer: &mut RendererContext, component_id: usize) -> Result<(), String> {
+    pub fn draw(&mut self, renderer: &mut RendererContext, component_id: usize) -> Result<(), UiError> {
         let active_camera_id = renderer.active_camera_id;
         renderer.set_camera_as_active(self.ui_camera_id)?;
 
@@ -191,18 +222,31 @@ impl UiContext {
         component.draw(renderer)?;
 
         if self.debug {
-            self.debug_frame.position = component_position;
-            self.debug_frame.size = component_size;
-            self.debug_frame.color = Color::SolidColor(match component_is_active {
+            let color = match component_is_active {
                 true => SolidColor::new(1.0, 0.0, 0.0, 1.0),
                 false => SolidColor::new(0.2, 0.2, 0.2, 1.0),
-            });
-            self.debug_frame.update();
+            };
 
-            renderer.draw(&mut self.debug_frame)?;
+            renderer.debug_add_rectangle(component_position, component_size, color, None);
         }
 
         renderer.set_camera_as_active(active_camera_id)?;
         Ok(())
     }
+
+    fn apply_scale(&mut self, renderer: &mut RendererContext) -> Result<(), UiError> {
+        let ui_camera = renderer.cameras.get_mut(self.ui_camera_id)?;
+        let main_canvas = self.components.get_mut(self.main_canvas_id)?;
+
+        ui_camera.size = self.window_size;
+        ui_camera.scale_factor = self.scale_factor;
+        ui_camera.dirty = true;
+        main_canvas.set_size(ComponentSize::Absolute(ui_camera.get_logical_size()));
+
+        for component in self.components.iter_mut() {
+            component.set_dirty_flag(true);
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,43 @@
+use lemao_core::error::CoreError;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UiError {
+    Core(CoreError),
+    ComponentNotFound { id: usize },
+    InvalidCast { id: usize },
+    InvalidOperation(String),
+}
+
+impl fmt::Display for UiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Core(error) => write!(f, "{}", error),
+            Self::ComponentNotFound { id } => write!(f, "Component {} not found", id),
+            Self::InvalidCast { id } => write!(f, "Component {} cannot be downcasted", id),
+            Self::InvalidOperation(message) => write!(f, "Invalid operation: {}", message),
+        }
+    }
+}
+
+impl Error for UiError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Core(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<CoreError> for UiError {
+    fn from(error: CoreError) -> Self {
+        Self::Core(error)
+    }
+}
+
+impl From<UiError> for String {
+    fn from(error: UiError) -> Self {
+        error.to_string()
+    }
+}
@@ -2,5 +2,6 @@
 
 pub mod components;
 pub mod context;
+pub mod error;
 pub mod events;
 pub mod utils;
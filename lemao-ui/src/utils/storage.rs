@@ -1,4 +1,5 @@
 use crate::components::Component;
+use crate::error::UiError;
 use std::collections::VecDeque;
 
 #[derive(Default)]
@@ -19,32 +20,32 @@ impl UiStorage {
         id
     }
 
-    pub fn get(&self, id: usize) -> Result<&dyn Component, String> {
+    pub fn get(&self, id: usize) -> Result<&dyn Component, UiError> {
         match self.data.get(id) {
             Some(Some(item)) => Ok(item.as_ref()),
-            _ => Err(format!("Component {} not found", id)),
+            _ => Err(UiError::ComponentNotFound { id }),
         }
     }
 
-    pub fn get_and_cast<C>(&self, id: usize) -> Result<&C, String>
+    pub fn get_and_cast<C>(&self, id: usize) -> Result<&C, UiError>
     where
         C: Component + 'static,
     {
-        self.get(id)?.as_any().downcast_ref::<C>().ok_or_else(|| format!("Component {} cannot be downcasted", id))
+        self.get(id)?.as_any().downcast_ref::<C>().ok_or(UiError::InvalidCast { id })
     }
 
-    pub fn get_mut(&mut self, id: usize) -> Result<&mut dyn Component, String> {
+    pub fn get_mut(&mut self, id: usize) -> Result<&mut dyn Component, UiError> {
         match self.data.get_mut(id) {
             Some(Some(item)) => Ok(item.as_mut()),
-            _ => Err(format!("Component {} not found", id)),
+            _ => Err(UiError::ComponentNotFound { id }),
         }
     }
 
-    pub fn get_and_cast_mut<C>(&mut self, id: usize) -> Result<&mut C, String>
+    pub fn get_and_cast_mut<C>(&mut self, id: usize) -> Result<&mut C, UiError>
     where
         C: Component + 'static,
     {
-        self.get_mut(id)?.as_any_mut().downcast_mut::<C>().ok_or_else(|| format!("Component {} cannot be downcasted", id))
+        self.get_mut(id)?.as_any_mut().downcast_mut::<C>().ok_or(UiError::InvalidCast { id })
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &Box<dyn Component>> {
@@ -55,9 +56,9 @@ impl UiStorage {
         self.data.iter_mut().filter_map(|p| p.as_mut())
     }
 
-    pub fn remove(&mut self, id: usize) -> Result<(), String> {
+    pub fn remove(&mut self, id: usize) -> Result<(), UiError> {
         if id >= self.data.len() || self.data[id].is_none() {
-            return Err(format!("Component {} not found", id));
+            return Err(UiError::ComponentNotFound { id });
         }
 
         self.data[id] = None;